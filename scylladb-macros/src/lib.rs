@@ -1,6 +1,7 @@
 use proc_macro::TokenStream;
 use quote::quote;
 use scylladb_parse::{
+    parse_script as parse_cql_script,
     List,
     Nothing,
     StatementStream,
@@ -10,6 +11,7 @@ use scylladb_parse::{
     TaggedPermissionStatement,
     TaggedRoleStatement,
     TaggedSecondaryIndexStatement,
+    TaggedServiceLevelStatement,
     TaggedStatement,
     TaggedTriggerStatement,
     TaggedUserDefinedFunctionStatement,
@@ -555,8 +557,8 @@ impl Parse for StatementFormatArg {
 
 #[proc_macro]
 pub fn parse_statement(item: TokenStream) -> TokenStream {
-    let ParseStatementArgs { statement, args } = syn::parse_macro_input!(item as ParseStatementArgs);
-    let statement = statement.value();
+    let ParseStatementArgs { statement: lit, args } = syn::parse_macro_input!(item as ParseStatementArgs);
+    let statement = lit.value();
     let mut stream = StatementStream::new(&statement);
     if let Some(args) = args {
         for arg in args.args {
@@ -573,7 +575,10 @@ pub fn parse_statement(item: TokenStream) -> TokenStream {
             }
         }
     }
-    let res = stream.parse::<TaggedStatement>().unwrap();
+    let res = match stream.try_parse::<TaggedStatement>() {
+        Ok(res) => res,
+        Err(e) => return syn::Error::new(lit.span(), e).to_compile_error().into(),
+    };
     let res = match res {
         TaggedStatement::DataDefinition(stmt) => match stmt {
             TaggedDataDefinitionStatement::Use(stmt) => quote!(#stmt),
@@ -689,14 +694,24 @@ pub fn parse_statement(item: TokenStream) -> TokenStream {
             TaggedTriggerStatement::Create(stmt) => quote!(#stmt),
             TaggedTriggerStatement::Drop(stmt) => quote!(#stmt),
         },
+        TaggedStatement::Describe(stmt) => quote!(#stmt),
+        TaggedStatement::ServiceLevel(stmt) => match stmt {
+            TaggedServiceLevelStatement::Create(stmt) => quote!(#stmt),
+            TaggedServiceLevelStatement::Alter(stmt) => quote!(#stmt),
+            TaggedServiceLevelStatement::Drop(stmt) => quote!(#stmt),
+            TaggedServiceLevelStatement::List(stmt) => quote!(#stmt),
+            TaggedServiceLevelStatement::Attach(stmt) => quote!(#stmt),
+            TaggedServiceLevelStatement::Detach(stmt) => quote!(#stmt),
+            TaggedServiceLevelStatement::ListAttached(stmt) => quote!(#stmt),
+        },
     };
     res.into()
 }
 
 #[proc_macro]
 pub fn parse_statements(item: TokenStream) -> TokenStream {
-    let ParseStatementArgs { statement, args } = syn::parse_macro_input!(item as ParseStatementArgs);
-    let statement = statement.value();
+    let ParseStatementArgs { statement: lit, args } = syn::parse_macro_input!(item as ParseStatementArgs);
+    let statement = lit.value();
     let mut stream = StatementStream::new(&statement);
     if let Some(args) = args {
         for arg in args.args {
@@ -713,7 +728,110 @@ pub fn parse_statements(item: TokenStream) -> TokenStream {
             }
         }
     }
-    let res = stream.parse_from::<List<TaggedStatement, Nothing>>().unwrap();
+    let res = match stream.try_parse_from::<List<TaggedStatement, Nothing>>() {
+        Ok(res) => res,
+        Err(e) => return syn::Error::new(lit.span(), e).to_compile_error().into(),
+    };
     let res = quote!(vec![#(#res),*]);
     res.into()
 }
+
+struct ParseScriptArgs {
+    script: syn::LitStr,
+    file: Option<std::path::PathBuf>,
+}
+
+impl Parse for ParseScriptArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitStr) {
+            return Ok(Self {
+                script: input.parse()?,
+                file: None,
+            });
+        }
+        let key = input
+            .parse::<syn::Ident>()
+            .ok()
+            .filter(|key| key == "file")
+            .ok_or_else(|| input.error("Expected a string literal or `file = \"path\"`"))?;
+        input.parse::<syn::Token![=]>()?;
+        let path = input.parse::<syn::LitStr>()?;
+        // Relative to the manifest directory, see `parse_script`
+        let file = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join(path.value());
+        let script = std::fs::read_to_string(&file)
+            .map_err(|e| syn::Error::new_spanned(&key, format!("Unable to read {}: {}", file.display(), e)))?;
+        Ok(Self {
+            script: syn::LitStr::new(&script, path.span()),
+            file: Some(file),
+        })
+    }
+}
+
+/// Parse a CQL script containing multiple statements into a `Vec<Statement>`.
+/// The script may be a string literal or a file given as `file = "path"`, where the path
+/// is relative to the directory of the calling crate's `Cargo.toml` (`CARGO_MANIFEST_DIR`).
+///
+/// ```
+/// use scylladb_macros::parse_script;
+/// use scylladb_parse::*;
+///
+/// let statements: Vec<Statement> = parse_script!(
+///     "CREATE KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+///      DROP KEYSPACE movies;"
+/// );
+/// assert_eq!(statements.len(), 2);
+/// let statements: Vec<Statement> = parse_script!(file = "src/movies.cql");
+/// assert_eq!(statements.len(), 3);
+/// ```
+///
+/// Scripts which fail to parse are reported at compile time:
+///
+/// ```compile_fail
+/// use scylladb_macros::parse_script;
+/// use scylladb_parse::*;
+///
+/// let statements: Vec<Statement> = parse_script!("SELECT * FORM movies;");
+/// ```
+///
+/// As are missing files, and paths given in any other form:
+///
+/// ```compile_fail
+/// use scylladb_macros::parse_script;
+/// use scylladb_parse::*;
+///
+/// let statements: Vec<Statement> = parse_script!(file = "src/missing.cql");
+/// ```
+///
+/// ```compile_fail
+/// use scylladb_macros::parse_script;
+/// use scylladb_parse::*;
+///
+/// let statements: Vec<Statement> = parse_script!(include_str!("movies.cql"));
+/// ```
+#[proc_macro]
+pub fn parse_script(item: TokenStream) -> TokenStream {
+    let ParseScriptArgs { script, file } = syn::parse_macro_input!(item as ParseScriptArgs);
+    let statements = match parse_cql_script(&script.value()) {
+        Ok(statements) => statements.into_iter().map(|(statement, _)| statement),
+        Err(e) => {
+            let mut errors = e.errors.iter().map(|e| syn::Error::new(script.span(), e));
+            let mut error = errors.next().unwrap();
+            errors.for_each(|e| error.combine(e));
+            return error.to_compile_error().into();
+        }
+    };
+    // Track the script file so changes to it trigger a rebuild
+    let track = file.map(|f| {
+        let f = f.display().to_string();
+        quote!(
+            const _: &str = include_str!(#f);
+        )
+    });
+    let res = quote! {
+        {
+            #track
+            vec![#(#statements),*]
+        }
+    };
+    res.into()
+}
@@ -13,9 +13,9 @@ pub fn parse_from_str_derive(input: TokenStream) -> TokenStream {
     let (imp, ty, wher) = generics.split_for_impl();
     let mut res = quote! {
         impl #imp FromStr for #ident #ty #wher {
-            type Err = anyhow::Error;
-            fn from_str(s: &str) -> anyhow::Result<Self> {
-                StatementStream::new(s).parse()
+            type Err = ParseError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                StatementStream::new(s).try_parse()
             }
         }
     };
@@ -244,3 +244,98 @@ pub fn to_tokens_derive(input: TokenStream) -> TokenStream {
     };
     res.into()
 }
+
+/// Convert a type name to the snake case used for its visitor methods, i.e. `CqlType` => `cql_type`
+fn snake_case(ident: &syn::Ident) -> String {
+    let chars = ident.to_string().chars().collect::<Vec<_>>();
+    let mut res = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev_lower = i > 0 && chars[i - 1].is_lowercase();
+            let next_lower = i > 0 && chars.get(i + 1).map(|c| c.is_lowercase()).unwrap_or(false);
+            if prev_lower || next_lower {
+                res.push('_');
+            }
+            res.extend(c.to_lowercase());
+        } else {
+            res.push(*c);
+        }
+    }
+    res
+}
+
+/// Destructure a set of fields into bindings, returning the pattern and the bindings
+fn visit_fields(fields: &syn::Fields) -> (proc_macro2::TokenStream, Vec<syn::Ident>) {
+    match fields {
+        syn::Fields::Named(f) => {
+            let names = f.named.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+            (quote!({ #(#names),* }), names)
+        }
+        syn::Fields::Unnamed(f) => {
+            let names = (0..f.unnamed.len())
+                .map(|i| quote::format_ident!("f_{}", i))
+                .collect::<Vec<_>>();
+            (quote!(( #(#names),* )), names)
+        }
+        syn::Fields::Unit => (quote!(), Vec::new()),
+    }
+}
+
+#[proc_macro_derive(Visitable)]
+pub fn visitable_derive(input: TokenStream) -> TokenStream {
+    let syn::DeriveInput {
+        ident, generics, data, ..
+    } = syn::parse_macro_input!(input as syn::DeriveInput);
+    let (imp, ty, wher) = generics.split_for_impl();
+    let snake = snake_case(&ident);
+    let visit = quote::format_ident!("visit_{}", snake);
+    let visit_mut = quote::format_ident!("visit_{}_mut", snake);
+    let arms = match data {
+        syn::Data::Struct(s) => {
+            let (pat, names) = visit_fields(&s.fields);
+            vec![(quote!(Self #pat), names)]
+        }
+        syn::Data::Enum(e) => e
+            .variants
+            .iter()
+            .map(|v| {
+                let var_id = &v.ident;
+                let (pat, names) = visit_fields(&v.fields);
+                (quote!(Self::#var_id #pat), names)
+            })
+            .collect(),
+        syn::Data::Union(_) => panic!("Unions not supported!"),
+    };
+    let walk = arms
+        .iter()
+        .map(|(pat, names)| quote!(#pat => { #(#names.accept(visitor);)* }));
+    let walk_mut = arms
+        .iter()
+        .map(|(pat, names)| quote!(#pat => { #(#names.accept_mut(visitor);)* }));
+    quote! {
+        impl #imp Visitable for #ident #ty #wher {
+            fn accept<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+                visitor.#visit(self)
+            }
+
+            fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                visitor.#visit_mut(self)
+            }
+
+            #[allow(unused_variables)]
+            fn walk<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+                match self {
+                    #(#walk)*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                match self {
+                    #(#walk_mut)*
+                }
+            }
+        }
+    }
+    .into()
+}
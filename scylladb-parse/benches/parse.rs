@@ -0,0 +1,104 @@
+//! Parser benchmarks for representative DDL and DML statements.
+//!
+//! Run with `RUST_BACKTRACE=0 cargo bench -p scylladb-parse`, as backtraces captured for the errors of failed
+//! alternatives otherwise dominate the measurements. See `results.md` for the recorded baseline and how to compare
+//! against it.
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    Criterion,
+};
+use scylladb_parse::{
+    DataDefinitionStatement,
+    DataManipulationStatement,
+    StatementStream,
+};
+
+const DDL: &[(&str, &str)] = &[
+    (
+        "create_keyspace",
+        "CREATE KEYSPACE IF NOT EXISTS my_keyspace
+        WITH replication = {'class': 'NetworkTopologyStrategy', 'dc1': 3, 'dc2': 2}
+        AND durable_writes = true",
+    ),
+    (
+        "create_table",
+        "CREATE TABLE IF NOT EXISTS my_keyspace.timeline (
+            userid uuid,
+            posted_month int,
+            posted_time uuid,
+            body text,
+            posted_by text,
+            tags set<text>,
+            attributes map<text, blob>,
+            PRIMARY KEY ((userid, posted_month), posted_time)
+        ) WITH CLUSTERING ORDER BY (posted_time DESC)
+        AND comment = 'A timeline'
+        AND gc_grace_seconds = 864000
+        AND compaction = {'class': 'LeveledCompactionStrategy'}",
+    ),
+    (
+        "alter_table",
+        "ALTER TABLE my_keyspace.timeline ADD likes counter, shares int",
+    ),
+];
+
+const DML: &[(&str, &str)] = &[
+    (
+        "select",
+        "SELECT userid, posted_time, body, writetime(body) AS written FROM my_keyspace.timeline
+        WHERE userid = ? AND posted_month IN (1, 2, 3) AND posted_time > :since
+        ORDER BY posted_time DESC PER PARTITION LIMIT 2 LIMIT 100 ALLOW FILTERING",
+    ),
+    (
+        "insert",
+        "INSERT INTO my_keyspace.timeline (userid, posted_month, posted_time, body, tags)
+        VALUES (?, 12, ?, 'Hello world', {'greeting', 'first'})
+        IF NOT EXISTS USING TTL 86400",
+    ),
+    (
+        "update",
+        "UPDATE my_keyspace.timeline USING TIMESTAMP 1234 SET body = 'edited', tags = tags + {'edited'}, attributes['k'] = ?
+        WHERE userid = ? AND posted_month = 12 AND posted_time = ? IF body = 'Hello world'",
+    ),
+    (
+        "batch",
+        "BEGIN BATCH
+            INSERT INTO my_keyspace.timeline (userid, posted_month, posted_time, body) VALUES (?, ?, ?, ?);
+            UPDATE my_keyspace.timeline SET body = ? WHERE userid = ? AND posted_month = ? AND posted_time = ?;
+            DELETE tags FROM my_keyspace.timeline WHERE userid = ? AND posted_month = ? AND posted_time = ?;
+        APPLY BATCH",
+    ),
+];
+
+fn bench_ddl(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ddl");
+    for (name, statement) in DDL {
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                StatementStream::new(black_box(statement))
+                    .parse::<DataDefinitionStatement>()
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_dml(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dml");
+    for (name, statement) in DML {
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                StatementStream::new(black_box(statement))
+                    .parse::<DataManipulationStatement>()
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ddl, bench_dml);
+criterion_main!(benches);
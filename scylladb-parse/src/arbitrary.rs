@@ -0,0 +1,1227 @@
+//! [`proptest`] strategies for the statement syntax tree, and a harness which checks that values survive a round
+//! trip through their `Display` and `Parse` implementations.
+//!
+//! Generated values are valid statements, so they only use the canonical form of each node which the parser produces.
+//! Unquoted names start with `h`, `q`, `x`, `y` or `z` so that they can never be mistaken for a keyword or a constant.
+
+use super::*;
+use proptest::{
+    arbitrary::{
+        any,
+        Arbitrary,
+    },
+    collection::{
+        btree_map,
+        btree_set,
+        vec,
+    },
+    option,
+    prelude::*,
+    sample::select,
+    strategy::BoxedStrategy,
+    test_runner::{
+        TestCaseError,
+        TestError,
+        TestRunner,
+    },
+};
+use std::fmt::Debug;
+
+/// Check that a value is parsed back from its displayed statement, consuming all of it.
+pub fn check_round_trip<T>(value: &T) -> Result<(), TestCaseError>
+where
+    T: 'static + Parse<Output = T> + Display + Debug + Clone + PartialEq,
+{
+    let statement = value.to_string();
+    let mut stream = StatementStream::new(&statement);
+    let parsed = stream
+        .try_parse::<T>()
+        .map_err(|e| TestCaseError::fail(format!("Failed to parse `{}`: {}", statement, e)))?;
+    stream.skip_whitespace();
+    prop_assert_eq!(
+        stream.remaining(),
+        0,
+        "Unparsed input remaining in `{}` after {:?}",
+        statement,
+        parsed
+    );
+    prop_assert_eq!(
+        &parsed,
+        value,
+        "Round trip of `{}` produced a different value",
+        statement
+    );
+    Ok(())
+}
+
+/// Run the round trip property over the values of a strategy, returning the minimal failing value if any.
+pub fn round_trip_with<S>(strategy: S) -> Result<(), TestError<S::Value>>
+where
+    S: Strategy,
+    S::Value: 'static + Parse<Output = S::Value> + Display + Clone + PartialEq,
+{
+    TestRunner::default().run(&strategy, |value| check_round_trip(&value))
+}
+
+/// Run the round trip property over arbitrary values of a type.
+pub fn round_trip<T>() -> Result<(), TestError<T>>
+where
+    T: 'static + Arbitrary + Parse<Output = T> + Display + Clone + PartialEq,
+{
+    round_trip_with(any::<T>())
+}
+
+macro_rules! arbitrary {
+    ($($t:ty => $s:expr;)*) => {
+        $(
+            impl Arbitrary for $t {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+                fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                    $s.boxed()
+                }
+            }
+        )*
+    };
+}
+
+fn unquoted() -> impl Strategy<Value = String> {
+    "[hqxyz][a-z0-9_]{0,7}"
+}
+
+fn text() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9 _.,]{0,12}"
+}
+
+fn positive() -> impl Strategy<Value = i32> {
+    0..=i32::MAX
+}
+
+fn non_empty<T: Debug>(element: impl Strategy<Value = T>) -> impl Strategy<Value = Vec<T>> {
+    vec(element, 1..4)
+}
+
+fn float() -> impl Strategy<Value = f32> {
+    (0..1000u32, 0..1000u32).prop_map(|(i, f)| format!("{}.{}", i, f).parse().unwrap())
+}
+
+fn chance() -> impl Strategy<Value = f32> {
+    (0..=1000u16).prop_map(|n| n as f32 / 1000.0)
+}
+
+fn string(value: impl Strategy<Value = String>) -> impl Strategy<Value = LitStr> {
+    value.prop_map(|value| LitStr {
+        kind: LitStrKind::Quoted,
+        value,
+    })
+}
+
+fn term() -> impl Strategy<Value = Term> {
+    let leaf = prop_oneof![
+        4 => any::<Constant>().prop_map(Term::Constant),
+        1 => any::<BindMarker>().prop_map(Term::BindMarker),
+        1 => (cql_type(), any::<Name>()).prop_map(|(hint, ident)| Term::TypeHint { hint, ident }),
+    ];
+    leaf.prop_recursive(3, 16, 4, |inner| {
+        prop_oneof![
+            literal(inner.clone()).prop_map(Term::Literal),
+            function_call(inner.clone()).prop_map(Term::FunctionCall),
+            arithmetic(inner),
+        ]
+    })
+}
+
+fn starts_like_name(value: &impl Display) -> bool {
+    value.to_string().starts_with(|c: char| c.is_alphabetic() || c == '"')
+}
+
+fn is_numeric(term: &Term) -> bool {
+    matches!(term, Term::Constant(Constant::Integer(_) | Constant::Float(_)))
+}
+
+/// Arithmetic is right associative without precedence, and the only unary operator is negation of a non-constant.
+fn arithmetic(inner: BoxedStrategy<Term>) -> impl Strategy<Value = Term> {
+    let operand = inner
+        .clone()
+        .prop_filter("Nested arithmetic", |t| !matches!(t, Term::ArithmeticOp { .. }));
+    prop_oneof![
+        (operand.clone(), any::<ArithmeticOp>(), inner)
+            .prop_filter("Ambiguous negative number", |(lhs, op, rhs)| {
+                !(rhs.to_string().starts_with('-') || *op == ArithmeticOp::Sub && is_numeric(lhs))
+            })
+            .prop_map(|(lhs, op, rhs)| Term::ArithmeticOp {
+                lhs: Some(Box::new(lhs)),
+                op,
+                rhs: Box::new(rhs),
+            }),
+        operand
+            .prop_filter("Negative constant", |t| !matches!(t, Term::Constant(_)))
+            .prop_map(|rhs| Term::ArithmeticOp {
+                lhs: None,
+                op: ArithmeticOp::Sub,
+                rhs: Box::new(rhs),
+            }),
+    ]
+}
+
+fn function_call(inner: BoxedStrategy<Term>) -> impl Strategy<Value = FunctionCall> {
+    (any::<FunctionName>(), vec(inner, 0..3)).prop_map(|(name, args)| FunctionCall { name, args })
+}
+
+fn literal(inner: BoxedStrategy<Term>) -> impl Strategy<Value = Literal> {
+    prop_oneof![
+        collection_literal(inner.clone()).prop_map(Literal::Collection),
+        user_defined_literal(inner.clone()).prop_map(Literal::UserDefined),
+        tuple_literal(inner).prop_map(Literal::Tuple),
+    ]
+}
+
+fn collection_literal(inner: BoxedStrategy<Term>) -> impl Strategy<Value = CollectionTypeLiteral> {
+    prop_oneof![
+        list_literal(inner.clone()).prop_map(CollectionTypeLiteral::List),
+        set_literal(inner.clone()).prop_map(CollectionTypeLiteral::Set),
+        map_literal(inner).prop_map(CollectionTypeLiteral::Map),
+    ]
+}
+
+fn list_literal(inner: BoxedStrategy<Term>) -> impl Strategy<Value = ListLiteral> {
+    non_empty(inner).prop_map(|elements| ListLiteral { elements })
+}
+
+fn set_literal(inner: BoxedStrategy<Term>) -> impl Strategy<Value = SetLiteral> {
+    btree_set(inner, 1..4).prop_map(|elements| SetLiteral { elements })
+}
+
+fn map_literal(inner: BoxedStrategy<Term>) -> impl Strategy<Value = MapLiteral> {
+    btree_map(inner.clone(), inner, 1..4).prop_map(|elements| MapLiteral { elements })
+}
+
+fn tuple_literal(inner: BoxedStrategy<Term>) -> impl Strategy<Value = TupleLiteral> {
+    non_empty(inner).prop_map(|elements| TupleLiteral { elements })
+}
+
+fn user_defined_literal(inner: BoxedStrategy<Term>) -> impl Strategy<Value = UserDefinedTypeLiteral> {
+    btree_map(any::<Name>(), inner, 1..4).prop_map(|fields| UserDefinedTypeLiteral { fields })
+}
+
+/// Literals whose terms are constants, as used by options and role settings.
+fn constant_map() -> impl Strategy<Value = MapLiteral> {
+    btree_map(
+        string(text()).prop_map(|s| Term::Constant(Constant::String(s))),
+        any::<Constant>().prop_map(Term::Constant),
+        1..4,
+    )
+    .prop_map(|elements| MapLiteral { elements })
+}
+
+fn cql_type() -> impl Strategy<Value = CqlType> {
+    let leaf = prop_oneof![
+        4 => any::<NativeType>().prop_map(CqlType::Native),
+        1 => any::<UserDefinedType>().prop_map(CqlType::UserDefined),
+    ];
+    leaf.prop_recursive(2, 8, 3, |inner| {
+        prop_oneof![
+            collection_type(inner.clone()).prop_map(|c| CqlType::Collection(Box::new(c))),
+            non_empty(inner).prop_map(CqlType::Tuple),
+        ]
+    })
+}
+
+fn collection_type(inner: BoxedStrategy<CqlType>) -> impl Strategy<Value = CollectionType> {
+    prop_oneof![
+        inner.clone().prop_map(CollectionType::List),
+        inner.clone().prop_map(CollectionType::Set),
+        (inner.clone(), inner).prop_map(|(k, v)| CollectionType::Map(k, v)),
+    ]
+}
+
+fn selector() -> impl Strategy<Value = Selector> {
+    // Terms which start like a name are read as columns or functions
+    let term = term().prop_filter("Term starts like a name", |t| !starts_like_name(t));
+    let leaf = prop_oneof![
+        4 => any::<Name>().prop_map(SelectorKind::Column),
+        1 => term.prop_map(SelectorKind::Term),
+        1 => Just(SelectorKind::Count),
+    ];
+    let kind = leaf.prop_recursive(2, 8, 3, |inner| {
+        let aliased = (inner.clone(), option::of(any::<Name>())).prop_map(|(kind, as_id)| Selector { kind, as_id });
+        prop_oneof![
+            (inner, any::<CqlType>())
+                .prop_map(|(kind, t)| SelectorKind::Cast(Box::new(Selector { kind, as_id: None }), t)),
+            (any::<Name>(), vec(aliased, 0..3))
+                .prop_map(|(function, args)| SelectorKind::Function(SelectorFunction { function, args })),
+        ]
+    });
+    (kind, option::of(any::<Name>())).prop_map(|(kind, as_id)| Selector { kind, as_id })
+}
+
+arbitrary! {
+    Name => prop_oneof![
+        3 => unquoted().prop_map(Name::Unquoted),
+        1 => "[A-Z][a-zA-Z0-9 _]{0,7}".prop_map(Name::Quoted),
+    ];
+    KeyspaceQualifiedName => (option::of(any::<Name>()), any::<Name>())
+        .prop_map(|(keyspace, name)| KeyspaceQualifiedName { keyspace, name });
+    LitStrKind => select(vec![LitStrKind::Quoted, LitStrKind::Escaped]);
+    LitStr => prop_oneof![
+        string(text()),
+        "[a-zA-Z0-9 _.,']{0,11}[a-zA-Z0-9 _.,']?".prop_map(|value| LitStr {
+            kind: LitStrKind::Escaped,
+            value,
+        }),
+    ];
+    BindMarker => prop_oneof![Just(BindMarker::Anonymous), any::<Name>().prop_map(BindMarker::Named)];
+    ArithmeticOp => select(vec![
+        ArithmeticOp::Add,
+        ArithmeticOp::Sub,
+        ArithmeticOp::Mul,
+        ArithmeticOp::Div,
+        ArithmeticOp::Mod,
+    ]);
+    Operator => select(vec![
+        Operator::Equal,
+        Operator::NotEqual,
+        Operator::GreaterThan,
+        Operator::GreaterThanOrEqual,
+        Operator::LessThan,
+        Operator::LessThanOrEqual,
+        Operator::In,
+        Operator::Contains,
+        Operator::ContainsKey,
+        Operator::Like,
+    ]);
+    Constant => prop_oneof![
+        Just(Constant::Null),
+        any::<LitStr>().prop_map(Constant::String),
+        any::<i64>().prop_map(|i| Constant::Integer(i.to_string())),
+        (any::<i32>(), "[0-9]{1,4}", option::of("[eE][+-]?[0-9]{1,2}")).prop_map(|(i, f, e)| {
+            Constant::Float(format!("{}.{}{}", i, f, e.unwrap_or_default()))
+        }),
+        any::<bool>().prop_map(Constant::Boolean),
+        // UUIDs and hex strings start with a letter so that they are not read as numbers
+        (0xa..=0xfu128, any::<u128>()).prop_map(|(h, u)| Constant::Uuid(Uuid::from_u128(h << 124 | u >> 4))),
+        (0xa0..=0xffu8, vec(any::<u8>(), 0..8)).prop_map(|(h, mut b)| {
+            b.insert(0, h);
+            Constant::Hex(b)
+        }),
+        vec(any::<u8>(), 1..8).prop_map(Constant::Blob),
+    ];
+    Term => term();
+    Literal => literal(term().boxed());
+    CollectionTypeLiteral => collection_literal(term().boxed());
+    ListLiteral => list_literal(term().boxed());
+    SetLiteral => set_literal(term().boxed());
+    MapLiteral => map_literal(term().boxed());
+    TupleLiteral => tuple_literal(term().boxed());
+    UserDefinedTypeLiteral => user_defined_literal(term().boxed());
+    FunctionCall => function_call(term().boxed());
+    Selector => selector();
+    SelectorKind => selector().prop_map(|s| s.kind);
+    NativeType => select(vec![
+        NativeType::Ascii,
+        NativeType::Bigint,
+        NativeType::Blob,
+        NativeType::Boolean,
+        NativeType::Counter,
+        NativeType::Date,
+        NativeType::Decimal,
+        NativeType::Double,
+        NativeType::Duration,
+        NativeType::Float,
+        NativeType::Inet,
+        NativeType::Int,
+        NativeType::Smallint,
+        NativeType::Text,
+        NativeType::Time,
+        NativeType::Timestamp,
+        NativeType::Timeuuid,
+        NativeType::Tinyint,
+        NativeType::Uuid,
+        NativeType::Varchar,
+        NativeType::Varint,
+    ]);
+    CqlType => prop_oneof![
+        8 => cql_type(),
+        1 => any::<LitStr>().prop_map(CqlType::Custom),
+    ];
+    CollectionType => collection_type(cql_type().boxed());
+    DurationLiteral => (0..1000, 0..1000, 0..1_000_000_000_000_000i64)
+        .prop_map(|(months, days, nanos)| DurationLiteral { months, days, nanos });
+    StatementOpt => (any::<Name>(), any::<StatementOptValue>()).prop_map(|(name, value)| StatementOpt { name, value });
+    StatementOptValue => prop_oneof![
+        any::<Name>().prop_map(StatementOptValue::Identifier),
+        any::<Constant>().prop_map(StatementOptValue::Constant),
+        constant_map().prop_map(StatementOptValue::Map),
+    ];
+    FieldDefinition => (any::<Name>(), any::<CqlType>()).prop_map(|(name, data_type)| FieldDefinition { name, data_type });
+    ColumnDefinition => (any::<Name>(), any::<CqlType>(), any::<bool>(), any::<bool>()).prop_map(
+        |(name, data_type, static_column, primary_key)| ColumnDefinition {
+            name,
+            data_type,
+            static_column,
+            primary_key,
+        }
+    );
+    PartitionKey => non_empty(any::<Name>()).prop_map(|columns| PartitionKey { columns });
+    PrimaryKey => (any::<PartitionKey>(), option::of(non_empty(any::<Name>()))).prop_map(
+        |(partition_key, clustering_columns)| PrimaryKey {
+            partition_key,
+            clustering_columns,
+        }
+    );
+    Order => select(vec![Order::Ascending, Order::Descending]);
+    ColumnOrder => (any::<Name>(), any::<Order>()).prop_map(|(column, order)| ColumnOrder { column, order });
+    Relation => prop_oneof![
+        (any::<Name>(), any::<Operator>(), any::<Term>())
+            .prop_map(|(column, operator, term)| Relation::Normal { column, operator, term }),
+        (non_empty(any::<Name>()), any::<Operator>(), any::<TupleLiteral>()).prop_map(
+            |(columns, operator, tuple_literal)| Relation::Tuple {
+                columns,
+                operator,
+                tuple_literal,
+            }
+        ),
+        (non_empty(any::<Name>()), any::<Operator>(), any::<Term>())
+            .prop_map(|(columns, operator, term)| Relation::Token { columns, operator, term }),
+        any::<Name>().prop_map(|column| Relation::MVExclusion { column }),
+    ];
+    Replication => prop_oneof![
+        positive().prop_map(Replication::simple),
+        (option::of(positive()), btree_map(string("[a-z][a-z0-9_]{0,7}"), positive(), 0..3)).prop_map(
+            |(replication_factor, datacenters)| Replication {
+                class: "NetworkTopologyStrategy".into(),
+                replication_factor,
+                datacenters,
+            }
+        ),
+    ];
+    SpeculativeRetry => prop_oneof![
+        Just(SpeculativeRetry::None),
+        Just(SpeculativeRetry::Always),
+        float().prop_map(SpeculativeRetry::Percentile),
+        "[0-9]{1,3}ms".prop_map(|s| SpeculativeRetry::Custom(s.into())),
+    ];
+    SizeTieredCompactionStrategy => (
+        compaction_common(),
+        (option::of(positive()), option::of(float()), option::of(float())),
+    )
+        .prop_map(|(common, (min_sstable_size, bucket_low, bucket_high))| {
+            let (
+                enabled,
+                tombstone_threshold,
+                tombstone_compaction_interval,
+                log_all,
+                unchecked_tombstone_compaction,
+                only_purge_repaired_tombstone,
+                min_threshold,
+                max_threshold,
+            ) = common;
+            SizeTieredCompactionStrategy {
+                enabled,
+                tombstone_threshold,
+                tombstone_compaction_interval,
+                log_all,
+                unchecked_tombstone_compaction,
+                only_purge_repaired_tombstone,
+                min_threshold,
+                max_threshold,
+                min_sstable_size,
+                bucket_low,
+                bucket_high,
+            }
+        });
+    LeveledCompactionStrategy => (compaction_common(), option::of(positive()), option::of(positive())).prop_map(
+        |(common, sstable_size_in_mb, fanout_size)| {
+            let (
+                enabled,
+                tombstone_threshold,
+                tombstone_compaction_interval,
+                log_all,
+                unchecked_tombstone_compaction,
+                only_purge_repaired_tombstone,
+                min_threshold,
+                max_threshold,
+            ) = common;
+            LeveledCompactionStrategy {
+                enabled,
+                tombstone_threshold,
+                tombstone_compaction_interval,
+                log_all,
+                unchecked_tombstone_compaction,
+                only_purge_repaired_tombstone,
+                min_threshold,
+                max_threshold,
+                sstable_size_in_mb,
+                fanout_size,
+            }
+        }
+    );
+    TimeWindowCompactionStrategy => (
+        compaction_common(),
+        (option::of(any::<JavaTimeUnit>()), option::of(positive()), option::of(any::<bool>())),
+    )
+        .prop_map(
+            |(common, (compaction_window_unit, compaction_window_size, unsafe_aggressive_sstable_expiration))| {
+                let (
+                    enabled,
+                    tombstone_threshold,
+                    tombstone_compaction_interval,
+                    log_all,
+                    unchecked_tombstone_compaction,
+                    only_purge_repaired_tombstone,
+                    min_threshold,
+                    max_threshold,
+                ) = common;
+                TimeWindowCompactionStrategy {
+                    enabled,
+                    tombstone_threshold,
+                    tombstone_compaction_interval,
+                    log_all,
+                    unchecked_tombstone_compaction,
+                    only_purge_repaired_tombstone,
+                    min_threshold,
+                    max_threshold,
+                    compaction_window_unit,
+                    compaction_window_size,
+                    unsafe_aggressive_sstable_expiration,
+                }
+            }
+        );
+    Compaction => prop_oneof![
+        any::<SizeTieredCompactionStrategy>().prop_map(Compaction::SizeTiered),
+        any::<LeveledCompactionStrategy>().prop_map(Compaction::Leveled),
+        any::<TimeWindowCompactionStrategy>().prop_map(Compaction::TimeWindow),
+    ];
+    JavaTimeUnit => select(vec![JavaTimeUnit::Minutes, JavaTimeUnit::Hours, JavaTimeUnit::Days]);
+    Compression => (
+        option::of(string("[A-Z][a-zA-Z]{0,11}Compressor")),
+        option::of(any::<bool>()),
+        option::of(positive()),
+        option::of(chance()),
+        option::of(positive()),
+    )
+        .prop_map(
+            |(class, enabled, chunk_length_in_kb, crc_check_chance, compression_level)| Compression {
+                class,
+                enabled,
+                chunk_length_in_kb,
+                crc_check_chance,
+                compression_level,
+            }
+        )
+        .prop_filter("Empty compression options", |c| c.to_string() != "{}");
+    Caching => (option::of(any::<Keys>()), option::of(any::<RowsPerPartition>()))
+        .prop_filter("Empty caching options", |(keys, rows)| keys.is_some() || rows.is_some())
+        .prop_map(|(keys, rows_per_partition)| Caching { keys, rows_per_partition });
+    Keys => select(vec![Keys::All, Keys::None]);
+    RowsPerPartition => prop_oneof![
+        Just(RowsPerPartition::All),
+        Just(RowsPerPartition::None),
+        positive().prop_map(RowsPerPartition::Count),
+    ];
+    TableOpts => (
+        (
+            any::<bool>(),
+            option::of(non_empty(any::<ColumnOrder>())),
+            option::of(string(text())),
+            option::of(any::<SpeculativeRetry>()),
+            option::of(any::<ChangeDataCapture>()),
+            option::of(positive()),
+            option::of(chance()),
+        ),
+        (
+            option::of(positive()),
+            option::of(any::<Compaction>()),
+            option::of(any::<Compression>()),
+            option::of(any::<Caching>()),
+            option::of(positive()),
+            option::of(any::<bool>()),
+        ),
+        (
+            option::of(any::<TombstoneGc>()),
+            option::of(any::<PerPartitionRateLimit>()),
+            option::of(positive()),
+            option::of(any::<bool>()),
+            option::of(btree_map(text(), text(), 1..3)),
+        ),
+    )
+        .prop_map(
+            |(
+                (
+                    compact_storage,
+                    clustering_order,
+                    comment,
+                    speculative_retry,
+                    change_data_capture,
+                    gc_grace_seconds,
+                    bloom_filter_fp_chance,
+                ),
+                (default_time_to_live, compaction, compression, caching, memtable_flush_period_in_ms, read_repair),
+                (tombstone_gc, per_partition_rate_limit, paxos_grace_seconds, synchronous_updates, scylla_tags),
+            )| TableOpts {
+                compact_storage,
+                clustering_order,
+                comment,
+                speculative_retry,
+                change_data_capture,
+                gc_grace_seconds,
+                bloom_filter_fp_chance,
+                default_time_to_live,
+                compaction,
+                compression,
+                caching,
+                memtable_flush_period_in_ms,
+                read_repair,
+                tombstone_gc,
+                per_partition_rate_limit,
+                paxos_grace_seconds,
+                synchronous_updates,
+                scylla_tags,
+            }
+        )
+        .prop_filter("No table options", |o| *o != TableOpts::default());
+    ChangeDataCapture => (
+        option::of(any::<bool>()),
+        option::of(any::<CdcPreimage>()),
+        option::of(any::<bool>()),
+        option::of(any::<CdcDelta>()),
+        option::of(positive()),
+    )
+        .prop_map(|(enabled, preimage, postimage, delta, ttl)| ChangeDataCapture {
+            enabled,
+            preimage,
+            postimage,
+            delta,
+            ttl,
+        })
+        .prop_filter("Empty cdc options", |c| *c != ChangeDataCapture::default());
+    CdcPreimage => select(vec![CdcPreimage::Disabled, CdcPreimage::Enabled, CdcPreimage::Full]);
+    CdcDelta => select(vec![CdcDelta::Full, CdcDelta::Keys]);
+    TombstoneGc => (option::of(any::<TombstoneGcMode>()), option::of(positive()))
+        .prop_map(|(mode, propagation_delay_in_seconds)| TombstoneGc {
+            mode,
+            propagation_delay_in_seconds,
+        })
+        .prop_filter("Empty tombstone_gc options", |t| *t != TombstoneGc::default());
+    TombstoneGcMode => select(vec![
+        TombstoneGcMode::Timeout,
+        TombstoneGcMode::Disabled,
+        TombstoneGcMode::Immediate,
+        TombstoneGcMode::Repair,
+    ]);
+    PerPartitionRateLimit => (option::of(positive()), option::of(positive()))
+        .prop_map(|(max_reads_per_second, max_writes_per_second)| PerPartitionRateLimit {
+            max_reads_per_second,
+            max_writes_per_second,
+        })
+        .prop_filter("Empty rate limit options", |l| *l != PerPartitionRateLimit::default());
+    Statement => prop_oneof![
+        any::<DataDefinitionStatement>().prop_map(Statement::DataDefinition),
+        any::<DataManipulationStatement>().prop_map(Statement::DataManipulation),
+        any::<SecondaryIndexStatement>().prop_map(Statement::SecondaryIndex),
+        any::<MaterializedViewStatement>().prop_map(Statement::MaterializedView),
+        any::<RoleStatement>().prop_map(Statement::Role),
+        any::<PermissionStatement>().prop_map(Statement::Permission),
+        any::<UserStatement>().prop_map(Statement::User),
+        any::<UserDefinedFunctionStatement>().prop_map(Statement::UserDefinedFunction),
+        any::<UserDefinedTypeStatement>().prop_map(Statement::UserDefinedType),
+        any::<TriggerStatement>().prop_map(Statement::Trigger),
+        any::<DescribeStatement>().prop_map(Statement::Describe),
+        any::<ServiceLevelStatement>().prop_map(Statement::ServiceLevel),
+    ];
+    DataDefinitionStatement => prop_oneof![
+        any::<UseStatement>().prop_map(DataDefinitionStatement::Use),
+        any::<CreateKeyspaceStatement>().prop_map(DataDefinitionStatement::CreateKeyspace),
+        any::<AlterKeyspaceStatement>().prop_map(DataDefinitionStatement::AlterKeyspace),
+        any::<DropKeyspaceStatement>().prop_map(DataDefinitionStatement::DropKeyspace),
+        any::<CreateTableStatement>().prop_map(DataDefinitionStatement::CreateTable),
+        any::<AlterTableStatement>().prop_map(DataDefinitionStatement::AlterTable),
+        any::<DropTableStatement>().prop_map(DataDefinitionStatement::DropTable),
+        any::<TruncateStatement>().prop_map(DataDefinitionStatement::Truncate),
+    ];
+    UseStatement => any::<Name>().prop_map(|keyspace| UseStatement { keyspace });
+    KeyspaceOpts => (any::<Replication>(), option::of(any::<bool>()))
+        .prop_map(|(replication, durable_writes)| KeyspaceOpts { replication, durable_writes });
+    CreateKeyspaceStatement => (any::<bool>(), any::<Name>(), any::<KeyspaceOpts>()).prop_map(
+        |(if_not_exists, keyspace, options)| CreateKeyspaceStatement {
+            if_not_exists,
+            keyspace,
+            options,
+        }
+    );
+    AlterKeyspaceStatement => (any::<Name>(), any::<KeyspaceOpts>())
+        .prop_map(|(keyspace, options)| AlterKeyspaceStatement { keyspace, options });
+    DropKeyspaceStatement => (any::<bool>(), any::<Name>())
+        .prop_map(|(if_exists, keyspace)| DropKeyspaceStatement { if_exists, keyspace });
+    CreateTableStatement => (
+        any::<bool>(),
+        any::<KeyspaceQualifiedName>(),
+        non_empty(any::<ColumnDefinition>()),
+        option::of(any::<PrimaryKey>()),
+        option::of(base_table_opts()),
+    )
+        .prop_map(|(if_not_exists, table, columns, primary_key, options)| CreateTableStatement {
+            if_not_exists,
+            table,
+            columns,
+            primary_key,
+            options,
+        });
+    AlterTableStatement => (any::<KeyspaceQualifiedName>(), any::<AlterTableInstruction>())
+        .prop_map(|(table, instruction)| AlterTableStatement { table, instruction });
+    AlterTableInstruction => prop_oneof![
+        non_empty(any::<ColumnDefinition>()).prop_map(AlterTableInstruction::Add),
+        non_empty(any::<Name>()).prop_map(AlterTableInstruction::Drop),
+        (any::<Name>(), any::<CqlType>()).prop_map(|(n, t)| AlterTableInstruction::Alter(n, t)),
+        base_table_opts().prop_map(AlterTableInstruction::With),
+    ];
+    DropTableStatement => (any::<bool>(), any::<KeyspaceQualifiedName>())
+        .prop_map(|(if_exists, table)| DropTableStatement { if_exists, table });
+    TruncateStatement => any::<KeyspaceQualifiedName>().prop_map(|table| TruncateStatement { table });
+    DataManipulationStatement => prop_oneof![
+        any::<SelectStatement>().prop_map(DataManipulationStatement::Select),
+        any::<InsertStatement>().prop_map(DataManipulationStatement::Insert),
+        any::<UpdateStatement>().prop_map(DataManipulationStatement::Update),
+        any::<DeleteStatement>().prop_map(DataManipulationStatement::Delete),
+        any::<BatchStatement>().prop_map(DataManipulationStatement::Batch),
+    ];
+    SelectStatement => (
+        (
+            any::<bool>(),
+            any::<SelectClause>(),
+            any::<KeyspaceQualifiedName>(),
+            option::of(any::<WhereClause>()),
+            option::of(any::<GroupByClause>()),
+            option::of(any::<OrderByClause>()),
+        ),
+        (
+            option::of(any::<Limit>()),
+            option::of(any::<Limit>()),
+            any::<bool>(),
+            any::<bool>(),
+            option::of(any::<DurationLiteral>()),
+        ),
+    )
+        .prop_map(
+            |(
+                (distinct, select_clause, from, where_clause, group_by_clause, order_by_clause),
+                (per_partition_limit, limit, allow_filtering, bypass_cache, timeout),
+            )| SelectStatement {
+                distinct,
+                select_clause,
+                from,
+                where_clause,
+                group_by_clause,
+                order_by_clause,
+                per_partition_limit,
+                limit,
+                allow_filtering,
+                bypass_cache,
+                timeout,
+            }
+        );
+    SelectClause => prop_oneof![
+        Just(SelectClause::All),
+        non_empty(any::<Selector>()).prop_map(SelectClause::Selectors),
+    ];
+    SelectorFunction => (any::<Name>(), vec(any::<Selector>(), 0..3))
+        .prop_map(|(function, args)| SelectorFunction { function, args });
+    InsertStatement => (
+        any::<KeyspaceQualifiedName>(),
+        any::<InsertKind>(),
+        any::<bool>(),
+        option::of(update_parameters()),
+    )
+        .prop_map(|(table, kind, if_not_exists, using)| InsertStatement {
+            table,
+            kind,
+            if_not_exists,
+            using,
+        });
+    InsertKind => prop_oneof![
+        non_empty(any::<Name>())
+            .prop_flat_map(|names| {
+                let len = names.len();
+                (Just(names), vec(any::<Term>(), len))
+            })
+            .prop_map(|(names, elements)| InsertKind::NameValue {
+                names,
+                values: TupleLiteral { elements },
+            }),
+        (string("\\{\"[a-z]{1,6}\": [0-9]{1,4}\\}"), option::of(any::<ColumnDefault>()))
+            .prop_map(|(json, default)| InsertKind::Json { json, default }),
+    ];
+    ColumnDefault => select(vec![ColumnDefault::Null, ColumnDefault::Unset]);
+    UpdateParameter => prop_oneof![
+        any::<Limit>().prop_map(UpdateParameter::TTL),
+        any::<Limit>().prop_map(UpdateParameter::Timestamp),
+        any::<DurationLiteral>().prop_map(UpdateParameter::Timeout),
+    ];
+    Limit => prop_oneof![
+        positive().prop_map(Limit::Literal),
+        any::<BindMarker>().prop_map(Limit::BindMarker),
+    ];
+    UpdateStatement => (
+        any::<KeyspaceQualifiedName>(),
+        option::of(update_parameters()),
+        non_empty(any::<Assignment>()),
+        any::<WhereClause>(),
+        option::of(any::<IfClause>()),
+    )
+        .prop_map(|(table, using, set_clause, where_clause, if_clause)| UpdateStatement {
+            table,
+            using,
+            set_clause,
+            where_clause,
+            if_clause,
+        });
+    Assignment => prop_oneof![
+        (any::<SimpleSelection>(), any::<Term>())
+            // `a = b + c` is read as an arithmetic assignment
+            .prop_filter("Ambiguous arithmetic", |(_, term)| match term {
+                Term::ArithmeticOp { lhs: Some(lhs), .. } => !starts_like_name(lhs),
+                _ => true,
+            })
+            .prop_map(|(selection, term)| Assignment::Simple { selection, term }),
+        (any::<Name>(), any::<Name>(), select(vec![ArithmeticOp::Add, ArithmeticOp::Sub]), any::<Term>()).prop_map(
+            |(assignee, lhs, op, rhs)| Assignment::Arithmetic {
+                assignee,
+                lhs,
+                op,
+                rhs,
+            }
+        ),
+        (any::<Name>(), any::<ListLiteral>(), any::<Name>())
+            .prop_map(|(assignee, list, item)| Assignment::Append { assignee, list, item }),
+    ];
+    SimpleSelection => prop_oneof![
+        any::<Name>().prop_map(SimpleSelection::Column),
+        (any::<Name>(), any::<Term>()).prop_map(|(n, t)| SimpleSelection::Term(n, t)),
+        (any::<Name>(), any::<Name>()).prop_map(|(n, f)| SimpleSelection::Field(n, f)),
+    ];
+    Condition => (any::<SimpleSelection>(), any::<Operator>(), any::<Term>())
+        .prop_map(|(lhs, op, rhs)| Condition { lhs, op, rhs });
+    IfClause => prop_oneof![
+        Just(IfClause::Exists),
+        non_empty(any::<Condition>()).prop_map(IfClause::Conditions),
+    ];
+    DeleteStatement => (
+        option::of(non_empty(any::<SimpleSelection>())),
+        any::<KeyspaceQualifiedName>(),
+        option::of(update_parameters()),
+        any::<WhereClause>(),
+        option::of(any::<IfClause>()),
+    )
+        .prop_map(|(selections, from, using, where_clause, if_clause)| DeleteStatement {
+            selections,
+            from,
+            using,
+            where_clause,
+            if_clause,
+        });
+    BatchStatement => (any::<BatchKind>(), option::of(update_parameters()), non_empty(any::<ModificationStatement>()))
+        .prop_map(|(kind, using, statements)| BatchStatement { kind, using, statements });
+    ModificationStatement => prop_oneof![
+        any::<InsertStatement>().prop_map(ModificationStatement::Insert),
+        any::<UpdateStatement>().prop_map(ModificationStatement::Update),
+        any::<DeleteStatement>().prop_map(ModificationStatement::Delete),
+    ];
+    BatchKind => select(vec![BatchKind::Logged, BatchKind::Unlogged, BatchKind::Counter]);
+    WhereClause => non_empty(any::<Relation>()).prop_map(|relations| WhereClause { relations });
+    GroupByClause => non_empty(any::<Name>()).prop_map(|columns| GroupByClause { columns });
+    OrderByClause => non_empty(any::<ColumnOrder>()).prop_map(|columns| OrderByClause { columns });
+    SecondaryIndexStatement => prop_oneof![
+        any::<CreateIndexStatement>().prop_map(SecondaryIndexStatement::Create),
+        any::<DropIndexStatement>().prop_map(SecondaryIndexStatement::Drop),
+    ];
+    CreateIndexStatement => (
+        any::<bool>(),
+        any::<bool>(),
+        option::of(any::<Name>()),
+        any::<KeyspaceQualifiedName>(),
+        any::<IndexIdentifier>(),
+        option::of(any::<IndexClass>()),
+    )
+        .prop_map(|(custom, if_not_exists, name, table, index_id, using)| CreateIndexStatement {
+            custom,
+            if_not_exists,
+            name,
+            table,
+            index_id,
+            using,
+        });
+    IndexIdentifier => prop_oneof![
+        any::<Name>().prop_map(IndexIdentifier::Column),
+        (any::<IndexQualifier>(), any::<Name>()).prop_map(|(q, n)| IndexIdentifier::Qualified(q, n)),
+    ];
+    IndexQualifier => select(vec![
+        IndexQualifier::Keys,
+        IndexQualifier::Values,
+        IndexQualifier::Entries,
+        IndexQualifier::Full,
+    ]);
+    IndexClass => (string("[a-zA-Z][a-zA-Z0-9_.]{0,15}"), option::of(constant_map()))
+        .prop_map(|(path, options)| IndexClass { path, options });
+    DropIndexStatement => (any::<bool>(), any::<KeyspaceQualifiedName>()).prop_map(|(if_exists, name)| DropIndexStatement { if_exists, name });
+    MaterializedViewStatement => prop_oneof![
+        any::<CreateMaterializedViewStatement>().prop_map(MaterializedViewStatement::Create),
+        any::<AlterMaterializedViewStatement>().prop_map(MaterializedViewStatement::Alter),
+        any::<DropMaterializedViewStatement>().prop_map(MaterializedViewStatement::Drop),
+    ];
+    CreateMaterializedViewStatement => (
+        any::<bool>(),
+        any::<KeyspaceQualifiedName>(),
+        any::<SelectStatement>(),
+        any::<PrimaryKey>(),
+        option::of(any::<TableOpts>()),
+    )
+        .prop_map(|(if_not_exists, name, select_statement, primary_key, table_opts)| {
+            CreateMaterializedViewStatement {
+                if_not_exists,
+                name,
+                select_statement: Box::new(select_statement),
+                primary_key,
+                table_opts: table_opts.map(Box::new),
+            }
+        });
+    AlterMaterializedViewStatement => (any::<KeyspaceQualifiedName>(), any::<TableOpts>())
+        .prop_map(|(name, table_opts)| AlterMaterializedViewStatement {
+            name,
+            table_opts: Box::new(table_opts),
+        });
+    DropMaterializedViewStatement => (any::<bool>(), any::<KeyspaceQualifiedName>())
+        .prop_map(|(if_exists, name)| DropMaterializedViewStatement { if_exists, name });
+    FunctionDeclaration => (any::<FunctionName>(), vec(any::<ArgumentDeclaration>(), 0..3))
+        .prop_map(|(name, args)| FunctionDeclaration { name, args });
+    FunctionReference => (any::<FunctionName>(), option::of(vec(any::<CqlType>(), 0..3)))
+        .prop_map(|(name, args)| FunctionReference { name, args });
+    FunctionSignature => (any::<FunctionName>(), vec(any::<CqlType>(), 0..3))
+        .prop_map(|(name, args)| FunctionSignature { name, args });
+    UserDefinedFunctionStatement => prop_oneof![
+        any::<CreateFunctionStatement>().prop_map(UserDefinedFunctionStatement::Create),
+        any::<DropFunctionStatement>().prop_map(UserDefinedFunctionStatement::Drop),
+        any::<CreateAggregateFunctionStatement>().prop_map(UserDefinedFunctionStatement::CreateAggregate),
+        any::<DropAggregateFunctionStatement>().prop_map(UserDefinedFunctionStatement::DropAggregate),
+    ];
+    CreateFunctionStatement => (
+        (any::<bool>(), any::<bool>(), any::<FunctionDeclaration>()),
+        (any::<OnNullInput>(), any::<CqlType>(), any::<Name>(), any::<LitStr>()),
+    )
+        .prop_map(
+            |((or_replace, if_not_exists, func), (on_null_input, return_type, language, body))| {
+                CreateFunctionStatement {
+                    or_replace,
+                    if_not_exists,
+                    func,
+                    on_null_input,
+                    return_type,
+                    language,
+                    body,
+                }
+            }
+        );
+    OnNullInput => select(vec![OnNullInput::Called, OnNullInput::ReturnsNull]);
+    DropFunctionStatement => (any::<bool>(), any::<FunctionReference>())
+        .prop_map(|(if_exists, func)| DropFunctionStatement { if_exists, func });
+    CreateAggregateFunctionStatement => (
+        (any::<bool>(), any::<bool>(), any::<FunctionSignature>()),
+        (
+            any::<FunctionName>(),
+            any::<CqlType>(),
+            option::of(any::<FunctionName>()),
+            option::of(any::<Term>()),
+        ),
+    )
+        .prop_map(
+            |(
+                (or_replace, if_not_exists, func),
+                (state_modifying_fn, state_value_type, final_fn, init_condition),
+            )| CreateAggregateFunctionStatement {
+                or_replace,
+                if_not_exists,
+                func,
+                state_modifying_fn,
+                state_value_type,
+                final_fn,
+                init_condition,
+            }
+        );
+    DropAggregateFunctionStatement => (any::<bool>(), any::<FunctionReference>())
+        .prop_map(|(if_exists, func)| DropAggregateFunctionStatement { if_exists, func });
+    RoleStatement => prop_oneof![
+        any::<CreateRoleStatement>().prop_map(RoleStatement::Create),
+        any::<AlterRoleStatement>().prop_map(RoleStatement::Alter),
+        any::<DropRoleStatement>().prop_map(RoleStatement::Drop),
+        any::<GrantRoleStatement>().prop_map(RoleStatement::Grant),
+        any::<RevokeRoleStatement>().prop_map(RoleStatement::Revoke),
+        any::<ListRolesStatement>().prop_map(RoleStatement::List),
+    ];
+    RoleOpt => prop_oneof![
+        string(text()).prop_map(RoleOpt::Password),
+        any::<bool>().prop_map(RoleOpt::Login),
+        any::<bool>().prop_map(RoleOpt::Superuser),
+        constant_map().prop_map(RoleOpt::Options),
+        btree_set(string("[a-z][a-z0-9_]{0,7}").prop_map(|s| Term::Constant(Constant::String(s))), 1..3)
+            .prop_map(|elements| RoleOpt::AccessToDatacenters(SetLiteral { elements })),
+        Just(RoleOpt::AccessToAllDatacenters),
+    ];
+    CreateRoleStatement => (any::<bool>(), any::<Name>(), option::of(btree_set(any::<RoleOpt>(), 1..4)))
+        .prop_map(|(if_not_exists, name, options)| CreateRoleStatement {
+            if_not_exists,
+            name,
+            options,
+        });
+    AlterRoleStatement => (any::<Name>(), btree_set(any::<RoleOpt>(), 1..4))
+        .prop_map(|(name, options)| AlterRoleStatement { name, options });
+    DropRoleStatement => (any::<bool>(), any::<Name>()).prop_map(|(if_exists, name)| DropRoleStatement { if_exists, name });
+    GrantRoleStatement => (any::<Name>(), any::<Name>()).prop_map(|(name, to)| GrantRoleStatement { name, to });
+    RevokeRoleStatement => (any::<Name>(), any::<Name>()).prop_map(|(name, from)| RevokeRoleStatement { name, from });
+    ListRolesStatement => (option::of(any::<Name>()), any::<bool>())
+        .prop_map(|(of, no_recursive)| ListRolesStatement { of, no_recursive });
+    Permission => select(vec![
+        Permission::Create,
+        Permission::Alter,
+        Permission::Drop,
+        Permission::Select,
+        Permission::Modify,
+        Permission::Authorize,
+        Permission::Describe,
+        Permission::Execute,
+    ]);
+    PermissionKind => prop_oneof![Just(PermissionKind::All), any::<Permission>().prop_map(PermissionKind::One)];
+    Resource => prop_oneof![
+        Just(Resource::AllKeyspaces),
+        any::<Name>().prop_map(Resource::Keyspace),
+        any::<KeyspaceQualifiedName>().prop_map(Resource::Table),
+        Just(Resource::AllRoles),
+        any::<Name>().prop_map(Resource::Role),
+        option::of(any::<Name>()).prop_map(|keyspace| Resource::AllFunctions { keyspace }),
+        any::<FunctionReference>().prop_map(Resource::Function),
+        Just(Resource::AllMBeans),
+        string("[a-zA-Z0-9.:=]{1,16}").prop_map(Resource::MBean),
+    ];
+    PermissionStatement => prop_oneof![
+        any::<GrantPermissionStatement>().prop_map(PermissionStatement::Grant),
+        any::<RevokePermissionStatement>().prop_map(PermissionStatement::Revoke),
+        any::<ListPermissionsStatement>().prop_map(PermissionStatement::List),
+    ];
+    GrantPermissionStatement => (any::<PermissionKind>(), any::<Resource>(), any::<Name>())
+        .prop_map(|(permission, resource, to)| GrantPermissionStatement { permission, resource, to });
+    RevokePermissionStatement => (any::<PermissionKind>(), any::<Resource>(), any::<Name>())
+        .prop_map(|(permission, resource, from)| RevokePermissionStatement {
+            permission,
+            resource,
+            from,
+        });
+    ListPermissionsStatement => (
+        any::<PermissionKind>(),
+        option::of(any::<Resource>()),
+        option::of(any::<Name>()),
+        any::<bool>(),
+    )
+        .prop_map(|(permission, resource, of, no_recursive)| ListPermissionsStatement {
+            permission,
+            resource,
+            // NORECURSIVE is only displayed for a role
+            no_recursive: no_recursive && of.is_some(),
+            of,
+        });
+    UserStatement => prop_oneof![
+        any::<CreateUserStatement>().prop_map(UserStatement::Create),
+        any::<AlterUserStatement>().prop_map(UserStatement::Alter),
+        any::<DropUserStatement>().prop_map(UserStatement::Drop),
+        any::<ListUsersStatement>().prop_map(UserStatement::List),
+    ];
+    CreateUserStatement => (any::<bool>(), any::<Name>(), option::of(string(text())), option::of(any::<bool>()))
+        .prop_map(|(if_not_exists, name, with_password, superuser)| CreateUserStatement {
+            if_not_exists,
+            name,
+            with_password,
+            superuser,
+        });
+    AlterUserStatement => (any::<Name>(), option::of(string(text())), option::of(any::<bool>()))
+        .prop_map(|(name, with_password, superuser)| AlterUserStatement {
+            name,
+            with_password,
+            superuser,
+        });
+    DropUserStatement => (any::<bool>(), any::<Name>()).prop_map(|(if_exists, name)| DropUserStatement { if_exists, name });
+    ListUsersStatement => Just(ListUsersStatement);
+    UserDefinedTypeStatement => prop_oneof![
+        any::<CreateUserDefinedTypeStatement>().prop_map(UserDefinedTypeStatement::Create),
+        any::<AlterUserDefinedTypeStatement>().prop_map(UserDefinedTypeStatement::Alter),
+        any::<DropUserDefinedTypeStatement>().prop_map(UserDefinedTypeStatement::Drop),
+    ];
+    CreateUserDefinedTypeStatement => (any::<bool>(), any::<KeyspaceQualifiedName>(), non_empty(any::<FieldDefinition>()))
+        .prop_map(|(if_not_exists, name, fields)| CreateUserDefinedTypeStatement {
+            if_not_exists,
+            name,
+            fields,
+        });
+    AlterUserDefinedTypeStatement => (any::<KeyspaceQualifiedName>(), any::<AlterTypeInstruction>())
+        .prop_map(|(name, instruction)| AlterUserDefinedTypeStatement { name, instruction });
+    AlterTypeInstruction => prop_oneof![
+        any::<FieldDefinition>().prop_map(AlterTypeInstruction::Add),
+        non_empty((any::<Name>(), any::<Name>())).prop_map(AlterTypeInstruction::Rename),
+    ];
+    DropUserDefinedTypeStatement => (any::<bool>(), any::<KeyspaceQualifiedName>())
+        .prop_map(|(if_exists, name)| DropUserDefinedTypeStatement { if_exists, name });
+    TriggerStatement => prop_oneof![
+        any::<CreateTriggerStatement>().prop_map(TriggerStatement::Create),
+        any::<DropTriggerStatement>().prop_map(TriggerStatement::Drop),
+    ];
+    CreateTriggerStatement => (any::<bool>(), any::<Name>(), any::<KeyspaceQualifiedName>(), string("[a-zA-Z][a-zA-Z0-9_.]{0,15}"))
+        .prop_map(|(if_not_exists, name, table, using)| CreateTriggerStatement {
+            if_not_exists,
+            name,
+            table,
+            using,
+        });
+    DropTriggerStatement => (any::<bool>(), any::<Name>(), any::<KeyspaceQualifiedName>())
+        .prop_map(|(if_exists, name, table)| DropTriggerStatement { if_exists, name, table });
+    DescribeStatement => prop_oneof![
+        Just(DescribeStatement::Cluster),
+        (any::<bool>(), any::<bool>())
+            .prop_map(|(full, with_internals)| DescribeStatement::from(DescribeSchemaStatement { full, with_internals })),
+        Just(DescribeStatement::Keyspaces),
+        (any::<bool>(), option::of(any::<Name>()), any::<bool>())
+            .prop_map(|(only, name, with_internals)| {
+                DescribeStatement::from(DescribeKeyspaceStatement { only, name, with_internals })
+            }),
+        Just(DescribeStatement::Tables),
+        (any::<KeyspaceQualifiedName>(), any::<bool>())
+            .prop_map(|(name, with_internals)| DescribeStatement::from(DescribeTableStatement { name, with_internals })),
+        Just(DescribeStatement::Types),
+        any::<KeyspaceQualifiedName>().prop_map(DescribeStatement::Type),
+        Just(DescribeStatement::Functions),
+        any::<KeyspaceQualifiedName>().prop_map(DescribeStatement::Function),
+        Just(DescribeStatement::Aggregates),
+        any::<KeyspaceQualifiedName>().prop_map(DescribeStatement::Aggregate),
+        (any::<KeyspaceQualifiedName>(), any::<bool>())
+            .prop_map(|(name, with_internals)| {
+                DescribeStatement::from(DescribeMaterializedViewStatement { name, with_internals })
+            }),
+        any::<KeyspaceQualifiedName>().prop_map(DescribeStatement::Index),
+        any::<KeyspaceQualifiedName>().prop_map(DescribeStatement::Object),
+    ];
+    ServiceLevelStatement => prop_oneof![
+        any::<CreateServiceLevelStatement>().prop_map(ServiceLevelStatement::Create),
+        any::<AlterServiceLevelStatement>().prop_map(ServiceLevelStatement::Alter),
+        any::<DropServiceLevelStatement>().prop_map(ServiceLevelStatement::Drop),
+        any::<ListServiceLevelsStatement>().prop_map(ServiceLevelStatement::List),
+        any::<AttachServiceLevelStatement>().prop_map(ServiceLevelStatement::Attach),
+        any::<DetachServiceLevelStatement>().prop_map(ServiceLevelStatement::Detach),
+        any::<ListAttachedServiceLevelsStatement>().prop_map(ServiceLevelStatement::ListAttached),
+    ];
+    WorkloadType => select(vec![WorkloadType::Unspecified, WorkloadType::Interactive, WorkloadType::Batch]);
+    ServiceLevelOpt => prop_oneof![
+        option::of(any::<DurationLiteral>()).prop_map(ServiceLevelOpt::Timeout),
+        any::<WorkloadType>().prop_map(ServiceLevelOpt::WorkloadType),
+        (1..=1000).prop_map(ServiceLevelOpt::Shares),
+    ];
+    CreateServiceLevelStatement => (any::<bool>(), any::<Name>(), option::of(btree_set(any::<ServiceLevelOpt>(), 1..4)))
+        .prop_map(|(if_not_exists, name, options)| CreateServiceLevelStatement {
+            if_not_exists,
+            name,
+            options,
+        });
+    AlterServiceLevelStatement => (any::<Name>(), btree_set(any::<ServiceLevelOpt>(), 1..4))
+        .prop_map(|(name, options)| AlterServiceLevelStatement { name, options });
+    DropServiceLevelStatement => (any::<bool>(), any::<Name>())
+        .prop_map(|(if_exists, name)| DropServiceLevelStatement { if_exists, name });
+    ListServiceLevelsStatement => option::of(any::<Name>()).prop_map(|name| ListServiceLevelsStatement { name });
+    AttachServiceLevelStatement => (any::<Name>(), any::<Name>())
+        .prop_map(|(name, to)| AttachServiceLevelStatement { name, to });
+    DetachServiceLevelStatement => any::<Name>().prop_map(|from| DetachServiceLevelStatement { from });
+    ListAttachedServiceLevelsStatement => option::of(any::<Name>())
+        .prop_map(|of| ListAttachedServiceLevelsStatement { of });
+}
+
+fn update_parameters() -> impl Strategy<Value = Vec<UpdateParameter>> {
+    // Each parameter may only be given once
+    (
+        option::of(any::<Limit>().prop_map(UpdateParameter::TTL)),
+        option::of(any::<Limit>().prop_map(UpdateParameter::Timestamp)),
+        option::of(any::<DurationLiteral>().prop_map(UpdateParameter::Timeout)),
+    )
+        .prop_map(|(ttl, timestamp, timeout)| ttl.into_iter().chain(timestamp).chain(timeout).collect::<Vec<_>>())
+        .prop_filter("No update parameters", |params| !params.is_empty())
+        .prop_shuffle()
+}
+
+/// Options of tables which are not materialized views.
+fn base_table_opts() -> impl Strategy<Value = TableOpts> {
+    any::<TableOpts>().prop_filter("View only option", |o| o.synchronous_updates.is_none())
+}
+
+#[allow(clippy::type_complexity)]
+fn compaction_common() -> impl Strategy<
+    Value = (
+        Option<bool>,
+        Option<f32>,
+        Option<i32>,
+        Option<bool>,
+        Option<bool>,
+        Option<bool>,
+        Option<i32>,
+        Option<i32>,
+    ),
+> {
+    (
+        option::of(any::<bool>()),
+        option::of(chance()),
+        option::of(positive()),
+        option::of(any::<bool>()),
+        option::of(any::<bool>()),
+        option::of(any::<bool>()),
+        option::of(positive()),
+        option::of(positive()),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_syntax() {
+        round_trip::<Name>().unwrap();
+        round_trip::<KeyspaceQualifiedName>().unwrap();
+        round_trip::<LitStr>().unwrap();
+        round_trip::<BindMarker>().unwrap();
+        round_trip::<Operator>().unwrap();
+        round_trip::<Constant>().unwrap();
+        round_trip::<CqlType>().unwrap();
+        round_trip::<Term>().unwrap();
+        round_trip::<Selector>().unwrap();
+        round_trip::<DurationLiteral>().unwrap();
+    }
+
+    #[test]
+    fn test_round_trip_options() {
+        round_trip::<StatementOpt>().unwrap();
+        round_trip::<ColumnDefinition>().unwrap();
+        round_trip::<PrimaryKey>().unwrap();
+        round_trip::<Relation>().unwrap();
+        round_trip::<Replication>().unwrap();
+        round_trip::<SpeculativeRetry>().unwrap();
+        round_trip::<TableOpts>().unwrap();
+    }
+
+    #[test]
+    fn test_round_trip_statements() {
+        round_trip::<DataDefinitionStatement>().unwrap();
+        round_trip::<DataManipulationStatement>().unwrap();
+        round_trip::<SecondaryIndexStatement>().unwrap();
+        round_trip::<MaterializedViewStatement>().unwrap();
+        round_trip::<RoleStatement>().unwrap();
+        round_trip::<PermissionStatement>().unwrap();
+        round_trip::<UserStatement>().unwrap();
+        round_trip::<UserDefinedFunctionStatement>().unwrap();
+        round_trip::<UserDefinedTypeStatement>().unwrap();
+        round_trip::<TriggerStatement>().unwrap();
+        round_trip::<DescribeStatement>().unwrap();
+        round_trip::<ServiceLevelStatement>().unwrap();
+        round_trip::<Statement>().unwrap();
+    }
+}
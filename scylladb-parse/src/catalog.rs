@@ -0,0 +1,689 @@
+use super::{
+    diff::same_type,
+    schema::ident,
+    validate::term_matches,
+    *,
+};
+
+/// Whether a function computes a value per row or over a group of rows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FunctionKind {
+    Scalar,
+    Aggregate,
+}
+
+/// A function call resolved to the signature of a built-in or user defined function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolvedFunction {
+    /// The qualified name of the function. Built-in functions belong to the `system` keyspace.
+    pub name: FunctionName,
+    pub kind: FunctionKind,
+    pub args: Vec<CqlType>,
+    pub return_type: CqlType,
+}
+
+/// A column in the result of a `SELECT` statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResultColumn {
+    /// The alias of the selector, or otherwise the name of the selected column or the selector's text
+    pub name: String,
+    pub data_type: CqlType,
+}
+
+/// Resolves function calls against the built-in functions and the user defined functions and aggregates of a
+/// schema, for statements on a given table.
+pub struct FunctionCatalog<'a> {
+    schema: &'a Schema,
+    keyspace: &'a KeyspaceSchema,
+    table: &'a TableSchema,
+}
+
+impl Schema {
+    /// Get the function catalog for statements on a table or materialized view.
+    pub fn function_catalog(&self, table: impl Into<KeyspaceQualifiedName>) -> anyhow::Result<FunctionCatalog<'_>> {
+        let table = table.into();
+        let keyspace = self
+            .keyspaces
+            .get(&self.keyspace_of(&table)?)
+            .ok_or_else(|| anyhow::anyhow!("Unknown table {}", table))?;
+        Ok(FunctionCatalog::new(
+            self,
+            keyspace,
+            keyspace
+                .relation(table.name.clone())
+                .ok_or_else(|| anyhow::anyhow!("Unknown table {}", table))?,
+        ))
+    }
+
+    /// Predict the columns returned by a select statement.
+    pub fn result_columns(&self, statement: &SelectStatement) -> anyhow::Result<Vec<ResultColumn>> {
+        self.function_catalog(statement.from.clone())?
+            .result_columns(&statement.select_clause)
+    }
+}
+
+/// An argument of a function call
+#[derive(Copy, Clone)]
+enum Arg<'t> {
+    Selector(&'t Selector),
+    Term(&'t Term),
+}
+
+impl Display for Arg<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Selector(s) => s.fmt(f),
+            Self::Term(t) => t.fmt(f),
+        }
+    }
+}
+
+fn native(t: NativeType) -> CqlType {
+    CqlType::Native(t)
+}
+
+fn system(name: &str) -> FunctionName {
+    FunctionName {
+        keyspace: Some(Name::Unquoted("system".to_string())),
+        name: Name::Unquoted(name.to_string()),
+    }
+}
+
+fn is_numeric(data_type: &CqlType) -> bool {
+    use NativeType::*;
+    matches!(
+        data_type,
+        CqlType::Native(Tinyint | Smallint | Int | Bigint | Varint | Decimal | Float | Double | Counter)
+    )
+}
+
+/// Whether a value can be converted by a `CAST` selector.
+fn is_castable(from: &CqlType, to: &CqlType) -> bool {
+    use NativeType::*;
+    same_type(from, to)
+        || is_numeric(from) && is_numeric(to) && *to != native(Counter)
+        || match (from, to) {
+            (CqlType::Native(f), CqlType::Native(Ascii | Text | Varchar)) => *f != Blob,
+            (CqlType::Native(f), CqlType::Native(t)) => matches!(
+                (f, t),
+                (Date, Timestamp) | (Timestamp, Date) | (Timeuuid, Date | Timestamp)
+            ),
+            _ => false,
+        }
+}
+
+/// The type of a constant when it is not assigned to a value of a known type.
+fn natural_type(constant: &Constant) -> Option<CqlType> {
+    Some(native(match constant {
+        Constant::Null => return None,
+        Constant::String(_) => NativeType::Text,
+        Constant::Integer(i) if i.parse::<i32>().is_ok() => NativeType::Int,
+        Constant::Integer(i) if i.parse::<i64>().is_ok() => NativeType::Bigint,
+        Constant::Integer(_) => NativeType::Varint,
+        Constant::Float(_) => NativeType::Double,
+        Constant::Boolean(_) => NativeType::Boolean,
+        Constant::Uuid(_) => NativeType::Uuid,
+        Constant::Hex(_) | Constant::Blob(_) => NativeType::Blob,
+    }))
+}
+
+/// The built-in functions with fixed signatures, as their overloads' argument and return types
+fn builtin_signatures(name: &str) -> Vec<(Vec<NativeType>, NativeType)> {
+    use NativeType::*;
+    match name {
+        "now" | "currenttimeuuid" => vec![(vec![], Timeuuid)],
+        "uuid" => vec![(vec![], Uuid)],
+        "currenttimestamp" => vec![(vec![], Timestamp)],
+        "currentdate" => vec![(vec![], Date)],
+        "currenttime" => vec![(vec![], Time)],
+        "mintimeuuid" | "maxtimeuuid" => vec![(vec![Timestamp], Timeuuid)],
+        "dateof" => vec![(vec![Timeuuid], Timestamp)],
+        "unixtimestampof" => vec![(vec![Timeuuid], Bigint)],
+        "todate" => vec![(vec![Timeuuid], Date), (vec![Timestamp], Date)],
+        "totimestamp" => vec![(vec![Timeuuid], Timestamp), (vec![Date], Timestamp)],
+        "tounixtimestamp" => vec![
+            (vec![Timeuuid], Bigint),
+            (vec![Timestamp], Bigint),
+            (vec![Date], Bigint),
+        ],
+        n => {
+            if let Some(t) = n.strip_prefix("blobas").and_then(|t| NativeType::from_str(t).ok()) {
+                vec![(vec![Blob], t)]
+            } else if let Some(t) = n.strip_suffix("asblob").and_then(|t| NativeType::from_str(t).ok()) {
+                vec![(vec![t], Blob)]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl<'a> FunctionCatalog<'a> {
+    pub(crate) fn new(schema: &'a Schema, keyspace: &'a KeyspaceSchema, table: &'a TableSchema) -> Self {
+        Self {
+            schema,
+            keyspace,
+            table,
+        }
+    }
+
+    fn column(&self, name: &Name) -> anyhow::Result<&'a ColumnSchema> {
+        self.table
+            .column(name.clone())
+            .ok_or_else(|| anyhow::anyhow!("Unknown column {} in table {}", name, self.table.name))
+    }
+
+    /// Resolve a function call in a term. The expected type is required by functions whose return type depends
+    /// on the value they are assigned to, such as `fromJson`.
+    pub fn resolve_call(&self, call: &FunctionCall, expected: Option<&CqlType>) -> anyhow::Result<ResolvedFunction> {
+        let args = call.args.iter().map(Arg::Term).collect::<Vec<_>>();
+        self.resolve(&call.name, &args, expected)
+    }
+
+    /// Resolve a function call in a selector.
+    pub fn resolve_selector(&self, function: &SelectorFunction) -> anyhow::Result<ResolvedFunction> {
+        let args = function.args.iter().map(Arg::Selector).collect::<Vec<_>>();
+        let name = FunctionName {
+            keyspace: None,
+            name: function.function.clone(),
+        };
+        self.resolve(&name, &args, None)
+    }
+
+    /// Get the type of the values returned by a selector.
+    pub fn selector_type(&self, selector: &Selector) -> anyhow::Result<CqlType> {
+        Ok(match &selector.kind {
+            SelectorKind::Column(c) => self.column(c)?.data_type.clone(),
+            SelectorKind::Term(t) => self
+                .term_type(t)
+                .ok_or_else(|| anyhow::anyhow!("Cannot infer the type of selector {}", t))?,
+            SelectorKind::Cast(s, t) => {
+                let from = self.selector_type(s)?;
+                if !is_castable(&from, t) {
+                    anyhow::bail!("Cannot cast {} of type {} to {}", s, from, t);
+                }
+                t.clone()
+            }
+            SelectorKind::Function(f) => self.resolve_selector(f)?.return_type,
+            SelectorKind::Count => native(NativeType::Bigint),
+        })
+    }
+
+    /// Get the columns returned by a selection.
+    pub fn result_columns(&self, select_clause: &SelectClause) -> anyhow::Result<Vec<ResultColumn>> {
+        Ok(match select_clause {
+            // Primary key columns come first, followed by the others in alphabetical order
+            SelectClause::All => {
+                let mut others = self
+                    .table
+                    .columns
+                    .iter()
+                    .filter(|c| !c.is_primary_key())
+                    .collect::<Vec<_>>();
+                others.sort_by_key(|c| ident(&c.name));
+                self.table
+                    .partition_key_columns()
+                    .chain(self.table.clustering_key_columns())
+                    .chain(others)
+                    .map(|c| ResultColumn {
+                        name: ident(&c.name),
+                        data_type: c.data_type.clone(),
+                    })
+                    .collect()
+            }
+            SelectClause::Selectors(selectors) => selectors
+                .iter()
+                .map(|s| {
+                    Ok(ResultColumn {
+                        name: match (&s.as_id, &s.kind) {
+                            (Some(id), _) => ident(id),
+                            (None, SelectorKind::Column(c)) => ident(c),
+                            (None, SelectorKind::Count) => "count".to_string(),
+                            (None, kind) => kind.to_string(),
+                        },
+                        data_type: self.selector_type(s)?,
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+
+    /// Get the type of a term, when it does not depend on the value it is assigned to
+    fn term_type(&self, term: &Term) -> Option<CqlType> {
+        match term {
+            Term::Constant(c) => natural_type(c),
+            Term::FunctionCall(f) => self.resolve_call(f, None).ok().map(|f| f.return_type),
+            Term::TypeHint { hint, .. } => Some(hint.clone()),
+            _ => None,
+        }
+    }
+
+    /// Get the type of an argument, if it is known without considering the function's parameters.
+    /// Constants and bind markers take the type of the parameter they are passed to.
+    fn arg_type(&self, arg: Arg) -> anyhow::Result<Option<CqlType>> {
+        Ok(match arg {
+            Arg::Selector(Selector {
+                kind: SelectorKind::Term(t),
+                ..
+            })
+            | Arg::Term(t) => match t {
+                Term::FunctionCall(f) => self.resolve_call(f, None).ok().map(|f| f.return_type),
+                Term::TypeHint { hint, .. } => Some(hint.clone()),
+                _ => None,
+            },
+            Arg::Selector(s) => Some(self.selector_type(s)?),
+        })
+    }
+
+    fn arg_matches(&self, arg: Arg, param: &CqlType) -> anyhow::Result<bool> {
+        Ok(match self.arg_type(arg)? {
+            Some(t) => same_type(&t, param),
+            None => match arg {
+                Arg::Selector(Selector {
+                    kind: SelectorKind::Term(t),
+                    ..
+                })
+                | Arg::Term(t) => match t {
+                    Term::FunctionCall(f) => self
+                        .resolve_call(f, Some(param))
+                        .is_ok_and(|f| same_type(&f.return_type, param)),
+                    t => term_matches(self.keyspace, param, t),
+                },
+                Arg::Selector(_) => false,
+            },
+        })
+    }
+
+    /// Get the type of the argument of a generic function, which cannot be taken from its parameters
+    fn infer_arg(&self, name: &FunctionName, arg: Arg) -> anyhow::Result<CqlType> {
+        let data_type = match arg {
+            Arg::Selector(Selector {
+                kind: SelectorKind::Term(t),
+                ..
+            })
+            | Arg::Term(t) => self.term_type(t),
+            Arg::Selector(s) => Some(self.selector_type(s)?),
+        };
+        data_type.ok_or_else(|| anyhow::anyhow!("Cannot infer the type of argument {} of function {}", arg, name))
+    }
+
+    fn resolve(
+        &self,
+        name: &FunctionName,
+        args: &[Arg],
+        expected: Option<&CqlType>,
+    ) -> anyhow::Result<ResolvedFunction> {
+        let function = ident(&name.name);
+        let builtin = name.keyspace.as_ref().map(|ks| ident(ks) == "system").unwrap_or(true);
+        if builtin {
+            if let Some(res) = self.resolve_generic(name, &function, args, expected) {
+                return res;
+            }
+        }
+        let mut candidates = Vec::new();
+        if builtin {
+            candidates.extend(
+                builtin_signatures(&function)
+                    .into_iter()
+                    .map(|(a, r)| ResolvedFunction {
+                        name: system(&function),
+                        kind: FunctionKind::Scalar,
+                        args: a.into_iter().map(native).collect(),
+                        return_type: native(r),
+                    }),
+            );
+        }
+        let keyspace = match &name.keyspace {
+            Some(ks) if ident(ks) == "system" => None,
+            Some(ks) => Some(
+                self.schema
+                    .keyspace(ks.clone())
+                    .ok_or_else(|| anyhow::anyhow!("Unknown keyspace {}", ks))?,
+            ),
+            None => Some(self.keyspace),
+        };
+        if let Some(keyspace) = keyspace {
+            let qualified = FunctionName {
+                keyspace: Some(keyspace.name.clone()),
+                name: name.name.clone(),
+            };
+            candidates.extend(keyspace.functions(name.name.clone()).map(|f| ResolvedFunction {
+                name: qualified.clone(),
+                kind: FunctionKind::Scalar,
+                args: f.func.args.iter().map(|a| a.data_type.clone()).collect(),
+                return_type: f.return_type.clone(),
+            }));
+            for a in keyspace.aggregates(name.name.clone()) {
+                let return_type = match &a.final_fn {
+                    Some(final_fn) => keyspace
+                        .functions(final_fn.name.clone())
+                        .find(|f| f.func.args.len() == 1 && same_type(&f.func.args[0].data_type, &a.state_value_type))
+                        .map(|f| f.return_type.clone())
+                        .ok_or_else(|| anyhow::anyhow!("Unknown final function {} of aggregate {}", final_fn, name))?,
+                    None => a.state_value_type.clone(),
+                };
+                candidates.push(ResolvedFunction {
+                    name: qualified.clone(),
+                    kind: FunctionKind::Aggregate,
+                    args: a.func.args.clone(),
+                    return_type,
+                });
+            }
+        }
+        if candidates.is_empty() {
+            anyhow::bail!("Unknown function {}", name);
+        }
+        candidates.retain(|c| c.args.len() == args.len());
+        if candidates.is_empty() {
+            anyhow::bail!(
+                "Invalid number of arguments in call to function {}: found {}",
+                name,
+                args.len()
+            );
+        }
+        let mut matching = Vec::new();
+        for c in candidates.iter() {
+            let mut matches = true;
+            for (arg, param) in args.iter().zip(c.args.iter()) {
+                matches &= self.arg_matches(*arg, param)?;
+            }
+            if matches {
+                matching.push(c);
+            }
+        }
+        if matching.len() > 1 {
+            if let Some(expected) = expected {
+                matching.retain(|c| same_type(&c.return_type, expected));
+            }
+        }
+        let signatures = |c: &[&ResolvedFunction]| {
+            c.iter()
+                .map(|c| {
+                    format!(
+                        "{}({})",
+                        c.name,
+                        c.args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", ")
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match matching[..] {
+            [res] => Ok(res.clone()),
+            [] => anyhow::bail!(
+                "Invalid arguments in call to function {}, expected one of: {}",
+                name,
+                signatures(&candidates.iter().collect::<Vec<_>>())
+            ),
+            _ => anyhow::bail!(
+                "Ambiguous call to function {}, candidates: {}",
+                name,
+                signatures(&matching)
+            ),
+        }
+    }
+
+    /// Resolve the built-in functions whose signatures depend on their arguments, the table or the expected type.
+    fn resolve_generic(
+        &self,
+        name: &FunctionName,
+        function: &str,
+        args: &[Arg],
+        expected: Option<&CqlType>,
+    ) -> Option<anyhow::Result<ResolvedFunction>> {
+        let res = |args: Vec<CqlType>, return_type: CqlType, kind: FunctionKind| ResolvedFunction {
+            name: system(function),
+            kind,
+            args,
+            return_type,
+        };
+        let single_arg = || match args {
+            [arg] => Ok(*arg),
+            _ => Err(anyhow::anyhow!(
+                "Invalid number of arguments in call to function {}: found {}",
+                name,
+                args.len()
+            )),
+        };
+        let resolve = || -> anyhow::Result<ResolvedFunction> {
+            Ok(match function {
+                "token" => {
+                    let types = self
+                        .table
+                        .partition_key_columns()
+                        .map(|c| c.data_type.clone())
+                        .collect::<Vec<_>>();
+                    if types.len() != args.len() {
+                        anyhow::bail!(
+                            "Function {} expects the {} partition key columns of table {}, found {} arguments",
+                            name,
+                            types.len(),
+                            self.table.name,
+                            args.len()
+                        );
+                    }
+                    for (arg, t) in args.iter().zip(types.iter()) {
+                        if !self.arg_matches(*arg, t)? {
+                            anyhow::bail!("Invalid argument {} for function {}, expected {}", arg, name, t);
+                        }
+                    }
+                    res(types, native(NativeType::Bigint), FunctionKind::Scalar)
+                }
+                "writetime" | "ttl" => {
+                    let column = match single_arg()? {
+                        Arg::Selector(Selector {
+                            kind: SelectorKind::Column(c),
+                            ..
+                        }) => self.column(c)?,
+                        arg => anyhow::bail!("Function {} expects a column, found {}", name, arg),
+                    };
+                    if column.is_primary_key() {
+                        anyhow::bail!("Cannot use {} on primary key column {}", name, column.name);
+                    }
+                    let return_type = if function == "ttl" {
+                        NativeType::Int
+                    } else {
+                        NativeType::Bigint
+                    };
+                    res(
+                        vec![column.data_type.clone()],
+                        native(return_type),
+                        FunctionKind::Scalar,
+                    )
+                }
+                "tojson" => res(
+                    vec![self.infer_arg(name, single_arg()?)?],
+                    native(NativeType::Text),
+                    FunctionKind::Scalar,
+                ),
+                "fromjson" => {
+                    let text = native(NativeType::Text);
+                    let arg = single_arg()?;
+                    if !self.arg_matches(arg, &text)? {
+                        anyhow::bail!("Invalid argument {} for function {}, expected {}", arg, name, text);
+                    }
+                    let return_type = expected.cloned().ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Function {} can only be used where the type of its result is known",
+                            name
+                        )
+                    })?;
+                    res(vec![text], return_type, FunctionKind::Scalar)
+                }
+                "count" => res(
+                    vec![self.infer_arg(name, single_arg()?)?],
+                    native(NativeType::Bigint),
+                    FunctionKind::Aggregate,
+                ),
+                "max" | "min" => {
+                    let t = self.infer_arg(name, single_arg()?)?;
+                    res(vec![t.clone()], t, FunctionKind::Aggregate)
+                }
+                "sum" | "avg" => {
+                    let t = self.infer_arg(name, single_arg()?)?;
+                    if !is_numeric(&t) {
+                        anyhow::bail!("Function {} expects a numeric argument, found {}", name, t);
+                    }
+                    res(vec![t.clone()], t, FunctionKind::Aggregate)
+                }
+                _ => unreachable!(),
+            })
+        };
+        matches!(
+            function,
+            "token" | "writetime" | "ttl" | "tojson" | "fromjson" | "count" | "max" | "min" | "sum" | "avg"
+        )
+        .then(resolve)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        CREATE KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+        USE movies;
+        CREATE TABLE nerd_movies (
+            movie text,
+            year int,
+            released date,
+            updated timeuuid,
+            rating float,
+            tags set<text>,
+            PRIMARY KEY ((movie), year)
+        );
+        CREATE FUNCTION double_rating (r float) RETURNS NULL ON NULL INPUT RETURNS double LANGUAGE lua AS 'return r * 2';
+        CREATE FUNCTION double_rating (r double) RETURNS NULL ON NULL INPUT RETURNS double LANGUAGE lua AS 'return r * 2';
+        CREATE FUNCTION rating_state (s tuple<int, double>, r float) CALLED ON NULL INPUT RETURNS tuple<int, double> LANGUAGE lua AS 'return s';
+        CREATE FUNCTION rating_final (s tuple<int, double>) CALLED ON NULL INPUT RETURNS double LANGUAGE lua AS 'return 0';
+        CREATE AGGREGATE average_rating (float) SFUNC rating_state STYPE tuple<int, double> FINALFUNC rating_final INITCOND (0, 0);
+    "#;
+
+    fn result_columns(statement: &str) -> anyhow::Result<Vec<(String, String)>> {
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        Ok(schema
+            .result_columns(&statement.parse().unwrap())?
+            .into_iter()
+            .map(|c| (c.name, c.data_type.to_string()))
+            .collect())
+    }
+
+    fn column(name: &str, data_type: &str) -> (String, String) {
+        (name.to_string(), data_type.parse::<CqlType>().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_result_columns() {
+        assert_eq!(
+            result_columns("SELECT * FROM nerd_movies").unwrap(),
+            vec![
+                column("movie", "text"),
+                column("year", "int"),
+                column("rating", "float"),
+                column("released", "date"),
+                column("tags", "set<text>"),
+                column("updated", "timeuuid"),
+            ]
+        );
+        assert_eq!(
+            result_columns(
+                "SELECT movie, toTimestamp(released), toDate(updated) AS day, writetime(rating), ttl(tags), \
+                 token(movie), toJson(tags), blobAsInt(intAsBlob(year)), CAST(year AS text), COUNT(*), \
+                 max(rating), sum(year), double_rating(rating), average_rating(rating), now() FROM nerd_movies"
+            )
+            .unwrap(),
+            vec![
+                column("movie", "text"),
+                column("toTimestamp(released)", "timestamp"),
+                column("day", "date"),
+                column("writetime(rating)", "bigint"),
+                column("ttl(tags)", "int"),
+                column("token(movie)", "bigint"),
+                column("toJson(tags)", "text"),
+                column("blobAsInt(intAsBlob(year))", "int"),
+                column("CAST(year AS TEXT)", "text"),
+                column("count", "bigint"),
+                column("max(rating)", "float"),
+                column("sum(year)", "int"),
+                column("double_rating(rating)", "double"),
+                column("average_rating(rating)", "double"),
+                column("now()", "timeuuid"),
+            ]
+        );
+        for (statement, error) in [
+            ("SELECT unknown(movie) FROM nerd_movies", "Unknown function unknown"),
+            (
+                "SELECT toDate(movie) FROM nerd_movies",
+                "Invalid arguments in call to function toDate, expected one of: system.todate(TIMEUUID), system.todate(TIMESTAMP)",
+            ),
+            (
+                "SELECT now(movie) FROM nerd_movies",
+                "Invalid number of arguments in call to function now: found 1",
+            ),
+            (
+                "SELECT writetime(year) FROM nerd_movies",
+                "Cannot use writetime on primary key column year",
+            ),
+            (
+                "SELECT sum(movie) FROM nerd_movies",
+                "Function sum expects a numeric argument, found TEXT",
+            ),
+            (
+                "SELECT token(movie, year) FROM nerd_movies",
+                "Function token expects the 1 partition key columns of table nerd_movies, found 2 arguments",
+            ),
+            ("SELECT CAST(tags AS int) FROM nerd_movies", "Cannot cast tags of type SET<TEXT> to INT"),
+            (
+                "SELECT double_rating(year) FROM nerd_movies",
+                "Invalid arguments in call to function double_rating, expected one of: movies.double_rating(FLOAT), movies.double_rating(DOUBLE)",
+            ),
+        ] {
+            assert_eq!(result_columns(statement).unwrap_err().to_string(), error, "{}", statement);
+        }
+    }
+
+    #[test]
+    fn test_resolve_calls() {
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        let catalog = schema.function_catalog("nerd_movies").unwrap();
+        let resolve = |call: &str, expected: Option<&str>| {
+            catalog
+                .resolve_call(
+                    &call.parse().unwrap(),
+                    expected.map(|e| e.parse::<CqlType>().unwrap()).as_ref(),
+                )
+                .map(|f| (f.name.to_string(), f.return_type.to_string()))
+        };
+        assert_eq!(
+            resolve("minTimeuuid('2013-01-01 00:05+0000')", None).unwrap(),
+            ("system.mintimeuuid".to_string(), "TIMEUUID".to_string())
+        );
+        assert_eq!(
+            resolve("fromJson('[1, 2]')", Some("list<int>")).unwrap(),
+            ("system.fromjson".to_string(), "LIST<INT>".to_string())
+        );
+        assert!(resolve("fromJson('[1, 2]')", None).is_err());
+        assert_eq!(
+            resolve("movies.double_rating(?)", None).unwrap_err().to_string(),
+            "Ambiguous call to function movies.double_rating, candidates: movies.double_rating(FLOAT), movies.double_rating(DOUBLE)"
+        );
+        assert_eq!(
+            resolve("double_rating(1.5)", None).unwrap_err().to_string(),
+            "Ambiguous call to function double_rating, candidates: movies.double_rating(FLOAT), movies.double_rating(DOUBLE)"
+        );
+        assert_eq!(
+            resolve("double_rating(double r)", None).unwrap().0,
+            "movies.double_rating"
+        );
+        assert_eq!(
+            resolve("toUnixTimestamp(now())", None).unwrap(),
+            ("system.tounixtimestamp".to_string(), "BIGINT".to_string())
+        );
+        assert_eq!(resolve("textAsBlob('a')", None).unwrap().1, "BLOB");
+        assert_eq!(
+            resolve("other.f(1)", None).unwrap_err().to_string(),
+            "Unknown keyspace other"
+        );
+    }
+}
@@ -17,10 +17,14 @@ use super::{
     Name,
     Parens,
     Parse,
+    ParseError,
     SignedNumber,
     StatementStream,
     Tag,
     TokenWrapper,
+    Visit,
+    VisitMut,
+    Visitable,
 };
 use chrono::{
     Datelike,
@@ -57,7 +61,8 @@ use std::{
 };
 use uuid::Uuid;
 
-#[derive(ParseFromStr, Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ArithmeticOp {
     Add,
     Sub,
@@ -104,7 +109,8 @@ impl Parse for ArithmeticOp {
     }
 }
 
-#[derive(ParseFromStr, Copy, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Operator {
     Equal,
     NotEqual,
@@ -279,7 +285,8 @@ impl Parse for TimeUnit {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, From)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, From, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Term {
     Constant(Constant),
     Literal(Literal),
@@ -522,7 +529,8 @@ impl_from_constant_to_term!(f64);
 impl_from_constant_to_term!(bool);
 impl_from_constant_to_term!(Uuid);
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Constant {
     Null,
     String(LitStr),
@@ -571,6 +579,9 @@ impl Parse for Constant {
             Constant::Null
         } else if let Some(ss) = s.parse()? {
             Constant::String(ss)
+        } else if s.peekn(2).is_some_and(|s| s.eq_ignore_ascii_case("0x")) {
+            s.nextn(2);
+            Constant::Blob(s.parse_from::<Hex>()?)
         } else if let Some(f) = s.parse_from::<Option<Float>>()? {
             Constant::Float(f)
         } else if let Some(i) = s.parse_from::<Option<SignedNumber>>()? {
@@ -579,9 +590,6 @@ impl Parse for Constant {
             Constant::Boolean(b)
         } else if let Some(u) = s.parse()? {
             Constant::Uuid(u)
-        } else if s.peekn(2).map(|s| s.to_lowercase().as_str() == "0x").unwrap_or(false) {
-            s.nextn(2);
-            Constant::Blob(s.parse_from::<Hex>()?)
         } else if let Some(h) = s.parse_from::<Option<Hex>>()? {
             Constant::Hex(h)
         } else {
@@ -731,7 +739,8 @@ impl From<Uuid> for Constant {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Literal {
     Collection(CollectionTypeLiteral),
     UserDefined(UserDefinedTypeLiteral),
@@ -763,7 +772,8 @@ impl Display for Literal {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, From, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, From, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CqlType {
     Native(NativeType),
     #[from(ignore)]
@@ -817,7 +827,8 @@ impl From<CollectionType> for CqlType {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NativeType {
     Ascii,
     Bigint,
@@ -916,7 +927,8 @@ impl Parse for NativeType {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CollectionTypeLiteral {
     List(ListLiteral),
     Set(SetLiteral),
@@ -948,7 +960,8 @@ impl Display for CollectionTypeLiteral {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CollectionType {
     List(CqlType),
     Set(CqlType),
@@ -998,9 +1011,11 @@ impl Display for CollectionType {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedMapLiteral)]
 pub struct MapLiteral {
+    #[cfg_attr(feature = "serde", serde(with = "crate::map_entries"))]
     pub elements: BTreeMap<Term, Term>,
 }
 
@@ -1083,7 +1098,8 @@ impl<T: Into<Term>> From<BTreeMap<T, T>> for MapLiteral {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TupleLiteral {
     pub elements: Vec<Term>,
 }
@@ -1122,7 +1138,8 @@ impl<T: Into<Term>> From<Vec<T>> for TupleLiteral {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SetLiteral {
     pub elements: BTreeSet<Term>,
 }
@@ -1172,7 +1189,8 @@ impl<T: Into<Term>> From<BTreeSet<T>> for SetLiteral {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListLiteral {
     pub elements: Vec<Term>,
 }
@@ -1292,7 +1310,8 @@ impl ISO8601Builder {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, Default, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, Default, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DurationLiteral {
     pub months: i32,
     pub days: i32,
@@ -1666,8 +1685,10 @@ impl From<ISO8601> for DurationLiteral {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UserDefinedTypeLiteral {
+    #[cfg_attr(feature = "serde", serde(with = "crate::map_entries"))]
     pub fields: BTreeMap<Name, Term>,
 }
 
@@ -1708,6 +1729,8 @@ pub type UserDefinedType = KeyspaceQualifiedName;
 #[cfg(test)]
 mod test {
     use super::*;
+    #[cfg(feature = "serde")]
+    use crate::Statement;
 
     #[test]
     fn test_duration_literals() {
@@ -1769,4 +1792,47 @@ mod test {
         assert!("2w6y8mo96ns4u".parse::<DurationLiteral>().is_err());
         assert!("2w6b8mo96ns4us".parse::<DurationLiteral>().is_err());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_representation() {
+        assert_eq!(
+            serde_json::to_value("movies.\"Nerd Movies\"".parse::<KeyspaceQualifiedName>().unwrap()).unwrap(),
+            serde_json::json!({"keyspace": {"Unquoted": "movies"}, "name": {"Quoted": "Nerd Movies"}})
+        );
+        assert_eq!(
+            serde_json::to_value("{'scifi': 1}".parse::<MapLiteral>().unwrap()).unwrap(),
+            serde_json::json!({"elements": [[
+                {"Constant": {"String": {"kind": "Quoted", "value": "scifi"}}},
+                {"Constant": {"Integer": "1"}}
+            ]]})
+        );
+        assert_eq!(
+            serde_json::to_value("?".parse::<Term>().unwrap()).unwrap(),
+            serde_json::json!({"BindMarker": "Anonymous"})
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        for statement in [
+            "CREATE KEYSPACE IF NOT EXISTS movies WITH replication = {'class': 'NetworkTopologyStrategy', 'dc1': 3, 'dc2': 2} AND durable_writes = true",
+            "CREATE TYPE movies.person (first_name text, \"Last Name\" text)",
+            "CREATE TABLE movies.nerd_movies (movie text, year int, director person, tags map<text, int>, PRIMARY KEY ((movie), year)) WITH CLUSTERING ORDER BY (year DESC) AND comment = 'movies'",
+            "SELECT DISTINCT movie, writetime(year) AS w FROM movies.nerd_movies WHERE movie IN ('Alien', :m) AND token(movie) > 0 LIMIT ? ALLOW FILTERING",
+            "INSERT INTO nerd_movies (movie, year, director, tags, id) VALUES ('Serenity', -2005, {first_name: 'Joss'}, {'scifi': 1}, f47ac10b-58cc-4372-a567-0e02b2c3d479) IF NOT EXISTS USING TTL 86400",
+            "UPDATE nerd_movies SET tags['western'] = 2, rating = 4.5 WHERE movie = 'Serenity' AND year = 2005 IF rating = null",
+            "BEGIN UNLOGGED BATCH USING TIMESTAMP 1234 DELETE tags FROM nerd_movies WHERE movie = ?; INSERT INTO views (movie) VALUES (?); APPLY BATCH",
+            "DROP TABLE IF EXISTS movies.nerd_movies",
+            "DESCRIBE TABLE movies.nerd_movies WITH INTERNALS",
+        ] {
+            let parsed = statement.parse::<Statement>().unwrap();
+            let json = serde_json::to_string(&parsed).unwrap();
+            let deserialized = serde_json::from_str::<Statement>(&json).unwrap();
+            assert_eq!(parsed, deserialized, "{}", json);
+            assert_eq!(parsed.to_string(), deserialized.to_string());
+            assert_eq!(parsed, deserialized.to_string().parse().unwrap());
+        }
+    }
 }
@@ -0,0 +1,933 @@
+use super::{
+    schema::ident,
+    AlterKeyspaceStatement,
+    AlterMaterializedViewStatement,
+    AlterTableInstruction,
+    AlterTableStatement,
+    AlterTypeInstruction,
+    AlterUserDefinedTypeStatement,
+    CollectionType,
+    ColumnDefinition,
+    ColumnKind,
+    ColumnSchema,
+    CqlType,
+    CreateAggregateFunctionStatement,
+    CreateFunctionStatement,
+    CreateIndexStatement,
+    CreateKeyspaceStatement,
+    CreateMaterializedViewStatement,
+    CreateTableStatement,
+    CreateUserDefinedTypeStatement,
+    DropAggregateFunctionStatement,
+    DropFunctionStatement,
+    DropIndexStatement,
+    DropKeyspaceStatement,
+    DropMaterializedViewStatement,
+    DropTableStatement,
+    DropUserDefinedTypeStatement,
+    FieldDefinition,
+    FunctionReference,
+    FunctionSignature,
+    IndexIdentifier,
+    IndexSchema,
+    KeyspaceOpts,
+    KeyspaceQualifiedName,
+    KeyspaceSchema,
+    Name,
+    NativeType,
+    Schema,
+    Statement,
+    TableOpts,
+    TableSchema,
+    UserTypeSchema,
+    ViewSchema,
+};
+use std::{
+    collections::BTreeSet,
+    fmt::{
+        Display,
+        Formatter,
+    },
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// The schema element affected by a change.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaObject {
+    Keyspace(Name),
+    Table(KeyspaceQualifiedName),
+    TableOptions(KeyspaceQualifiedName),
+    Column(KeyspaceQualifiedName, Name),
+    UserType(KeyspaceQualifiedName),
+    Field(KeyspaceQualifiedName, Name),
+    Index(KeyspaceQualifiedName),
+    View(KeyspaceQualifiedName),
+    ViewOptions(KeyspaceQualifiedName),
+    Function(FunctionSignature),
+    Aggregate(FunctionSignature),
+}
+
+impl SchemaObject {
+    pub fn keyspace(&self) -> Option<&Name> {
+        match self {
+            Self::Keyspace(n) => Some(n),
+            Self::Table(n)
+            | Self::TableOptions(n)
+            | Self::Column(n, _)
+            | Self::UserType(n)
+            | Self::Field(n, _)
+            | Self::Index(n)
+            | Self::View(n)
+            | Self::ViewOptions(n) => n.keyspace.as_ref(),
+            Self::Function(f) | Self::Aggregate(f) => f.name.keyspace.as_ref(),
+        }
+    }
+}
+
+impl Display for SchemaObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Keyspace(n) => write!(f, "keyspace {}", n),
+            Self::Table(n) => write!(f, "table {}", n),
+            Self::TableOptions(n) => write!(f, "options of table {}", n),
+            Self::Column(t, c) => write!(f, "column {}.{}", t, c),
+            Self::UserType(n) => write!(f, "type {}", n),
+            Self::Field(t, n) => write!(f, "field {}.{}", t, n),
+            Self::Index(n) => write!(f, "index {}", n),
+            Self::View(n) => write!(f, "materialized view {}", n),
+            Self::ViewOptions(n) => write!(f, "options of materialized view {}", n),
+            Self::Function(s) => write!(f, "function {}", s),
+            Self::Aggregate(s) => write!(f, "aggregate {}", s),
+        }
+    }
+}
+
+/// A single difference between two schemas, along with the statement which applies it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchemaChange {
+    pub kind: ChangeKind,
+    pub object: SchemaObject,
+    pub statement: Statement,
+}
+
+/// A difference between two schemas which cannot be expressed in CQL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedChange {
+    pub object: SchemaObject,
+    pub reason: String,
+}
+
+impl Display for UnsupportedChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cannot migrate {}: {}", self.object, self.reason)
+    }
+}
+
+/// The differences between two schemas. Changes are ordered so that
+/// their statements can be executed in sequence.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SchemaDiff {
+    pub changes: Vec<SchemaChange>,
+    pub unsupported: Vec<UnsupportedChange>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.unsupported.is_empty()
+    }
+
+    /// Get the migration statements, or an error listing every change which cannot be expressed.
+    pub fn statements(&self) -> anyhow::Result<Vec<Statement>> {
+        if !self.unsupported.is_empty() {
+            anyhow::bail!(
+                "{}",
+                self.unsupported
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+        Ok(self.changes.iter().map(|c| c.statement.clone()).collect())
+    }
+}
+
+impl Display for SchemaDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in self.changes.iter() {
+            writeln!(f, "{};", change.statement)?;
+        }
+        for change in self.unsupported.iter() {
+            writeln!(f, "-- {}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// The stages of a migration, in execution order.
+#[derive(Default)]
+struct Phases {
+    keyspaces: Vec<SchemaChange>,
+    create_types: Vec<SchemaChange>,
+    create_functions: Vec<SchemaChange>,
+    create_aggregates: Vec<SchemaChange>,
+    create_tables: Vec<SchemaChange>,
+    drop_dependents: Vec<SchemaChange>,
+    alter_tables: Vec<SchemaChange>,
+    create_dependents: Vec<SchemaChange>,
+    drop_tables: Vec<SchemaChange>,
+    drop_aggregates: Vec<SchemaChange>,
+    drop_functions: Vec<SchemaChange>,
+    drop_types: Vec<SchemaChange>,
+    drop_keyspaces: Vec<SchemaChange>,
+    unsupported: Vec<UnsupportedChange>,
+}
+
+impl Phases {
+    fn unsupported(&mut self, object: SchemaObject, reason: impl Into<String>) {
+        self.unsupported.push(UnsupportedChange {
+            object,
+            reason: reason.into(),
+        });
+    }
+
+    fn into_diff(self) -> SchemaDiff {
+        SchemaDiff {
+            changes: [
+                self.keyspaces,
+                self.create_types,
+                self.create_functions,
+                self.create_aggregates,
+                self.create_tables,
+                self.drop_dependents,
+                self.alter_tables,
+                self.create_dependents,
+                self.drop_tables,
+                self.drop_aggregates,
+                self.drop_functions,
+                self.drop_types,
+                self.drop_keyspaces,
+            ]
+            .concat(),
+            unsupported: self.unsupported,
+        }
+    }
+}
+
+fn change(kind: ChangeKind, object: SchemaObject, statement: impl Into<Statement>) -> SchemaChange {
+    SchemaChange {
+        kind,
+        object,
+        statement: statement.into(),
+    }
+}
+
+fn qualified(keyspace: &Name, name: &Name) -> KeyspaceQualifiedName {
+    KeyspaceQualifiedName {
+        keyspace: Some(keyspace.clone()),
+        name: name.clone(),
+    }
+}
+
+/// Get a type with user defined type names made comparable across schemas.
+pub(crate) fn canonical_type(data_type: &CqlType) -> CqlType {
+    match data_type {
+        CqlType::UserDefined(t) => CqlType::UserDefined(KeyspaceQualifiedName {
+            keyspace: None,
+            name: Name::Quoted(ident(&t.name)),
+        }),
+        CqlType::Collection(c) => CqlType::Collection(Box::new(match c.as_ref() {
+            CollectionType::List(t) => CollectionType::List(canonical_type(t)),
+            CollectionType::Set(t) => CollectionType::Set(canonical_type(t)),
+            CollectionType::Map(k, v) => CollectionType::Map(canonical_type(k), canonical_type(v)),
+        })),
+        CqlType::Tuple(t) => CqlType::Tuple(t.iter().map(canonical_type).collect()),
+        CqlType::Native(NativeType::Varchar) => CqlType::Native(NativeType::Text),
+        t => t.clone(),
+    }
+}
+
+pub(crate) fn same_type(a: &CqlType, b: &CqlType) -> bool {
+    canonical_type(a) == canonical_type(b)
+}
+
+fn names_eq<'a>(a: impl IntoIterator<Item = &'a Name>, b: impl IntoIterator<Item = &'a Name>) -> bool {
+    a.into_iter().map(ident).eq(b.into_iter().map(ident))
+}
+
+fn function_signature(keyspace: &Name, f: &CreateFunctionStatement) -> FunctionSignature {
+    FunctionSignature {
+        name: qualified(keyspace, &f.func.name.name),
+        args: f.func.args.iter().map(|a| a.data_type.clone()).collect(),
+    }
+}
+
+fn same_signature(a: &FunctionSignature, b: &FunctionSignature) -> bool {
+    ident(&a.name.name) == ident(&b.name.name)
+        && a.args.len() == b.args.len()
+        && a.args.iter().zip(b.args.iter()).all(|(a, b)| same_type(a, b))
+}
+
+/// Get a function definition qualified by its keyspace, without creation flags, so it can be compared.
+fn normalize_function(keyspace: &Name, f: &CreateFunctionStatement) -> CreateFunctionStatement {
+    let mut f = f.clone();
+    f.or_replace = false;
+    f.if_not_exists = false;
+    f.func.name.keyspace = Some(keyspace.clone());
+    f.func
+        .args
+        .iter_mut()
+        .for_each(|a| a.data_type = canonical_type(&a.data_type));
+    f.return_type = canonical_type(&f.return_type);
+    f
+}
+
+fn normalize_aggregate(keyspace: &Name, f: &CreateAggregateFunctionStatement) -> CreateAggregateFunctionStatement {
+    let mut f = f.clone();
+    f.or_replace = false;
+    f.if_not_exists = false;
+    f.func.name.keyspace = Some(keyspace.clone());
+    f.func.args = f.func.args.iter().map(canonical_type).collect();
+    f.state_value_type = canonical_type(&f.state_value_type);
+    f.state_modifying_fn.keyspace = Some(keyspace.clone());
+    if let Some(final_fn) = f.final_fn.as_mut() {
+        final_fn.keyspace = Some(keyspace.clone());
+    }
+    f
+}
+
+fn same_index(a: &IndexSchema, b: &IndexSchema) -> bool {
+    let target = |i: &IndexSchema| match &i.target {
+        IndexIdentifier::Column(n) => (None, ident(n)),
+        IndexIdentifier::Qualified(q, n) => (Some(q.clone()), ident(n)),
+    };
+    ident(&a.table) == ident(&b.table) && target(a) == target(b) && a.custom == b.custom && a.using == b.using
+}
+
+fn same_view(a: &ViewSchema, b: &ViewSchema) -> bool {
+    ident(&a.base_table) == ident(&b.base_table)
+        && a.select_statement == b.select_statement
+        && names_eq(&a.table.partition_key, &b.table.partition_key)
+        && names_eq(&a.table.clustering_columns, &b.table.clustering_columns)
+}
+
+/// Order user defined types so that every type follows the types it uses.
+fn sort_types(keyspace: &KeyspaceSchema) -> Vec<&UserTypeSchema> {
+    fn visit<'a>(
+        keyspace: &'a KeyspaceSchema,
+        udt: &'a UserTypeSchema,
+        visited: &mut BTreeSet<String>,
+        res: &mut Vec<&'a UserTypeSchema>,
+    ) {
+        if !visited.insert(ident(&udt.name)) {
+            return;
+        }
+        let mut types = Vec::new();
+        for field in udt.fields.iter() {
+            super::schema::user_types(&field.data_type, &mut types);
+        }
+        for t in types {
+            if let Some(dep) = keyspace.user_type(t.name.clone()) {
+                visit(keyspace, dep, visited, res);
+            }
+        }
+        res.push(udt);
+    }
+    let mut visited = BTreeSet::new();
+    let mut res = Vec::new();
+    for udt in keyspace.types.values() {
+        visit(keyspace, udt, &mut visited, &mut res);
+    }
+    res
+}
+
+/// Get the options which changed between two option sets, along with any options
+/// which were removed and therefore cannot be expressed.
+fn diff_table_opts(from: Option<&TableOpts>, to: Option<&TableOpts>) -> (Option<TableOpts>, Vec<&'static str>) {
+    let default = TableOpts::default();
+    let from = from.unwrap_or(&default);
+    let to = to.unwrap_or(&default);
+    let mut changed = TableOpts::default();
+    let mut any = false;
+    let mut removed = Vec::new();
+    macro_rules! diff {
+        ($($field:ident),*) => {
+            $(if from.$field != to.$field {
+                if to.$field.is_some() {
+                    changed.$field = to.$field.clone();
+                    any = true;
+                } else {
+                    removed.push(stringify!($field));
+                }
+            })*
+        };
+    }
+    diff!(
+        comment,
+        speculative_retry,
+        change_data_capture,
+        gc_grace_seconds,
+        bloom_filter_fp_chance,
+        default_time_to_live,
+        compaction,
+        compression,
+        caching,
+        memtable_flush_period_in_ms,
+        read_repair,
+        tombstone_gc,
+        per_partition_rate_limit,
+        paxos_grace_seconds,
+        synchronous_updates,
+        scylla_tags
+    );
+    (any.then_some(changed), removed)
+}
+
+impl Schema {
+    /// Compute the changes needed to migrate this schema to the target schema.
+    pub fn diff(&self, target: &Schema) -> SchemaDiff {
+        let mut phases = Phases::default();
+        for (key, ks) in self.keyspaces.iter() {
+            if !target.keyspaces.contains_key(key) {
+                phases.drop_keyspaces.push(change(
+                    ChangeKind::Removed,
+                    SchemaObject::Keyspace(ks.name.clone()),
+                    DropKeyspaceStatement {
+                        if_exists: false,
+                        keyspace: ks.name.clone(),
+                    },
+                ));
+            }
+        }
+        for (key, to) in target.keyspaces.iter() {
+            match self.keyspaces.get(key) {
+                Some(from) => {
+                    if from.options != to.options {
+                        phases.keyspaces.push(change(
+                            ChangeKind::Changed,
+                            SchemaObject::Keyspace(to.name.clone()),
+                            AlterKeyspaceStatement {
+                                keyspace: to.name.clone(),
+                                options: to.options.clone(),
+                            },
+                        ));
+                    }
+                    diff_keyspace(from, to, &mut phases);
+                }
+                None => {
+                    phases.keyspaces.push(change(
+                        ChangeKind::Added,
+                        SchemaObject::Keyspace(to.name.clone()),
+                        CreateKeyspaceStatement {
+                            if_not_exists: false,
+                            keyspace: to.name.clone(),
+                            options: to.options.clone(),
+                        },
+                    ));
+                    diff_keyspace(
+                        &KeyspaceSchema::new(to.name.clone(), KeyspaceOpts::default()),
+                        to,
+                        &mut phases,
+                    );
+                }
+            }
+        }
+        phases.into_diff()
+    }
+}
+
+fn diff_keyspace(from: &KeyspaceSchema, to: &KeyspaceSchema, phases: &mut Phases) {
+    let ks = &to.name;
+    diff_types(from, to, phases);
+    diff_functions(from, to, phases);
+
+    for (key, table) in from.tables.iter() {
+        if !to.tables.contains_key(key) {
+            let name = qualified(ks, &table.name);
+            phases.drop_tables.push(change(
+                ChangeKind::Removed,
+                SchemaObject::Table(name.clone()),
+                DropTableStatement {
+                    if_exists: false,
+                    table: name,
+                },
+            ));
+        }
+    }
+    for (key, table) in to.tables.iter() {
+        match from.tables.get(key) {
+            Some(existing) => diff_table(ks, existing, table, phases),
+            None => {
+                let name = qualified(ks, &table.name);
+                phases.create_tables.push(change(
+                    ChangeKind::Added,
+                    SchemaObject::Table(name.clone()),
+                    CreateTableStatement {
+                        if_not_exists: false,
+                        table: name,
+                        columns: table
+                            .columns
+                            .iter()
+                            .map(|c| ColumnDefinition {
+                                name: c.name.clone(),
+                                data_type: c.data_type.clone(),
+                                static_column: c.kind == ColumnKind::Static,
+                                primary_key: false,
+                            })
+                            .collect(),
+                        primary_key: Some(table.primary_key()),
+                        options: table.options.clone(),
+                    },
+                ));
+            }
+        }
+    }
+
+    // Indexes on dropped tables are removed along with them
+    for (key, index) in from.indexes.iter() {
+        if to.indexes.get(key).map(|i| same_index(index, i)).unwrap_or(false)
+            || !to.tables.contains_key(&ident(&index.table))
+        {
+            continue;
+        }
+        phases.drop_dependents.push(change(
+            ChangeKind::Removed,
+            SchemaObject::Index(qualified(ks, &index.name)),
+            DropIndexStatement {
+                if_exists: false,
+                name: qualified(ks, &index.name),
+            },
+        ));
+    }
+    for (key, index) in to.indexes.iter() {
+        if from.indexes.get(key).map(|i| same_index(index, i)).unwrap_or(false) {
+            continue;
+        }
+        phases.create_dependents.push(change(
+            ChangeKind::Added,
+            SchemaObject::Index(qualified(ks, &index.name)),
+            CreateIndexStatement {
+                custom: index.custom,
+                if_not_exists: false,
+                name: Some(index.name.clone()),
+                table: qualified(ks, &index.table),
+                index_id: index.target.clone(),
+                using: index.using.clone(),
+            },
+        ));
+    }
+
+    for (key, view) in from.views.iter() {
+        if to.views.get(key).map(|v| same_view(view, v)).unwrap_or(false) {
+            continue;
+        }
+        let name = qualified(ks, &view.table.name);
+        phases.drop_dependents.push(change(
+            ChangeKind::Removed,
+            SchemaObject::View(name.clone()),
+            DropMaterializedViewStatement { if_exists: false, name },
+        ));
+    }
+    for (key, view) in to.views.iter() {
+        let name = qualified(ks, &view.table.name);
+        match from.views.get(key).filter(|v| same_view(view, v)) {
+            Some(existing) => {
+                let (changed, removed) = diff_table_opts(existing.table.options.as_ref(), view.table.options.as_ref());
+                for option in removed {
+                    phases.unsupported(
+                        SchemaObject::ViewOptions(name.clone()),
+                        format!("option {} cannot be reset to its default", option),
+                    );
+                }
+                if let Some(table_opts) = changed {
+                    phases.create_dependents.push(change(
+                        ChangeKind::Changed,
+                        SchemaObject::ViewOptions(name.clone()),
+                        AlterMaterializedViewStatement {
+                            name,
+                            table_opts: Box::new(table_opts),
+                        },
+                    ));
+                }
+            }
+            None => phases.create_dependents.push(change(
+                ChangeKind::Added,
+                SchemaObject::View(name.clone()),
+                CreateMaterializedViewStatement {
+                    if_not_exists: false,
+                    name,
+                    select_statement: Box::new(view.select_statement.clone()),
+                    primary_key: view.table.primary_key(),
+                    table_opts: view.table.options.clone().map(Box::new),
+                },
+            )),
+        }
+    }
+}
+
+fn diff_types(from: &KeyspaceSchema, to: &KeyspaceSchema, phases: &mut Phases) {
+    let ks = &to.name;
+    for udt in sort_types(from).into_iter().rev() {
+        if to.user_type(udt.name.clone()).is_none() {
+            let name = qualified(ks, &udt.name);
+            phases.drop_types.push(change(
+                ChangeKind::Removed,
+                SchemaObject::UserType(name.clone()),
+                DropUserDefinedTypeStatement { if_exists: false, name },
+            ));
+        }
+    }
+    for udt in sort_types(to) {
+        let name = qualified(ks, &udt.name);
+        let existing = match from.user_type(udt.name.clone()) {
+            Some(existing) => existing,
+            None => {
+                phases.create_types.push(change(
+                    ChangeKind::Added,
+                    SchemaObject::UserType(name.clone()),
+                    CreateUserDefinedTypeStatement {
+                        if_not_exists: false,
+                        name,
+                        fields: udt.fields.clone(),
+                    },
+                ));
+                continue;
+            }
+        };
+        for field in existing.fields.iter() {
+            match udt.field(field.name.clone()) {
+                Some(f) if !same_type(&f.data_type, &field.data_type) => phases.unsupported(
+                    SchemaObject::Field(name.clone(), field.name.clone()),
+                    format!(
+                        "field type cannot be changed from {} to {}",
+                        field.data_type, f.data_type
+                    ),
+                ),
+                Some(_) => (),
+                None => phases.unsupported(
+                    SchemaObject::Field(name.clone(), field.name.clone()),
+                    "fields cannot be removed from a type",
+                ),
+            }
+        }
+        for field in udt.fields.iter() {
+            if existing.field(field.name.clone()).is_none() {
+                phases.create_types.push(change(
+                    ChangeKind::Added,
+                    SchemaObject::Field(name.clone(), field.name.clone()),
+                    AlterUserDefinedTypeStatement {
+                        name: name.clone(),
+                        instruction: AlterTypeInstruction::Add(FieldDefinition {
+                            name: field.name.clone(),
+                            data_type: field.data_type.clone(),
+                        }),
+                    },
+                ));
+            }
+        }
+    }
+}
+
+fn diff_functions(from: &KeyspaceSchema, to: &KeyspaceSchema, phases: &mut Phases) {
+    let ks = &to.name;
+    for f in from.functions.iter() {
+        let sig = function_signature(ks, f);
+        if !to
+            .functions
+            .iter()
+            .any(|t| same_signature(&sig, &function_signature(ks, t)))
+        {
+            phases.drop_functions.push(change(
+                ChangeKind::Removed,
+                SchemaObject::Function(sig.clone()),
+                DropFunctionStatement {
+                    if_exists: false,
+                    func: FunctionReference {
+                        name: sig.name,
+                        args: Some(sig.args),
+                    },
+                },
+            ));
+        }
+    }
+    for f in to.functions.iter() {
+        let sig = function_signature(ks, f);
+        let mut statement = normalize_function(ks, f);
+        let kind = match from
+            .functions
+            .iter()
+            .find(|t| same_signature(&sig, &function_signature(ks, t)))
+        {
+            Some(existing) if normalize_function(ks, existing) == statement => continue,
+            Some(_) => {
+                statement.or_replace = true;
+                ChangeKind::Changed
+            }
+            None => ChangeKind::Added,
+        };
+        phases
+            .create_functions
+            .push(change(kind, SchemaObject::Function(sig), statement));
+    }
+
+    let signature = |f: &CreateAggregateFunctionStatement| FunctionSignature {
+        name: qualified(ks, &f.func.name.name),
+        args: f.func.args.clone(),
+    };
+    for f in from.aggregates.iter() {
+        let sig = signature(f);
+        if !to.aggregates.iter().any(|t| same_signature(&sig, &signature(t))) {
+            phases.drop_aggregates.push(change(
+                ChangeKind::Removed,
+                SchemaObject::Aggregate(sig.clone()),
+                DropAggregateFunctionStatement {
+                    if_exists: false,
+                    func: FunctionReference {
+                        name: sig.name,
+                        args: Some(sig.args),
+                    },
+                },
+            ));
+        }
+    }
+    for f in to.aggregates.iter() {
+        let sig = signature(f);
+        let mut statement = normalize_aggregate(ks, f);
+        let kind = match from.aggregates.iter().find(|t| same_signature(&sig, &signature(t))) {
+            Some(existing) if normalize_aggregate(ks, existing) == statement => continue,
+            Some(_) => {
+                statement.or_replace = true;
+                ChangeKind::Changed
+            }
+            None => ChangeKind::Added,
+        };
+        phases
+            .create_aggregates
+            .push(change(kind, SchemaObject::Aggregate(sig), statement));
+    }
+}
+
+fn diff_table(ks: &Name, from: &TableSchema, to: &TableSchema, phases: &mut Phases) {
+    let name = qualified(ks, &to.name);
+    if !names_eq(&from.partition_key, &to.partition_key) || !names_eq(&from.clustering_columns, &to.clustering_columns)
+    {
+        phases.unsupported(
+            SchemaObject::Table(name),
+            format!(
+                "primary key cannot be changed from {} to {}",
+                from.primary_key(),
+                to.primary_key()
+            ),
+        );
+        return;
+    }
+    let order = |t: &TableSchema| {
+        t.options
+            .as_ref()
+            .and_then(|o| o.clustering_order.as_ref())
+            .map(|o| {
+                o.iter()
+                    .map(|c| (ident(&c.column), c.order.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+    if order(from) != order(to) {
+        phases.unsupported(SchemaObject::Table(name.clone()), "clustering order cannot be changed");
+    }
+    let compact = |t: &TableSchema| t.options.as_ref().map(|o| o.compact_storage).unwrap_or_default();
+    if compact(from) != compact(to) {
+        phases.unsupported(SchemaObject::Table(name.clone()), "compact storage cannot be changed");
+    }
+
+    let alter = |instruction| AlterTableStatement {
+        table: name.clone(),
+        instruction,
+    };
+    let mut dropped = Vec::new();
+    for column in from.columns.iter() {
+        if to.column(column.name.clone()).is_none() {
+            dropped.push(column.name.clone());
+        }
+    }
+    let mut added = Vec::new();
+    for column in to.columns.iter() {
+        let object = SchemaObject::Column(name.clone(), column.name.clone());
+        match from.column(column.name.clone()) {
+            Some(existing) => {
+                if existing.kind != column.kind {
+                    phases.unsupported(
+                        object,
+                        "static columns cannot be changed to regular columns or vice versa",
+                    );
+                } else if !same_type(&existing.data_type, &column.data_type) {
+                    // Scylla rejects every column type change, even between compatible types
+                    phases.unsupported(
+                        object,
+                        format!(
+                            "type cannot be changed from {} to {}",
+                            existing.data_type, column.data_type
+                        ),
+                    );
+                }
+            }
+            None => added.push(column),
+        }
+    }
+    for ColumnSchema {
+        name: c,
+        data_type,
+        kind,
+    } in added
+    {
+        phases.alter_tables.push(change(
+            ChangeKind::Added,
+            SchemaObject::Column(name.clone(), c.clone()),
+            alter(AlterTableInstruction::Add(vec![ColumnDefinition {
+                name: c.clone(),
+                data_type: data_type.clone(),
+                static_column: *kind == ColumnKind::Static,
+                primary_key: false,
+            }])),
+        ));
+    }
+    for c in dropped {
+        phases.alter_tables.push(change(
+            ChangeKind::Removed,
+            SchemaObject::Column(name.clone(), c.clone()),
+            alter(AlterTableInstruction::Drop(vec![c])),
+        ));
+    }
+
+    let (changed, removed) = diff_table_opts(from.options.as_ref(), to.options.as_ref());
+    for option in removed {
+        phases.unsupported(
+            SchemaObject::TableOptions(name.clone()),
+            format!("option {} cannot be reset to its default", option),
+        );
+    }
+    if let Some(options) = changed {
+        phases.alter_tables.push(change(
+            ChangeKind::Changed,
+            SchemaObject::TableOptions(name.clone()),
+            alter(AlterTableInstruction::With(options)),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FROM: &str = r#"
+        CREATE KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+        CREATE KEYSPACE old WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+        USE movies;
+        CREATE TYPE person (first_name text, last_name text);
+        CREATE TABLE nerd_movies (
+            movie text,
+            year int,
+            director person,
+            box_office int,
+            budget int,
+            PRIMARY KEY (movie, year)
+        ) WITH comment = 'Movies';
+        CREATE INDEX budget_idx ON nerd_movies (budget);
+        CREATE TABLE old_movies (movie text PRIMARY KEY);
+    "#;
+
+    const TO: &str = r#"
+        CREATE KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 3};
+        CREATE KEYSPACE reviews WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+        USE movies;
+        CREATE TYPE address (city text);
+        CREATE TYPE person (first_name text, last_name text, home address);
+        CREATE TABLE nerd_movies (
+            movie text,
+            year int,
+            director person,
+            box_office int,
+            rating float,
+            PRIMARY KEY (movie, year)
+        ) WITH comment = 'Nerd movies';
+        CREATE INDEX ON nerd_movies (director);
+        CREATE TABLE reviews.reviews (movie text, reviewer text, review text, PRIMARY KEY (movie, reviewer));
+    "#;
+
+    #[test]
+    fn test_schema_diff() {
+        let from = Schema::from_script(FROM).unwrap();
+        let to = Schema::from_script(TO).unwrap();
+        let diff = from.diff(&to);
+        assert!(diff.unsupported.is_empty(), "{:?}", diff.unsupported);
+        let statements = diff.statements().unwrap();
+        assert_eq!(
+            statements.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            vec![
+                "ALTER KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 3}",
+                "CREATE KEYSPACE reviews WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+                "CREATE TYPE movies.address (city TEXT)",
+                "ALTER TYPE movies.person ADD home address",
+                "CREATE TABLE reviews.reviews (movie TEXT, reviewer TEXT, review TEXT, PRIMARY KEY (movie, reviewer))",
+                "DROP INDEX movies.budget_idx",
+                "ALTER TABLE movies.nerd_movies ADD rating FLOAT",
+                "ALTER TABLE movies.nerd_movies DROP (budget)",
+                "ALTER TABLE movies.nerd_movies WITH comment = 'Nerd movies'",
+                "CREATE INDEX nerd_movies_director_idx ON movies.nerd_movies(director)",
+                "DROP TABLE movies.old_movies",
+                "DROP KEYSPACE old",
+            ]
+        );
+
+        let mut migrated = from.clone();
+        for statement in statements {
+            migrated.apply(statement).unwrap();
+        }
+        assert!(migrated.diff(&to).is_empty());
+        assert!(to.diff(&to).is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_schema_diff() {
+        let from = Schema::from_script(FROM).unwrap();
+        let to = Schema::from_script(
+            &FROM
+                .replace("PRIMARY KEY (movie, year)", "PRIMARY KEY ((movie, year))")
+                .replace(
+                    "CREATE TYPE person (first_name text, last_name text)",
+                    "CREATE TYPE person (first_name text)",
+                )
+                .replace("budget int", "budget smallint"),
+        )
+        .unwrap();
+        let diff = from.diff(&to);
+        assert_eq!(diff.unsupported.len(), 2);
+        assert_eq!(
+            diff.unsupported[0].object,
+            SchemaObject::Field(
+                KeyspaceQualifiedName {
+                    keyspace: Some("movies".into()),
+                    name: "person".into()
+                },
+                "last_name".into()
+            )
+        );
+        assert!(diff.statements().is_err());
+
+        // Even compatible type changes are rejected by Scylla
+        for data_type in ["smallint", "varint", "blob"] {
+            let to = Schema::from_script(&FROM.replace("budget int", &format!("budget {}", data_type))).unwrap();
+            let diff = from.diff(&to);
+            assert_eq!(diff.unsupported.len(), 1, "{}", data_type);
+            assert!(diff.unsupported[0].reason.contains("type cannot be changed"));
+            assert!(diff.changes.is_empty(), "{}", data_type);
+            assert!(!diff.to_string().contains("ALTER TABLE"), "{}", data_type);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeSet,
+    fmt::{
+        Display,
+        Formatter,
+    },
+};
+
+/// The furthest position at which a parser failed, along with every alternative
+/// that was attempted there.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Expected {
+    pub(crate) pos: usize,
+    pub(crate) alternatives: BTreeSet<Cow<'static, str>>,
+}
+
+impl Expected {
+    pub(crate) fn insert(&mut self, pos: usize, alternative: Cow<'static, str>) {
+        if pos > self.pos || self.alternatives.is_empty() {
+            self.pos = pos;
+            self.alternatives.clear();
+        }
+        if pos == self.pos {
+            self.alternatives.insert(alternative);
+        }
+    }
+}
+
+/// Get the 1-based line and column of a character offset in the source text.
+pub(crate) fn line_column(source: &str, pos: usize) -> (usize, usize) {
+    let (mut line, mut column) = (1, 1);
+    for c in source.chars().take(pos) {
+        if c == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
+
+/// An error produced when a statement cannot be parsed, located in the source text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The underlying parser error message
+    pub message: String,
+    /// The character offset of the error
+    pub pos: usize,
+    /// The 1-based line of the error
+    pub line: usize,
+    /// The 1-based column of the error, in characters
+    pub column: usize,
+    /// The tokens which would have been accepted at this position
+    pub expected: BTreeSet<String>,
+    /// The offending source line with a caret pointing at the error
+    pub snippet: String,
+}
+
+impl ParseError {
+    pub fn new(source: &str, pos: usize, expected: BTreeSet<String>, message: impl Into<String>) -> Self {
+        let (line, column) = line_column(source, pos);
+        let text = source
+            .chars()
+            .skip(pos + 1 - column)
+            .take_while(|&c| c != '\n')
+            .collect::<String>();
+        let text = text.trim_end_matches('\r');
+        let gutter = " ".repeat(line.to_string().len());
+        let offset = text
+            .chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let snippet = format!("{} |\n{} | {}\n{} | {}^", gutter, line, text, gutter, offset);
+        Self {
+            message: message.into(),
+            pos,
+            line,
+            column,
+            expected,
+            snippet,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}\n{}",
+            self.message, self.line, self.column, self.snippet
+        )?;
+        match self.expected.len() {
+            0 => Ok(()),
+            1 => write!(f, "\nexpected {}", self.expected.iter().next().unwrap()),
+            _ => write!(
+                f,
+                "\nexpected one of {}",
+                self.expected.iter().cloned().collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        SelectStatement,
+        Statement,
+    };
+
+    #[test]
+    fn test_parse_error_position() {
+        let err = "SELECT movie, director\nFORM NerdMovies;"
+            .parse::<SelectStatement>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column, err.pos), (2, 1, 23));
+        assert!(err.expected.contains("FROM"));
+        assert!(err.snippet.ends_with("2 | FORM NerdMovies;\n  | ^"));
+
+        let err = "CREATE TABLE test (\n    id int PRIMARY KEY\n    name text\n)"
+            .parse::<Statement>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (3, 5));
+        assert!(err.expected.contains("')'"));
+        assert!(err.expected.contains("','"));
+    }
+}
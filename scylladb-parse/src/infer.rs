@@ -0,0 +1,576 @@
+use super::{
+    schema::ident,
+    *,
+};
+
+/// The type of a bind marker, inferred from a schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindMarkerType {
+    /// The name of a named bind marker, or otherwise the name of the value it receives, following the names used by
+    /// the database in prepared statement metadata, i.e. `movie`, `in(movie)`, `key(tags)` or `[ttl]`
+    pub name: String,
+    pub data_type: CqlType,
+    /// Whether the value is a component of the partition key, and can be used to compute the request's token
+    pub partition_key: bool,
+}
+
+/// Statements whose bind marker types can be inferred from a schema.
+pub trait InferBindMarkers {
+    fn infer_with(&self, schema: &Schema, markers: &mut Vec<BindMarkerType>) -> anyhow::Result<()>;
+}
+
+impl Schema {
+    /// Infer the type of each bind marker in a statement, in the order they are bound.
+    pub fn bind_marker_types(&self, statement: &impl InferBindMarkers) -> anyhow::Result<Vec<BindMarkerType>> {
+        let mut markers = Vec::new();
+        statement.infer_with(self, &mut markers)?;
+        Ok(markers)
+    }
+}
+
+fn native(t: NativeType) -> CqlType {
+    CqlType::Native(t)
+}
+
+fn has_bind_markers<T: Visitable>(node: &T) -> bool {
+    struct Check(bool);
+    impl<'ast> Visit<'ast> for Check {
+        fn visit_bind_marker(&mut self, _: &'ast BindMarker) {
+            self.0 = true;
+        }
+    }
+    let mut check = Check(false);
+    node.accept(&mut check);
+    check.0
+}
+
+/// The table targeted by a statement.
+struct Context<'a> {
+    schema: &'a Schema,
+    keyspace: &'a KeyspaceSchema,
+    table: &'a TableSchema,
+}
+
+impl<'a> Context<'a> {
+    fn resolve(schema: &'a Schema, name: &KeyspaceQualifiedName) -> anyhow::Result<Self> {
+        let keyspace = schema
+            .keyspaces
+            .get(&schema.keyspace_of(name)?)
+            .ok_or_else(|| anyhow::anyhow!("Unknown table {}", name))?;
+        let table = keyspace
+            .relation(name.name.clone())
+            .ok_or_else(|| anyhow::anyhow!("Unknown table {}", name))?;
+        Ok(Self {
+            schema,
+            keyspace,
+            table,
+        })
+    }
+
+    fn column(&self, name: &Name) -> anyhow::Result<&'a ColumnSchema> {
+        self.table
+            .column(name.clone())
+            .ok_or_else(|| anyhow::anyhow!("Unknown column {} in table {}", name, self.table.name))
+    }
+
+    /// Record the bind markers of a term which is assigned to a value of the given type
+    fn term(
+        &self,
+        name: &str,
+        data_type: &CqlType,
+        partition_key: bool,
+        term: &Term,
+        m: &mut Vec<BindMarkerType>,
+    ) -> anyhow::Result<()> {
+        match term {
+            Term::BindMarker(marker) => m.push(BindMarkerType {
+                name: match marker {
+                    BindMarker::Named(n) => ident(n),
+                    BindMarker::Anonymous => name.to_string(),
+                },
+                data_type: data_type.clone(),
+                partition_key,
+            }),
+            Term::Constant(_) | Term::TypeHint { .. } => (),
+            Term::ArithmeticOp { lhs, rhs, .. } => {
+                if let Some(lhs) = lhs {
+                    self.term(name, data_type, false, lhs, m)?;
+                }
+                self.term(name, data_type, false, rhs, m)?;
+            }
+            Term::FunctionCall(f) => self.function(f, m)?,
+            Term::Literal(l) if has_bind_markers(l) => match (l, data_type) {
+                (Literal::Collection(c), CqlType::Collection(t)) => match (c, t.as_ref()) {
+                    (CollectionTypeLiteral::List(l), CollectionType::List(t)) => {
+                        for e in l.elements.iter() {
+                            self.term(name, t, false, e, m)?;
+                        }
+                    }
+                    (CollectionTypeLiteral::Set(s), CollectionType::Set(t)) => {
+                        for e in s.elements.iter() {
+                            self.term(name, t, false, e, m)?;
+                        }
+                    }
+                    (CollectionTypeLiteral::Map(l), CollectionType::Map(k, v)) => {
+                        for (mk, mv) in l.elements.iter() {
+                            self.term(&format!("key({})", name), k, false, mk, m)?;
+                            self.term(&format!("value({})", name), v, false, mv, m)?;
+                        }
+                    }
+                    _ => anyhow::bail!("Invalid literal {} for {} of type {}", l, name, data_type),
+                },
+                (Literal::UserDefined(u), CqlType::UserDefined(t)) => {
+                    let udt = self
+                        .keyspace
+                        .user_type(t.name.clone())
+                        .ok_or_else(|| anyhow::anyhow!("Unknown type {}", t))?;
+                    for (field, value) in u.fields.iter() {
+                        let f = udt
+                            .field(field.clone())
+                            .ok_or_else(|| anyhow::anyhow!("Unknown field {} of type {}", field, t))?;
+                        self.term(&ident(field), &f.data_type, false, value, m)?;
+                    }
+                }
+                (Literal::Tuple(t), CqlType::Tuple(types)) if t.elements.len() == types.len() => {
+                    for (e, t) in t.elements.iter().zip(types.iter()) {
+                        self.term(name, t, false, e, m)?;
+                    }
+                }
+                _ => anyhow::bail!("Invalid literal {} for {} of type {}", l, name, data_type),
+            },
+            Term::Literal(_) => (),
+        }
+        Ok(())
+    }
+
+    /// Record the bind markers of an `IN` relation or condition.
+    fn term_in(
+        &self,
+        name: &str,
+        data_type: &CqlType,
+        partition_key: bool,
+        term: &Term,
+        m: &mut Vec<BindMarkerType>,
+    ) -> anyhow::Result<()> {
+        match term {
+            Term::Literal(Literal::Tuple(t)) => {
+                for e in t.elements.iter() {
+                    self.term(name, data_type, partition_key, e, m)?;
+                }
+                Ok(())
+            }
+            t => self.term(
+                &format!("in({})", name),
+                &CollectionType::list(data_type.clone()).into(),
+                false,
+                t,
+                m,
+            ),
+        }
+    }
+
+    fn function(&self, f: &FunctionCall, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        if !f.args.iter().any(has_bind_markers) {
+            return Ok(());
+        }
+        let name = ident(&f.name.name);
+        let args = match name.as_str() {
+            "token" => {
+                for (arg, col) in f.args.iter().zip(self.table.partition_key_columns()) {
+                    self.term(&ident(&col.name), &col.data_type, true, arg, m)?;
+                }
+                return Ok(());
+            }
+            "mintimeuuid" | "maxtimeuuid" => vec![native(NativeType::Timestamp)],
+            n if n.starts_with("blobas") => vec![native(NativeType::Blob)],
+            n if n.ends_with("asblob") => vec![native(NativeType::from_str(&n[..n.len() - 6])?)],
+            _ => {
+                let keyspace = match &f.name.keyspace {
+                    Some(ks) => self
+                        .schema
+                        .keyspace(ks.clone())
+                        .ok_or_else(|| anyhow::anyhow!("Unknown keyspace {}", ks))?,
+                    None => self.keyspace,
+                };
+                let mut candidates = keyspace
+                    .functions(f.name.name.clone())
+                    .filter(|c| c.func.args.len() == f.args.len());
+                match (candidates.next(), candidates.next()) {
+                    (Some(c), None) => c.func.args.iter().map(|a| a.data_type.clone()).collect(),
+                    (None, _) => anyhow::bail!("Unknown function {} with {} arguments", f.name, f.args.len()),
+                    _ => anyhow::bail!("Cannot infer the argument types of overloaded function {}", f.name),
+                }
+            }
+        };
+        if args.len() != f.args.len() {
+            anyhow::bail!("Function {} expects {} arguments", f.name, args.len());
+        }
+        for (arg, data_type) in f.args.iter().zip(args.iter()) {
+            self.term(&format!("arg({})", name), data_type, false, arg, m)?;
+        }
+        Ok(())
+    }
+
+    /// Get the type of the value targeted by a selection, recording the bind marker of an element index.
+    fn selection(&self, selection: &SimpleSelection, m: &mut Vec<BindMarkerType>) -> anyhow::Result<(String, CqlType)> {
+        Ok(match selection {
+            SimpleSelection::Column(c) => (ident(c), self.column(c)?.data_type.clone()),
+            SimpleSelection::Term(c, index) => {
+                let col = self.column(c)?;
+                let name = ident(c);
+                let (index_name, key, value) = match &col.data_type {
+                    CqlType::Collection(t) => match t.as_ref() {
+                        CollectionType::List(v) => ("idx", native(NativeType::Int), v.clone()),
+                        CollectionType::Map(k, v) => ("key", k.clone(), v.clone()),
+                        CollectionType::Set(_) => {
+                            anyhow::bail!("Elements of set column {} cannot be accessed by index", c)
+                        }
+                    },
+                    _ => anyhow::bail!("Column {} is not a list or map", c),
+                };
+                self.term(&format!("{}({})", index_name, name), &key, false, index, m)?;
+                (format!("value({})", name), value)
+            }
+            SimpleSelection::Field(c, field) => {
+                let col = self.column(c)?;
+                let udt = match &col.data_type {
+                    CqlType::UserDefined(t) => self
+                        .keyspace
+                        .user_type(t.name.clone())
+                        .ok_or_else(|| anyhow::anyhow!("Unknown type {}", t))?,
+                    _ => anyhow::bail!("Column {} is not a user defined type", c),
+                };
+                let f = udt
+                    .field(field.clone())
+                    .ok_or_else(|| anyhow::anyhow!("Unknown field {} of column {}", field, c))?;
+                (ident(field), f.data_type.clone())
+            }
+        })
+    }
+
+    fn where_clause(&self, where_clause: &WhereClause, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        for relation in where_clause.relations.iter() {
+            match relation {
+                Relation::Normal { column, operator, term } => {
+                    let col = self.column(column)?;
+                    let name = ident(column);
+                    let partition_key = col.kind == ColumnKind::PartitionKey;
+                    match operator {
+                        Operator::In => self.term_in(&name, &col.data_type, partition_key, term, m)?,
+                        Operator::Contains => match &col.data_type {
+                            CqlType::Collection(t) => match t.as_ref() {
+                                CollectionType::List(t) | CollectionType::Set(t) | CollectionType::Map(_, t) => {
+                                    self.term(&name, t, false, term, m)?
+                                }
+                            },
+                            _ => anyhow::bail!("CONTAINS can only be used on collection columns, found {}", column),
+                        },
+                        Operator::ContainsKey => match &col.data_type {
+                            CqlType::Collection(t) => match t.as_ref() {
+                                CollectionType::Map(k, _) => self.term(&name, k, false, term, m)?,
+                                _ => anyhow::bail!("CONTAINS KEY can only be used on map columns, found {}", column),
+                            },
+                            _ => anyhow::bail!("CONTAINS KEY can only be used on map columns, found {}", column),
+                        },
+                        Operator::Equal => self.term(&name, &col.data_type, partition_key, term, m)?,
+                        _ => self.term(&name, &col.data_type, false, term, m)?,
+                    }
+                }
+                Relation::Tuple {
+                    columns,
+                    operator,
+                    tuple_literal,
+                } => {
+                    let cols = columns
+                        .iter()
+                        .map(|c| self.column(c))
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    let name = format!(
+                        "({})",
+                        cols.iter().map(|c| ident(&c.name)).collect::<Vec<_>>().join(", ")
+                    );
+                    if *operator == Operator::In {
+                        let types = CqlType::Tuple(cols.iter().map(|c| c.data_type.clone()).collect());
+                        for e in tuple_literal.elements.iter() {
+                            self.term(&name, &types, false, e, m)?;
+                        }
+                    } else {
+                        if tuple_literal.elements.len() != cols.len() {
+                            anyhow::bail!("Expected {} values for {}, found {}", cols.len(), name, tuple_literal);
+                        }
+                        for (e, col) in tuple_literal.elements.iter().zip(cols.iter()) {
+                            self.term(&ident(&col.name), &col.data_type, false, e, m)?;
+                        }
+                    }
+                }
+                Relation::Token { term, .. } => {
+                    self.term("partition key token", &native(NativeType::Bigint), false, term, m)?
+                }
+                Relation::MVExclusion { .. } => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn if_clause(&self, if_clause: Option<&IfClause>, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        if let Some(IfClause::Conditions(conditions)) = if_clause {
+            for Condition { lhs, op, rhs } in conditions {
+                let (name, data_type) = self.selection(lhs, m)?;
+                match op {
+                    Operator::In => self.term_in(&name, &data_type, false, rhs, m)?,
+                    _ => self.term(&name, &data_type, false, rhs, m)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn limit(name: &str, data_type: NativeType, limit: Option<&Limit>, m: &mut Vec<BindMarkerType>) {
+    if let Some(Limit::BindMarker(marker)) = limit {
+        m.push(BindMarkerType {
+            name: match marker {
+                BindMarker::Named(n) => ident(n),
+                BindMarker::Anonymous => name.to_string(),
+            },
+            data_type: native(data_type),
+            partition_key: false,
+        });
+    }
+}
+
+fn using(using: Option<&Vec<UpdateParameter>>, m: &mut Vec<BindMarkerType>) {
+    for param in using.into_iter().flatten() {
+        match param {
+            UpdateParameter::TTL(l) => limit("[ttl]", NativeType::Int, Some(l), m),
+            UpdateParameter::Timestamp(l) => limit("[timestamp]", NativeType::Bigint, Some(l), m),
+            UpdateParameter::Timeout(_) => (),
+        }
+    }
+}
+
+impl InferBindMarkers for SelectStatement {
+    fn infer_with(&self, schema: &Schema, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        let ctx = Context::resolve(schema, &self.from)?;
+        if has_bind_markers(&self.select_clause) {
+            anyhow::bail!("Cannot infer the type of bind markers in the selection of {}", self);
+        }
+        if let Some(where_clause) = &self.where_clause {
+            ctx.where_clause(where_clause, m)?;
+        }
+        limit(
+            "[per_partition_limit]",
+            NativeType::Int,
+            self.per_partition_limit.as_ref(),
+            m,
+        );
+        limit("[limit]", NativeType::Int, self.limit.as_ref(), m);
+        Ok(())
+    }
+}
+
+impl InferBindMarkers for InsertStatement {
+    fn infer_with(&self, schema: &Schema, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        let ctx = Context::resolve(schema, &self.table)?;
+        if let InsertKind::NameValue { names, values } = &self.kind {
+            if names.len() != values.elements.len() {
+                anyhow::bail!("Expected {} values, found {}", names.len(), values.elements.len());
+            }
+            for (name, value) in names.iter().zip(values.elements.iter()) {
+                let col = ctx.column(name)?;
+                ctx.term(
+                    &ident(name),
+                    &col.data_type,
+                    col.kind == ColumnKind::PartitionKey,
+                    value,
+                    m,
+                )?;
+            }
+        }
+        using(self.using.as_ref(), m);
+        Ok(())
+    }
+}
+
+impl InferBindMarkers for UpdateStatement {
+    fn infer_with(&self, schema: &Schema, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        let ctx = Context::resolve(schema, &self.table)?;
+        using(self.using.as_ref(), m);
+        for assignment in self.set_clause.iter() {
+            match assignment {
+                Assignment::Simple { selection, term } => {
+                    let (name, data_type) = ctx.selection(selection, m)?;
+                    ctx.term(&name, &data_type, false, term, m)?;
+                }
+                Assignment::Arithmetic { assignee, op, rhs, .. } => {
+                    let col = ctx.column(assignee)?;
+                    let data_type = match &col.data_type {
+                        CqlType::Native(NativeType::Counter) => native(NativeType::Bigint),
+                        CqlType::Collection(t) => match (t.as_ref(), op) {
+                            (CollectionType::Map(k, _), ArithmeticOp::Sub) => CollectionType::set(k.clone()).into(),
+                            _ => col.data_type.clone(),
+                        },
+                        t => t.clone(),
+                    };
+                    ctx.term(&ident(assignee), &data_type, false, rhs, m)?;
+                }
+                Assignment::Append { assignee, list, .. } => {
+                    let col = ctx.column(assignee)?;
+                    let term = Term::Literal(Literal::Collection(CollectionTypeLiteral::List(list.clone())));
+                    ctx.term(&ident(assignee), &col.data_type, false, &term, m)?;
+                }
+            }
+        }
+        ctx.where_clause(&self.where_clause, m)?;
+        ctx.if_clause(self.if_clause.as_ref(), m)
+    }
+}
+
+impl InferBindMarkers for DeleteStatement {
+    fn infer_with(&self, schema: &Schema, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        let ctx = Context::resolve(schema, &self.from)?;
+        for selection in self.selections.iter().flatten() {
+            ctx.selection(selection, m)?;
+        }
+        using(self.using.as_ref(), m);
+        ctx.where_clause(&self.where_clause, m)?;
+        ctx.if_clause(self.if_clause.as_ref(), m)
+    }
+}
+
+impl InferBindMarkers for ModificationStatement {
+    fn infer_with(&self, schema: &Schema, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        match self {
+            Self::Insert(s) => s.infer_with(schema, m),
+            Self::Update(s) => s.infer_with(schema, m),
+            Self::Delete(s) => s.infer_with(schema, m),
+        }
+    }
+}
+
+impl InferBindMarkers for BatchStatement {
+    fn infer_with(&self, schema: &Schema, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        using(self.using.as_ref(), m);
+        for statement in self.statements.iter() {
+            statement.infer_with(schema, m)?;
+        }
+        Ok(())
+    }
+}
+
+impl InferBindMarkers for DataManipulationStatement {
+    fn infer_with(&self, schema: &Schema, m: &mut Vec<BindMarkerType>) -> anyhow::Result<()> {
+        match self {
+            Self::Select(s) => s.infer_with(schema, m),
+            Self::Insert(s) => s.infer_with(schema, m),
+            Self::Update(s) => s.infer_with(schema, m),
+            Self::Delete(s) => s.infer_with(schema, m),
+            Self::Batch(s) => s.infer_with(schema, m),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        CREATE KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+        USE movies;
+        CREATE TYPE person (first_name text, last_name text);
+        CREATE TABLE nerd_movies (
+            movie text,
+            studio text,
+            year int,
+            director person,
+            rating float,
+            tags set<text>,
+            awards map<text, int>,
+            PRIMARY KEY ((movie, studio), year)
+        );
+    "#;
+
+    fn infer(statement: &str) -> Vec<(String, String, bool)> {
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        schema
+            .bind_marker_types(&statement.parse::<DataManipulationStatement>().unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.name, t.data_type.to_string(), t.partition_key))
+            .collect()
+    }
+
+    fn marker(name: &str, data_type: &str, partition_key: bool) -> (String, String, bool) {
+        (
+            name.to_string(),
+            data_type.parse::<CqlType>().unwrap().to_string(),
+            partition_key,
+        )
+    }
+
+    #[test]
+    fn test_infer_bind_markers() {
+        assert_eq!(
+            infer("SELECT * FROM nerd_movies WHERE movie = ? AND studio IN (?, :s) AND year > ? LIMIT ?"),
+            vec![
+                marker("movie", "text", true),
+                marker("studio", "text", true),
+                marker("s", "text", true),
+                marker("year", "int", false),
+                marker("[limit]", "int", false),
+            ]
+        );
+        assert_eq!(
+            infer("SELECT * FROM nerd_movies WHERE token(movie, studio) > token(?, ?) AND token(movie, studio) < ? AND year IN ?"),
+            vec![
+                marker("movie", "text", true),
+                marker("studio", "text", true),
+                marker("partition key token", "bigint", false),
+                marker("in(year)", "list<int>", false),
+            ]
+        );
+        assert_eq!(
+            infer("INSERT INTO nerd_movies (movie, studio, year, director, tags) VALUES (?, ?, ?, {first_name: ?}, ?) USING TTL ? AND TIMESTAMP ?"),
+            vec![
+                marker("movie", "text", true),
+                marker("studio", "text", true),
+                marker("year", "int", false),
+                marker("first_name", "text", false),
+                marker("tags", "set<text>", false),
+                marker("[ttl]", "int", false),
+                marker("[timestamp]", "bigint", false),
+            ]
+        );
+        assert_eq!(
+            infer("UPDATE nerd_movies SET awards[?] = ?, awards = awards - ?, director.last_name = ? WHERE movie = ? AND studio = ? AND year = ? IF rating IN ?"),
+            vec![
+                marker("key(awards)", "text", false),
+                marker("value(awards)", "int", false),
+                marker("awards", "set<text>", false),
+                marker("last_name", "text", false),
+                marker("movie", "text", true),
+                marker("studio", "text", true),
+                marker("year", "int", false),
+                marker("in(rating)", "list<float>", false),
+            ]
+        );
+        assert_eq!(
+            infer("BEGIN BATCH USING TIMESTAMP ? DELETE awards[?] FROM nerd_movies WHERE movie = 'a' AND studio = ? AND year = 1; APPLY BATCH"),
+            vec![
+                marker("[timestamp]", "bigint", false),
+                marker("key(awards)", "text", false),
+                marker("studio", "text", true),
+            ]
+        );
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        for statement in [
+            "SELECT * FROM unknown WHERE a = ?",
+            "SELECT * FROM nerd_movies WHERE unknown = ?",
+            "UPDATE nerd_movies SET tags[?] = 'a' WHERE movie = 'a'",
+        ] {
+            assert!(schema
+                .bind_marker_types(&statement.parse::<DataManipulationStatement>().unwrap())
+                .is_err());
+        }
+    }
+}
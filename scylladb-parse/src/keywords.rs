@@ -2,6 +2,7 @@ use super::{
     Alphanumeric,
     CustomToTokens,
     Parse,
+    ParseError,
     StatementStream,
 };
 use scylladb_parse_macros::{
@@ -75,17 +76,35 @@ pub enum ReservedKeyword {
 macro_rules! keyword {
     ($t:ident) => {
         #[derive(ParseFromStr, Copy, Clone, Debug)]
+        #[allow(non_camel_case_types)]
         pub struct $t;
         impl Parse for $t {
             type Output = Self;
             fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
                 let this = stringify!($t);
-                if let Some(token) = s.nextn(this.len()) {
-                    if token.to_uppercase().as_str() != this {
-                        anyhow::bail!("Expected keyword '{}', found {}", this, s.info_with_token(token))
+                let pos = s.current_pos();
+                if let Some(token) = s.peekn(this.len()) {
+                    // The keyword must be the whole word lexeme, so that e.g. `TYPES` is not read as `TYPE` followed
+                    // by `S`
+                    if !s.peek_word().is_some_and(|word| word.eq_ignore_ascii_case(this)) {
+                        s.expect(pos, this);
+                        if s.is_speculative() {
+                            return Err(anyhow::Error::msg(concat!(
+                                "Expected keyword '",
+                                stringify!($t),
+                                "'"
+                            )));
+                        }
+                        anyhow::bail!(
+                            "Expected keyword '{}', found {}",
+                            this,
+                            s.info_with_token(token.to_string())
+                        )
                     }
+                    s.nextn(this.len());
                     Ok($t)
                 } else {
+                    s.expect(pos, this);
                     anyhow::bail!("Expected keyword '{}', found end of stream", this)
                 }
             }
@@ -96,6 +115,7 @@ macro_rules! keyword {
 keyword!(ACCESS);
 keyword!(ADD);
 keyword!(AGGREGATE);
+keyword!(AGGREGATES);
 keyword!(ALL);
 keyword!(ALLOW);
 keyword!(ALTER);
@@ -104,6 +124,8 @@ keyword!(APPLY);
 keyword!(AS);
 keyword!(ASC);
 keyword!(ASCII);
+keyword!(ATTACH);
+keyword!(ATTACHED);
 keyword!(AUTHORIZE);
 keyword!(BATCH);
 keyword!(BEGIN);
@@ -115,6 +137,7 @@ keyword!(BYPASS);
 keyword!(CALLED);
 keyword!(CACHE);
 keyword!(CAST);
+keyword!(CLUSTER);
 keyword!(CLUSTERING);
 keyword!(COLUMNFAMILY);
 keyword!(COMPACT);
@@ -130,6 +153,7 @@ keyword!(DEFAULT);
 keyword!(DELETE);
 keyword!(DESC);
 keyword!(DESCRIBE);
+keyword!(DETACH);
 keyword!(DISTINCT);
 keyword!(DOUBLE);
 keyword!(DROP);
@@ -156,6 +180,7 @@ keyword!(INITCOND);
 keyword!(INPUT);
 keyword!(INSERT);
 keyword!(INT);
+keyword!(INTERNALS);
 keyword!(INTO);
 keyword!(IS);
 keyword!(JSON);
@@ -181,6 +206,7 @@ keyword!(NOT);
 keyword!(NULL);
 keyword!(OF);
 keyword!(ON);
+keyword!(ONLY);
 keyword!(OPTIONS);
 keyword!(OR);
 keyword!(ORDER);
@@ -199,14 +225,18 @@ keyword!(ROLE);
 keyword!(ROLES);
 keyword!(SCHEMA);
 keyword!(SELECT);
+keyword!(SERVICE_LEVEL);
+keyword!(SERVICE_LEVELS);
 keyword!(SET);
 keyword!(SFUNC);
+keyword!(SHARES);
 keyword!(SMALLINT);
 keyword!(STATIC);
 keyword!(STORAGE);
 keyword!(STYPE);
 keyword!(SUPERUSER);
 keyword!(TABLE);
+keyword!(TABLES);
 keyword!(TEXT);
 keyword!(TIME);
 keyword!(TIMEOUT);
@@ -221,6 +251,7 @@ keyword!(TTL);
 keyword!(TUPLE);
 keyword!(TRUE);
 keyword!(TYPE);
+keyword!(TYPES);
 keyword!(UNLOGGED);
 keyword!(UNSET);
 keyword!(UPDATE);
@@ -235,6 +266,7 @@ keyword!(VARINT);
 keyword!(VIEW);
 keyword!(WHERE);
 keyword!(WITH);
+keyword!(WORKLOAD_TYPE);
 keyword!(WRITETIME);
 
 impl Display for ReservedKeyword {
@@ -385,11 +417,21 @@ macro_rules! punctuation {
         impl Parse for $t {
             type Output = Self;
             fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-                let c = s.parse::<char>()?;
-                if c != $c {
-                    anyhow::bail!("Expected '{}', found {}", $c, s.info_with_token(c.to_string()));
+                let pos = s.current_pos();
+                match s.peek() {
+                    Some(c) if c == $c => {
+                        s.next();
+                        Ok($t)
+                    }
+                    Some(c) => {
+                        s.expect(pos, format!("'{}'", $c));
+                        anyhow::bail!("Expected '{}', found {}", $c, s.info_with_token(c.to_string()));
+                    }
+                    None => {
+                        s.expect(pos, format!("'{}'", $c));
+                        anyhow::bail!("Expected '{}', found end of stream", $c)
+                    }
                 }
-                Ok($t)
             }
         }
     };
@@ -0,0 +1,126 @@
+use std::ops::Range;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LexemeKind {
+    Whitespace,
+    /// A `--`, `//` or `/* */` comment
+    Comment,
+    /// A run of alphanumeric characters and underscores, such as a keyword, an unquoted name or a number
+    Word,
+    /// A double quoted name
+    QuotedName,
+    /// A single quoted or `$$` escaped string literal
+    String,
+    /// Any other single character
+    Symbol,
+}
+
+/// A lexeme of a statement, located in the source text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lexeme {
+    pub kind: LexemeKind,
+    /// The byte range of the lexeme
+    pub span: Range<usize>,
+    /// The character offset of the start of the lexeme
+    pub pos: usize,
+}
+
+impl Lexeme {
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.span.clone()]
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Find the end of a quoted lexeme, where quotes are escaped by doubling them
+fn quoted_end(rest: &str, quote: char) -> usize {
+    let mut chars = rest.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == quote && chars.next_if(|&(_, c)| c == quote).is_none() {
+            return i + c.len_utf8();
+        }
+    }
+    rest.len()
+}
+
+/// Split a statement or script into lexemes. Every character of the source belongs to exactly one lexeme, so
+/// lexing cannot fail; unterminated quotes and comments extend to the end of the source.
+pub fn tokenize(source: &str) -> Vec<Lexeme> {
+    let mut lexemes = Vec::new();
+    let (mut start, mut pos) = (0, 0);
+    while let Some(c) = source[start..].chars().next() {
+        let rest = &source[start..];
+        let (kind, len) = if c.is_whitespace() {
+            (
+                LexemeKind::Whitespace,
+                rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len()),
+            )
+        } else if rest.starts_with("--") || rest.starts_with("//") {
+            (LexemeKind::Comment, rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            (LexemeKind::Comment, comment.find("*/").map_or(rest.len(), |e| e + 4))
+        } else if let Some(string) = rest.strip_prefix("$$") {
+            (LexemeKind::String, string.find("$$").map_or(rest.len(), |e| e + 4))
+        } else if c == '\'' {
+            (LexemeKind::String, quoted_end(rest, c))
+        } else if c == '"' {
+            (LexemeKind::QuotedName, quoted_end(rest, c))
+        } else if is_word(c) {
+            (LexemeKind::Word, rest.find(|c| !is_word(c)).unwrap_or(rest.len()))
+        } else {
+            (LexemeKind::Symbol, c.len_utf8())
+        };
+        lexemes.push(Lexeme {
+            kind,
+            span: start..start + len,
+            pos,
+        });
+        start += len;
+        pos += rest[..len].chars().count();
+    }
+    lexemes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let source = "SELECT \"My Col\", ß_1 FROM t -- done\nWHERE a = 'it''s' AND b = $$x$y$$ /* end */;";
+        let lexemes = tokenize(source);
+        assert_eq!(
+            lexemes
+                .iter()
+                .filter(|l| l.kind != LexemeKind::Whitespace)
+                .map(|l| (l.kind, l.text(source)))
+                .collect::<Vec<_>>(),
+            vec![
+                (LexemeKind::Word, "SELECT"),
+                (LexemeKind::QuotedName, "\"My Col\""),
+                (LexemeKind::Symbol, ","),
+                (LexemeKind::Word, "ß_1"),
+                (LexemeKind::Word, "FROM"),
+                (LexemeKind::Word, "t"),
+                (LexemeKind::Comment, "-- done"),
+                (LexemeKind::Word, "WHERE"),
+                (LexemeKind::Word, "a"),
+                (LexemeKind::Symbol, "="),
+                (LexemeKind::String, "'it''s'"),
+                (LexemeKind::Word, "AND"),
+                (LexemeKind::Word, "b"),
+                (LexemeKind::Symbol, "="),
+                (LexemeKind::String, "$$x$y$$"),
+                (LexemeKind::Comment, "/* end */"),
+                (LexemeKind::Symbol, ";"),
+            ]
+        );
+        assert!(lexemes.windows(2).all(|w| w[0].span.end == w[1].span.start));
+        let last = lexemes.last().unwrap();
+        assert_eq!(last.pos, source.chars().count() - 1);
+        assert_eq!(tokenize("'open").len(), 1);
+    }
+}
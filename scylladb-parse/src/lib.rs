@@ -1,7 +1,16 @@
-use anymap::{
-    AnyMap,
-    Map,
-};
+//! Statement parsing library for Scylla CQL.
+//!
+//! ## Features
+//!
+//! - `serde`: Derives `Serialize` and `Deserialize` for the statement syntax tree, i.e. every statement, term, type and
+//!   option type. The representation follows the type definitions: structs are objects keyed by field name, enums are
+//!   externally tagged by variant name (`{"Unquoted": "movie"}`, or `"Anonymous"` for unit variants), `Option`s are
+//!   `null` when absent, and maps whose keys are not strings (map and user defined type literals, replication
+//!   datacenters) are arrays of `[key, value]` pairs. Deserialized statements display as equivalent CQL.
+//! - `proptest`: Implements `proptest::arbitrary::Arbitrary` for the syntax tree, generating values in their canonical
+//!   form so that displaying and re-parsing them yields the same value. [`check_round_trip`], [`round_trip`] and
+//!   [`round_trip_with`] run that check, so downstream extensions can reuse it.
+
 use derive_builder::Builder;
 use derive_more::{
     From,
@@ -15,8 +24,14 @@ use quote::{
 use scylladb_parse_macros::{
     ParseFromStr,
     ToTokens,
+    Visitable,
 };
 use std::{
+    any::{
+        Any,
+        TypeId,
+    },
+    borrow::Cow,
     cell::RefCell,
     collections::{
         BTreeMap,
@@ -31,6 +46,10 @@ use std::{
         Display,
         Formatter,
     },
+    hash::{
+        BuildHasherDefault,
+        Hasher,
+    },
     marker::PhantomData,
     rc::Rc,
     str::FromStr,
@@ -46,6 +65,73 @@ use keywords::*;
 mod data_types;
 pub use data_types::*;
 
+mod error;
+use error::Expected;
+pub use error::ParseError;
+
+mod script;
+pub use script::*;
+
+mod schema;
+pub use schema::*;
+
+mod diff;
+pub use diff::*;
+
+mod validate;
+pub use validate::*;
+
+mod normalize;
+pub use normalize::*;
+
+mod infer;
+pub use infer::*;
+
+mod value;
+pub use value::*;
+
+mod catalog;
+pub use catalog::*;
+
+mod visit;
+pub use visit::*;
+
+mod lexer;
+pub use lexer::*;
+
+#[cfg(feature = "proptest")]
+mod arbitrary;
+#[cfg(feature = "proptest")]
+pub use arbitrary::*;
+
+/// Serializes maps as sequences of `[key, value]` pairs, as their keys cannot be represented as JSON object keys.
+#[cfg(feature = "serde")]
+mod map_entries {
+    use serde::{
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+    };
+    use std::collections::BTreeMap;
+
+    pub fn serialize<K: Serialize, V: Serialize, S: Serializer>(
+        map: &BTreeMap<K, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Ord,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
 pub struct StreamInfo {
     pub next_token: String,
     pub pos: usize,
@@ -67,6 +153,7 @@ impl Display for StreamInfo {
 #[derive(Debug)]
 pub struct Cached<T: Parse> {
     pub value: T::Output,
+    /// The number of bytes consumed by the parsed value
     pub len: usize,
 }
 
@@ -88,27 +175,74 @@ impl<T: Parse> Cached<T> {
     }
 }
 
+/// A hasher for the parse cache, whose keys are byte offsets and type ids that need no DoS resistance
+#[derive(Default)]
+struct CacheHasher(u64);
+
+impl Hasher for CacheHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.write_u64(*b as u64);
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+type ParseCache = HashMap<(usize, TypeId), Box<dyn Any>, BuildHasherDefault<CacheHasher>>;
+
+/// A cursor over a statement, from which the `Parse` implementations consume its text.
+///
+/// The statement is split into lexemes up front. Whitespace is skipped, and keywords and unquoted names are matched
+/// against whole word lexemes, in a single step. Peeking returns slices of the statement, so looking ahead never
+/// allocates. The `Parse` implementations of literals and punctuation still read the text at the cursor rather than
+/// lexemes, and alternatives backtrack through a cache of the values parsed at each offset.
 #[derive(Clone)]
 pub struct StatementStream<'a> {
-    cursor: std::iter::Peekable<std::str::Chars<'a>>,
+    source: &'a str,
+    lexemes: Rc<[Lexeme]>,
+    /// The index of the lexeme containing the cursor
+    lexeme: usize,
+    /// The byte offset of the cursor
+    offset: usize,
     pos: usize,
-    rem: usize,
-    cache: Rc<RefCell<HashMap<usize, AnyMap>>>,
+    len: usize,
+    cache: Rc<RefCell<ParseCache>>,
     ordered_tags: Rc<RefCell<Vec<TokenStream>>>,
     curr_ordered_tag: usize,
     keyed_tags: Rc<RefCell<HashMap<String, TokenStream>>>,
+    expected: Rc<RefCell<Expected>>,
+    /// Whether errors from this stream will be discarded, so they need not be descriptive
+    speculative: bool,
 }
 
 impl<'a> StatementStream<'a> {
     pub fn new(statement: &'a str) -> Self {
+        let lexemes = tokenize(statement);
+        let cache = ParseCache::with_capacity_and_hasher(lexemes.len() * 4, Default::default());
         Self {
-            cursor: statement.chars().peekable(),
+            source: statement,
+            len: lexemes.last().map_or(0, |l| l.pos + l.text(statement).chars().count()),
+            lexemes: lexemes.into(),
+            lexeme: 0,
+            offset: 0,
             pos: 0,
-            rem: statement.chars().count(),
-            cache: Default::default(),
+            cache: Rc::new(RefCell::new(cache)),
             ordered_tags: Default::default(),
             curr_ordered_tag: Default::default(),
             keyed_tags: Default::default(),
+            expected: Default::default(),
+            speculative: false,
         }
     }
 
@@ -121,14 +255,27 @@ impl<'a> StatementStream<'a> {
     }
 
     pub fn info(&self) -> StreamInfo {
-        self.info_with_token(self.clone().parse_from::<Token>().unwrap_or_default())
+        if self.speculative {
+            return self.info_with_token(String::new());
+        }
+        let rest = self.source[self.offset..].trim_start();
+        self.info_with_token(rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())].to_string())
     }
 
     fn info_with_token(&self, next_token: String) -> StreamInfo {
+        if self.speculative {
+            return StreamInfo {
+                next_token,
+                pos: self.pos,
+                rem: self.remaining(),
+                ordered_tags: Vec::new(),
+                keyed_tags: HashMap::new(),
+            };
+        }
         StreamInfo {
             next_token,
             pos: self.pos,
-            rem: self.rem,
+            rem: self.remaining(),
             ordered_tags: self.ordered_tags.borrow().iter().map(|t| t.to_string()).collect(),
             keyed_tags: self
                 .keyed_tags
@@ -139,108 +286,161 @@ impl<'a> StatementStream<'a> {
         }
     }
 
+    /// Whether errors from this stream will be discarded, as it is checking for an optional value
+    pub fn is_speculative(&self) -> bool {
+        self.speculative
+    }
+
+    /// Record that `alternative` would have been accepted at `pos`.
+    /// Only the alternatives at the furthest failed position are kept.
+    pub fn expect(&self, pos: usize, alternative: impl Into<Cow<'static, str>>) {
+        self.expected.borrow_mut().insert(pos, alternative.into());
+    }
+
+    /// Create a located error from a parse failure on this stream.
+    pub fn error(&self, error: anyhow::Error) -> ParseError {
+        let expected = self.expected.borrow();
+        let (pos, alternatives) = if expected.alternatives.is_empty() || expected.pos < self.pos {
+            (self.pos, Default::default())
+        } else {
+            (
+                expected.pos,
+                expected.alternatives.iter().map(|a| a.to_string()).collect(),
+            )
+        };
+        ParseError::new(self.source, pos, alternatives, format!("{:#}", error))
+    }
+
     pub fn current_pos(&self) -> usize {
         self.pos
     }
 
     pub fn remaining(&self) -> usize {
-        self.rem
+        self.len - self.pos
     }
 
     pub fn nremaining(&self, n: usize) -> bool {
-        self.rem >= n
+        self.remaining() >= n
+    }
+
+    /// Get the lexeme containing the cursor
+    pub fn lexeme(&self) -> Option<&Lexeme> {
+        self.lexemes.get(self.lexeme)
     }
 
     pub fn peek(&mut self) -> Option<char> {
-        if self.rem == 0 {
-            return None;
-        }
-        self.cursor.peek().map(|c| *c)
+        self.source[self.offset..].chars().next()
     }
 
-    pub fn peekn(&mut self, n: usize) -> Option<String> {
-        if self.rem < n {
-            return None;
-        }
-        let mut cursor = self.cursor.clone();
-        let mut res = String::new();
-        for _ in 0..n {
-            if let Some(next) = cursor.next() {
-                res.push(next);
-            } else {
-                return None;
-            }
-        }
-        Some(res)
+    /// Peek the next `n` characters
+    pub fn peekn(&self, n: usize) -> Option<&'a str> {
+        let rest = &self.source[self.offset..];
+        let end = rest.char_indices().map(|(i, _)| i).chain(Some(rest.len())).nth(n)?;
+        Some(&rest[..end])
     }
 
     pub(crate) fn next(&mut self) -> Option<char> {
-        if self.rem == 0 {
-            return None;
-        }
-        let res = self.cursor.next();
-        if res.is_some() {
+        let res = self.peek();
+        if let Some(c) = res {
+            self.offset += c.len_utf8();
             self.pos += 1;
-            self.rem -= 1;
+            while self.lexemes.get(self.lexeme).is_some_and(|l| l.span.end <= self.offset) {
+                self.lexeme += 1;
+            }
         }
         res
     }
 
-    pub(crate) fn nextn(&mut self, n: usize) -> Option<String> {
-        if self.nremaining(n) {
-            let mut res = String::new();
-            for _ in 0..n {
-                res.push(self.next().unwrap());
-            }
-            Some(res)
-        } else {
-            None
-        }
+    pub(crate) fn nextn(&mut self, n: usize) -> Option<&'a str> {
+        self.peekn(n).map(|res| {
+            self.seek(self.offset + res.len());
+            res
+        })
+    }
+
+    /// Peek the rest of the word lexeme at the cursor, if any
+    pub fn peek_word(&self) -> Option<&'a str> {
+        let end = self
+            .lexeme()
+            .filter(|l| l.kind == LexemeKind::Word && l.span.start <= self.offset)?
+            .span
+            .end;
+        Some(&self.source[self.offset..end])
+    }
+
+    /// Consume the rest of the word lexeme at the cursor, if any
+    pub(crate) fn next_word(&mut self) -> Option<&'a str> {
+        self.peek_word().map(|res| {
+            self.seek(self.offset + res.len());
+            res
+        })
+    }
+
+    /// Consume the characters at the cursor for as long as they match a predicate
+    pub(crate) fn next_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.source[self.offset..];
+        let res = &rest[..rest.find(|c| !f(c)).unwrap_or(rest.len())];
+        self.seek(self.offset + res.len());
+        res
+    }
+
+    /// Move the cursor to a byte offset
+    fn seek(&mut self, offset: usize) {
+        self.offset = offset;
+        self.lexeme = self.lexemes.partition_point(|l| l.span.end <= offset);
+        self.pos = match self.lexemes.get(self.lexeme) {
+            Some(l) => l.pos + self.source[l.span.start..offset].chars().count(),
+            None => self.len,
+        };
     }
 
     fn skip_whitespace(&mut self) {
-        while let Some(c) = self.peek() {
-            if c.is_whitespace() {
-                self.next();
-                continue;
-            } else {
-                break;
-            };
+        if let Some(end) = self
+            .lexeme()
+            .filter(|l| l.kind == LexemeKind::Whitespace)
+            .map(|l| l.span.end)
+        {
+            self.seek(end);
+        }
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
         }
     }
 
     fn check_cache<P: 'static + Parse>(&self) -> bool {
-        self.cache
-            .borrow()
-            .get(&self.pos)
-            .and_then(|m| m.get::<Cached<P>>())
-            .is_some()
+        self.cache.borrow().contains_key(&(self.offset, TypeId::of::<P>()))
     }
 
     fn retrieve_cache<P: 'static + Parse>(&self) -> Option<Cached<P>>
     where
-        P::Output: Clone,
+        P::Output: 'static + Clone,
     {
         self.cache
             .borrow()
-            .get(&self.pos)
-            .and_then(|m| m.get::<Cached<P>>().cloned())
+            .get(&(self.offset, TypeId::of::<P>()))
+            .and_then(|c| c.downcast_ref::<Cached<P>>().cloned())
     }
 
-    fn set_cache<P: 'static + Parse>(&self, value: P::Output, prev_pos: usize) {
-        let mut cache = self.cache.borrow_mut();
-        let map = cache.entry(prev_pos).or_insert_with(Map::new);
-        map.insert(Cached::<P>::new(value, self.pos - prev_pos));
+    fn set_cache<P: 'static + Parse>(&self, value: P::Output, prev_offset: usize)
+    where
+        P::Output: 'static,
+    {
+        self.cache.borrow_mut().insert(
+            (prev_offset, TypeId::of::<P>()),
+            Box::new(Cached::<P>::new(value, self.offset - prev_offset)),
+        );
     }
 
-    fn set_and_retrieve_cache<P: 'static + Parse>(&self, value: P::Output, prev_pos: usize) -> P::Output
+    fn set_and_retrieve_cache<P: 'static + Parse>(&self, value: P::Output, prev_offset: usize) -> P::Output
     where
-        P::Output: Clone,
+        P::Output: 'static + Clone,
     {
         let mut cache = self.cache.borrow_mut();
-        let map = cache.entry(prev_pos).or_insert_with(Map::new);
-        map.entry::<Cached<P>>()
-            .or_insert(Cached::new(value, self.pos - prev_pos))
+        cache
+            .entry((prev_offset, TypeId::of::<P>()))
+            .or_insert_with(|| Box::new(Cached::<P>::new(value, self.offset - prev_offset)))
+            .downcast_ref::<Cached<P>>()
+            .unwrap()
             .value
             .clone()
     }
@@ -267,8 +467,9 @@ impl<'a> StatementStream<'a> {
             return true;
         }
         let mut this = self.clone();
+        this.speculative = true;
         this.skip_whitespace();
-        P::parse(&mut this).map(|p| this.set_cache::<P>(p, self.pos)).is_ok()
+        P::parse(&mut this).map(|p| this.set_cache::<P>(p, self.offset)).is_ok()
     }
 
     pub fn find<P: 'static + Parse<Output = P> + Clone>(&self) -> Option<P> {
@@ -276,10 +477,11 @@ impl<'a> StatementStream<'a> {
             return Some(cached.value);
         }
         let mut this = self.clone();
+        this.speculative = true;
         this.skip_whitespace();
         P::parse(&mut this)
             .ok()
-            .map(|p| this.set_and_retrieve_cache::<P>(p, self.pos))
+            .map(|p| this.set_and_retrieve_cache::<P>(p, self.offset))
     }
 
     pub fn find_from<P: 'static + Parse>(&self) -> Option<P::Output>
@@ -290,33 +492,45 @@ impl<'a> StatementStream<'a> {
             return Some(cached.value);
         }
         let mut this = self.clone();
+        this.speculative = true;
         this.skip_whitespace();
         P::parse(&mut this)
             .ok()
-            .map(|p| this.set_and_retrieve_cache::<P>(p, self.pos))
+            .map(|p| this.set_and_retrieve_cache::<P>(p, self.offset))
     }
 
     pub fn parse<P: 'static + Parse<Output = P> + Clone>(&mut self) -> anyhow::Result<P> {
-        let pos = self.pos;
+        let offset = self.offset;
         if let Some(cached) = self.retrieve_cache::<P>() {
-            self.nextn(cached.len);
+            self.seek(offset + cached.len);
             return Ok(cached.value);
         }
         self.skip_whitespace();
-        P::parse(self).map(|p| self.set_and_retrieve_cache::<P>(p, pos))
+        P::parse(self)
     }
 
     pub fn parse_from<P: 'static + Parse>(&mut self) -> anyhow::Result<P::Output>
     where
         P::Output: 'static + Clone,
     {
-        let pos = self.pos;
+        let offset = self.offset;
         if let Some(cached) = self.retrieve_cache::<P>() {
-            self.nextn(cached.len);
+            self.seek(offset + cached.len);
             return Ok(cached.value);
         }
         self.skip_whitespace();
-        P::parse(self).map(|p| self.set_and_retrieve_cache::<P>(p, pos))
+        P::parse(self)
+    }
+
+    pub fn try_parse<P: 'static + Parse<Output = P> + Clone>(&mut self) -> Result<P, ParseError> {
+        self.parse().map_err(|e| self.error(e))
+    }
+
+    pub fn try_parse_from<P: 'static + Parse>(&mut self) -> Result<P::Output, ParseError>
+    where
+        P::Output: 'static + Clone,
+    {
+        self.parse_from::<P>().map_err(|e| self.error(e))
     }
 }
 
@@ -500,13 +714,7 @@ pub struct Whitespace;
 impl Parse for Whitespace {
     type Output = Self;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        while let Some(c) = s.peek() {
-            if c.is_whitespace() {
-                s.next();
-            } else {
-                break;
-            }
-        }
+        s.skip_whitespace();
         Ok(Whitespace)
     }
 }
@@ -516,18 +724,14 @@ pub struct Token;
 impl Parse for Token {
     type Output = String;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        let mut res = String::new();
-        while let Some(c) = s.next() {
-            if c.is_whitespace() {
-                break;
-            } else {
-                res.push(c);
-            }
-        }
+        let rest = &s.source[s.offset..];
+        let res = &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())];
         if res.is_empty() {
             anyhow::bail!("End of statement!")
         }
-        Ok(res)
+        s.seek(s.offset + res.len());
+        s.next();
+        Ok(res.to_string())
     }
 }
 
@@ -655,22 +859,14 @@ pub struct Alpha;
 impl Parse for Alpha {
     type Output = String;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        let mut res = String::new();
-        while let Some(c) = s.peek() {
-            if c.is_alphabetic() {
-                res.push(c);
-                s.next();
-            } else {
-                if res.is_empty() {
-                    anyhow::bail!("Expected text, found: {}", s.info())
-                }
-                break;
-            }
-        }
+        let res = s.next_while(char::is_alphabetic);
         if res.is_empty() {
+            if s.remaining() > 0 {
+                anyhow::bail!("Expected text, found: {}", s.info())
+            }
             anyhow::bail!("End of statement!")
         }
-        Ok(res)
+        Ok(res.to_string())
     }
 }
 
@@ -679,19 +875,11 @@ pub struct Hex;
 impl Parse for Hex {
     type Output = Vec<u8>;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        let mut res = String::new();
-        while let Some(c) = s.peek() {
-            if c.is_alphanumeric() {
-                res.push(c);
-                s.next();
-            } else {
-                if res.is_empty() {
-                    anyhow::bail!("Expected hex, found: {}", s.info())
-                }
-                break;
-            }
-        }
+        let res = s.next_while(char::is_alphanumeric);
         if res.is_empty() {
+            if s.remaining() > 0 {
+                anyhow::bail!("Expected hex, found: {}", s.info())
+            }
             anyhow::bail!("End of statement!")
         }
         Ok(hex::decode(res)?)
@@ -703,19 +891,11 @@ pub struct Alphanumeric;
 impl Parse for Alphanumeric {
     type Output = String;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        let mut res = String::new();
-        while let Some(c) = s.peek() {
-            if c.is_alphanumeric() {
-                res.push(c);
-                s.next();
-            } else {
-                break;
-            }
-        }
+        let res = s.next_while(char::is_alphanumeric);
         if res.is_empty() {
             anyhow::bail!("End of statement!")
         }
-        Ok(res)
+        Ok(res.to_string())
     }
 }
 
@@ -724,22 +904,15 @@ pub struct Number;
 impl Parse for Number {
     type Output = String;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        let mut res = String::new();
-        while let Some(c) = s.peek() {
-            if c.is_numeric() {
-                res.push(c);
-                s.next();
-            } else {
-                if res.is_empty() {
-                    anyhow::bail!("Expected number, found: {}", s.info())
-                }
-                break;
-            }
-        }
+        let res = s.next_while(char::is_numeric);
         if res.is_empty() {
+            if s.remaining() > 0 {
+                s.expect(s.current_pos(), "number");
+                anyhow::bail!("Expected number, found: {}", s.info())
+            }
             anyhow::bail!("End of statement!")
         }
-        Ok(res)
+        Ok(res.to_string())
     }
 }
 
@@ -766,6 +939,7 @@ impl Parse for SignedNumber {
                 }
             } else {
                 if res.is_empty() {
+                    s.expect(s.current_pos(), "number");
                     anyhow::bail!("Expected signed number, found: {}", s.info())
                 }
                 break;
@@ -834,6 +1008,7 @@ impl Parse for Float {
                 }
             } else {
                 if res.is_empty() {
+                    s.expect(s.current_pos(), "float");
                     anyhow::bail!("Expected float, found: {}", s.info())
                 }
                 break;
@@ -881,7 +1056,8 @@ parse_peek_group!(Angles, LeftAngle, RightAngle);
 parse_peek_group!(SingleQuoted, SingleQuote, SingleQuote);
 parse_peek_group!(DoubleQuoted, DoubleQuote, DoubleQuote);
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BindMarker {
     #[try_into(ignore)]
     Anonymous,
@@ -919,8 +1095,10 @@ impl<T: Into<Name>> From<T> for BindMarker {
 impl Parse for Uuid {
     type Output = Self;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        if let Some(u) = s.nextn(36) {
-            Ok(uuid::Uuid::parse_str(&u)?)
+        if let Some(u) = s.peekn(36) {
+            let res = uuid::Uuid::parse_str(u)?;
+            s.nextn(36);
+            Ok(res)
         } else {
             anyhow::bail!("Expected UUID, found {}", s.info())
         }
@@ -934,13 +1112,15 @@ impl<'a> CustomToTokens<'a> for Uuid {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LitStrKind {
     Quoted,
     Escaped,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LitStr {
     pub kind: LitStrKind,
     pub value: String,
@@ -965,30 +1145,29 @@ impl LitStr {
 impl Parse for LitStr {
     type Output = Self;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        let mut res = String::new();
-        let mut kind = LitStrKind::Quoted;
-        if s.peek() == Some('\'') {
-            s.next();
-        } else if s.peekn(2).map(|s| s.as_str() == "$$").unwrap_or(false) {
-            kind = LitStrKind::Escaped;
-            s.nextn(2);
+        let (kind, close) = if s.peek() == Some('\'') {
+            (LitStrKind::Quoted, "'")
+        } else if s.peekn(2) == Some("$$") {
+            (LitStrKind::Escaped, "$$")
         } else {
+            s.expect(s.current_pos(), "string literal");
             return Err(anyhow::anyhow!(
                 "Expected opening quote for LitStr, found: {}",
                 s.info()
             ));
-        }
-        while let Some(c) = s.next() {
-            if kind == LitStrKind::Escaped && c == '$' && s.peek().map(|c| c == '$').unwrap_or(false) {
-                s.next();
-                return Ok(LitStr { kind, value: res });
-            } else if kind == LitStrKind::Quoted && c == '\'' {
-                return Ok(LitStr { kind, value: res });
-            } else {
-                res.push(c);
+        };
+        s.nextn(close.chars().count());
+        let rest = &s.source[s.offset..];
+        match rest.find(close) {
+            Some(end) => {
+                s.seek(s.offset + end + close.len());
+                Ok(LitStr {
+                    kind,
+                    value: rest[..end].to_string(),
+                })
             }
+            None => anyhow::bail!("End of statement!"),
         }
-        anyhow::bail!("End of statement!")
     }
 }
 
@@ -1023,7 +1202,8 @@ impl From<&str> for LitStr {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Name {
     Quoted(String),
     Unquoted(String),
@@ -1050,32 +1230,30 @@ impl Name {
 impl Parse for Name {
     type Output = Self;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self> {
-        let mut res = String::new();
-        if s.peek().map(|c| c == '\"').unwrap_or(false) {
+        let pos = s.current_pos();
+        if s.peek() == Some('"') {
             s.next();
-            while let Some(c) = s.next() {
-                if c == '\"' {
-                    return Ok(Self::Quoted(res));
-                } else {
-                    res.push(c);
+            let rest = &s.source[s.offset..];
+            match rest.find('"') {
+                Some(end) => {
+                    s.seek(s.offset + end + 1);
+                    Ok(Self::Quoted(rest[..end].to_string()))
                 }
+                None => anyhow::bail!("End of statement!"),
             }
-            anyhow::bail!("End of statement!")
         } else {
-            while let Some(c) = s.peek() {
-                if c.is_alphanumeric() || c == '_' {
-                    s.next();
-                    res.push(c);
-                } else {
-                    break;
-                }
-            }
+            let res = s.next_word().unwrap_or_default();
             if res.is_empty() {
+                s.expect(pos, "identifier");
                 anyhow::bail!("End of statement!")
-            } else if ReservedKeyword::from_str(&res).is_ok() {
+            } else if !res.starts_with(char::is_alphabetic) {
+                s.expect(pos, "identifier");
+                anyhow::bail!("Invalid name: {} does not start with a letter", res)
+            } else if ReservedKeyword::from_str(res).is_ok() {
+                s.expect(pos, "identifier");
                 anyhow::bail!("Invalid name: {} is a reserved keyword", res)
             }
-            return Ok(Self::Unquoted(res));
+            Ok(Self::Unquoted(res.to_string()))
         }
     }
 }
@@ -1127,7 +1305,8 @@ impl<N: Into<Name>> KeyspaceQualifyExt for N {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedKeyspaceQualifiedName)]
 pub struct KeyspaceQualifiedName {
     pub keyspace: Option<Name>,
@@ -1191,7 +1370,8 @@ impl From<&str> for KeyspaceQualifiedName {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedStatementOpt)]
 pub struct StatementOpt {
     pub name: Name,
@@ -1238,7 +1418,8 @@ impl Display for StatementOpt {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, From)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, From, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedStatementOptValue)]
 pub enum StatementOptValue {
     Identifier(Name),
@@ -1300,7 +1481,8 @@ impl Display for TaggedStatementOptValue {
     }
 }
 
-#[derive(Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColumnDefinition {
     #[builder(setter(into))]
     pub name: Name,
@@ -1354,7 +1536,8 @@ impl<T: Into<Name>> From<(T, NativeType)> for ColumnDefinition {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PrimaryKey {
     pub partition_key: PartitionKey,
     pub clustering_columns: Option<Vec<Name>>,
@@ -1423,7 +1606,8 @@ impl<N: Into<Name>> From<Vec<N>> for PrimaryKey {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PartitionKey {
     pub columns: Vec<Name>,
 }
@@ -1479,8 +1663,9 @@ impl<N: Into<Name>> From<N> for PartitionKey {
     }
 }
 
-// TODO: Scylla encryption opts and caching?
-#[derive(Builder, Clone, Debug, Default, ToTokens, PartialEq)]
+// TODO: Scylla encryption opts?
+#[derive(Builder, Clone, Debug, Default, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), default, build_fn(validate = "Self::validate"))]
 pub struct TableOpts {
     pub compact_storage: bool,
@@ -1488,7 +1673,8 @@ pub struct TableOpts {
     #[builder(setter(into))]
     pub comment: Option<LitStr>,
     pub speculative_retry: Option<SpeculativeRetry>,
-    pub change_data_capture: Option<bool>,
+    #[builder(setter(into))]
+    pub change_data_capture: Option<ChangeDataCapture>,
     pub gc_grace_seconds: Option<i32>,
     pub bloom_filter_fp_chance: Option<f32>,
     pub default_time_to_live: Option<i32>,
@@ -1497,6 +1683,13 @@ pub struct TableOpts {
     pub caching: Option<Caching>,
     pub memtable_flush_period_in_ms: Option<i32>,
     pub read_repair: Option<bool>,
+    #[builder(setter(into))]
+    pub tombstone_gc: Option<TombstoneGc>,
+    pub per_partition_rate_limit: Option<PerPartitionRateLimit>,
+    pub paxos_grace_seconds: Option<i32>,
+    /// Only applicable to materialized views.
+    pub synchronous_updates: Option<bool>,
+    pub scylla_tags: Option<BTreeMap<String, String>>,
 }
 
 impl Parse for TableOpts {
@@ -1548,6 +1741,8 @@ impl Parse for TableOpts {
                                     anyhow::bail!("Duplicate change data capture option");
                                 } else if let StatementOptValue::Constant(Constant::Boolean(b)) = value {
                                     res.change_data_capture(b);
+                                } else if let StatementOptValue::Map(m) = value {
+                                    res.change_data_capture(ChangeDataCapture::try_from(m)?);
                                 } else {
                                     anyhow::bail!("Invalid change data capture value: {}", value);
                                 }
@@ -1628,6 +1823,63 @@ impl Parse for TableOpts {
                                     anyhow::bail!("Invalid read_repair value: {}", value);
                                 }
                             }
+                            "tombstone_gc" => {
+                                if res.tombstone_gc.is_some() {
+                                    anyhow::bail!("Duplicate tombstone_gc option");
+                                } else if let StatementOptValue::Map(m) = value {
+                                    res.tombstone_gc(TombstoneGc::try_from(m)?);
+                                } else {
+                                    anyhow::bail!("Invalid tombstone_gc value: {}", value);
+                                }
+                            }
+                            "per_partition_rate_limit" => {
+                                if res.per_partition_rate_limit.is_some() {
+                                    anyhow::bail!("Duplicate per_partition_rate_limit option");
+                                } else if let StatementOptValue::Map(m) = value {
+                                    res.per_partition_rate_limit(m.try_into()?);
+                                } else {
+                                    anyhow::bail!("Invalid per_partition_rate_limit value: {}", value);
+                                }
+                            }
+                            "paxos_grace_seconds" => {
+                                if res.paxos_grace_seconds.is_some() {
+                                    anyhow::bail!("Duplicate paxos_grace_seconds option");
+                                } else if let StatementOptValue::Constant(Constant::Integer(i)) = value {
+                                    res.paxos_grace_seconds(i.parse()?);
+                                } else {
+                                    anyhow::bail!("Invalid paxos_grace_seconds value: {}", value);
+                                }
+                            }
+                            "synchronous_updates" => {
+                                if res.synchronous_updates.is_some() {
+                                    anyhow::bail!("Duplicate synchronous_updates option");
+                                } else if let StatementOptValue::Constant(Constant::Boolean(b)) = value {
+                                    res.synchronous_updates(b);
+                                } else {
+                                    anyhow::bail!("Invalid synchronous_updates value: {}", value);
+                                }
+                            }
+                            "scylla_tags" => {
+                                if res.scylla_tags.is_some() {
+                                    anyhow::bail!("Duplicate scylla_tags option");
+                                } else if let StatementOptValue::Map(m) = value {
+                                    let mut tags = BTreeMap::new();
+                                    for (k, v) in m.elements {
+                                        match (k, v) {
+                                            (
+                                                Term::Constant(Constant::String(k)),
+                                                Term::Constant(Constant::String(v)),
+                                            ) => {
+                                                tags.insert(k.value, v.value);
+                                            }
+                                            (k, v) => anyhow::bail!("Invalid scylla_tags entry: {}: {}", k, v),
+                                        }
+                                    }
+                                    res.scylla_tags(tags);
+                                } else {
+                                    anyhow::bail!("Invalid scylla_tags value: {}", value);
+                                }
+                            }
                             _ => anyhow::bail!("Invalid table option: {}", name),
                         }
                     }
@@ -1692,12 +1944,38 @@ impl Display for TableOpts {
                 .to_string(),
             );
         }
+        if let Some(ref c) = self.tombstone_gc {
+            res.push(format!("tombstone_gc = {}", c));
+        }
+        if let Some(ref c) = self.per_partition_rate_limit {
+            res.push(format!("per_partition_rate_limit = {}", c));
+        }
+        if let Some(c) = self.paxos_grace_seconds {
+            res.push(format!("paxos_grace_seconds = {}", c));
+        }
+        if let Some(c) = self.synchronous_updates {
+            res.push(format!("synchronous_updates = {}", c));
+        }
+        if let Some(ref c) = self.scylla_tags {
+            res.push(format!(
+                "scylla_tags = {{{}}}",
+                c.iter()
+                    .map(|(k, v)| format!("{}: {}", LitStr::from(k.clone()), LitStr::from(v.clone())))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
         write!(f, "{}", res.join(" AND "))
     }
 }
 
 impl TableOptsBuilder {
     fn validate(&self) -> Result<(), String> {
+        if let Some(v) = self.paxos_grace_seconds.flatten() {
+            if v < 0 {
+                return Err(format!("paxos_grace_seconds must be a positive integer, found {}", v));
+            }
+        }
         if self.compact_storage.is_some()
             || self.clustering_order.as_ref().map(|v| v.is_some()).unwrap_or_default()
             || self.comment.as_ref().map(|v| v.is_some()).unwrap_or_default()
@@ -1727,6 +2005,23 @@ impl TableOptsBuilder {
                 .map(|v| v.is_some())
                 .unwrap_or_default()
             || self.read_repair.as_ref().map(|v| v.is_some()).unwrap_or_default()
+            || self.tombstone_gc.as_ref().map(|v| v.is_some()).unwrap_or_default()
+            || self
+                .per_partition_rate_limit
+                .as_ref()
+                .map(|v| v.is_some())
+                .unwrap_or_default()
+            || self
+                .paxos_grace_seconds
+                .as_ref()
+                .map(|v| v.is_some())
+                .unwrap_or_default()
+            || self
+                .synchronous_updates
+                .as_ref()
+                .map(|v| v.is_some())
+                .unwrap_or_default()
+            || self.scylla_tags.as_ref().map(|v| v.is_some()).unwrap_or_default()
         {
             Ok(())
         } else {
@@ -1735,7 +2030,8 @@ impl TableOptsBuilder {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColumnOrder {
     pub column: Name,
     pub order: Order,
@@ -1764,7 +2060,8 @@ impl<T: Into<Name>> From<(T, Order)> for ColumnOrder {
     }
 }
 
-#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Order {
     Ascending,
     Descending,
@@ -1798,7 +2095,8 @@ impl Display for Order {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Relation {
     Normal {
         column: Name,
@@ -1911,11 +2209,13 @@ impl Display for Relation {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedReplication)]
 pub struct Replication {
     pub class: LitStr,
     pub replication_factor: Option<i32>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::map_entries"))]
     pub datacenters: BTreeMap<LitStr, i32>,
 }
 
@@ -2101,7 +2401,8 @@ impl Parse for TaggedReplication {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SpeculativeRetry {
     None,
     Always,
@@ -2152,7 +2453,8 @@ impl Display for SpeculativeRetry {
     }
 }
 
-#[derive(Builder, Copy, Clone, Debug, Default, ToTokens, PartialEq)]
+#[derive(Builder, Copy, Clone, Debug, Default, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), default, build_fn(validate = "Self::validate"))]
 pub struct SizeTieredCompactionStrategy {
     enabled: Option<bool>,
@@ -2266,7 +2568,8 @@ impl Display for SizeTieredCompactionStrategy {
     }
 }
 
-#[derive(Builder, Copy, Clone, Debug, Default, ToTokens, PartialEq)]
+#[derive(Builder, Copy, Clone, Debug, Default, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), default, build_fn(validate = "Self::validate"))]
 pub struct LeveledCompactionStrategy {
     enabled: Option<bool>,
@@ -2371,7 +2674,8 @@ impl Display for LeveledCompactionStrategy {
     }
 }
 
-#[derive(Builder, Copy, Clone, Debug, Default, ToTokens, PartialEq)]
+#[derive(Builder, Copy, Clone, Debug, Default, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), default, build_fn(validate = "Self::validate"))]
 pub struct TimeWindowCompactionStrategy {
     enabled: Option<bool>,
@@ -2483,7 +2787,8 @@ impl Display for TimeWindowCompactionStrategy {
 
 pub trait CompactionType: Display + Into<Compaction> {}
 
-#[derive(Clone, Debug, From, TryInto, ToTokens, PartialEq)]
+#[derive(Clone, Debug, From, TryInto, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Compaction {
     SizeTiered(SizeTieredCompactionStrategy),
     Leveled(LeveledCompactionStrategy),
@@ -2666,7 +2971,8 @@ impl Display for Compaction {
     }
 }
 
-#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JavaTimeUnit {
     Minutes,
     Hours,
@@ -2704,7 +3010,8 @@ impl Display for JavaTimeUnit {
     }
 }
 
-#[derive(Builder, Clone, Debug, Default, ToTokens, PartialEq)]
+#[derive(Builder, Clone, Debug, Default, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), default)]
 pub struct Compression {
     #[builder(setter(into))]
@@ -2775,7 +3082,8 @@ impl TryFrom<MapLiteral> for Compression {
     }
 }
 
-#[derive(Builder, Clone, Debug, Default, ToTokens, PartialEq, Eq)]
+#[derive(Builder, Clone, Debug, Default, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), default)]
 pub struct Caching {
     keys: Option<Keys>,
@@ -2824,7 +3132,8 @@ impl TryFrom<MapLiteral> for Caching {
     }
 }
 
-#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Keys {
     All,
     None,
@@ -2858,7 +3167,8 @@ impl Display for Keys {
     }
 }
 
-#[derive(ParseFromStr, Copy, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RowsPerPartition {
     All,
     None,
@@ -2892,6 +3202,349 @@ impl Display for RowsPerPartition {
     }
 }
 
+/// Read a Scylla extension option value, which may be given either as a literal or as its string form
+/// (as `DESCRIBE` outputs them). Values are case insensitive.
+fn extension_option<T: FromStr>(key: &str, value: Term) -> anyhow::Result<T>
+where
+    T::Err: Display,
+{
+    let value = match value {
+        Term::Constant(Constant::String(s)) => s.value,
+        value => value.to_string(),
+    };
+    value
+        .to_lowercase()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid {} value {}: {}", key, value, e))
+}
+
+#[derive(Builder, Copy, Clone, Debug, Default, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(setter(strip_option), default, build_fn(validate = "Self::validate"))]
+pub struct ChangeDataCapture {
+    pub enabled: Option<bool>,
+    pub preimage: Option<CdcPreimage>,
+    pub postimage: Option<bool>,
+    pub delta: Option<CdcDelta>,
+    pub ttl: Option<i32>,
+}
+
+impl ChangeDataCapture {
+    pub fn build() -> ChangeDataCaptureBuilder {
+        ChangeDataCaptureBuilder::default()
+    }
+}
+
+impl ChangeDataCaptureBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(v) = self.ttl.flatten() {
+            if v < 0 {
+                return Err(format!("ttl must be a positive integer, found {}", v));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<bool> for ChangeDataCapture {
+    fn from(enabled: bool) -> Self {
+        Self {
+            enabled: Some(enabled),
+            ..Default::default()
+        }
+    }
+}
+
+impl Display for ChangeDataCapture {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut res = Vec::new();
+        if let Some(enabled) = self.enabled {
+            res.push(format!("'enabled': {}", enabled));
+        }
+        if let Some(preimage) = self.preimage {
+            res.push(format!("'preimage': {}", preimage));
+        }
+        if let Some(postimage) = self.postimage {
+            res.push(format!("'postimage': {}", postimage));
+        }
+        if let Some(delta) = self.delta {
+            res.push(format!("'delta': {}", delta));
+        }
+        if let Some(ttl) = self.ttl {
+            res.push(format!("'ttl': {}", ttl));
+        }
+        write!(f, "{{{}}}", res.join(", "))
+    }
+}
+
+impl TryFrom<MapLiteral> for ChangeDataCapture {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MapLiteral) -> Result<Self, Self::Error> {
+        let mut builder = Self::build();
+        for (k, v) in value.elements {
+            if let Term::Constant(Constant::String(s)) = k {
+                match s.value.to_lowercase().as_str() {
+                    "enabled" => builder.enabled(extension_option("enabled", v)?),
+                    "preimage" => builder.preimage(extension_option("preimage", v)?),
+                    "postimage" => builder.postimage(extension_option("postimage", v)?),
+                    "delta" => builder.delta(extension_option("delta", v)?),
+                    "ttl" => builder.ttl(extension_option("ttl", v)?),
+                    _ => anyhow::bail!("Invalid cdc option: {}", s),
+                };
+            } else {
+                anyhow::bail!("Invalid key in cdc map literal!");
+            }
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Whether CDC log entries record the row state before a change.
+#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CdcPreimage {
+    /// No preimage is recorded.
+    Disabled,
+    /// The preimage holds the columns affected by the change.
+    Enabled,
+    /// The preimage holds the entire row.
+    Full,
+}
+
+impl FromStr for CdcPreimage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "false" => Ok(CdcPreimage::Disabled),
+            "true" => Ok(CdcPreimage::Enabled),
+            "full" => Ok(CdcPreimage::Full),
+            _ => Err(anyhow::anyhow!("Invalid preimage: {}", s)),
+        }
+    }
+}
+
+impl Display for CdcPreimage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CdcPreimage::Disabled => write!(f, "false"),
+            CdcPreimage::Enabled => write!(f, "true"),
+            CdcPreimage::Full => write!(f, "'full'"),
+        }
+    }
+}
+
+/// Which columns CDC delta entries record.
+#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CdcDelta {
+    Full,
+    Keys,
+}
+
+impl FromStr for CdcDelta {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "full" => Ok(CdcDelta::Full),
+            "keys" => Ok(CdcDelta::Keys),
+            _ => Err(anyhow::anyhow!("Invalid delta: {}", s)),
+        }
+    }
+}
+
+impl Display for CdcDelta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CdcDelta::Full => write!(f, "'full'"),
+            CdcDelta::Keys => write!(f, "'keys'"),
+        }
+    }
+}
+
+#[derive(Builder, Copy, Clone, Debug, Default, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(setter(strip_option), default, build_fn(validate = "Self::validate"))]
+pub struct TombstoneGc {
+    pub mode: Option<TombstoneGcMode>,
+    pub propagation_delay_in_seconds: Option<i32>,
+}
+
+impl TombstoneGc {
+    pub fn build() -> TombstoneGcBuilder {
+        TombstoneGcBuilder::default()
+    }
+}
+
+impl TombstoneGcBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(v) = self.propagation_delay_in_seconds.flatten() {
+            if v < 0 {
+                return Err(format!(
+                    "propagation_delay_in_seconds must be a positive integer, found {}",
+                    v
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<TombstoneGcMode> for TombstoneGc {
+    fn from(mode: TombstoneGcMode) -> Self {
+        Self {
+            mode: Some(mode),
+            propagation_delay_in_seconds: None,
+        }
+    }
+}
+
+impl Display for TombstoneGc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut res = Vec::new();
+        if let Some(mode) = self.mode {
+            res.push(format!("'mode': {}", mode));
+        }
+        if let Some(propagation_delay_in_seconds) = self.propagation_delay_in_seconds {
+            res.push(format!(
+                "'propagation_delay_in_seconds': {}",
+                propagation_delay_in_seconds
+            ));
+        }
+        write!(f, "{{{}}}", res.join(", "))
+    }
+}
+
+impl TryFrom<MapLiteral> for TombstoneGc {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MapLiteral) -> Result<Self, Self::Error> {
+        let mut builder = Self::build();
+        for (k, v) in value.elements {
+            if let Term::Constant(Constant::String(s)) = k {
+                match s.value.to_lowercase().as_str() {
+                    "mode" => builder.mode(extension_option("mode", v)?),
+                    "propagation_delay_in_seconds" => {
+                        builder.propagation_delay_in_seconds(extension_option("propagation_delay_in_seconds", v)?)
+                    }
+                    _ => anyhow::bail!("Invalid tombstone_gc option: {}", s),
+                };
+            } else {
+                anyhow::bail!("Invalid key in tombstone_gc map literal!");
+            }
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// When tombstones become eligible for garbage collection.
+#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TombstoneGcMode {
+    /// After `gc_grace_seconds`.
+    Timeout,
+    /// Never.
+    Disabled,
+    /// As soon as they are written.
+    Immediate,
+    /// Once the data they shadow has been repaired.
+    Repair,
+}
+
+impl FromStr for TombstoneGcMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "timeout" => Ok(TombstoneGcMode::Timeout),
+            "disabled" => Ok(TombstoneGcMode::Disabled),
+            "immediate" => Ok(TombstoneGcMode::Immediate),
+            "repair" => Ok(TombstoneGcMode::Repair),
+            _ => Err(anyhow::anyhow!("Invalid tombstone_gc mode: {}", s)),
+        }
+    }
+}
+
+impl Display for TombstoneGcMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TombstoneGcMode::Timeout => write!(f, "'timeout'"),
+            TombstoneGcMode::Disabled => write!(f, "'disabled'"),
+            TombstoneGcMode::Immediate => write!(f, "'immediate'"),
+            TombstoneGcMode::Repair => write!(f, "'repair'"),
+        }
+    }
+}
+
+#[derive(Builder, Copy, Clone, Debug, Default, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(setter(strip_option), default, build_fn(validate = "Self::validate"))]
+pub struct PerPartitionRateLimit {
+    pub max_reads_per_second: Option<i32>,
+    pub max_writes_per_second: Option<i32>,
+}
+
+impl PerPartitionRateLimit {
+    pub fn build() -> PerPartitionRateLimitBuilder {
+        PerPartitionRateLimitBuilder::default()
+    }
+}
+
+impl PerPartitionRateLimitBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(v) = self.max_reads_per_second.flatten() {
+            if v < 0 {
+                return Err(format!("max_reads_per_second must be a positive integer, found {}", v));
+            }
+        }
+        if let Some(v) = self.max_writes_per_second.flatten() {
+            if v < 0 {
+                return Err(format!("max_writes_per_second must be a positive integer, found {}", v));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for PerPartitionRateLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut res = Vec::new();
+        if let Some(max_reads_per_second) = self.max_reads_per_second {
+            res.push(format!("'max_reads_per_second': {}", max_reads_per_second));
+        }
+        if let Some(max_writes_per_second) = self.max_writes_per_second {
+            res.push(format!("'max_writes_per_second': {}", max_writes_per_second));
+        }
+        write!(f, "{{{}}}", res.join(", "))
+    }
+}
+
+impl TryFrom<MapLiteral> for PerPartitionRateLimit {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MapLiteral) -> Result<Self, Self::Error> {
+        let mut builder = Self::build();
+        for (k, v) in value.elements {
+            if let Term::Constant(Constant::String(s)) = k {
+                match s.value.to_lowercase().as_str() {
+                    "max_reads_per_second" => {
+                        builder.max_reads_per_second(extension_option("max_reads_per_second", v)?)
+                    }
+                    "max_writes_per_second" => {
+                        builder.max_writes_per_second(extension_option("max_writes_per_second", v)?)
+                    }
+                    _ => anyhow::bail!("Invalid per_partition_rate_limit option: {}", s),
+                };
+            } else {
+                anyhow::bail!("Invalid key in per_partition_rate_limit map literal!");
+            }
+        }
+        Ok(builder.build()?)
+    }
+}
+
 pub fn format_cql_f32(f: f32) -> String {
     let s = f.to_string();
     if let Ok(res) = StatementStream::new(&s).parse_from::<Float>() {
@@ -0,0 +1,240 @@
+use super::*;
+
+/// A data manipulation statement whose literal values have been replaced by bind markers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NormalizedStatement<T> {
+    pub statement: T,
+    /// One term per bind marker in the normalized statement, in order. Extracted literals are kept as-is, while
+    /// bind markers which were already present are recorded as `Term::BindMarker` so that their values can be
+    /// interleaved with the extracted ones.
+    pub values: Vec<Term>,
+}
+
+impl<T> NormalizedStatement<T> {
+    /// Get the extracted literals, skipping the pre-existing bind markers
+    pub fn literals(&self) -> impl Iterator<Item = &Term> {
+        self.values.iter().filter(|v| !matches!(v, Term::BindMarker(_)))
+    }
+}
+
+impl<T: Display> NormalizedStatement<T> {
+    /// Get the canonical text of the normalized statement. Statements which only differ by their literal values,
+    /// keyword casing, whitespace or unnecessary identifier quoting produce the same text.
+    pub fn text(&self) -> String {
+        self.statement.to_string()
+    }
+}
+
+/// Rewrites statements for prepared statement reuse by extracting their literal values
+pub trait Normalize: Visitable + Sized {
+    fn normalize(mut self) -> NormalizedStatement<Self> {
+        let mut normalizer = Normalizer {
+            values: Vec::new(),
+            extract: true,
+        };
+        self.accept_mut(&mut normalizer);
+        NormalizedStatement {
+            statement: self,
+            values: normalizer.values,
+        }
+    }
+}
+
+impl Normalize for DataManipulationStatement {}
+impl Normalize for SelectStatement {}
+impl Normalize for InsertStatement {}
+impl Normalize for UpdateStatement {}
+impl Normalize for DeleteStatement {}
+impl Normalize for BatchStatement {}
+impl Normalize for ModificationStatement {}
+
+struct Normalizer {
+    values: Vec<Term>,
+    /// Whether literals in the current position may be replaced with bind markers
+    extract: bool,
+}
+
+impl Normalizer {
+    fn without_extraction(&mut self, f: impl FnOnce(&mut Self)) {
+        let extract = std::mem::replace(&mut self.extract, false);
+        f(self);
+        self.extract = extract;
+    }
+
+    fn bind_marker(&mut self, marker: &mut BindMarker) {
+        self.values
+            .push(Term::BindMarker(std::mem::replace(marker, BindMarker::Anonymous)));
+    }
+}
+
+/// Check whether a term is made only of constants and literals, and can be bound as a single value.
+/// User defined type literals are excluded as their fields cannot be ordered without a schema.
+fn is_constant(term: &Term) -> bool {
+    struct Check(bool);
+    impl<'ast> Visit<'ast> for Check {
+        fn visit_term(&mut self, node: &'ast Term) {
+            match node {
+                Term::Literal(Literal::UserDefined(_)) => self.0 = false,
+                Term::Constant(_) | Term::Literal(_) => node.walk(self),
+                _ => self.0 = false,
+            }
+        }
+    }
+    let mut check = Check(true);
+    term.accept(&mut check);
+    check.0
+}
+
+impl VisitMut for Normalizer {
+    fn visit_name_mut(&mut self, node: &mut Name) {
+        match node {
+            Name::Unquoted(s) => *s = s.to_lowercase(),
+            Name::Quoted(s) => {
+                let needs_quotes = !s.starts_with(|c: char| c.is_ascii_lowercase())
+                    || !s
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+                    || ReservedKeyword::from_str(s).is_ok();
+                if !needs_quotes {
+                    *node = Name::Unquoted(std::mem::take(s));
+                }
+            }
+        }
+    }
+
+    fn visit_select_clause_mut(&mut self, node: &mut SelectClause) {
+        self.without_extraction(|n| node.walk_mut(n))
+    }
+
+    fn visit_update_parameter_mut(&mut self, node: &mut UpdateParameter) {
+        match node {
+            // Timestamps are bigints, which would be mistaken for ints when bound without a schema
+            UpdateParameter::Timestamp(_) => self.without_extraction(|n| node.walk_mut(n)),
+            _ => node.walk_mut(self),
+        }
+    }
+
+    fn visit_relation_mut(&mut self, node: &mut Relation) {
+        match node {
+            // `IN (...)` is bound element-wise, as a bound tuple would be encoded as a tuple rather than a list
+            Relation::Normal {
+                column,
+                operator: Operator::In,
+                term: Term::Literal(Literal::Tuple(tuple)),
+            } => {
+                column.accept_mut(self);
+                tuple.accept_mut(self);
+            }
+            _ => node.walk_mut(self),
+        }
+    }
+
+    fn visit_condition_mut(&mut self, node: &mut Condition) {
+        match node {
+            Condition {
+                lhs,
+                op: Operator::In,
+                rhs: Term::Literal(Literal::Tuple(tuple)),
+            } => {
+                lhs.accept_mut(self);
+                tuple.accept_mut(self);
+            }
+            _ => node.walk_mut(self),
+        }
+    }
+
+    fn visit_limit_mut(&mut self, node: &mut Limit) {
+        match node {
+            Limit::Literal(n) if self.extract => {
+                self.values.push(Term::constant(*n));
+                *node = Limit::BindMarker(BindMarker::Anonymous);
+            }
+            Limit::Literal(_) => (),
+            Limit::BindMarker(marker) => self.bind_marker(marker),
+        }
+    }
+
+    fn visit_term_mut(&mut self, node: &mut Term) {
+        match node {
+            Term::BindMarker(marker) => self.bind_marker(marker),
+            Term::Constant(_) | Term::Literal(_) if self.extract && is_constant(node) => {
+                self.values
+                    .push(std::mem::replace(node, Term::BindMarker(BindMarker::Anonymous)));
+            }
+            Term::ArithmeticOp { .. } | Term::TypeHint { .. } => self.without_extraction(|n| node.walk_mut(n)),
+            _ => node.walk_mut(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let normalized = "select A, \"B\", writetime(c) FROM \"My_Keyspace\".\"tbl\"
+            WHERE a = 'x' AND b IN (1, 2) AND c > :c AND token(a) > token(1)
+            LIMIT 10"
+            .parse::<SelectStatement>()
+            .unwrap()
+            .normalize();
+        assert_eq!(
+            normalized.text(),
+            "SELECT a, \"B\", writetime(c) FROM \"My_Keyspace\".tbl WHERE a = ? AND b IN (?, ?) AND c > ? AND TOKEN (a) > token(?) LIMIT ?"
+        );
+        assert_eq!(
+            normalized.values,
+            vec![
+                Term::constant(LitStr::quoted("x")),
+                Term::constant(1),
+                Term::constant(2),
+                Term::BindMarker(BindMarker::Named("c".into())),
+                Term::constant(1),
+                Term::constant(10),
+            ]
+        );
+        assert_eq!(normalized.literals().count(), 5);
+        assert_eq!(
+            normalized.statement,
+            normalized.text().parse::<SelectStatement>().unwrap()
+        );
+
+        let a =
+            "UPDATE ks.tbl USING TTL 100 AND TIMESTAMP 1234 SET m['k'] = {1, 2}, c = c + 1, u = {x: 1, y: 'z'} WHERE id = 5 IF v = null"
+                .parse::<UpdateStatement>()
+                .unwrap()
+                .normalize();
+        let b = "update ks.TBL using ttl 60 and timestamp 1234 set M['j'] = {3} ,  c = c + 2, U = {Y: 'w', x: 0} where ID = 6 if V = null"
+            .parse::<UpdateStatement>()
+            .unwrap()
+            .normalize();
+        assert_eq!(a.text(), b.text());
+        assert_eq!(
+            a.text(),
+            "UPDATE ks.tbl USING TTL ? AND TIMESTAMP 1234 SET m[?] = ?, c = c + ?, u = {x: ?, y: ?} WHERE id = ? IF v = ?"
+        );
+        assert_eq!(a.values.len(), 8);
+
+        let batch = "BEGIN BATCH
+            INSERT INTO tbl (a, b) VALUES (?, [1, ?]);
+            DELETE FROM tbl WHERE a = 'y';
+            APPLY BATCH"
+            .parse::<BatchStatement>()
+            .unwrap()
+            .normalize();
+        assert_eq!(
+            batch.text(),
+            "BEGIN BATCH INSERT INTO tbl (a, b) VALUES (?, [?, ?]); DELETE FROM tbl WHERE a = ? APPLY BATCH"
+        );
+        assert_eq!(
+            batch.values,
+            vec![
+                Term::BindMarker(BindMarker::Anonymous),
+                Term::constant(1),
+                Term::BindMarker(BindMarker::Anonymous),
+                Term::constant(LitStr::quoted("y")),
+            ]
+        );
+    }
+}
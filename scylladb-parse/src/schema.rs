@@ -0,0 +1,1135 @@
+use super::{
+    diff::same_type,
+    parse_script,
+    AlterTableInstruction,
+    AlterTypeInstruction,
+    CollectionType,
+    CqlType,
+    CreateAggregateFunctionStatement,
+    CreateFunctionStatement,
+    CreateIndexStatement,
+    CreateMaterializedViewStatement,
+    CreateTableStatement,
+    DataDefinitionStatement,
+    FieldDefinition,
+    FunctionReference,
+    IndexClass,
+    IndexIdentifier,
+    KeyspaceOpts,
+    KeyspaceQualifiedName,
+    MaterializedViewStatement,
+    Name,
+    NativeType,
+    PartitionKey,
+    PrimaryKey,
+    SecondaryIndexStatement,
+    SelectClause,
+    SelectStatement,
+    SelectorKind,
+    Statement,
+    TableOpts,
+    UserDefinedFunctionStatement,
+    UserDefinedTypeStatement,
+};
+use std::collections::{
+    btree_map::Entry,
+    BTreeMap,
+};
+
+/// Get the canonical form of an identifier. Unquoted identifiers are case-insensitive.
+pub(crate) fn ident(name: &Name) -> String {
+    match name {
+        Name::Quoted(n) => n.clone(),
+        Name::Unquoted(n) => n.to_lowercase(),
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColumnKind {
+    PartitionKey,
+    Clustering,
+    Static,
+    Regular,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnSchema {
+    pub name: Name,
+    pub data_type: CqlType,
+    pub kind: ColumnKind,
+}
+
+impl ColumnSchema {
+    pub fn is_primary_key(&self) -> bool {
+        matches!(self.kind, ColumnKind::PartitionKey | ColumnKind::Clustering)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableSchema {
+    pub name: Name,
+    pub columns: Vec<ColumnSchema>,
+    pub partition_key: Vec<Name>,
+    pub clustering_columns: Vec<Name>,
+    pub options: Option<TableOpts>,
+}
+
+impl TableSchema {
+    pub fn column(&self, name: impl Into<Name>) -> Option<&ColumnSchema> {
+        let name = ident(&name.into());
+        self.columns.iter().find(|c| ident(&c.name) == name)
+    }
+
+    pub fn partition_key_columns(&self) -> impl Iterator<Item = &ColumnSchema> {
+        self.partition_key.iter().filter_map(move |n| self.column(n.clone()))
+    }
+
+    pub fn clustering_key_columns(&self) -> impl Iterator<Item = &ColumnSchema> {
+        self.clustering_columns
+            .iter()
+            .filter_map(move |n| self.column(n.clone()))
+    }
+
+    pub fn primary_key(&self) -> PrimaryKey {
+        PrimaryKey {
+            partition_key: PartitionKey {
+                columns: self.partition_key.clone(),
+            },
+            clustering_columns: if self.clustering_columns.is_empty() {
+                None
+            } else {
+                Some(self.clustering_columns.clone())
+            },
+        }
+    }
+
+    fn build(
+        name: Name,
+        columns: Vec<(Name, CqlType, bool)>,
+        primary_key: PrimaryKey,
+        options: Option<TableOpts>,
+    ) -> anyhow::Result<Self> {
+        let mut res = TableSchema {
+            name,
+            columns: Vec::new(),
+            partition_key: primary_key.partition_key.columns,
+            clustering_columns: primary_key.clustering_columns.unwrap_or_default(),
+            options,
+        };
+        for (name, data_type, static_column) in columns {
+            if res.column(name.clone()).is_some() {
+                anyhow::bail!("Duplicate column {} in table {}", name, res.name);
+            }
+            let kind = if res.partition_key.iter().any(|n| ident(n) == ident(&name)) {
+                ColumnKind::PartitionKey
+            } else if res.clustering_columns.iter().any(|n| ident(n) == ident(&name)) {
+                ColumnKind::Clustering
+            } else if static_column {
+                ColumnKind::Static
+            } else {
+                ColumnKind::Regular
+            };
+            if static_column && kind != ColumnKind::Static {
+                anyhow::bail!("Primary key column {} cannot be static", name);
+            }
+            res.columns.push(ColumnSchema { name, data_type, kind });
+        }
+        if res.partition_key.is_empty() {
+            anyhow::bail!("Table {} has no partition key", res.name);
+        }
+        for key in res.partition_key.iter().chain(res.clustering_columns.iter()) {
+            if res.column(key.clone()).is_none() {
+                anyhow::bail!("Unknown primary key column {} in table {}", key, res.name);
+            }
+        }
+        if res.clustering_columns.is_empty() && res.columns.iter().any(|c| c.kind == ColumnKind::Static) {
+            anyhow::bail!("Static columns are only allowed in tables with clustering columns");
+        }
+        if let Some(order) = res.options.as_ref().and_then(|o| o.clustering_order.as_ref()) {
+            for c in order {
+                if !res.clustering_columns.iter().any(|n| ident(n) == ident(&c.column)) {
+                    anyhow::bail!("Clustering order references non-clustering column {}", c.column);
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewSchema {
+    pub base_table: Name,
+    pub table: TableSchema,
+    /// The view's defining query, with the base table qualified by its keyspace
+    pub select_statement: SelectStatement,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserTypeSchema {
+    pub name: Name,
+    pub fields: Vec<FieldDefinition>,
+}
+
+impl UserTypeSchema {
+    pub fn field(&self, name: impl Into<Name>) -> Option<&FieldDefinition> {
+        let name = ident(&name.into());
+        self.fields.iter().find(|f| ident(&f.name) == name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexSchema {
+    pub name: Name,
+    pub table: Name,
+    pub target: IndexIdentifier,
+    pub custom: bool,
+    pub using: Option<IndexClass>,
+}
+
+impl IndexSchema {
+    pub fn column(&self) -> &Name {
+        match &self.target {
+            IndexIdentifier::Column(n) | IndexIdentifier::Qualified(_, n) => n,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyspaceSchema {
+    pub name: Name,
+    pub options: KeyspaceOpts,
+    pub tables: BTreeMap<String, TableSchema>,
+    pub views: BTreeMap<String, ViewSchema>,
+    pub types: BTreeMap<String, UserTypeSchema>,
+    pub indexes: BTreeMap<String, IndexSchema>,
+    pub functions: Vec<CreateFunctionStatement>,
+    pub aggregates: Vec<CreateAggregateFunctionStatement>,
+}
+
+impl KeyspaceSchema {
+    pub fn new(name: Name, options: KeyspaceOpts) -> Self {
+        Self {
+            name,
+            options,
+            tables: Default::default(),
+            views: Default::default(),
+            types: Default::default(),
+            indexes: Default::default(),
+            functions: Default::default(),
+            aggregates: Default::default(),
+        }
+    }
+
+    pub fn table(&self, name: impl Into<Name>) -> Option<&TableSchema> {
+        self.tables.get(&ident(&name.into()))
+    }
+
+    pub fn view(&self, name: impl Into<Name>) -> Option<&ViewSchema> {
+        self.views.get(&ident(&name.into()))
+    }
+
+    pub fn user_type(&self, name: impl Into<Name>) -> Option<&UserTypeSchema> {
+        self.types.get(&ident(&name.into()))
+    }
+
+    pub fn index(&self, name: impl Into<Name>) -> Option<&IndexSchema> {
+        self.indexes.get(&ident(&name.into()))
+    }
+
+    pub fn functions(&self, name: impl Into<Name>) -> impl Iterator<Item = &CreateFunctionStatement> {
+        let name = ident(&name.into());
+        self.functions.iter().filter(move |f| ident(&f.func.name.name) == name)
+    }
+
+    pub fn aggregates(&self, name: impl Into<Name>) -> impl Iterator<Item = &CreateAggregateFunctionStatement> {
+        let name = ident(&name.into());
+        self.aggregates.iter().filter(move |f| ident(&f.func.name.name) == name)
+    }
+
+    /// Get a table or materialized view by name.
+    pub fn relation(&self, name: impl Into<Name>) -> Option<&TableSchema> {
+        let name = name.into();
+        self.table(name.clone()).or_else(|| self.view(name).map(|v| &v.table))
+    }
+
+    fn check_types(&self, data_type: &CqlType) -> anyhow::Result<()> {
+        let mut types = Vec::new();
+        user_types(data_type, &mut types);
+        for t in types {
+            if let Some(ks) = &t.keyspace {
+                if ident(ks) != ident(&self.name) {
+                    anyhow::bail!("User type {} must be defined in keyspace {}", t, self.name);
+                }
+            }
+            if self.user_type(t.name.clone()).is_none() {
+                anyhow::bail!("Unknown user type {}", t);
+            }
+        }
+        Ok(())
+    }
+
+    fn uses_type(&self, name: &str) -> Option<String> {
+        let uses = |t: &CqlType| {
+            let mut types = Vec::new();
+            user_types(t, &mut types);
+            types.iter().any(|t| ident(&t.name) == name)
+        };
+        for table in self.tables.values() {
+            if let Some(c) = table.columns.iter().find(|c| uses(&c.data_type)) {
+                return Some(format!("column {} of table {}", c.name, table.name));
+            }
+        }
+        for udt in self.types.values() {
+            if udt.fields.iter().any(|f| uses(&f.data_type)) {
+                return Some(format!("type {}", udt.name));
+            }
+        }
+        for func in self.functions.iter() {
+            if func.func.args.iter().any(|a| uses(&a.data_type)) || uses(&func.return_type) {
+                return Some(format!("function {}", func.func.name));
+            }
+        }
+        for agg in self.aggregates.iter() {
+            if agg.func.args.iter().any(&uses) || uses(&agg.state_value_type) {
+                return Some(format!("aggregate {}", agg.func.name));
+            }
+        }
+        None
+    }
+}
+
+/// Collect every user defined type referenced by a type.
+pub(crate) fn user_types<'a>(data_type: &'a CqlType, types: &mut Vec<&'a KeyspaceQualifiedName>) {
+    match data_type {
+        CqlType::UserDefined(t) => types.push(t),
+        CqlType::Collection(c) => match c.as_ref() {
+            CollectionType::List(t) | CollectionType::Set(t) => user_types(t, types),
+            CollectionType::Map(k, v) => {
+                user_types(k, types);
+                user_types(v, types);
+            }
+        },
+        CqlType::Tuple(t) => t.iter().for_each(|t| user_types(t, types)),
+        CqlType::Native(_) | CqlType::Custom(_) => (),
+    }
+}
+
+fn merge_table_opts(current: &mut Option<TableOpts>, new: TableOpts) {
+    let current = match current {
+        Some(current) => current,
+        None => {
+            current.replace(new);
+            return;
+        }
+    };
+    current.compact_storage |= new.compact_storage;
+    macro_rules! merge {
+        ($($field:ident),*) => {
+            $(if new.$field.is_some() {
+                current.$field = new.$field;
+            })*
+        };
+    }
+    merge!(
+        clustering_order,
+        comment,
+        speculative_retry,
+        change_data_capture,
+        gc_grace_seconds,
+        bloom_filter_fp_chance,
+        default_time_to_live,
+        compaction,
+        compression,
+        caching,
+        memtable_flush_period_in_ms,
+        read_repair,
+        tombstone_gc,
+        per_partition_rate_limit,
+        paxos_grace_seconds,
+        synchronous_updates,
+        scylla_tags
+    );
+}
+
+/// An in-memory schema model, built by applying DDL statements in order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Schema {
+    pub keyspaces: BTreeMap<String, KeyspaceSchema>,
+    pub current_keyspace: Option<Name>,
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a schema from a script of DDL statements.
+    pub fn from_script(script: &str) -> anyhow::Result<Self> {
+        let mut schema = Self::default();
+        for (statement, span) in parse_script(script)? {
+            schema
+                .apply(statement)
+                .map_err(|e| anyhow::anyhow!("{} at line {}, column {}", e, span.line, span.column))?;
+        }
+        Ok(schema)
+    }
+
+    pub fn keyspace(&self, name: impl Into<Name>) -> Option<&KeyspaceSchema> {
+        self.keyspaces.get(&ident(&name.into()))
+    }
+
+    pub fn table(&self, name: impl Into<KeyspaceQualifiedName>) -> Option<&TableSchema> {
+        let name = name.into();
+        self.keyspace_of(&name)
+            .ok()
+            .and_then(|ks| self.keyspaces.get(&ks))
+            .and_then(|ks| ks.table(name.name))
+    }
+
+    pub fn view(&self, name: impl Into<KeyspaceQualifiedName>) -> Option<&ViewSchema> {
+        let name = name.into();
+        self.keyspace_of(&name)
+            .ok()
+            .and_then(|ks| self.keyspaces.get(&ks))
+            .and_then(|ks| ks.view(name.name))
+    }
+
+    /// Get a table or materialized view by name.
+    pub fn relation(&self, name: impl Into<KeyspaceQualifiedName>) -> Option<&TableSchema> {
+        let name = name.into();
+        self.keyspace_of(&name)
+            .ok()
+            .and_then(|ks| self.keyspaces.get(&ks))
+            .and_then(|ks| ks.relation(name.name))
+    }
+
+    pub fn user_type(&self, name: impl Into<KeyspaceQualifiedName>) -> Option<&UserTypeSchema> {
+        let name = name.into();
+        self.keyspace_of(&name)
+            .ok()
+            .and_then(|ks| self.keyspaces.get(&ks))
+            .and_then(|ks| ks.user_type(name.name))
+    }
+
+    pub(crate) fn keyspace_of(&self, name: &KeyspaceQualifiedName) -> anyhow::Result<String> {
+        name.keyspace
+            .as_ref()
+            .or(self.current_keyspace.as_ref())
+            .map(ident)
+            .ok_or_else(|| anyhow::anyhow!("No keyspace specified for {} and no keyspace in use", name))
+    }
+
+    fn keyspace_mut(&mut self, name: &KeyspaceQualifiedName) -> anyhow::Result<&mut KeyspaceSchema> {
+        let ks = self.keyspace_of(name)?;
+        self.keyspaces
+            .get_mut(&ks)
+            .ok_or_else(|| anyhow::anyhow!("Keyspace {} does not exist", ks))
+    }
+
+    /// Apply a statement to the schema, validating the change.
+    /// Statements which do not modify the schema are ignored.
+    pub fn apply(&mut self, statement: impl Into<Statement>) -> anyhow::Result<()> {
+        match statement.into() {
+            Statement::DataDefinition(s) => self.apply_data_definition(s),
+            Statement::SecondaryIndex(s) => self.apply_secondary_index(s),
+            Statement::MaterializedView(s) => self.apply_materialized_view(s),
+            Statement::UserDefinedFunction(s) => self.apply_user_defined_function(s),
+            Statement::UserDefinedType(s) => self.apply_user_defined_type(s),
+            _ => Ok(()),
+        }
+    }
+
+    fn apply_data_definition(&mut self, statement: DataDefinitionStatement) -> anyhow::Result<()> {
+        match statement {
+            DataDefinitionStatement::Use(s) => {
+                if self.keyspace(s.keyspace.clone()).is_none() {
+                    anyhow::bail!("Keyspace {} does not exist", s.keyspace);
+                }
+                self.current_keyspace.replace(s.keyspace);
+            }
+            DataDefinitionStatement::CreateKeyspace(s) => match self.keyspaces.entry(ident(&s.keyspace)) {
+                Entry::Occupied(_) if s.if_not_exists => (),
+                Entry::Occupied(_) => anyhow::bail!("Keyspace {} already exists", s.keyspace),
+                Entry::Vacant(e) => {
+                    e.insert(KeyspaceSchema::new(s.keyspace, s.options));
+                }
+            },
+            DataDefinitionStatement::AlterKeyspace(s) => {
+                let ks = self
+                    .keyspaces
+                    .get_mut(&ident(&s.keyspace))
+                    .ok_or_else(|| anyhow::anyhow!("Keyspace {} does not exist", s.keyspace))?;
+                ks.options = s.options;
+            }
+            DataDefinitionStatement::DropKeyspace(s) => {
+                let key = ident(&s.keyspace);
+                if self.keyspaces.remove(&key).is_none() && !s.if_exists {
+                    anyhow::bail!("Keyspace {} does not exist", s.keyspace);
+                }
+                if self.current_keyspace.as_ref().map(ident) == Some(key) {
+                    self.current_keyspace = None;
+                }
+            }
+            DataDefinitionStatement::CreateTable(s) => self.create_table(s)?,
+            DataDefinitionStatement::AlterTable(s) => {
+                let ks = self.keyspace_mut(&s.table)?;
+                let key = ident(&s.table.name);
+                if !ks.tables.contains_key(&key) {
+                    anyhow::bail!("Table {} does not exist", s.table);
+                }
+                match s.instruction {
+                    // Columns are added to and dropped from a copy of the table, so that the schema is left untouched
+                    // if any of them is invalid
+                    AlterTableInstruction::Add(columns) => {
+                        let mut table = ks.tables[&key].clone();
+                        let has_clustering = !table.clustering_columns.is_empty();
+                        for c in columns {
+                            if c.primary_key {
+                                anyhow::bail!("Cannot add primary key column {}", c.name);
+                            }
+                            if c.static_column && !has_clustering {
+                                anyhow::bail!("Static columns are only allowed in tables with clustering columns");
+                            }
+                            if table.column(c.name.clone()).is_some() {
+                                anyhow::bail!("Column {} already exists in table {}", c.name, s.table);
+                            }
+                            ks.check_types(&c.data_type)?;
+                            let kind = if c.static_column {
+                                ColumnKind::Static
+                            } else {
+                                ColumnKind::Regular
+                            };
+                            table.columns.push(ColumnSchema {
+                                name: c.name,
+                                data_type: c.data_type,
+                                kind,
+                            });
+                        }
+                        ks.tables.insert(key, table);
+                    }
+                    AlterTableInstruction::Drop(columns) => {
+                        let mut table = ks.tables[&key].clone();
+                        for c in columns {
+                            let column = table
+                                .column(c.clone())
+                                .ok_or_else(|| anyhow::anyhow!("Column {} does not exist in table {}", c, s.table))?;
+                            if column.is_primary_key() {
+                                anyhow::bail!("Cannot drop primary key column {}", c);
+                            }
+                            if let Some(i) = ks
+                                .indexes
+                                .values()
+                                .find(|i| ident(&i.table) == key && ident(i.column()) == ident(&c))
+                            {
+                                anyhow::bail!("Cannot drop column {} because it is used by index {}", c, i.name);
+                            }
+                            if let Some(v) = ks
+                                .views
+                                .values()
+                                .find(|v| ident(&v.base_table) == key && v.table.column(c.clone()).is_some())
+                            {
+                                anyhow::bail!(
+                                    "Cannot drop column {} because it is used by materialized view {}",
+                                    c,
+                                    v.table.name
+                                );
+                            }
+                            table.columns.retain(|col| ident(&col.name) != ident(&c));
+                        }
+                        ks.tables.insert(key, table);
+                    }
+                    AlterTableInstruction::Alter(c, data_type) => {
+                        ks.check_types(&data_type)?;
+                        let table = ks.tables.get_mut(&key).unwrap();
+                        let column = table
+                            .columns
+                            .iter_mut()
+                            .find(|col| ident(&col.name) == ident(&c))
+                            .ok_or_else(|| anyhow::anyhow!("Column {} does not exist in table {}", c, s.table))?;
+                        if column.is_primary_key() {
+                            anyhow::bail!("Cannot change the type of primary key column {}", c);
+                        }
+                        if !same_type(&column.data_type, &data_type) && !is_compatible(&column.data_type, &data_type) {
+                            anyhow::bail!(
+                                "Cannot change the type of column {} from {} to {}",
+                                c,
+                                column.data_type,
+                                data_type
+                            );
+                        }
+                        column.data_type = data_type;
+                    }
+                    AlterTableInstruction::With(options) => {
+                        if options.clustering_order.is_some() {
+                            anyhow::bail!("Cannot change the clustering order of table {}", s.table);
+                        }
+                        merge_table_opts(&mut ks.tables.get_mut(&key).unwrap().options, options);
+                    }
+                }
+            }
+            DataDefinitionStatement::DropTable(s) => {
+                let ks = self.keyspace_mut(&s.table)?;
+                let key = ident(&s.table.name);
+                if !ks.tables.contains_key(&key) {
+                    if s.if_exists {
+                        return Ok(());
+                    }
+                    anyhow::bail!("Table {} does not exist", s.table);
+                }
+                if let Some(v) = ks.views.values().find(|v| ident(&v.base_table) == key) {
+                    anyhow::bail!(
+                        "Cannot drop table {} because it has materialized view {}",
+                        s.table,
+                        v.table.name
+                    );
+                }
+                ks.tables.remove(&key);
+                ks.indexes.retain(|_, i| ident(&i.table) != key);
+            }
+            DataDefinitionStatement::Truncate(s) => {
+                if self.table(s.table.clone()).is_none() {
+                    anyhow::bail!("Table {} does not exist", s.table);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn create_table(&mut self, s: CreateTableStatement) -> anyhow::Result<()> {
+        let ks = self.keyspace_mut(&s.table)?;
+        let key = ident(&s.table.name);
+        if ks.tables.contains_key(&key) || ks.views.contains_key(&key) {
+            if s.if_not_exists {
+                return Ok(());
+            }
+            anyhow::bail!("Table {} already exists", s.table);
+        }
+        let inline = s.columns.iter().filter(|c| c.primary_key).collect::<Vec<_>>();
+        let primary_key = match (inline.as_slice(), s.primary_key) {
+            ([], Some(pk)) => pk,
+            ([c], None) => PrimaryKey::partition_key(c.name.clone()),
+            ([], None) => anyhow::bail!("Table {} has no primary key", s.table),
+            _ => anyhow::bail!("Table {} has multiple primary key definitions", s.table),
+        };
+        for c in s.columns.iter() {
+            ks.check_types(&c.data_type)?;
+        }
+        let table = TableSchema::build(
+            s.table.name,
+            s.columns
+                .into_iter()
+                .map(|c| (c.name, c.data_type, c.static_column))
+                .collect(),
+            primary_key,
+            s.options,
+        )?;
+        ks.tables.insert(key, table);
+        Ok(())
+    }
+
+    fn apply_secondary_index(&mut self, statement: SecondaryIndexStatement) -> anyhow::Result<()> {
+        match statement {
+            SecondaryIndexStatement::Create(s) => self.create_index(s)?,
+            SecondaryIndexStatement::Drop(s) => {
+                let ks = self.keyspace_mut(&s.name)?;
+                if ks.indexes.remove(&ident(&s.name.name)).is_none() && !s.if_exists {
+                    anyhow::bail!("Index {} does not exist", s.name);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn create_index(&mut self, s: CreateIndexStatement) -> anyhow::Result<()> {
+        let ks = self.keyspace_mut(&s.table)?;
+        let table = ks
+            .table(s.table.name.clone())
+            .ok_or_else(|| anyhow::anyhow!("Table {} does not exist", s.table))?;
+        let column = match &s.index_id {
+            IndexIdentifier::Column(n) | IndexIdentifier::Qualified(_, n) => n,
+        };
+        if table.column(column.clone()).is_none() {
+            anyhow::bail!("Column {} does not exist in table {}", column, s.table);
+        }
+        let name = s
+            .name
+            .clone()
+            .unwrap_or_else(|| Name::Unquoted(format!("{}_{}_idx", ident(&s.table.name), ident(column))));
+        let key = ident(&name);
+        if ks.indexes.contains_key(&key) {
+            if s.if_not_exists {
+                return Ok(());
+            }
+            anyhow::bail!("Index {} already exists", name);
+        }
+        ks.indexes.insert(
+            key,
+            IndexSchema {
+                name,
+                table: s.table.name,
+                target: s.index_id,
+                custom: s.custom,
+                using: s.using,
+            },
+        );
+        Ok(())
+    }
+
+    fn apply_materialized_view(&mut self, statement: MaterializedViewStatement) -> anyhow::Result<()> {
+        match statement {
+            MaterializedViewStatement::Create(s) => self.create_view(s)?,
+            MaterializedViewStatement::Alter(s) => {
+                let ks = self.keyspace_mut(&s.name)?;
+                let view = ks
+                    .views
+                    .get_mut(&ident(&s.name.name))
+                    .ok_or_else(|| anyhow::anyhow!("Materialized view {} does not exist", s.name))?;
+                merge_table_opts(&mut view.table.options, *s.table_opts);
+            }
+            MaterializedViewStatement::Drop(s) => {
+                let ks = self.keyspace_mut(&s.name)?;
+                if ks.views.remove(&ident(&s.name.name)).is_none() && !s.if_exists {
+                    anyhow::bail!("Materialized view {} does not exist", s.name);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn create_view(&mut self, s: CreateMaterializedViewStatement) -> anyhow::Result<()> {
+        let ks_name = self.keyspace_of(&s.name)?;
+        if self.keyspace_of(&s.select_statement.from)? != ks_name {
+            anyhow::bail!(
+                "Materialized view {} must be in the same keyspace as its base table",
+                s.name
+            );
+        }
+        let ks = self.keyspace_mut(&s.name)?;
+        let key = ident(&s.name.name);
+        if ks.tables.contains_key(&key) || ks.views.contains_key(&key) {
+            if s.if_not_exists {
+                return Ok(());
+            }
+            anyhow::bail!("Materialized view {} already exists", s.name);
+        }
+        let base = ks
+            .table(s.select_statement.from.name.clone())
+            .ok_or_else(|| anyhow::anyhow!("Base table {} does not exist", s.select_statement.from))?;
+        let columns = match &s.select_statement.select_clause {
+            SelectClause::All => base.columns.iter().collect::<Vec<_>>(),
+            SelectClause::Selectors(selectors) => {
+                let mut columns = Vec::new();
+                for selector in selectors {
+                    match &selector.kind {
+                        SelectorKind::Column(c) => columns
+                            .push(base.column(c.clone()).ok_or_else(|| {
+                                anyhow::anyhow!("Column {} does not exist in table {}", c, base.name)
+                            })?),
+                        _ => anyhow::bail!("Materialized views may only select columns, found {}", selector),
+                    }
+                }
+                // Primary key columns are always included in the view
+                for c in base.columns.iter().filter(|c| c.is_primary_key()) {
+                    if !columns.iter().any(|col| ident(&col.name) == ident(&c.name)) {
+                        columns.push(c);
+                    }
+                }
+                columns
+            }
+        };
+        for c in base.columns.iter().filter(|c| c.is_primary_key()) {
+            let in_key = s
+                .primary_key
+                .partition_key
+                .columns
+                .iter()
+                .chain(s.primary_key.clustering_columns.iter().flatten())
+                .any(|n| ident(n) == ident(&c.name));
+            if !in_key {
+                anyhow::bail!(
+                    "Materialized view {} primary key must include base table primary key column {}",
+                    s.name,
+                    c.name
+                );
+            }
+        }
+        let table = TableSchema::build(
+            s.name.name,
+            columns
+                .into_iter()
+                .map(|c| (c.name.clone(), c.data_type.clone(), false))
+                .collect(),
+            s.primary_key,
+            s.table_opts.map(|o| *o),
+        )?;
+        let mut select_statement = *s.select_statement;
+        select_statement.from.keyspace = Some(ks.name.clone());
+        let view = ViewSchema {
+            base_table: base.name.clone(),
+            table,
+            select_statement,
+        };
+        ks.views.insert(key, view);
+        Ok(())
+    }
+
+    fn apply_user_defined_type(&mut self, statement: UserDefinedTypeStatement) -> anyhow::Result<()> {
+        match statement {
+            UserDefinedTypeStatement::Create(s) => {
+                let ks = self.keyspace_mut(&s.name)?;
+                let key = ident(&s.name.name);
+                if ks.types.contains_key(&key) {
+                    if s.if_not_exists {
+                        return Ok(());
+                    }
+                    anyhow::bail!("Type {} already exists", s.name);
+                }
+                let mut udt = UserTypeSchema {
+                    name: s.name.name,
+                    fields: Vec::new(),
+                };
+                for field in s.fields {
+                    if udt.field(field.name.clone()).is_some() {
+                        anyhow::bail!("Duplicate field {} in type {}", field.name, udt.name);
+                    }
+                    ks.check_types(&field.data_type)?;
+                    udt.fields.push(field);
+                }
+                ks.types.insert(key, udt);
+            }
+            UserDefinedTypeStatement::Alter(s) => {
+                let ks = self.keyspace_mut(&s.name)?;
+                let key = ident(&s.name.name);
+                if !ks.types.contains_key(&key) {
+                    anyhow::bail!("Type {} does not exist", s.name);
+                }
+                match s.instruction {
+                    AlterTypeInstruction::Add(field) => {
+                        if ks.types[&key].field(field.name.clone()).is_some() {
+                            anyhow::bail!("Field {} already exists in type {}", field.name, s.name);
+                        }
+                        ks.check_types(&field.data_type)?;
+                        ks.types.get_mut(&key).unwrap().fields.push(field);
+                    }
+                    AlterTypeInstruction::Rename(renames) => {
+                        // Rename the fields of a copy of the type, so it is left untouched if any rename is invalid
+                        let mut udt = ks.types[&key].clone();
+                        for (from, to) in renames {
+                            if udt.field(to.clone()).is_some() {
+                                anyhow::bail!("Field {} already exists in type {}", to, s.name);
+                            }
+                            let field = udt
+                                .fields
+                                .iter_mut()
+                                .find(|f| ident(&f.name) == ident(&from))
+                                .ok_or_else(|| anyhow::anyhow!("Field {} does not exist in type {}", from, s.name))?;
+                            field.name = to;
+                        }
+                        ks.types.insert(key, udt);
+                    }
+                }
+            }
+            UserDefinedTypeStatement::Drop(s) => {
+                let ks = self.keyspace_mut(&s.name)?;
+                let key = ident(&s.name.name);
+                if !ks.types.contains_key(&key) {
+                    if s.if_exists {
+                        return Ok(());
+                    }
+                    anyhow::bail!("Type {} does not exist", s.name);
+                }
+                if let Some(user) = ks.uses_type(&key) {
+                    anyhow::bail!("Cannot drop type {} because it is used by {}", s.name, user);
+                }
+                ks.types.remove(&key);
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_user_defined_function(&mut self, statement: UserDefinedFunctionStatement) -> anyhow::Result<()> {
+        match statement {
+            UserDefinedFunctionStatement::Create(s) => {
+                let ks = self.keyspace_mut(&s.func.name)?;
+                for t in s.func.args.iter().map(|a| &a.data_type).chain(Some(&s.return_type)) {
+                    ks.check_types(t)?;
+                }
+                let existing = ks.functions.iter().position(|f| {
+                    ident(&f.func.name.name) == ident(&s.func.name.name)
+                        && f.func
+                            .args
+                            .iter()
+                            .map(|a| &a.data_type)
+                            .eq(s.func.args.iter().map(|a| &a.data_type))
+                });
+                match existing {
+                    Some(i) if s.or_replace => ks.functions[i] = s,
+                    Some(_) if s.if_not_exists => (),
+                    Some(_) => anyhow::bail!("Function {} already exists", s.func.name),
+                    None => ks.functions.push(s),
+                }
+            }
+            UserDefinedFunctionStatement::Drop(s) => {
+                let ks = self.keyspace_mut(&s.func.name)?;
+                let matches = matching_functions(
+                    ks.functions.iter().map(|f| {
+                        (
+                            &f.func.name.name,
+                            f.func.args.iter().map(|a| a.data_type.clone()).collect(),
+                        )
+                    }),
+                    &s.func,
+                )?;
+                match matches {
+                    Some(i) => {
+                        ks.functions.remove(i);
+                    }
+                    None if s.if_exists => (),
+                    None => anyhow::bail!("Function {} does not exist", s.func),
+                }
+            }
+            UserDefinedFunctionStatement::CreateAggregate(s) => {
+                let ks = self.keyspace_mut(&s.func.name)?;
+                for t in s.func.args.iter().chain(Some(&s.state_value_type)) {
+                    ks.check_types(t)?;
+                }
+                if ks
+                    .functions(s.state_modifying_fn.name.clone())
+                    .all(|f| f.func.args.len() != s.func.args.len() + 1)
+                {
+                    anyhow::bail!("State function {} does not exist", s.state_modifying_fn);
+                }
+                if let Some(final_fn) = &s.final_fn {
+                    if ks.functions(final_fn.name.clone()).all(|f| f.func.args.len() != 1) {
+                        anyhow::bail!("Final function {} does not exist", final_fn);
+                    }
+                }
+                let existing = ks
+                    .aggregates
+                    .iter()
+                    .position(|f| ident(&f.func.name.name) == ident(&s.func.name.name) && f.func.args == s.func.args);
+                match existing {
+                    Some(i) if s.or_replace => ks.aggregates[i] = s,
+                    Some(_) if s.if_not_exists => (),
+                    Some(_) => anyhow::bail!("Aggregate {} already exists", s.func.name),
+                    None => ks.aggregates.push(s),
+                }
+            }
+            UserDefinedFunctionStatement::DropAggregate(s) => {
+                let ks = self.keyspace_mut(&s.func.name)?;
+                let matches = matching_functions(
+                    ks.aggregates.iter().map(|f| (&f.func.name.name, f.func.args.clone())),
+                    &s.func,
+                )?;
+                match matches {
+                    Some(i) => {
+                        ks.aggregates.remove(i);
+                    }
+                    None if s.if_exists => (),
+                    None => anyhow::bail!("Aggregate {} does not exist", s.func),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Find the position of the function overload referenced by a DROP statement.
+fn matching_functions<'a>(
+    functions: impl Iterator<Item = (&'a Name, Vec<CqlType>)>,
+    reference: &FunctionReference,
+) -> anyhow::Result<Option<usize>> {
+    let name = ident(&reference.name.name);
+    let matches = functions
+        .enumerate()
+        .filter(|(_, (n, args))| ident(n) == name && reference.args.as_ref().map(|a| a == args).unwrap_or(true))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    if matches.len() > 1 {
+        anyhow::bail!("Function {} is ambiguous, specify the argument types", reference);
+    }
+    Ok(matches.first().copied())
+}
+
+/// Whether existing values of a column can be read as another type.
+fn is_compatible(from: &CqlType, to: &CqlType) -> bool {
+    use NativeType::*;
+    match (from, to) {
+        (CqlType::Native(f), CqlType::Native(t)) => matches!(
+            (f, t),
+            (Ascii, Text | Varchar)
+                | (Int | Smallint | Tinyint | Bigint, Varint)
+                | (Timeuuid, Uuid)
+                | (
+                    Ascii
+                        | Bigint
+                        | Boolean
+                        | Date
+                        | Decimal
+                        | Double
+                        | Float
+                        | Inet
+                        | Int
+                        | Smallint
+                        | Text
+                        | Time
+                        | Timestamp
+                        | Timeuuid
+                        | Tinyint
+                        | Uuid
+                        | Varchar
+                        | Varint,
+                    Blob
+                )
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        CREATE KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+        USE movies;
+        CREATE TYPE person (first_name text, last_name text);
+        CREATE TABLE nerd_movies (
+            movie text,
+            year int,
+            director person,
+            rating float STATIC,
+            PRIMARY KEY (movie, year)
+        );
+        CREATE INDEX ON nerd_movies (director);
+        CREATE MATERIALIZED VIEW movies_by_year AS
+            SELECT movie, year FROM nerd_movies
+            WHERE movie IS NOT NULL AND year IS NOT NULL
+            PRIMARY KEY (year, movie);
+    "#;
+
+    #[test]
+    fn test_schema_apply() {
+        let mut schema = Schema::from_script(SCHEMA).unwrap();
+        let ks = schema.keyspace("movies").unwrap();
+        let table = ks.table("NERD_MOVIES").unwrap();
+        assert_eq!(table.partition_key, vec![Name::from("movie")]);
+        assert_eq!(table.clustering_columns, vec![Name::from("year")]);
+        assert_eq!(table.column("rating").unwrap().kind, ColumnKind::Static);
+        assert_eq!(
+            table.column("director").unwrap().data_type,
+            CqlType::UserDefined("person".into())
+        );
+        assert!(ks.index("nerd_movies_director_idx").is_some());
+        assert_eq!(
+            ks.view("movies_by_year").unwrap().table.partition_key,
+            vec![Name::from("year")]
+        );
+        assert_eq!(ks.user_type("person").unwrap().fields.len(), 2);
+
+        schema
+            .apply(
+                "ALTER TABLE nerd_movies ADD main_actor text"
+                    .parse::<Statement>()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(schema.table("nerd_movies").unwrap().column("main_actor").is_some());
+        schema
+            .apply(
+                "ALTER TABLE nerd_movies ALTER main_actor TYPE blob"
+                    .parse::<Statement>()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            schema
+                .table("nerd_movies")
+                .unwrap()
+                .column("main_actor")
+                .unwrap()
+                .data_type,
+            CqlType::Native(NativeType::Blob)
+        );
+        schema
+            .apply("ALTER TYPE person ADD age int".parse::<Statement>().unwrap())
+            .unwrap();
+        schema
+            .apply("ALTER TYPE person RENAME age TO years".parse::<Statement>().unwrap())
+            .unwrap();
+        assert_eq!(
+            schema.user_type("person").unwrap().field("years").unwrap().data_type,
+            CqlType::Native(NativeType::Int)
+        );
+        schema
+            .apply("ALTER TABLE nerd_movies DROP main_actor".parse::<Statement>().unwrap())
+            .unwrap();
+        schema
+            .apply("DROP MATERIALIZED VIEW movies_by_year".parse::<Statement>().unwrap())
+            .unwrap();
+        schema
+            .apply("DROP TABLE movies.nerd_movies".parse::<Statement>().unwrap())
+            .unwrap();
+        assert!(schema.keyspace("movies").unwrap().indexes.is_empty());
+        schema.apply("DROP TYPE person".parse::<Statement>().unwrap()).unwrap();
+        schema
+            .apply("DROP KEYSPACE movies".parse::<Statement>().unwrap())
+            .unwrap();
+        assert!(schema.keyspaces.is_empty());
+    }
+
+    #[test]
+    fn test_schema_apply_is_atomic() {
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        // The invalid column or field comes after a valid one, which must not be applied either
+        for statement in [
+            "ALTER TABLE nerd_movies ADD main_actor text, movie text",
+            "ALTER TABLE nerd_movies ADD main_actor text, main_actor int",
+            "ALTER TABLE nerd_movies DROP (rating, director)",
+            "ALTER TABLE nerd_movies DROP (rating, unknown)",
+            "ALTER TYPE person RENAME first_name TO given_name middle_name TO nickname",
+        ] {
+            let mut altered = schema.clone();
+            assert!(
+                altered.apply(statement.parse::<Statement>().unwrap()).is_err(),
+                "{}",
+                statement
+            );
+            assert_eq!(altered, schema, "{}", statement);
+        }
+    }
+
+    #[test]
+    fn test_schema_validation() {
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        for statement in [
+            "CREATE TABLE nerd_movies (id int PRIMARY KEY)",
+            "CREATE TABLE no_key (id int, name text)",
+            "CREATE TABLE bad_key (id int, PRIMARY KEY (name))",
+            "CREATE TABLE bad_type (id int PRIMARY KEY, owner unknown_type)",
+            "CREATE TABLE other.t (id int PRIMARY KEY)",
+            "ALTER TABLE nerd_movies ADD movie text",
+            "ALTER TABLE nerd_movies DROP year",
+            "ALTER TABLE nerd_movies DROP director",
+            "ALTER TABLE nerd_movies ALTER unknown TYPE int",
+            "ALTER TABLE nerd_movies ALTER year TYPE varint",
+            "ALTER TABLE nerd_movies ALTER rating TYPE int",
+            "ALTER TYPE person RENAME middle_name TO nickname",
+            "DROP TYPE person",
+            "DROP TABLE nerd_movies",
+            "DROP TABLE unknown",
+            "DROP INDEX unknown",
+            "CREATE INDEX ON nerd_movies (unknown)",
+        ] {
+            assert!(
+                schema.clone().apply(statement.parse::<Statement>().unwrap()).is_err(),
+                "{}",
+                statement
+            );
+        }
+        schema
+            .clone()
+            .apply("DROP TABLE IF EXISTS unknown".parse::<Statement>().unwrap())
+            .unwrap();
+    }
+}
@@ -0,0 +1,312 @@
+use super::{
+    error::line_column,
+    tokenize,
+    LexemeKind,
+    ParseError,
+    Statement,
+    StatementStream,
+    Whitespace,
+};
+use std::fmt::{
+    Display,
+    Formatter,
+};
+
+/// The location of a statement within a script, in character offsets.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+    /// The 1-based line of the start of the statement
+    pub line: usize,
+    /// The 1-based column of the start of the statement
+    pub column: usize,
+    /// The comments which directly precede the statement
+    pub leading_comments: Vec<String>,
+    /// The comments which follow the statement on the same line
+    pub trailing_comments: Vec<String>,
+}
+
+/// The errors produced by parsing a script, along with every statement
+/// which could still be parsed.
+#[derive(Clone, Debug)]
+pub struct ScriptError {
+    pub statements: Vec<(Statement, SourceSpan)>,
+    pub errors: Vec<ParseError>,
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        )
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// A single statement's text, with comments blanked out so that
+/// character offsets still line up with the script.
+#[derive(Clone, Debug, Default)]
+struct Chunk {
+    start: usize,
+    end: usize,
+    text: String,
+    comments: Vec<String>,
+    trailing_comments: Vec<String>,
+}
+
+#[derive(Default)]
+struct Splitter {
+    chunks: Vec<Chunk>,
+    current: Option<Chunk>,
+    comments: Vec<String>,
+    last_end: usize,
+    same_line: bool,
+    batch: bool,
+    apply: bool,
+    applied: bool,
+}
+
+impl Splitter {
+    fn in_statement(&self) -> bool {
+        self.current.is_some() && !self.applied
+    }
+
+    fn token(&mut self, start: usize, text: &str) {
+        if self.applied {
+            self.finish();
+        }
+        let current = self.current.get_or_insert_with(|| Chunk {
+            start,
+            comments: std::mem::take(&mut self.comments),
+            ..Default::default()
+        });
+        current.text.push_str(text);
+        self.last_end = start + text.chars().count();
+        self.same_line = true;
+    }
+
+    fn word(&mut self, start: usize, text: &str) {
+        let first = self.current.is_none() || self.applied;
+        self.token(start, text);
+        let word = text.to_uppercase();
+        if first {
+            self.batch = word == "BEGIN";
+            self.apply = false;
+        } else if self.batch {
+            if self.apply && word == "BATCH" {
+                self.applied = true;
+            }
+            self.apply = word == "APPLY";
+        }
+    }
+
+    fn blank(&mut self, text: &str) {
+        if text.contains('\n') {
+            self.same_line = false;
+        }
+        if let Some(current) = self.current.as_mut().filter(|_| !self.applied) {
+            current
+                .text
+                .extend(text.chars().map(|c| if c.is_whitespace() { c } else { ' ' }));
+        }
+    }
+
+    fn comment(&mut self, text: &str) {
+        if self.in_statement() {
+            self.blank(text);
+        } else if self.same_line {
+            // A comment on the same line as the end of a statement belongs to it
+            let previous = if self.applied {
+                self.current.as_mut()
+            } else {
+                self.chunks.last_mut()
+            };
+            if let Some(previous) = previous {
+                previous.trailing_comments.push(text.trim().to_string());
+            }
+            self.same_line = !text.contains('\n');
+        } else {
+            self.comments.push(text.trim().to_string());
+        }
+    }
+
+    fn semicolon(&mut self, pos: usize) {
+        if self.current.is_none() {
+            self.comments.clear();
+            return;
+        }
+        let pad = pos - self.last_end;
+        if let Some(current) = self.current.as_mut() {
+            if self.applied {
+                current.text.push_str(&" ".repeat(pad));
+            }
+            current.text.push(';');
+        }
+        self.last_end = pos + 1;
+        self.same_line = true;
+        if !self.batch || self.applied {
+            self.finish();
+        }
+    }
+
+    fn finish(&mut self) {
+        if let Some(mut chunk) = self.current.take() {
+            chunk.end = self.last_end;
+            chunk.text.truncate(
+                chunk
+                    .text
+                    .chars()
+                    .take(chunk.end - chunk.start)
+                    .map(char::len_utf8)
+                    .sum(),
+            );
+            self.chunks.push(chunk);
+        }
+        self.batch = false;
+        self.apply = false;
+        self.applied = false;
+    }
+}
+
+fn split_script(script: &str) -> Vec<Chunk> {
+    let mut s = Splitter::default();
+    for lexeme in tokenize(script) {
+        let text = lexeme.text(script);
+        match lexeme.kind {
+            LexemeKind::Comment => s.comment(text),
+            LexemeKind::Whitespace => s.blank(text),
+            LexemeKind::Symbol if text == ";" => s.semicolon(lexeme.pos),
+            LexemeKind::Word => s.word(lexeme.pos, text),
+            _ => s.token(lexeme.pos, text),
+        }
+    }
+    s.finish();
+    s.chunks
+}
+
+/// Parse a script containing any number of statements separated by semicolons.
+/// Comments (`--`, `//` and `/* */`) are ignored, except for those directly preceding a
+/// statement or following it on the same line, which are kept in its span. Every statement
+/// is parsed even if some fail, so that all errors can be reported at once.
+pub fn parse_script(script: &str) -> Result<Vec<(Statement, SourceSpan)>, ScriptError> {
+    let mut statements = Vec::new();
+    let mut errors = Vec::new();
+    for chunk in split_script(script) {
+        let mut stream = StatementStream::new(&chunk.text);
+        let res = stream.try_parse::<Statement>().and_then(|statement| {
+            stream.parse_from::<Whitespace>().ok();
+            if stream.remaining() > 0 {
+                Err(stream.error(anyhow::anyhow!("Unexpected input after statement: {}", stream.info())))
+            } else {
+                Ok(statement)
+            }
+        });
+        match res {
+            Ok(statement) => {
+                let (line, column) = line_column(script, chunk.start);
+                statements.push((
+                    statement,
+                    SourceSpan {
+                        start: chunk.start,
+                        end: chunk.end,
+                        line,
+                        column,
+                        leading_comments: chunk.comments,
+                        trailing_comments: chunk.trailing_comments,
+                    },
+                ));
+            }
+            Err(e) => errors.push(ParseError::new(script, chunk.start + e.pos, e.expected, e.message)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(statements)
+    } else {
+        Err(ScriptError { statements, errors })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        DataDefinitionStatement,
+        DataManipulationStatement,
+    };
+
+    #[test]
+    fn test_parse_script() {
+        let script = r#"
+            -- The keyspace for our movies
+            CREATE KEYSPACE IF NOT EXISTS movies
+                WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1}; -- for testing only
+
+            /* The movies table,
+               keyed by title */
+            CREATE TABLE movies.nerd_movies (
+                movie text PRIMARY KEY, // the title
+                director text
+            );;
+
+            BEGIN BATCH
+                INSERT INTO movies.nerd_movies (movie, director) VALUES ('Serenity', 'Joss Whedon');
+                INSERT INTO movies.nerd_movies (movie, director) VALUES ('Alien; Resurrection', 'Jean-Pierre Jeunet');
+            APPLY BATCH
+            SELECT * FROM movies.nerd_movies
+        "#;
+        let statements = parse_script(script).unwrap();
+        assert_eq!(statements.len(), 4);
+        assert!(matches!(
+            statements[0].0,
+            Statement::DataDefinition(DataDefinitionStatement::CreateKeyspace(_))
+        ));
+        assert_eq!(statements[0].1.leading_comments, vec!["-- The keyspace for our movies"]);
+        assert_eq!(statements[0].1.trailing_comments, vec!["-- for testing only"]);
+        assert_eq!((statements[0].1.line, statements[0].1.column), (3, 13));
+        assert!(matches!(
+            statements[1].0,
+            Statement::DataDefinition(DataDefinitionStatement::CreateTable(_))
+        ));
+        assert_eq!(
+            statements[1].1.leading_comments,
+            vec!["/* The movies table,\n               keyed by title */"]
+        );
+        assert!(statements[1].1.trailing_comments.is_empty());
+        assert!(matches!(
+            statements[2].0,
+            Statement::DataManipulation(DataManipulationStatement::Batch(_))
+        ));
+        assert!(statements[2].1.leading_comments.is_empty());
+        assert!(matches!(
+            statements[3].0,
+            Statement::DataManipulation(DataManipulationStatement::Select(_))
+        ));
+        let span = &statements[3].1;
+        assert_eq!(
+            script
+                .chars()
+                .skip(span.start)
+                .take(span.end - span.start)
+                .collect::<String>(),
+            "SELECT * FROM movies.nerd_movies"
+        );
+    }
+
+    #[test]
+    fn test_parse_script_errors() {
+        let script = "USE movies;\nSELECT * FORM nerd_movies;\nDROP TABLE nerd_movies;\nTRUNCATE nerd_movies extra;";
+        let err = parse_script(script).unwrap_err();
+        assert_eq!(err.statements.len(), 2);
+        assert_eq!(err.errors.len(), 2);
+        assert_eq!((err.errors[0].line, err.errors[0].column), (2, 10));
+        assert_eq!((err.errors[1].line, err.errors[1].column), (4, 22));
+    }
+}
@@ -6,7 +6,8 @@ use crate::{
     TerminatingList,
 };
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDataDefinitionStatement)]
 pub enum DataDefinitionStatement {
     Use(UseStatement),
@@ -101,7 +102,8 @@ impl Display for DataDefinitionStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedUseStatement)]
 pub struct UseStatement {
     pub keyspace: Name,
@@ -144,7 +146,8 @@ impl<T: Into<Name>> From<T> for UseStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Default)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Default, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedKeyspaceOpts)]
 pub struct KeyspaceOpts {
     #[builder(setter(into))]
@@ -227,7 +230,8 @@ impl Display for KeyspaceOpts {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedCreateKeyspaceStatement)]
 pub struct CreateKeyspaceStatement {
     #[builder(setter(name = "set_if_not_exists"), default)]
@@ -304,7 +308,8 @@ impl KeyspaceOptionsExt for CreateKeyspaceStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedAlterKeyspaceStatement)]
 pub struct AlterKeyspaceStatement {
     #[builder(setter(into))]
@@ -350,7 +355,8 @@ impl Display for AlterKeyspaceStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropKeyspaceStatement)]
 pub struct DropKeyspaceStatement {
     #[builder(setter(name = "set_if_exists"), default)]
@@ -420,7 +426,8 @@ impl<T: Into<Name>> From<T> for DropKeyspaceStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
 #[parse_via(TaggedCreateTableStatement)]
 pub struct CreateTableStatement {
@@ -474,6 +481,14 @@ impl CreateTableStatementBuilder {
         if self.columns.as_ref().map(|s| s.is_empty()).unwrap_or(false) {
             return Err("Column definitions cannot be empty".to_string());
         }
+        if self
+            .options
+            .as_ref()
+            .and_then(|o| o.as_ref())
+            .is_some_and(|o| o.synchronous_updates.is_some())
+        {
+            return Err("synchronous_updates is only applicable to materialized views".to_string());
+        }
         Ok(())
     }
 }
@@ -483,6 +498,11 @@ impl TaggedCreateTableStatementBuilder {
         if self.columns.as_ref().map(|s| s.is_empty()).unwrap_or(false) {
             return Err("Column definitions cannot be empty".to_string());
         }
+        if let Some(Some(Tag::Value(o))) = self.options.as_ref() {
+            if o.synchronous_updates.is_some() {
+                return Err("synchronous_updates is only applicable to materialized views".to_string());
+            }
+        }
         Ok(())
     }
 }
@@ -535,16 +555,18 @@ impl Display for CreateTableStatement {
 }
 
 impl TableOptionsExt for CreateTableStatement {
-    fn table_opts(&self) -> &Option<TableOpts> {
-        &self.options
+    fn table_opts(&self) -> Option<&TableOpts> {
+        self.options.as_ref()
     }
 
-    fn table_opts_mut(&mut self) -> &mut Option<TableOpts> {
-        &mut self.options
+    fn table_opts_mut(&mut self) -> &mut TableOpts {
+        self.options.get_or_insert_with(Default::default)
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(build_fn(validate = "Self::validate"))]
 #[parse_via(TaggedAlterTableStatement)]
 pub struct AlterTableStatement {
     #[builder(setter(into))]
@@ -552,6 +574,17 @@ pub struct AlterTableStatement {
     pub instruction: AlterTableInstruction,
 }
 
+impl AlterTableStatementBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(AlterTableInstruction::With(o)) = self.instruction.as_ref() {
+            if o.synchronous_updates.is_some() {
+                return Err("synchronous_updates is only applicable to materialized views".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
 impl TryFrom<TaggedAlterTableStatement> for AlterTableStatement {
     type Error = anyhow::Error;
     fn try_from(value: TaggedAlterTableStatement) -> Result<Self, Self::Error> {
@@ -588,7 +621,9 @@ impl Display for AlterTableStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::large_enum_variant)]
 pub enum AlterTableInstruction {
     Add(Vec<ColumnDefinition>),
     Drop(Vec<Name>),
@@ -638,7 +673,11 @@ impl Parse for AlterTableInstruction {
                 let (col, _, ty) = s.parse::<(_, TYPE, _)>()?;
                 Self::Alter(col, ty)
             } else if s.parse::<Option<WITH>>()?.is_some() {
-                Self::With(s.parse_from::<TableOpts>()?)
+                let opts = s.parse_from::<TableOpts>()?;
+                if opts.synchronous_updates.is_some() {
+                    anyhow::bail!("synchronous_updates is only applicable to materialized views");
+                }
+                Self::With(opts)
             } else {
                 anyhow::bail!("Expected ALTER TABLE instruction, found {}", s.info());
             },
@@ -665,7 +704,8 @@ impl Display for AlterTableInstruction {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropTableStatement)]
 pub struct DropTableStatement {
     #[builder(setter(name = "set_if_exists"), default)]
@@ -735,7 +775,8 @@ impl<T: Into<KeyspaceQualifiedName>> From<T> for DropTableStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedTruncateStatement)]
 pub struct TruncateStatement {
     #[builder(setter(into))]
@@ -786,13 +827,19 @@ impl<T: Into<KeyspaceQualifiedName>> From<T> for TruncateStatement {
 mod test {
     use super::*;
     use crate::{
+        CdcDelta,
+        CdcPreimage,
+        ChangeDataCapture,
         Compaction,
         Compression,
         JavaTimeUnit,
         KeyspaceQualifyExt,
         NativeType,
         Order,
+        PerPartitionRateLimit,
         SpeculativeRetry,
+        TombstoneGc,
+        TombstoneGcMode,
     };
 
     #[test]
@@ -985,6 +1032,123 @@ mod test {
         assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
     }
 
+    #[test]
+    fn test_parse_scylla_table_options() {
+        let mut builder = CreateTableStatementBuilder::default();
+        builder
+            .table("test")
+            .columns(vec![
+                ("id", NativeType::Uuid).into(),
+                ("value", NativeType::Text).into(),
+            ])
+            .primary_key(crate::PrimaryKey::partition_key("id"));
+        let mut opts_builder = crate::TableOptsBuilder::default();
+        opts_builder
+            .change_data_capture(
+                ChangeDataCapture::build()
+                    .enabled(true)
+                    .preimage(CdcPreimage::Full)
+                    .postimage(false)
+                    .delta(CdcDelta::Keys)
+                    .ttl(3600)
+                    .build()
+                    .unwrap(),
+            )
+            .tombstone_gc(
+                TombstoneGc::build()
+                    .mode(TombstoneGcMode::Repair)
+                    .propagation_delay_in_seconds(60)
+                    .build()
+                    .unwrap(),
+            )
+            .per_partition_rate_limit(
+                PerPartitionRateLimit::build()
+                    .max_reads_per_second(100)
+                    .max_writes_per_second(50)
+                    .build()
+                    .unwrap(),
+            )
+            .paxos_grace_seconds(864000)
+            .scylla_tags(maplit::btreemap! {
+                "owner".to_string() => "team's".to_string(),
+            });
+        builder.options(opts_builder.build().unwrap());
+        let statement = builder.build().unwrap().to_string();
+        assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+        opts_builder.synchronous_updates(true);
+        builder.options(opts_builder.build().unwrap());
+        assert!(builder.build().is_err());
+
+        // As output by DESCRIBE
+        let statement = "ALTER TABLE test WITH cdc = {'delta': 'full', 'enabled': 'true', 'postimage': 'false', \
+            'preimage': 'false', 'ttl': '86400'} AND tombstone_gc = {'mode': 'timeout', \
+            'propagation_delay_in_seconds': '3600'}"
+            .parse::<AlterTableStatement>()
+            .unwrap();
+        let AlterTableInstruction::With(opts) = statement.instruction else {
+            panic!("Expected table options");
+        };
+        assert_eq!(
+            opts.change_data_capture,
+            Some(ChangeDataCapture {
+                enabled: Some(true),
+                preimage: Some(CdcPreimage::Disabled),
+                postimage: Some(false),
+                delta: Some(CdcDelta::Full),
+                ttl: Some(86400),
+            })
+        );
+        assert_eq!(
+            opts.tombstone_gc,
+            Some(TombstoneGc {
+                mode: Some(TombstoneGcMode::Timeout),
+                propagation_delay_in_seconds: Some(3600),
+            })
+        );
+        assert_eq!(
+            "ALTER TABLE test WITH cdc = true"
+                .parse::<AlterTableStatement>()
+                .unwrap(),
+            AlterTableStatement {
+                table: "test".into(),
+                instruction: AlterTableInstruction::With(
+                    crate::TableOptsBuilder::default()
+                        .change_data_capture(true)
+                        .build()
+                        .unwrap()
+                ),
+            }
+        );
+
+        assert!("ALTER TABLE test WITH cdc = {'preimage': 'some'}"
+            .parse::<AlterTableStatement>()
+            .is_err());
+        assert!("ALTER TABLE test WITH cdc = {'enabled': true, 'unknown': 1}"
+            .parse::<AlterTableStatement>()
+            .is_err());
+        assert!("ALTER TABLE test WITH tombstone_gc = {'mode': 'never'}"
+            .parse::<AlterTableStatement>()
+            .is_err());
+        assert!(
+            "ALTER TABLE test WITH per_partition_rate_limit = {'max_reads_per_second': -1}"
+                .parse::<AlterTableStatement>()
+                .is_err()
+        );
+        assert!("ALTER TABLE test WITH synchronous_updates = true"
+            .parse::<AlterTableStatement>()
+            .is_err());
+        assert!(AlterTableStatementBuilder::default()
+            .table("test")
+            .instruction(AlterTableInstruction::With(
+                crate::TableOptsBuilder::default()
+                    .synchronous_updates(true)
+                    .build()
+                    .unwrap()
+            ))
+            .build()
+            .is_err());
+    }
+
     #[test]
     fn test_parse_drop_table() {
         let mut builder = DropTableStatementBuilder::default();
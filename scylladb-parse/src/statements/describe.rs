@@ -0,0 +1,527 @@
+use super::*;
+
+#[derive(ParseFromStr, Clone, Debug, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[parse_via(TaggedDescribeStatement)]
+pub enum DescribeStatement {
+    Cluster,
+    #[from]
+    Schema(DescribeSchemaStatement),
+    Keyspaces,
+    #[from]
+    Keyspace(DescribeKeyspaceStatement),
+    Tables,
+    #[from]
+    Table(DescribeTableStatement),
+    Types,
+    Type(KeyspaceQualifiedName),
+    Functions,
+    Function(KeyspaceQualifiedName),
+    Aggregates,
+    Aggregate(KeyspaceQualifiedName),
+    #[from]
+    MaterializedView(DescribeMaterializedViewStatement),
+    Index(KeyspaceQualifiedName),
+    Object(KeyspaceQualifiedName),
+}
+
+impl DescribeStatement {
+    pub fn schema() -> Self {
+        DescribeSchemaStatement::default().into()
+    }
+
+    pub fn keyspace(name: impl Into<Name>) -> Self {
+        DescribeKeyspaceStatement {
+            name: Some(name.into()),
+            ..Default::default()
+        }
+        .into()
+    }
+
+    pub fn table(name: impl Into<KeyspaceQualifiedName>) -> Self {
+        DescribeTableStatement {
+            name: name.into(),
+            with_internals: false,
+        }
+        .into()
+    }
+
+    pub fn materialized_view(name: impl Into<KeyspaceQualifiedName>) -> Self {
+        DescribeMaterializedViewStatement {
+            name: name.into(),
+            with_internals: false,
+        }
+        .into()
+    }
+
+    /// Set WITH INTERNALS on the statement, if it supports it.
+    pub fn with_internals(mut self) -> Self {
+        match &mut self {
+            Self::Schema(DescribeSchemaStatement { with_internals, .. })
+            | Self::Keyspace(DescribeKeyspaceStatement { with_internals, .. })
+            | Self::Table(DescribeTableStatement { with_internals, .. })
+            | Self::MaterializedView(DescribeMaterializedViewStatement { with_internals, .. }) => {
+                *with_internals = true
+            }
+            _ => (),
+        }
+        self
+    }
+}
+
+impl TryFrom<TaggedDescribeStatement> for DescribeStatement {
+    type Error = anyhow::Error;
+
+    fn try_from(value: TaggedDescribeStatement) -> Result<Self, Self::Error> {
+        Ok(match value {
+            TaggedDescribeStatement::Cluster => Self::Cluster,
+            TaggedDescribeStatement::Schema(s) => Self::Schema(s),
+            TaggedDescribeStatement::Keyspaces => Self::Keyspaces,
+            TaggedDescribeStatement::Keyspace(s) => Self::Keyspace(s.try_into()?),
+            TaggedDescribeStatement::Tables => Self::Tables,
+            TaggedDescribeStatement::Table(s) => Self::Table(s.try_into()?),
+            TaggedDescribeStatement::Types => Self::Types,
+            TaggedDescribeStatement::Type(name) => Self::Type(name.try_into()?),
+            TaggedDescribeStatement::Functions => Self::Functions,
+            TaggedDescribeStatement::Function(name) => Self::Function(name.try_into()?),
+            TaggedDescribeStatement::Aggregates => Self::Aggregates,
+            TaggedDescribeStatement::Aggregate(name) => Self::Aggregate(name.try_into()?),
+            TaggedDescribeStatement::MaterializedView(s) => Self::MaterializedView(s.try_into()?),
+            TaggedDescribeStatement::Index(name) => Self::Index(name.try_into()?),
+            TaggedDescribeStatement::Object(name) => Self::Object(name.try_into()?),
+        })
+    }
+}
+
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[tokenize_as(DescribeStatement)]
+pub enum TaggedDescribeStatement {
+    Cluster,
+    Schema(DescribeSchemaStatement),
+    Keyspaces,
+    Keyspace(TaggedDescribeKeyspaceStatement),
+    Tables,
+    Table(TaggedDescribeTableStatement),
+    Types,
+    Type(TaggedKeyspaceQualifiedName),
+    Functions,
+    Function(TaggedKeyspaceQualifiedName),
+    Aggregates,
+    Aggregate(TaggedKeyspaceQualifiedName),
+    MaterializedView(TaggedDescribeMaterializedViewStatement),
+    Index(TaggedKeyspaceQualifiedName),
+    Object(TaggedKeyspaceQualifiedName),
+}
+
+/// Parse the `DESCRIBE` keyword or its `DESC` abbreviation
+fn parse_describe(s: &mut StatementStream<'_>) -> anyhow::Result<()> {
+    if s.parse::<Option<DESCRIBE>>()?.is_none() {
+        s.parse::<DESC>()?;
+    }
+    Ok(())
+}
+
+impl Parse for TaggedDescribeStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        let mut rest = s.clone();
+        parse_describe(&mut rest)?;
+        // Keywords are matched as whole words so that objects named e.g. `tables_v2` are not mistaken for them
+        let mut keyword = String::new();
+        let mut peek = rest.clone();
+        peek.skip_whitespace();
+        while let Some(c) = peek.peek().filter(|c| c.is_alphanumeric() || *c == '_') {
+            keyword.push(c.to_ascii_uppercase());
+            peek.next();
+        }
+        // Statements with options parse their own `DESCRIBE` prefix
+        match keyword.as_str() {
+            "FULL" | "SCHEMA" => return Ok(Self::Schema(s.parse()?)),
+            "ONLY" | "KEYSPACE" => return Ok(Self::Keyspace(s.parse()?)),
+            "TABLE" => return Ok(Self::Table(s.parse()?)),
+            "MATERIALIZED" => return Ok(Self::MaterializedView(s.parse()?)),
+            _ => *s = rest,
+        }
+        let res = match keyword.as_str() {
+            "CLUSTER" => {
+                s.parse::<CLUSTER>()?;
+                Self::Cluster
+            }
+            "KEYSPACES" => {
+                s.parse::<KEYSPACES>()?;
+                Self::Keyspaces
+            }
+            "TABLES" => {
+                s.parse::<TABLES>()?;
+                Self::Tables
+            }
+            "TYPES" => {
+                s.parse::<TYPES>()?;
+                Self::Types
+            }
+            "TYPE" => Self::Type(s.parse::<(TYPE, _)>()?.1),
+            "FUNCTIONS" => {
+                s.parse::<FUNCTIONS>()?;
+                Self::Functions
+            }
+            "FUNCTION" => Self::Function(s.parse::<(FUNCTION, _)>()?.1),
+            "AGGREGATES" => {
+                s.parse::<AGGREGATES>()?;
+                Self::Aggregates
+            }
+            "AGGREGATE" => Self::Aggregate(s.parse::<(AGGREGATE, _)>()?.1),
+            "INDEX" => Self::Index(s.parse::<(INDEX, _)>()?.1),
+            _ => Self::Object(s.parse()?),
+        };
+        s.parse::<Option<Semicolon>>()?;
+        Ok(res)
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Default, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DescribeSchemaStatement {
+    #[builder(setter(name = "set_full"), default)]
+    pub full: bool,
+    #[builder(setter(name = "set_with_internals"), default)]
+    pub with_internals: bool,
+}
+
+impl DescribeSchemaStatementBuilder {
+    /// Set FULL on the statement.
+    /// To undo this, use `set_full(false)`.
+    pub fn full(&mut self) -> &mut Self {
+        self.full.replace(true);
+        self
+    }
+
+    /// Set WITH INTERNALS on the statement.
+    /// To undo this, use `set_with_internals(false)`.
+    pub fn with_internals(&mut self) -> &mut Self {
+        self.with_internals.replace(true);
+        self
+    }
+}
+
+impl Parse for DescribeSchemaStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        parse_describe(s)?;
+        let mut res = DescribeSchemaStatementBuilder::default();
+        res.set_full(s.parse::<Option<FULL>>()?.is_some());
+        s.parse::<SCHEMA>()?;
+        res.set_with_internals(s.parse::<Option<(WITH, INTERNALS)>>()?.is_some());
+        s.parse::<Option<Semicolon>>()?;
+        Ok(res
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid DESCRIBE SCHEMA statement: {}", e))?)
+    }
+}
+
+impl Display for DescribeSchemaStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DESCRIBE {}SCHEMA{}",
+            if self.full { "FULL " } else { "" },
+            if self.with_internals { " WITH INTERNALS" } else { "" }
+        )
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Default, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[parse_via(TaggedDescribeKeyspaceStatement)]
+pub struct DescribeKeyspaceStatement {
+    #[builder(setter(name = "set_only"), default)]
+    pub only: bool,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<Name>,
+    #[builder(setter(name = "set_with_internals"), default)]
+    pub with_internals: bool,
+}
+
+impl TryFrom<TaggedDescribeKeyspaceStatement> for DescribeKeyspaceStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedDescribeKeyspaceStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            only: value.only,
+            name: value.name.map(|v| v.into_value()).transpose()?,
+            with_internals: value.with_internals,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Default)]
+#[tokenize_as(DescribeKeyspaceStatement)]
+pub struct TaggedDescribeKeyspaceStatement {
+    #[builder(setter(name = "set_only"), default)]
+    pub only: bool,
+    #[builder(setter(strip_option), default)]
+    pub name: Option<Tag<Name>>,
+    #[builder(setter(name = "set_with_internals"), default)]
+    pub with_internals: bool,
+}
+
+impl DescribeKeyspaceStatementBuilder {
+    /// Set ONLY on the statement, to describe the keyspace without its contents.
+    /// To undo this, use `set_only(false)`.
+    pub fn only(&mut self) -> &mut Self {
+        self.only.replace(true);
+        self
+    }
+
+    /// Set WITH INTERNALS on the statement.
+    /// To undo this, use `set_with_internals(false)`.
+    pub fn with_internals(&mut self) -> &mut Self {
+        self.with_internals.replace(true);
+        self
+    }
+}
+
+impl Parse for TaggedDescribeKeyspaceStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        parse_describe(s)?;
+        let mut res = TaggedDescribeKeyspaceStatementBuilder::default();
+        res.set_only(s.parse::<Option<ONLY>>()?.is_some());
+        s.parse::<KEYSPACE>()?;
+        if !(s.check::<WITH>() || s.check::<Semicolon>() || s.remaining() == 0) {
+            res.name(s.parse()?);
+        }
+        res.set_with_internals(s.parse::<Option<(WITH, INTERNALS)>>()?.is_some());
+        s.parse::<Option<Semicolon>>()?;
+        Ok(res
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid DESCRIBE KEYSPACE statement: {}", e))?)
+    }
+}
+
+impl Display for DescribeKeyspaceStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DESCRIBE {}KEYSPACE{}{}",
+            if self.only { "ONLY " } else { "" },
+            self.name.as_ref().map(|n| format!(" {}", n)).unwrap_or_default(),
+            if self.with_internals { " WITH INTERNALS" } else { "" }
+        )
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[parse_via(TaggedDescribeTableStatement)]
+pub struct DescribeTableStatement {
+    #[builder(setter(into))]
+    pub name: KeyspaceQualifiedName,
+    #[builder(setter(name = "set_with_internals"), default)]
+    pub with_internals: bool,
+}
+
+impl TryFrom<TaggedDescribeTableStatement> for DescribeTableStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedDescribeTableStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: value.name.try_into()?,
+            with_internals: value.with_internals,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[tokenize_as(DescribeTableStatement)]
+pub struct TaggedDescribeTableStatement {
+    pub name: TaggedKeyspaceQualifiedName,
+    #[builder(setter(name = "set_with_internals"), default)]
+    pub with_internals: bool,
+}
+
+impl DescribeTableStatementBuilder {
+    /// Set WITH INTERNALS on the statement.
+    /// To undo this, use `set_with_internals(false)`.
+    pub fn with_internals(&mut self) -> &mut Self {
+        self.with_internals.replace(true);
+        self
+    }
+}
+
+impl Parse for TaggedDescribeTableStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        parse_describe(s)?;
+        let mut res = TaggedDescribeTableStatementBuilder::default();
+        res.name(s.parse::<(TABLE, _)>()?.1)
+            .set_with_internals(s.parse::<Option<(WITH, INTERNALS)>>()?.is_some());
+        s.parse::<Option<Semicolon>>()?;
+        Ok(res
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid DESCRIBE TABLE statement: {}", e))?)
+    }
+}
+
+impl Display for DescribeTableStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DESCRIBE TABLE {}{}",
+            self.name,
+            if self.with_internals { " WITH INTERNALS" } else { "" }
+        )
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[parse_via(TaggedDescribeMaterializedViewStatement)]
+pub struct DescribeMaterializedViewStatement {
+    #[builder(setter(into))]
+    pub name: KeyspaceQualifiedName,
+    #[builder(setter(name = "set_with_internals"), default)]
+    pub with_internals: bool,
+}
+
+impl TryFrom<TaggedDescribeMaterializedViewStatement> for DescribeMaterializedViewStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedDescribeMaterializedViewStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: value.name.try_into()?,
+            with_internals: value.with_internals,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[tokenize_as(DescribeMaterializedViewStatement)]
+pub struct TaggedDescribeMaterializedViewStatement {
+    pub name: TaggedKeyspaceQualifiedName,
+    #[builder(setter(name = "set_with_internals"), default)]
+    pub with_internals: bool,
+}
+
+impl DescribeMaterializedViewStatementBuilder {
+    /// Set WITH INTERNALS on the statement.
+    /// To undo this, use `set_with_internals(false)`.
+    pub fn with_internals(&mut self) -> &mut Self {
+        self.with_internals.replace(true);
+        self
+    }
+}
+
+impl Parse for TaggedDescribeMaterializedViewStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        parse_describe(s)?;
+        let mut res = TaggedDescribeMaterializedViewStatementBuilder::default();
+        res.name(s.parse::<(MATERIALIZED, VIEW, _)>()?.2)
+            .set_with_internals(s.parse::<Option<(WITH, INTERNALS)>>()?.is_some());
+        s.parse::<Option<Semicolon>>()?;
+        Ok(res
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid DESCRIBE MATERIALIZED VIEW statement: {}", e))?)
+    }
+}
+
+impl Display for DescribeMaterializedViewStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DESCRIBE MATERIALIZED VIEW {}{}",
+            self.name,
+            if self.with_internals { " WITH INTERNALS" } else { "" }
+        )
+    }
+}
+
+impl Display for DescribeStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cluster => write!(f, "DESCRIBE CLUSTER"),
+            Self::Schema(stmt) => stmt.fmt(f),
+            Self::Keyspaces => write!(f, "DESCRIBE KEYSPACES"),
+            Self::Keyspace(stmt) => stmt.fmt(f),
+            Self::Tables => write!(f, "DESCRIBE TABLES"),
+            Self::Table(stmt) => stmt.fmt(f),
+            Self::Types => write!(f, "DESCRIBE TYPES"),
+            Self::Type(name) => write!(f, "DESCRIBE TYPE {}", name),
+            Self::Functions => write!(f, "DESCRIBE FUNCTIONS"),
+            Self::Function(name) => write!(f, "DESCRIBE FUNCTION {}", name),
+            Self::Aggregates => write!(f, "DESCRIBE AGGREGATES"),
+            Self::Aggregate(name) => write!(f, "DESCRIBE AGGREGATE {}", name),
+            Self::MaterializedView(stmt) => stmt.fmt(f),
+            Self::Index(name) => write!(f, "DESCRIBE INDEX {}", name),
+            Self::Object(name) => write!(f, "DESCRIBE {}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::KeyspaceQualifyExt;
+
+    #[test]
+    fn test_parse_describe() {
+        for statement in [
+            DescribeStatement::Cluster,
+            DescribeStatement::schema(),
+            DescribeSchemaStatementBuilder::default()
+                .full()
+                .with_internals()
+                .build()
+                .unwrap()
+                .into(),
+            DescribeStatement::Keyspaces,
+            DescribeStatement::keyspace("my_keyspace").with_internals(),
+            DescribeKeyspaceStatementBuilder::default()
+                .only()
+                .build()
+                .unwrap()
+                .into(),
+            DescribeKeyspaceStatementBuilder::default()
+                .only()
+                .name("my_keyspace")
+                .with_internals()
+                .build()
+                .unwrap()
+                .into(),
+            DescribeStatement::Tables,
+            DescribeStatement::table("my_keyspace".dot("my_table")),
+            DescribeTableStatementBuilder::default()
+                .name("my_table")
+                .with_internals()
+                .build()
+                .unwrap()
+                .into(),
+            DescribeStatement::Types,
+            DescribeStatement::Type("my_type".into()),
+            DescribeStatement::Functions,
+            DescribeStatement::Function("my_keyspace".dot("my_function")),
+            DescribeStatement::Aggregates,
+            DescribeStatement::Aggregate("my_aggregate".into()),
+            DescribeStatement::materialized_view("my_view").with_internals(),
+            DescribeMaterializedViewStatementBuilder::default()
+                .name("my_keyspace".dot("my_view"))
+                .build()
+                .unwrap()
+                .into(),
+            DescribeStatement::Index("my_index".into()),
+            DescribeStatement::Object("tables_v2".into()),
+        ] {
+            assert_eq!(statement, statement.to_string().parse().unwrap());
+        }
+        assert_eq!(
+            DescribeStatement::table("my_table"),
+            "desc table my_table;".parse().unwrap()
+        );
+        assert!(DescribeTableStatementBuilder::default().build().is_err());
+        assert_eq!(
+            DescribeStatement::from(DescribeSchemaStatementBuilder::default().full().build().unwrap()),
+            "DESC FULL SCHEMA".parse().unwrap()
+        );
+        assert_eq!(
+            Statement::from(DescribeStatement::Cluster),
+            "DESC CLUSTER".parse().unwrap()
+        );
+    }
+}
@@ -8,8 +8,10 @@ use crate::{
     ReservedKeyword,
     TupleLiteral,
 };
+use uuid::Uuid;
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDataManipulationStatement)]
 pub enum DataManipulationStatement {
     Select(SelectStatement),
@@ -51,7 +53,7 @@ impl Parse for TaggedDataManipulationStatement {
                 ReservedKeyword::INSERT => Self::Insert(s.parse()?),
                 ReservedKeyword::UPDATE => Self::Update(s.parse()?),
                 ReservedKeyword::DELETE => Self::Delete(s.parse()?),
-                ReservedKeyword::BATCH => Self::Batch(s.parse()?),
+                ReservedKeyword::BEGIN => Self::Batch(s.parse()?),
                 _ => anyhow::bail!("Expected a data manipulation statement, found {}", s.info()),
             }
         } else {
@@ -94,7 +96,8 @@ impl KeyspaceExt for DataManipulationStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
 #[parse_via(TaggedSelectStatement)]
 pub struct SelectStatement {
@@ -342,7 +345,8 @@ impl WhereExt for SelectStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SelectClause {
     All,
     Selectors(Vec<Selector>),
@@ -385,7 +389,8 @@ impl From<Vec<Selector>> for SelectClause {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Selector {
     #[builder(setter(into))]
     pub kind: SelectorKind,
@@ -461,7 +466,8 @@ impl Display for Selector {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SelectorFunction {
     pub function: Name,
     pub args: Vec<Selector>,
@@ -487,8 +493,11 @@ impl Parse for SelectorFunction {
     where
         Self: Sized,
     {
-        let (function, args) = s.parse_from::<(Name, Parens<List<Selector, Comma>>)>()?;
-        Ok(SelectorFunction { function, args })
+        let (function, args) = s.parse_from::<(Name, Parens<Option<List<Selector, Comma>>>)>()?;
+        Ok(SelectorFunction {
+            function,
+            args: args.unwrap_or_default(),
+        })
     }
 }
 
@@ -503,7 +512,8 @@ impl Display for SelectorFunction {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SelectorKind {
     Column(Name),
     Term(Term),
@@ -519,8 +529,9 @@ impl Parse for SelectorKind {
         Self: Sized,
     {
         Ok(if s.parse::<Option<CAST>>()?.is_some() {
-            let (selector, _, cql_type) = s.parse_from::<Parens<(Selector, AS, CqlType)>>()?;
-            Self::Cast(Box::new(selector), cql_type)
+            // The cast selector cannot be aliased, so its AS always precedes the type
+            let (kind, _, cql_type) = s.parse_from::<Parens<(SelectorKind, AS, CqlType)>>()?;
+            Self::Cast(Box::new(Selector { kind, as_id: None }), cql_type)
         } else if s.parse::<Option<COUNT>>()?.is_some() {
             // TODO: Double check that this is ok
             s.parse_from::<Parens<char>>()?;
@@ -549,7 +560,8 @@ impl Display for SelectorKind {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option))]
 #[parse_via(TaggedInsertStatement)]
 pub struct InsertStatement {
@@ -657,7 +669,8 @@ impl KeyspaceExt for InsertStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InsertKind {
     NameValue {
         names: Vec<Name>,
@@ -741,7 +754,8 @@ impl Display for InsertKind {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UpdateParameter {
     TTL(Limit),
     Timestamp(Limit),
@@ -787,7 +801,8 @@ impl Display for UpdateParameter {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
 #[parse_via(TaggedUpdateStatement)]
 pub struct UpdateStatement {
@@ -945,7 +960,8 @@ impl WhereExt for UpdateStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Assignment {
     Simple {
         selection: SimpleSelection,
@@ -993,10 +1009,16 @@ impl Assignment {
 impl Parse for Assignment {
     type Output = Self;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        // A UUID starting with a letter would otherwise be read as a subtraction from a column
+        let uuid = s.check::<(Name, Equals, Uuid)>();
         Ok(
             if let Some((assignee, _, list, _, item)) = s.parse::<Option<(_, Equals, _, Plus, _)>>()? {
                 Self::Append { assignee, list, item }
-            } else if let Some((assignee, _, lhs, op, rhs)) = s.parse::<Option<(_, Equals, _, _, _)>>()? {
+            } else if let Some((assignee, _, lhs, op, rhs)) = (!uuid)
+                .then(|| s.parse::<Option<(_, Equals, _, _, _)>>())
+                .transpose()?
+                .flatten()
+            {
                 Self::Arithmetic { assignee, lhs, op, rhs }
             } else {
                 let (selection, _, term) = s.parse::<(_, Equals, _)>()?;
@@ -1018,7 +1040,8 @@ impl Display for Assignment {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SimpleSelection {
     Column(Name),
     Term(Name, Term),
@@ -1068,7 +1091,8 @@ impl<N: Into<Name>> From<N> for SimpleSelection {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Condition {
     pub lhs: SimpleSelection,
     pub op: Operator,
@@ -1099,7 +1123,8 @@ impl Display for Condition {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IfClause {
     Exists,
     Conditions(Vec<Condition>),
@@ -1149,7 +1174,8 @@ impl Display for IfClause {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
 #[parse_via(TaggedDeleteStatement)]
 pub struct DeleteStatement {
@@ -1270,7 +1296,7 @@ impl Display for DeleteStatement {
                 write!(
                     f,
                     " USING {}",
-                    using.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ")
+                    using.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" AND ")
                 )?;
             }
         }
@@ -1298,7 +1324,8 @@ impl WhereExt for DeleteStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(build_fn(validate = "Self::validate"))]
 #[parse_via(TaggedBatchStatement)]
 pub struct BatchStatement {
@@ -1477,7 +1504,8 @@ impl Display for BatchStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedModificationStatement)]
 pub enum ModificationStatement {
     Insert(InsertStatement),
@@ -1546,7 +1574,8 @@ impl Display for ModificationStatement {
     }
 }
 
-#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BatchKind {
     Logged,
     Unlogged,
@@ -1572,7 +1601,8 @@ impl Default for BatchKind {
     }
 }
 
-#[derive(Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WhereClause {
     pub relations: Vec<Relation>,
 }
@@ -1608,7 +1638,8 @@ impl From<Vec<Relation>> for WhereClause {
     }
 }
 
-#[derive(Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GroupByClause {
     pub columns: Vec<Name>,
 }
@@ -1646,7 +1677,8 @@ impl<T: Into<Name>> From<Vec<T>> for GroupByClause {
     }
 }
 
-#[derive(Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderByClause {
     pub columns: Vec<ColumnOrder>,
 }
@@ -1684,7 +1716,8 @@ impl<T: Into<ColumnOrder>> From<Vec<T>> for OrderByClause {
     }
 }
 
-#[derive(Clone, Debug, From, ToTokens, PartialEq, Eq)]
+#[derive(Clone, Debug, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Limit {
     Literal(i32),
     #[from(ignore)]
@@ -1719,7 +1752,8 @@ impl<T: Into<BindMarker>> From<T> for Limit {
     }
 }
 
-#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColumnDefault {
     Null,
     Unset,
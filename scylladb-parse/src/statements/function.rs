@@ -2,7 +2,8 @@ use super::*;
 
 pub type FunctionName = KeyspaceQualifiedName;
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionDeclaration {
     #[builder(setter(into))]
     pub name: FunctionName,
@@ -23,8 +24,11 @@ impl Parse for FunctionDeclaration {
     type Output = Self;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
         let name = s.parse()?;
-        let args = s.parse_from::<Parens<List<ArgumentDeclaration, Comma>>>()?;
-        Ok(Self { name, args })
+        let args = s.parse_from::<Parens<Option<List<ArgumentDeclaration, Comma>>>>()?;
+        Ok(Self {
+            name,
+            args: args.unwrap_or_default(),
+        })
     }
 }
 
@@ -39,7 +43,8 @@ impl Display for FunctionDeclaration {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionReference {
     pub name: FunctionName,
     pub args: Option<Vec<CqlType>>,
@@ -58,8 +63,11 @@ impl Parse for FunctionReference {
     type Output = Self;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
         let name = s.parse()?;
-        let args = s.parse_from::<Option<Parens<List<CqlType, Comma>>>>()?;
-        Ok(Self { name, args })
+        let args = s.parse_from::<Option<Parens<Option<List<CqlType, Comma>>>>>()?;
+        Ok(Self {
+            name,
+            args: args.map(Option::unwrap_or_default),
+        })
     }
 }
 
@@ -102,7 +110,8 @@ impl From<&str> for FunctionReference {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionSignature {
     pub name: FunctionName,
     pub args: Vec<CqlType>,
@@ -122,7 +131,9 @@ impl Parse for FunctionSignature {
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
         Ok(Self {
             name: s.parse()?,
-            args: s.parse_from::<Parens<List<CqlType, Comma>>>()?,
+            args: s
+                .parse_from::<Parens<Option<List<CqlType, Comma>>>>()?
+                .unwrap_or_default(),
         })
     }
 }
@@ -138,7 +149,8 @@ impl Display for FunctionSignature {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionCall {
     pub name: FunctionName,
     pub args: Vec<Term>,
@@ -156,8 +168,11 @@ impl FunctionCall {
 impl Parse for FunctionCall {
     type Output = Self;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        let (name, args) = s.parse_from::<(FunctionName, Parens<List<Term, Comma>>)>()?;
-        Ok(Self { name, args })
+        let (name, args) = s.parse_from::<(FunctionName, Parens<Option<List<Term, Comma>>>)>()?;
+        Ok(Self {
+            name,
+            args: args.unwrap_or_default(),
+        })
     }
 }
 
@@ -172,7 +187,8 @@ impl Display for FunctionCall {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedUserDefinedFunctionStatement)]
 pub enum UserDefinedFunctionStatement {
     Create(CreateFunctionStatement),
@@ -243,7 +259,8 @@ impl Display for UserDefinedFunctionStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedCreateFunctionStatement)]
 pub struct CreateFunctionStatement {
     #[builder(setter(name = "set_or_replace"), default)]
@@ -341,7 +358,8 @@ impl Display for CreateFunctionStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OnNullInput {
     Called,
     ReturnsNull,
@@ -371,7 +389,8 @@ impl Display for OnNullInput {
 
 pub type ArgumentDeclaration = FieldDefinition;
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropFunctionStatement)]
 pub struct DropFunctionStatement {
     #[builder(setter(name = "set_if_exists"), default)]
@@ -431,7 +450,8 @@ impl Display for DropFunctionStatement {
         )
     }
 }
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option))]
 #[parse_via(TaggedCreateAggregateFunctionStatement)]
 pub struct CreateAggregateFunctionStatement {
@@ -556,7 +576,8 @@ impl Display for CreateAggregateFunctionStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropAggregateFunctionStatement)]
 pub struct DropAggregateFunctionStatement {
     #[builder(setter(name = "set_if_exists"), default)]
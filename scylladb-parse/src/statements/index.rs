@@ -1,6 +1,7 @@
 use super::*;
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedSecondaryIndexStatement)]
 pub enum SecondaryIndexStatement {
     Create(CreateIndexStatement),
@@ -48,7 +49,8 @@ impl Display for SecondaryIndexStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option))]
 #[parse_via(TaggedCreateIndexStatement)]
 pub struct CreateIndexStatement {
@@ -158,7 +160,8 @@ impl Display for CreateIndexStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IndexIdentifier {
     Column(Name),
     Qualified(IndexQualifier, Name),
@@ -185,11 +188,14 @@ impl IndexIdentifier {
 impl Parse for IndexIdentifier {
     type Output = Self;
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
-        Ok(if let Some(name) = s.parse()? {
-            IndexIdentifier::Column(name)
-        } else {
-            IndexIdentifier::Qualified(s.parse()?, s.parse_from::<Parens<Name>>()?)
-        })
+        // Qualifiers are not reserved, so they must be checked before column names
+        Ok(
+            if let Some((qualifier, name)) = s.parse_from::<Option<(IndexQualifier, Parens<Name>)>>()? {
+                IndexIdentifier::Qualified(qualifier, name)
+            } else {
+                IndexIdentifier::Column(s.parse()?)
+            },
+        )
     }
 }
 
@@ -208,7 +214,8 @@ impl<N: Into<Name>> From<N> for IndexIdentifier {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IndexQualifier {
     Keys,
     Values,
@@ -244,7 +251,8 @@ impl Display for IndexQualifier {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IndexClass {
     pub path: LitStr,
     pub options: Option<MapLiteral>,
@@ -285,13 +293,14 @@ impl Display for IndexClass {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropIndexStatement)]
 pub struct DropIndexStatement {
     #[builder(setter(name = "set_if_exists"), default)]
     pub if_exists: bool,
     #[builder(setter(into))]
-    pub name: Name,
+    pub name: KeyspaceQualifiedName,
 }
 
 impl TryFrom<TaggedDropIndexStatement> for DropIndexStatement {
@@ -300,7 +309,7 @@ impl TryFrom<TaggedDropIndexStatement> for DropIndexStatement {
     fn try_from(value: TaggedDropIndexStatement) -> anyhow::Result<Self> {
         Ok(Self {
             if_exists: value.if_exists,
-            name: value.name.into_value()?,
+            name: value.name.try_into()?,
         })
     }
 }
@@ -310,7 +319,7 @@ impl TryFrom<TaggedDropIndexStatement> for DropIndexStatement {
 pub struct TaggedDropIndexStatement {
     #[builder(setter(name = "set_if_exists"), default)]
     pub if_exists: bool,
-    pub name: Tag<Name>,
+    pub name: TaggedKeyspaceQualifiedName,
 }
 
 impl DropIndexStatementBuilder {
@@ -387,5 +396,12 @@ mod test {
         builder.if_exists();
         let statement = builder.build().unwrap().to_string();
         assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+        builder.name(KeyspaceQualifiedName {
+            keyspace: Some("my_keyspace".into()),
+            name: "my_index_name".into(),
+        });
+        let statement = builder.build().unwrap().to_string();
+        assert_eq!(statement, "DROP INDEX IF EXISTS my_keyspace.my_index_name");
+        assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
     }
 }
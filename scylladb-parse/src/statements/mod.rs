@@ -3,6 +3,7 @@ use crate::{
     Alpha,
     Brackets,
     Caching,
+    ChangeDataCapture,
     ColumnOrder,
     Compaction,
     CompactionType,
@@ -18,6 +19,8 @@ use crate::{
     Nothing,
     Parens,
     Parse,
+    ParseError,
+    PerPartitionRateLimit,
     Relation,
     Replication,
     SetLiteral,
@@ -31,6 +34,10 @@ use crate::{
     TaggedStatementOptValue,
     Term,
     TokenWrapper,
+    TombstoneGc,
+    Visit,
+    VisitMut,
+    Visitable,
 };
 use derive_builder::Builder;
 use derive_more::{
@@ -42,6 +49,7 @@ use scylladb_parse_macros::{
     ToTokens,
 };
 use std::{
+    collections::BTreeMap,
     convert::{
         TryFrom,
         TryInto,
@@ -74,7 +82,14 @@ pub use security::*;
 mod trigger;
 pub use trigger::*;
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq)]
+mod describe;
+pub use describe::*;
+
+mod service_level;
+pub use service_level::*;
+
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedStatement)]
 pub enum Statement {
     DataDefinition(DataDefinitionStatement),
@@ -87,6 +102,8 @@ pub enum Statement {
     UserDefinedFunction(UserDefinedFunctionStatement),
     UserDefinedType(UserDefinedTypeStatement),
     Trigger(TriggerStatement),
+    Describe(DescribeStatement),
+    ServiceLevel(ServiceLevelStatement),
 }
 
 impl TryFrom<TaggedStatement> for Statement {
@@ -103,6 +120,8 @@ impl TryFrom<TaggedStatement> for Statement {
             TaggedStatement::UserDefinedFunction(value) => Statement::UserDefinedFunction(value.try_into()?),
             TaggedStatement::UserDefinedType(value) => Statement::UserDefinedType(value.try_into()?),
             TaggedStatement::Trigger(value) => Statement::Trigger(value.try_into()?),
+            TaggedStatement::Describe(value) => Statement::Describe(value.try_into()?),
+            TaggedStatement::ServiceLevel(value) => Statement::ServiceLevel(value.try_into()?),
         })
     }
 }
@@ -144,7 +163,8 @@ impl_try_into_statements!(
     UserStatement => {CreateUserStatement, AlterUserStatement, DropUserStatement, ListUsersStatement},
     UserDefinedFunctionStatement => {CreateFunctionStatement, DropFunctionStatement, CreateAggregateFunctionStatement, DropAggregateFunctionStatement},
     UserDefinedTypeStatement => {CreateUserDefinedTypeStatement, AlterUserDefinedTypeStatement, DropUserDefinedTypeStatement},
-    TriggerStatement => {CreateTriggerStatement, DropTriggerStatement}
+    TriggerStatement => {CreateTriggerStatement, DropTriggerStatement},
+    ServiceLevelStatement => {CreateServiceLevelStatement, AlterServiceLevelStatement, DropServiceLevelStatement, ListServiceLevelsStatement, AttachServiceLevelStatement, DetachServiceLevelStatement, ListAttachedServiceLevelsStatement}
 );
 
 #[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq)]
@@ -161,6 +181,8 @@ pub enum TaggedStatement {
     UserDefinedFunction(TaggedUserDefinedFunctionStatement),
     UserDefinedType(TaggedUserDefinedTypeStatement),
     Trigger(TaggedTriggerStatement),
+    Describe(TaggedDescribeStatement),
+    ServiceLevel(TaggedServiceLevelStatement),
 }
 
 macro_rules! impl_try_into_tagged_statements {
@@ -200,7 +222,8 @@ impl_try_into_tagged_statements!(
     TaggedUserStatement => {TaggedCreateUserStatement, TaggedAlterUserStatement, TaggedDropUserStatement, ListUsersStatement},
     TaggedUserDefinedFunctionStatement => {TaggedCreateFunctionStatement, TaggedDropFunctionStatement, TaggedCreateAggregateFunctionStatement, TaggedDropAggregateFunctionStatement},
     TaggedUserDefinedTypeStatement => {TaggedCreateUserDefinedTypeStatement, TaggedAlterUserDefinedTypeStatement, TaggedDropUserDefinedTypeStatement},
-    TaggedTriggerStatement => {TaggedCreateTriggerStatement, TaggedDropTriggerStatement}
+    TaggedTriggerStatement => {TaggedCreateTriggerStatement, TaggedDropTriggerStatement},
+    TaggedServiceLevelStatement => {TaggedCreateServiceLevelStatement, TaggedAlterServiceLevelStatement, TaggedDropServiceLevelStatement, TaggedListServiceLevelsStatement, TaggedAttachServiceLevelStatement, TaggedDetachServiceLevelStatement, TaggedListAttachedServiceLevelsStatement}
 );
 
 impl Parse for TaggedStatement {
@@ -208,6 +231,14 @@ impl Parse for TaggedStatement {
     fn parse(s: &mut crate::StatementStream<'_>) -> anyhow::Result<Self::Output> {
         Ok(if s.check::<(LIST, USERS)>() {
             Self::User(s.parse()?)
+        } else if s.check::<(LIST, SERVICE_LEVEL)>()
+            || s.check::<(LIST, ALL, SERVICE_LEVELS)>()
+            || s.check::<(LIST, ATTACHED)>()
+            || s.check::<(LIST, ALL, ATTACHED)>()
+            || s.check::<ATTACH>()
+            || s.check::<DETACH>()
+        {
+            Self::ServiceLevel(s.parse()?)
         } else if s.check::<(LIST, ROLES)>() {
             Self::Role(s.parse()?)
         } else if s.check::<LIST>() {
@@ -219,6 +250,7 @@ impl Parse for TaggedStatement {
                     if let Some((_, keyword2)) = s.find::<(ReservedKeyword, ReservedKeyword)>() {
                         match keyword2 {
                             ReservedKeyword::KEYSPACE | ReservedKeyword::TABLE => Self::DataDefinition(s.parse()?),
+                            ReservedKeyword::INDEX => Self::SecondaryIndex(s.parse()?),
                             ReservedKeyword::OR => Self::UserDefinedFunction(s.parse()?),
                             _ => anyhow::bail!("Unexpected keyword following {}: {}", keyword, keyword2),
                         }
@@ -231,6 +263,7 @@ impl Parse for TaggedStatement {
                             "FUNCTION" | "AGGREGATE" => Self::UserDefinedFunction(s.parse()?),
                             "TYPE" => Self::UserDefinedType(s.parse()?),
                             "TRIGGER" => Self::Trigger(s.parse()?),
+                            "SERVICE" => Self::ServiceLevel(s.parse()?),
                             _ => anyhow::bail!("Unexpected keyword following {}: {}", keyword, keyword2),
                         }
                     } else {
@@ -238,14 +271,11 @@ impl Parse for TaggedStatement {
                     }
                 }
                 ReservedKeyword::GRANT | ReservedKeyword::REVOKE => {
-                    if let Some((_, keyword2)) = s.find_from::<(ReservedKeyword, Alpha)>() {
-                        match keyword2.to_uppercase().as_str() {
-                            "ROLE" => Self::Role(s.parse()?),
-                            "PERMISSION" => Self::Permission(s.parse()?),
-                            _ => anyhow::bail!("Unexpected keyword following {}: {}", keyword, keyword2),
-                        }
+                    // Only permissions are granted ON a resource
+                    if s.check::<TaggedPermissionStatement>() {
+                        Self::Permission(s.parse()?)
                     } else {
-                        anyhow::bail!("Unexpected token following {}: {}", keyword, s.info())
+                        Self::Role(s.parse()?)
                     }
                 }
                 ReservedKeyword::SELECT
@@ -253,6 +283,7 @@ impl Parse for TaggedStatement {
                 | ReservedKeyword::UPDATE
                 | ReservedKeyword::DELETE
                 | ReservedKeyword::BEGIN => Self::DataManipulation(s.parse()?),
+                ReservedKeyword::DESCRIBE | ReservedKeyword::DESC => Self::Describe(s.parse()?),
                 _ => anyhow::bail!("Expected statement, found {}", s.info()),
             }
         } else {
@@ -274,6 +305,8 @@ impl Display for Statement {
             Self::UserDefinedFunction(stmt) => stmt.fmt(f),
             Self::UserDefinedType(stmt) => stmt.fmt(f),
             Self::Trigger(stmt) => stmt.fmt(f),
+            Self::Describe(stmt) => stmt.fmt(f),
+            Self::ServiceLevel(stmt) => stmt.fmt(f),
         }
     }
 }
@@ -294,7 +327,8 @@ impl KeyspaceExt for Statement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FieldDefinition {
     pub name: Name,
     pub data_type: CqlType,
@@ -378,100 +412,87 @@ pub trait KeyspaceOptionsExt {
 }
 
 pub trait TableOptionsExt {
-    fn table_opts(&self) -> &Option<TableOpts>;
-    fn table_opts_mut(&mut self) -> &mut Option<TableOpts>;
+    fn table_opts(&self) -> Option<&TableOpts>;
+    /// Get the table options to modify, inserting the defaults if there are none
+    fn table_opts_mut(&mut self) -> &mut TableOpts;
     fn set_compact_storage(&mut self, compact_storage: bool) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .compact_storage = compact_storage;
+        self.table_opts_mut().compact_storage = compact_storage;
     }
     fn set_clustering_order(&mut self, clustering_order: Vec<ColumnOrder>) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .clustering_order
-            .replace(clustering_order);
+        self.table_opts_mut().clustering_order.replace(clustering_order);
     }
     fn set_comment(&mut self, comment: &str) {
         let c = comment.to_string().into();
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .comment
-            .replace(c);
+        self.table_opts_mut().comment.replace(c);
     }
 
     fn set_speculative_retry(&mut self, speculative_retry: SpeculativeRetry) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .speculative_retry
-            .replace(speculative_retry);
+        self.table_opts_mut().speculative_retry.replace(speculative_retry);
     }
 
-    fn set_change_data_capture(&mut self, cdc: bool) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .change_data_capture
-            .replace(cdc);
+    fn set_change_data_capture(&mut self, cdc: impl Into<ChangeDataCapture>) {
+        self.table_opts_mut().change_data_capture.replace(cdc.into());
     }
 
     fn set_gc_grace_seconds(&mut self, gc_grace_seconds: i32) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .gc_grace_seconds
-            .replace(gc_grace_seconds);
+        self.table_opts_mut().gc_grace_seconds.replace(gc_grace_seconds);
     }
 
     fn set_bloom_filter_fp_chance(&mut self, bloom_filter_fp_chance: f32) {
         self.table_opts_mut()
-            .get_or_insert_with(Default::default)
             .bloom_filter_fp_chance
             .replace(bloom_filter_fp_chance);
     }
 
     fn set_default_time_to_live(&mut self, default_time_to_live: i32) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .default_time_to_live
-            .replace(default_time_to_live);
+        self.table_opts_mut().default_time_to_live.replace(default_time_to_live);
     }
 
     fn set_compaction(&mut self, compaction: impl CompactionType) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .compaction
-            .replace(compaction.into());
+        self.table_opts_mut().compaction.replace(compaction.into());
     }
 
     fn set_compression(&mut self, compression: Compression) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .compression
-            .replace(compression);
+        self.table_opts_mut().compression.replace(compression);
     }
 
     fn set_caching(&mut self, caching: Caching) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .caching
-            .replace(caching);
+        self.table_opts_mut().caching.replace(caching);
     }
 
     fn set_memtable_flush_period_in_ms(&mut self, memtable_flush_period_in_ms: i32) {
         self.table_opts_mut()
-            .get_or_insert_with(Default::default)
             .memtable_flush_period_in_ms
             .replace(memtable_flush_period_in_ms);
     }
 
     fn set_read_repair(&mut self, read_repair: bool) {
-        self.table_opts_mut()
-            .get_or_insert_with(Default::default)
-            .read_repair
-            .replace(read_repair);
+        self.table_opts_mut().read_repair.replace(read_repair);
     }
     fn set_additional_write_policy(&mut self, speculative_retry: SpeculativeRetry) {
         self.set_speculative_retry(speculative_retry)
     }
 
+    fn set_tombstone_gc(&mut self, tombstone_gc: impl Into<TombstoneGc>) {
+        self.table_opts_mut().tombstone_gc.replace(tombstone_gc.into());
+    }
+
+    fn set_per_partition_rate_limit(&mut self, per_partition_rate_limit: PerPartitionRateLimit) {
+        self.table_opts_mut()
+            .per_partition_rate_limit
+            .replace(per_partition_rate_limit);
+    }
+
+    fn set_paxos_grace_seconds(&mut self, paxos_grace_seconds: i32) {
+        self.table_opts_mut().paxos_grace_seconds.replace(paxos_grace_seconds);
+    }
+
+    fn set_scylla_tags<K: Into<String>, V: Into<String>>(&mut self, scylla_tags: BTreeMap<K, V>) {
+        self.table_opts_mut()
+            .scylla_tags
+            .replace(scylla_tags.into_iter().map(|(k, v)| (k.into(), v.into())).collect());
+    }
+
     fn with_compact_storage(mut self, compact_storage: bool) -> Self
     where
         Self: Sized,
@@ -500,7 +521,7 @@ pub trait TableOptionsExt {
         self.set_speculative_retry(speculative_retry);
         self
     }
-    fn with_change_data_capture(mut self, cdc: bool) -> Self
+    fn with_change_data_capture(mut self, cdc: impl Into<ChangeDataCapture>) -> Self
     where
         Self: Sized,
     {
@@ -570,12 +591,40 @@ pub trait TableOptionsExt {
         self.set_read_repair(read_repair);
         self
     }
+    fn with_tombstone_gc(mut self, tombstone_gc: impl Into<TombstoneGc>) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_tombstone_gc(tombstone_gc);
+        self
+    }
+    fn with_per_partition_rate_limit(mut self, per_partition_rate_limit: PerPartitionRateLimit) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_per_partition_rate_limit(per_partition_rate_limit);
+        self
+    }
+    fn with_paxos_grace_seconds(mut self, paxos_grace_seconds: i32) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_paxos_grace_seconds(paxos_grace_seconds);
+        self
+    }
+    fn with_scylla_tags<K: Into<String>, V: Into<String>>(mut self, scylla_tags: BTreeMap<K, V>) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_scylla_tags(scylla_tags);
+        self
+    }
 
     fn get_compact_storage(&self) -> Option<&bool> {
-        self.table_opts().as_ref().map(|t| &t.compact_storage)
+        self.table_opts().map(|t| &t.compact_storage)
     }
     fn get_clustering_order(&self) -> Option<&Vec<ColumnOrder>> {
-        self.table_opts().as_ref().and_then(|t| t.clustering_order.as_ref())
+        self.table_opts().and_then(|t| t.clustering_order.as_ref())
     }
     fn get_comment(&self) -> Option<&String> {
         self.table_opts()
@@ -583,36 +632,68 @@ pub trait TableOptionsExt {
             .and_then(|t| t.comment.as_ref().map(|s| &s.value))
     }
     fn get_speculative_retry(&self) -> Option<&SpeculativeRetry> {
-        self.table_opts().as_ref().and_then(|t| t.speculative_retry.as_ref())
+        self.table_opts().and_then(|t| t.speculative_retry.as_ref())
     }
-    fn get_change_data_capture(&self) -> Option<bool> {
-        self.table_opts().as_ref().and_then(|t| t.change_data_capture)
+    fn get_change_data_capture(&self) -> Option<&ChangeDataCapture> {
+        self.table_opts().and_then(|t| t.change_data_capture.as_ref())
     }
     fn get_additional_write_policy(&self) -> Option<&SpeculativeRetry> {
-        self.table_opts().as_ref().and_then(|t| t.speculative_retry.as_ref())
+        self.table_opts().and_then(|t| t.speculative_retry.as_ref())
     }
     fn get_gc_grace_seconds(&self) -> Option<i32> {
-        self.table_opts().as_ref().and_then(|t| t.gc_grace_seconds)
+        self.table_opts().and_then(|t| t.gc_grace_seconds)
     }
     fn get_bloom_filter_fp_chance(&self) -> Option<f32> {
-        self.table_opts().as_ref().and_then(|t| t.bloom_filter_fp_chance)
+        self.table_opts().and_then(|t| t.bloom_filter_fp_chance)
     }
     fn get_default_time_to_live(&self) -> Option<i32> {
-        self.table_opts().as_ref().and_then(|t| t.default_time_to_live)
+        self.table_opts().and_then(|t| t.default_time_to_live)
     }
     fn get_compaction(&self) -> Option<&Compaction> {
-        self.table_opts().as_ref().and_then(|t| t.compaction.as_ref())
+        self.table_opts().and_then(|t| t.compaction.as_ref())
     }
     fn get_compression(&self) -> Option<&Compression> {
-        self.table_opts().as_ref().and_then(|t| t.compression.as_ref())
+        self.table_opts().and_then(|t| t.compression.as_ref())
     }
     fn get_caching(&self) -> Option<&Caching> {
-        self.table_opts().as_ref().and_then(|t| t.caching.as_ref())
+        self.table_opts().and_then(|t| t.caching.as_ref())
     }
     fn get_memtable_flush_period_in_ms(&self) -> Option<i32> {
-        self.table_opts().as_ref().and_then(|t| t.memtable_flush_period_in_ms)
+        self.table_opts().and_then(|t| t.memtable_flush_period_in_ms)
     }
     fn get_read_repair(&self) -> Option<bool> {
-        self.table_opts().as_ref().and_then(|t| t.read_repair)
+        self.table_opts().and_then(|t| t.read_repair)
+    }
+    fn get_tombstone_gc(&self) -> Option<&TombstoneGc> {
+        self.table_opts().and_then(|t| t.tombstone_gc.as_ref())
+    }
+    fn get_per_partition_rate_limit(&self) -> Option<&PerPartitionRateLimit> {
+        self.table_opts()
+            .as_ref()
+            .and_then(|t| t.per_partition_rate_limit.as_ref())
+    }
+    fn get_paxos_grace_seconds(&self) -> Option<i32> {
+        self.table_opts().and_then(|t| t.paxos_grace_seconds)
+    }
+    fn get_scylla_tags(&self) -> Option<&BTreeMap<String, String>> {
+        self.table_opts().and_then(|t| t.scylla_tags.as_ref())
+    }
+}
+
+/// Options which only apply to materialized views
+pub trait MaterializedViewOptionsExt: TableOptionsExt {
+    fn set_synchronous_updates(&mut self, synchronous_updates: bool) {
+        self.table_opts_mut().synchronous_updates.replace(synchronous_updates);
+    }
+
+    fn with_synchronous_updates(mut self, synchronous_updates: bool) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_synchronous_updates(synchronous_updates);
+        self
+    }
+    fn get_synchronous_updates(&self) -> Option<bool> {
+        self.table_opts().and_then(|t| t.synchronous_updates)
     }
 }
@@ -1,7 +1,8 @@
 use super::*;
 use std::collections::BTreeSet;
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedRoleStatement)]
 pub enum RoleStatement {
     Create(CreateRoleStatement),
@@ -72,7 +73,8 @@ impl Display for RoleStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, Ord, PartialOrd, Eq, ToTokens)]
+#[derive(ParseFromStr, Clone, Debug, Ord, PartialOrd, Eq, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RoleOpt {
     Password(LitStr),
     Login(bool),
@@ -194,7 +196,8 @@ pub trait RoleOptBuilderExt {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option))]
 #[parse_via(TaggedCreateRoleStatement)]
 pub struct CreateRoleStatement {
@@ -273,7 +276,7 @@ impl Display for CreateRoleStatement {
         write!(
             f,
             "CREATE ROLE{} {}{}",
-            if self.if_not_exists { "IF NOT EXISTS" } else { "" },
+            if self.if_not_exists { " IF NOT EXISTS" } else { "" },
             self.name,
             if let Some(ref opts) = self.options {
                 if !opts.is_empty() {
@@ -303,7 +306,8 @@ impl RoleOptBuilderExt for CreateRoleStatementBuilder {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(build_fn(validate = "Self::validate"))]
 #[parse_via(TaggedAlterRoleStatement)]
 pub struct AlterRoleStatement {
@@ -408,7 +412,8 @@ impl RoleOptBuilderExt for AlterRoleStatementBuilder {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropRoleStatement)]
 pub struct DropRoleStatement {
     #[builder(setter(name = "set_if_exists"), default)]
@@ -480,7 +485,8 @@ impl Display for DropRoleStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(into))]
 #[parse_via(TaggedGrantRoleStatement)]
 pub struct GrantRoleStatement {
@@ -526,7 +532,8 @@ impl Display for GrantRoleStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(into))]
 #[parse_via(TaggedRevokeRoleStatement)]
 pub struct RevokeRoleStatement {
@@ -572,7 +579,8 @@ impl Display for RevokeRoleStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option))]
 #[parse_via(TaggedListRolesStatement)]
 pub struct ListRolesStatement {
@@ -642,7 +650,8 @@ impl Display for ListRolesStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Permission {
     Create,
     Alter,
@@ -690,7 +699,8 @@ impl Display for Permission {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PermissionKind {
     All,
     One(Permission),
@@ -722,7 +732,8 @@ impl From<Permission> for PermissionKind {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Resource {
     AllKeyspaces,
     Keyspace(Name),
@@ -834,7 +845,8 @@ impl Display for Resource {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedPermissionStatement)]
 pub enum PermissionStatement {
     Grant(GrantPermissionStatement),
@@ -886,7 +898,8 @@ impl Display for PermissionStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(into))]
 #[parse_via(TaggedGrantPermissionStatement)]
 pub struct GrantPermissionStatement {
@@ -937,7 +950,8 @@ impl Display for GrantPermissionStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(into))]
 #[parse_via(TaggedRevokePermissionStatement)]
 pub struct RevokePermissionStatement {
@@ -988,7 +1002,8 @@ impl Display for RevokePermissionStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option))]
 #[parse_via(TaggedListPermissionsStatement)]
 pub struct ListPermissionsStatement {
@@ -1091,7 +1106,8 @@ impl Display for ListPermissionsStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedUserStatement)]
 pub enum UserStatement {
     Create(CreateUserStatement),
@@ -1149,7 +1165,8 @@ impl Display for UserStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option))]
 #[parse_via(TaggedCreateUserStatement)]
 pub struct CreateUserStatement {
@@ -1253,7 +1270,8 @@ impl Display for CreateUserStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(setter(strip_option))]
 #[parse_via(TaggedAlterUserStatement)]
 pub struct AlterUserStatement {
@@ -1337,7 +1355,8 @@ impl Display for AlterUserStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropUserStatement)]
 pub struct DropUserStatement {
     #[builder(setter(name = "set_if_exists"), default)]
@@ -1398,7 +1417,8 @@ impl Display for DropUserStatement {
     }
 }
 
-#[derive(ParseFromStr, Copy, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Copy, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListUsersStatement;
 
 impl Parse for ListUsersStatement {
@@ -1415,7 +1435,8 @@ impl Display for ListUsersStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedUserDefinedTypeStatement)]
 pub enum UserDefinedTypeStatement {
     Create(CreateUserDefinedTypeStatement),
@@ -1469,7 +1490,8 @@ impl Display for UserDefinedTypeStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(build_fn(validate = "Self::validate"))]
 #[parse_via(TaggedCreateUserDefinedTypeStatement)]
 pub struct CreateUserDefinedTypeStatement {
@@ -1553,7 +1575,8 @@ impl Display for CreateUserDefinedTypeStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[builder(build_fn(validate = "Self::validate"))]
 #[parse_via(TaggedAlterUserDefinedTypeStatement)]
 pub struct AlterUserDefinedTypeStatement {
@@ -1633,7 +1656,8 @@ impl Display for AlterUserDefinedTypeStatement {
     }
 }
 
-#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AlterTypeInstruction {
     Add(FieldDefinition),
     Rename(Vec<(Name, Name)>),
@@ -1682,7 +1706,8 @@ impl Display for AlterTypeInstruction {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropUserDefinedTypeStatement)]
 pub struct DropUserDefinedTypeStatement {
     #[builder(setter(name = "set_if_exists"), default)]
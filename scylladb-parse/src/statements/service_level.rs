@@ -0,0 +1,739 @@
+use super::*;
+use crate::DurationLiteral;
+use std::collections::BTreeSet;
+
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[parse_via(TaggedServiceLevelStatement)]
+pub enum ServiceLevelStatement {
+    Create(CreateServiceLevelStatement),
+    Alter(AlterServiceLevelStatement),
+    Drop(DropServiceLevelStatement),
+    List(ListServiceLevelsStatement),
+    Attach(AttachServiceLevelStatement),
+    Detach(DetachServiceLevelStatement),
+    ListAttached(ListAttachedServiceLevelsStatement),
+}
+
+impl TryFrom<TaggedServiceLevelStatement> for ServiceLevelStatement {
+    type Error = anyhow::Error;
+
+    fn try_from(value: TaggedServiceLevelStatement) -> Result<Self, Self::Error> {
+        Ok(match value {
+            TaggedServiceLevelStatement::Create(v) => ServiceLevelStatement::Create(v.try_into()?),
+            TaggedServiceLevelStatement::Alter(v) => ServiceLevelStatement::Alter(v.try_into()?),
+            TaggedServiceLevelStatement::Drop(v) => ServiceLevelStatement::Drop(v.try_into()?),
+            TaggedServiceLevelStatement::List(v) => ServiceLevelStatement::List(v.try_into()?),
+            TaggedServiceLevelStatement::Attach(v) => ServiceLevelStatement::Attach(v.try_into()?),
+            TaggedServiceLevelStatement::Detach(v) => ServiceLevelStatement::Detach(v.try_into()?),
+            TaggedServiceLevelStatement::ListAttached(v) => ServiceLevelStatement::ListAttached(v.try_into()?),
+        })
+    }
+}
+
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[tokenize_as(ServiceLevelStatement)]
+pub enum TaggedServiceLevelStatement {
+    Create(TaggedCreateServiceLevelStatement),
+    Alter(TaggedAlterServiceLevelStatement),
+    Drop(TaggedDropServiceLevelStatement),
+    List(TaggedListServiceLevelsStatement),
+    Attach(TaggedAttachServiceLevelStatement),
+    Detach(TaggedDetachServiceLevelStatement),
+    ListAttached(TaggedListAttachedServiceLevelsStatement),
+}
+
+impl Parse for TaggedServiceLevelStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        Ok(if s.check::<CREATE>() {
+            Self::Create(s.parse()?)
+        } else if s.check::<ALTER>() {
+            Self::Alter(s.parse()?)
+        } else if s.check::<DROP>() {
+            Self::Drop(s.parse()?)
+        } else if s.check::<ATTACH>() {
+            Self::Attach(s.parse()?)
+        } else if s.check::<DETACH>() {
+            Self::Detach(s.parse()?)
+        } else if s.check::<(LIST, ATTACHED)>() || s.check::<(LIST, ALL, ATTACHED)>() {
+            Self::ListAttached(s.parse()?)
+        } else if s.check::<LIST>() {
+            Self::List(s.parse()?)
+        } else {
+            anyhow::bail!("Expected a service level statement, found {}", s.info())
+        })
+    }
+}
+
+impl Display for ServiceLevelStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Create(stmt) => stmt.fmt(f),
+            Self::Alter(stmt) => stmt.fmt(f),
+            Self::Drop(stmt) => stmt.fmt(f),
+            Self::List(stmt) => stmt.fmt(f),
+            Self::Attach(stmt) => stmt.fmt(f),
+            Self::Detach(stmt) => stmt.fmt(f),
+            Self::ListAttached(stmt) => stmt.fmt(f),
+        }
+    }
+}
+
+#[derive(ParseFromStr, Copy, Clone, Debug, ToTokens, PartialEq, Eq, PartialOrd, Ord, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WorkloadType {
+    Unspecified,
+    Interactive,
+    Batch,
+}
+
+impl Parse for WorkloadType {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        let workload = s.parse::<LitStr>()?;
+        Ok(match workload.value.to_lowercase().as_str() {
+            "unspecified" => Self::Unspecified,
+            "interactive" => Self::Interactive,
+            "batch" => Self::Batch,
+            _ => anyhow::bail!("Invalid workload type: {}", workload),
+        })
+    }
+}
+
+impl Display for WorkloadType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unspecified => write!(f, "'unspecified'"),
+            Self::Interactive => write!(f, "'interactive'"),
+            Self::Batch => write!(f, "'batch'"),
+        }
+    }
+}
+
+/// An option of a service level. Options are unique by kind, so sets of them hold at most one of each.
+#[derive(ParseFromStr, Clone, Debug, ToTokens, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ServiceLevelOpt {
+    /// The timeout for requests of the service level, or `null` to remove it
+    Timeout(Option<DurationLiteral>),
+    WorkloadType(WorkloadType),
+    Shares(i32),
+}
+
+impl ServiceLevelOpt {
+    pub fn timeout(timeout: impl Into<Option<DurationLiteral>>) -> Self {
+        Self::Timeout(timeout.into())
+    }
+
+    pub fn workload_type(workload_type: WorkloadType) -> Self {
+        Self::WorkloadType(workload_type)
+    }
+
+    pub fn shares(shares: i32) -> Self {
+        Self::Shares(shares)
+    }
+
+    fn index(&self) -> u8 {
+        match self {
+            Self::Timeout(_) => 0,
+            Self::WorkloadType(_) => 1,
+            Self::Shares(_) => 2,
+        }
+    }
+}
+
+impl PartialEq for ServiceLevelOpt {
+    fn eq(&self, other: &Self) -> bool {
+        self.index() == other.index()
+    }
+}
+
+impl Eq for ServiceLevelOpt {}
+
+impl PartialOrd for ServiceLevelOpt {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ServiceLevelOpt {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.index().cmp(&other.index())
+    }
+}
+
+impl Parse for ServiceLevelOpt {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        Ok(if s.parse::<Option<(TIMEOUT, Equals)>>()?.is_some() {
+            if s.parse::<Option<NULL>>()?.is_some() {
+                Self::Timeout(None)
+            } else {
+                Self::Timeout(Some(s.parse()?))
+            }
+        } else if let Some(w) = s.parse_from::<If<(WORKLOAD_TYPE, Equals), WorkloadType>>()? {
+            Self::WorkloadType(w)
+        } else if let Some(n) = s.parse_from::<If<(SHARES, Equals), i32>>()? {
+            Self::Shares(n)
+        } else {
+            anyhow::bail!("Expected a service level option, found {}", s.info())
+        })
+    }
+}
+
+impl Display for ServiceLevelOpt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout(Some(d)) => write!(f, "timeout = {}", d),
+            Self::Timeout(None) => write!(f, "timeout = null"),
+            Self::WorkloadType(w) => write!(f, "workload_type = {}", w),
+            Self::Shares(n) => write!(f, "shares = {}", n),
+        }
+    }
+}
+
+pub trait ServiceLevelOptBuilderExt {
+    fn service_level_opts(&mut self) -> &mut BTreeSet<ServiceLevelOpt>;
+
+    fn timeout(&mut self, timeout: impl Into<Option<DurationLiteral>>) -> &mut Self {
+        self.service_level_opts()
+            .replace(ServiceLevelOpt::Timeout(timeout.into()));
+        self
+    }
+
+    fn workload_type(&mut self, workload_type: WorkloadType) -> &mut Self {
+        self.service_level_opts()
+            .replace(ServiceLevelOpt::WorkloadType(workload_type));
+        self
+    }
+
+    fn shares(&mut self, shares: i32) -> &mut Self {
+        self.service_level_opts().replace(ServiceLevelOpt::Shares(shares));
+        self
+    }
+}
+
+fn parse_service_level_opts(s: &mut StatementStream<'_>) -> anyhow::Result<Tag<BTreeSet<ServiceLevelOpt>>> {
+    Ok(match s.parse_from::<Tag<List<ServiceLevelOpt, AND>>>()? {
+        Tag::Value(o) => {
+            let mut opts = BTreeSet::new();
+            for opt in o {
+                if opts.contains(&opt) {
+                    anyhow::bail!("Duplicate option: {}", opt);
+                } else {
+                    opts.insert(opt);
+                }
+            }
+            Tag::Value(opts)
+        }
+        Tag::Tag(t) => Tag::Tag(t),
+    })
+}
+
+fn fmt_service_level_opts(opts: &BTreeSet<ServiceLevelOpt>) -> String {
+    opts.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(" AND ")
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(setter(strip_option))]
+#[parse_via(TaggedCreateServiceLevelStatement)]
+pub struct CreateServiceLevelStatement {
+    #[builder(setter(name = "set_if_not_exists"), default)]
+    pub if_not_exists: bool,
+    #[builder(setter(into))]
+    pub name: Name,
+    #[builder(default)]
+    pub options: Option<BTreeSet<ServiceLevelOpt>>,
+}
+
+impl TryFrom<TaggedCreateServiceLevelStatement> for CreateServiceLevelStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedCreateServiceLevelStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            if_not_exists: value.if_not_exists,
+            name: value.name.into_value()?,
+            options: value.options.map(|v| v.into_value()).transpose()?,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[builder(setter(strip_option))]
+#[tokenize_as(CreateServiceLevelStatement)]
+pub struct TaggedCreateServiceLevelStatement {
+    #[builder(setter(name = "set_if_not_exists"), default)]
+    pub if_not_exists: bool,
+    pub name: Tag<Name>,
+    #[builder(default)]
+    pub options: Option<Tag<BTreeSet<ServiceLevelOpt>>>,
+}
+
+impl CreateServiceLevelStatementBuilder {
+    /// Set IF NOT EXISTS on the statement.
+    /// To undo this, use `set_if_not_exists(false)`.
+    pub fn if_not_exists(&mut self) -> &mut Self {
+        self.if_not_exists.replace(true);
+        self
+    }
+}
+
+impl ServiceLevelOptBuilderExt for CreateServiceLevelStatementBuilder {
+    fn service_level_opts(&mut self) -> &mut BTreeSet<ServiceLevelOpt> {
+        self.options
+            .get_or_insert_with(|| Some(BTreeSet::new()))
+            .get_or_insert_with(BTreeSet::new)
+    }
+}
+
+impl Parse for TaggedCreateServiceLevelStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        s.parse::<(CREATE, SERVICE_LEVEL)>()?;
+        let mut res = TaggedCreateServiceLevelStatementBuilder::default();
+        res.set_if_not_exists(s.parse::<Option<(IF, NOT, EXISTS)>>()?.is_some())
+            .name(s.parse()?);
+        if s.parse::<Option<WITH>>()?.is_some() {
+            res.options(parse_service_level_opts(s)?);
+        }
+        s.parse::<Option<Semicolon>>()?;
+        res.build()
+            .map_err(|e| anyhow::anyhow!("Invalid CREATE SERVICE_LEVEL statement: {}", e))
+    }
+}
+
+impl Display for CreateServiceLevelStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CREATE SERVICE_LEVEL{} {}{}",
+            if self.if_not_exists { " IF NOT EXISTS" } else { "" },
+            self.name,
+            match self.options {
+                Some(ref opts) if !opts.is_empty() => format!(" WITH {}", fmt_service_level_opts(opts)),
+                _ => String::new(),
+            }
+        )
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(build_fn(validate = "Self::validate"))]
+#[parse_via(TaggedAlterServiceLevelStatement)]
+pub struct AlterServiceLevelStatement {
+    #[builder(setter(into))]
+    pub name: Name,
+    pub options: BTreeSet<ServiceLevelOpt>,
+}
+
+impl TryFrom<TaggedAlterServiceLevelStatement> for AlterServiceLevelStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedAlterServiceLevelStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: value.name.into_value()?,
+            options: value.options.into_value()?,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[builder(build_fn(validate = "Self::validate"))]
+#[tokenize_as(AlterServiceLevelStatement)]
+pub struct TaggedAlterServiceLevelStatement {
+    pub name: Tag<Name>,
+    pub options: Tag<BTreeSet<ServiceLevelOpt>>,
+}
+
+impl AlterServiceLevelStatementBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if self.options.as_ref().map(|s| s.is_empty()).unwrap_or(false) {
+            return Err("Service level options cannot be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl TaggedAlterServiceLevelStatementBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Tag::Value(v)) = &self.options {
+            if v.is_empty() {
+                return Err("Service level options cannot be empty".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ServiceLevelOptBuilderExt for AlterServiceLevelStatementBuilder {
+    fn service_level_opts(&mut self) -> &mut BTreeSet<ServiceLevelOpt> {
+        self.options.get_or_insert_with(BTreeSet::new)
+    }
+}
+
+impl Parse for TaggedAlterServiceLevelStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        s.parse::<(ALTER, SERVICE_LEVEL)>()?;
+        let mut res = TaggedAlterServiceLevelStatementBuilder::default();
+        res.name(s.parse()?);
+        s.parse::<WITH>()?;
+        res.options(parse_service_level_opts(s)?);
+        s.parse::<Option<Semicolon>>()?;
+        res.build()
+            .map_err(|e| anyhow::anyhow!("Invalid ALTER SERVICE_LEVEL statement: {}", e))
+    }
+}
+
+impl Display for AlterServiceLevelStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ALTER SERVICE_LEVEL {} WITH {}",
+            self.name,
+            fmt_service_level_opts(&self.options)
+        )
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[parse_via(TaggedDropServiceLevelStatement)]
+pub struct DropServiceLevelStatement {
+    #[builder(setter(name = "set_if_exists"), default)]
+    pub if_exists: bool,
+    #[builder(setter(into))]
+    pub name: Name,
+}
+
+impl TryFrom<TaggedDropServiceLevelStatement> for DropServiceLevelStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedDropServiceLevelStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            if_exists: value.if_exists,
+            name: value.name.into_value()?,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[tokenize_as(DropServiceLevelStatement)]
+pub struct TaggedDropServiceLevelStatement {
+    #[builder(setter(name = "set_if_exists"), default)]
+    pub if_exists: bool,
+    pub name: Tag<Name>,
+}
+
+impl DropServiceLevelStatementBuilder {
+    /// Set IF EXISTS on the statement.
+    /// To undo this, use `set_if_exists(false)`.
+    pub fn if_exists(&mut self) -> &mut Self {
+        self.if_exists.replace(true);
+        self
+    }
+}
+
+impl Parse for TaggedDropServiceLevelStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        s.parse::<(DROP, SERVICE_LEVEL)>()?;
+        let mut res = TaggedDropServiceLevelStatementBuilder::default();
+        res.set_if_exists(s.parse::<Option<(IF, EXISTS)>>()?.is_some())
+            .name(s.parse()?);
+        s.parse::<Option<Semicolon>>()?;
+        res.build()
+            .map_err(|e| anyhow::anyhow!("Invalid DROP SERVICE_LEVEL statement: {}", e))
+    }
+}
+
+impl Display for DropServiceLevelStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DROP SERVICE_LEVEL{} {}",
+            if self.if_exists { " IF EXISTS" } else { "" },
+            self.name
+        )
+    }
+}
+
+/// Lists a single service level, or all of them if no name is specified.
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(setter(strip_option))]
+#[parse_via(TaggedListServiceLevelsStatement)]
+pub struct ListServiceLevelsStatement {
+    #[builder(setter(into), default)]
+    pub name: Option<Name>,
+}
+
+impl TryFrom<TaggedListServiceLevelsStatement> for ListServiceLevelsStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedListServiceLevelsStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: value.name.map(|v| v.into_value()).transpose()?,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[builder(setter(strip_option))]
+#[tokenize_as(ListServiceLevelsStatement)]
+pub struct TaggedListServiceLevelsStatement {
+    #[builder(default)]
+    pub name: Option<Tag<Name>>,
+}
+
+impl Parse for TaggedListServiceLevelsStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        let mut res = TaggedListServiceLevelsStatementBuilder::default();
+        if s.parse::<Option<(LIST, ALL, SERVICE_LEVELS)>>()?.is_none() {
+            res.name(s.parse::<(LIST, SERVICE_LEVEL, _)>()?.2);
+        }
+        s.parse::<Option<Semicolon>>()?;
+        res.build()
+            .map_err(|e| anyhow::anyhow!("Invalid LIST SERVICE_LEVEL statement: {}", e))
+    }
+}
+
+impl Display for ListServiceLevelsStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "LIST SERVICE_LEVEL {}", name),
+            None => write!(f, "LIST ALL SERVICE_LEVELS"),
+        }
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(setter(into))]
+#[parse_via(TaggedAttachServiceLevelStatement)]
+pub struct AttachServiceLevelStatement {
+    pub name: Name,
+    pub to: Name,
+}
+
+impl TryFrom<TaggedAttachServiceLevelStatement> for AttachServiceLevelStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedAttachServiceLevelStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: value.name.into_value()?,
+            to: value.to.into_value()?,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[tokenize_as(AttachServiceLevelStatement)]
+pub struct TaggedAttachServiceLevelStatement {
+    pub name: Tag<Name>,
+    pub to: Tag<Name>,
+}
+
+impl Parse for TaggedAttachServiceLevelStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        s.parse::<(ATTACH, SERVICE_LEVEL)>()?;
+        let mut res = TaggedAttachServiceLevelStatementBuilder::default();
+        res.name(s.parse()?);
+        s.parse::<TO>()?;
+        res.to(s.parse()?);
+        s.parse::<Option<Semicolon>>()?;
+        res.build()
+            .map_err(|e| anyhow::anyhow!("Invalid ATTACH SERVICE_LEVEL statement: {}", e))
+    }
+}
+
+impl Display for AttachServiceLevelStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ATTACH SERVICE_LEVEL {} TO {}", self.name, self.to)
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(setter(into))]
+#[parse_via(TaggedDetachServiceLevelStatement)]
+pub struct DetachServiceLevelStatement {
+    pub from: Name,
+}
+
+impl TryFrom<TaggedDetachServiceLevelStatement> for DetachServiceLevelStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedDetachServiceLevelStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            from: value.from.into_value()?,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[tokenize_as(DetachServiceLevelStatement)]
+pub struct TaggedDetachServiceLevelStatement {
+    pub from: Tag<Name>,
+}
+
+impl Parse for TaggedDetachServiceLevelStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        s.parse::<(DETACH, SERVICE_LEVEL, FROM)>()?;
+        let mut res = TaggedDetachServiceLevelStatementBuilder::default();
+        res.from(s.parse()?);
+        s.parse::<Option<Semicolon>>()?;
+        res.build()
+            .map_err(|e| anyhow::anyhow!("Invalid DETACH SERVICE_LEVEL statement: {}", e))
+    }
+}
+
+impl Display for DetachServiceLevelStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DETACH SERVICE_LEVEL FROM {}", self.from)
+    }
+}
+
+/// Lists the service level attached to a role, or all attachments if no role is specified.
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[builder(setter(strip_option))]
+#[parse_via(TaggedListAttachedServiceLevelsStatement)]
+pub struct ListAttachedServiceLevelsStatement {
+    #[builder(setter(into), default)]
+    pub of: Option<Name>,
+}
+
+impl TryFrom<TaggedListAttachedServiceLevelsStatement> for ListAttachedServiceLevelsStatement {
+    type Error = anyhow::Error;
+    fn try_from(value: TaggedListAttachedServiceLevelsStatement) -> Result<Self, Self::Error> {
+        Ok(Self {
+            of: value.of.map(|v| v.into_value()).transpose()?,
+        })
+    }
+}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[builder(setter(strip_option))]
+#[tokenize_as(ListAttachedServiceLevelsStatement)]
+pub struct TaggedListAttachedServiceLevelsStatement {
+    #[builder(default)]
+    pub of: Option<Tag<Name>>,
+}
+
+impl Parse for TaggedListAttachedServiceLevelsStatement {
+    type Output = Self;
+    fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
+        let mut res = TaggedListAttachedServiceLevelsStatementBuilder::default();
+        if s.parse::<Option<(LIST, ALL, ATTACHED, SERVICE_LEVELS)>>()?.is_none() {
+            res.of(s.parse::<(LIST, ATTACHED, SERVICE_LEVEL, OF, _)>()?.4);
+        }
+        s.parse::<Option<Semicolon>>()?;
+        res.build()
+            .map_err(|e| anyhow::anyhow!("Invalid LIST ATTACHED SERVICE_LEVEL statement: {}", e))
+    }
+}
+
+impl Display for ListAttachedServiceLevelsStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.of {
+            Some(of) => write!(f, "LIST ATTACHED SERVICE_LEVEL OF {}", of),
+            None => write!(f, "LIST ALL ATTACHED SERVICE_LEVELS"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_create_service_level() {
+        let mut builder = CreateServiceLevelStatementBuilder::default();
+        assert!(builder.build().is_err());
+        builder.name("test_level");
+        let statement = builder.build().unwrap().to_string();
+        assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+        builder
+            .if_not_exists()
+            .timeout(DurationLiteral::default().ms(500))
+            .workload_type(WorkloadType::Interactive)
+            .shares(100);
+        let statement = builder.build().unwrap().to_string();
+        assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+        assert!("CREATE SERVICE_LEVEL test_level WITH shares = 1 AND shares = 2"
+            .parse::<CreateServiceLevelStatement>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_alter_service_level() {
+        let mut builder = AlterServiceLevelStatementBuilder::default();
+        builder.name("test_level");
+        assert!(builder.build().is_err());
+        builder.options(BTreeSet::new());
+        assert!(builder.build().is_err());
+        builder.timeout(None);
+        let statement = builder.build().unwrap().to_string();
+        assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+        builder.workload_type(WorkloadType::Batch);
+        let statement = builder.build().unwrap().to_string();
+        assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+    }
+
+    #[test]
+    fn test_parse_other_service_level_statements() {
+        let mut builder = DropServiceLevelStatementBuilder::default();
+        builder.name("test_level");
+        let statement = builder.build().unwrap().to_string();
+        assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+        builder.if_exists();
+        let statement = builder.build().unwrap().to_string();
+        assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+
+        for statement in [
+            ListServiceLevelsStatementBuilder::default().build().unwrap().into(),
+            ListServiceLevelsStatementBuilder::default()
+                .name("test_level")
+                .build()
+                .unwrap()
+                .into(),
+            AttachServiceLevelStatementBuilder::default()
+                .name("test_level")
+                .to("test_role")
+                .build()
+                .unwrap()
+                .into(),
+            DetachServiceLevelStatementBuilder::default()
+                .from("test_role")
+                .build()
+                .unwrap()
+                .into(),
+            ListAttachedServiceLevelsStatementBuilder::default()
+                .build()
+                .unwrap()
+                .into(),
+            ListAttachedServiceLevelsStatementBuilder::default()
+                .of("test_role")
+                .build()
+                .unwrap()
+                .into(),
+        ] {
+            let statement: Statement = statement;
+            assert_eq!(statement, statement.to_string().parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words() {
+        for statement in [
+            "LIST SERVICE_LEVELS",
+            "LIST SERVICE_LEVELS test_level",
+            "LIST ATTACHED SERVICE_LEVELS OF test_role",
+            "ATTACHED SERVICE_LEVEL test_level TO test_role",
+            "DROP SERVICE_LEVELS test_level",
+        ] {
+            assert!(statement.parse::<Statement>().is_err(), "{}", statement);
+        }
+    }
+}
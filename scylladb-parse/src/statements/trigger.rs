@@ -1,6 +1,7 @@
 use super::*;
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedTriggerStatement)]
 pub enum TriggerStatement {
     Create(CreateTriggerStatement),
@@ -47,7 +48,8 @@ impl Display for TriggerStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedCreateTriggerStatement)]
 pub struct CreateTriggerStatement {
     #[builder(setter(name = "set_if_not_exists"), default)]
@@ -121,7 +123,8 @@ impl Display for CreateTriggerStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropTriggerStatement)]
 pub struct DropTriggerStatement {
     #[builder(setter(name = "set_if_exists"), default)]
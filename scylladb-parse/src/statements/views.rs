@@ -1,7 +1,8 @@
 use super::*;
 use crate::PrimaryKey;
 
-#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq)]
+#[derive(ParseFromStr, Clone, Debug, TryInto, From, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedMaterializedViewStatement)]
 pub enum MaterializedViewStatement {
     Create(CreateMaterializedViewStatement),
@@ -53,7 +54,8 @@ impl Display for MaterializedViewStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedCreateMaterializedViewStatement)]
 #[builder(setter(strip_option))]
 pub struct CreateMaterializedViewStatement {
@@ -61,11 +63,12 @@ pub struct CreateMaterializedViewStatement {
     pub if_not_exists: bool,
     #[builder(setter(into))]
     pub name: KeyspaceQualifiedName,
-    pub select_statement: SelectStatement,
+    #[builder(setter(into))]
+    pub select_statement: Box<SelectStatement>,
     #[builder(setter(into))]
     pub primary_key: PrimaryKey,
-    #[builder(default)]
-    pub table_opts: Option<TableOpts>,
+    #[builder(setter(into), default)]
+    pub table_opts: Option<Box<TableOpts>>,
 }
 
 impl TryFrom<TaggedCreateMaterializedViewStatement> for CreateMaterializedViewStatement {
@@ -74,9 +77,9 @@ impl TryFrom<TaggedCreateMaterializedViewStatement> for CreateMaterializedViewSt
         Ok(Self {
             if_not_exists: value.if_not_exists,
             name: value.name.try_into()?,
-            select_statement: value.select_statement.into_value()?.try_into()?,
+            select_statement: Box::new(value.select_statement.into_value()?.try_into()?),
             primary_key: value.primary_key.into_value()?,
-            table_opts: value.table_opts.map(|v| v.into_value()).transpose()?,
+            table_opts: value.table_opts.map(|v| v.into_value()).transpose()?.map(Box::new),
         })
     }
 }
@@ -88,10 +91,11 @@ pub struct TaggedCreateMaterializedViewStatement {
     #[builder(setter(name = "set_if_not_exists"), default)]
     pub if_not_exists: bool,
     pub name: TaggedKeyspaceQualifiedName,
-    pub select_statement: Tag<TaggedSelectStatement>,
+    #[builder(setter(into))]
+    pub select_statement: Box<Tag<TaggedSelectStatement>>,
     pub primary_key: Tag<PrimaryKey>,
-    #[builder(default)]
-    pub table_opts: Option<Tag<TableOpts>>,
+    #[builder(setter(into), default)]
+    pub table_opts: Option<Box<Tag<TableOpts>>>,
 }
 
 impl CreateMaterializedViewStatementBuilder {
@@ -110,7 +114,7 @@ impl Parse for TaggedCreateMaterializedViewStatement {
         let mut res = TaggedCreateMaterializedViewStatementBuilder::default();
         res.set_if_not_exists(s.parse::<Option<(IF, NOT, EXISTS)>>()?.is_some())
             .name(s.parse()?)
-            .select_statement(s.parse::<(AS, _)>()?.1)
+            .select_statement(s.parse::<(AS, Tag<TaggedSelectStatement>)>()?.1)
             .primary_key(s.parse_from::<((PRIMARY, KEY), Parens<Tag<PrimaryKey>>)>()?.1);
         if let Some(p) = s.parse_from::<If<WITH, Tag<TableOpts>>>()? {
             res.table_opts(p);
@@ -139,12 +143,26 @@ impl Display for CreateMaterializedViewStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq)]
+impl TableOptionsExt for CreateMaterializedViewStatement {
+    fn table_opts(&self) -> Option<&TableOpts> {
+        self.table_opts.as_deref()
+    }
+
+    fn table_opts_mut(&mut self) -> &mut TableOpts {
+        self.table_opts.get_or_insert_with(Default::default)
+    }
+}
+
+impl MaterializedViewOptionsExt for CreateMaterializedViewStatement {}
+
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedAlterMaterializedViewStatement)]
 pub struct AlterMaterializedViewStatement {
     #[builder(setter(into))]
     pub name: KeyspaceQualifiedName,
-    pub table_opts: TableOpts,
+    #[builder(setter(into))]
+    pub table_opts: Box<TableOpts>,
 }
 
 impl TryFrom<TaggedAlterMaterializedViewStatement> for AlterMaterializedViewStatement {
@@ -152,7 +170,7 @@ impl TryFrom<TaggedAlterMaterializedViewStatement> for AlterMaterializedViewStat
     fn try_from(value: TaggedAlterMaterializedViewStatement) -> Result<Self, Self::Error> {
         Ok(Self {
             name: value.name.try_into()?,
-            table_opts: value.table_opts.into_value()?,
+            table_opts: Box::new(value.table_opts.into_value()?),
         })
     }
 }
@@ -161,7 +179,8 @@ impl TryFrom<TaggedAlterMaterializedViewStatement> for AlterMaterializedViewStat
 #[tokenize_as(AlterMaterializedViewStatement)]
 pub struct TaggedAlterMaterializedViewStatement {
     pub name: TaggedKeyspaceQualifiedName,
-    pub table_opts: Tag<TableOpts>,
+    #[builder(setter(into))]
+    pub table_opts: Box<Tag<TableOpts>>,
 }
 
 impl Parse for TaggedAlterMaterializedViewStatement {
@@ -169,7 +188,7 @@ impl Parse for TaggedAlterMaterializedViewStatement {
     fn parse(s: &mut StatementStream<'_>) -> anyhow::Result<Self::Output> {
         s.parse::<(ALTER, MATERIALIZED, VIEW)>()?;
         let mut res = TaggedAlterMaterializedViewStatementBuilder::default();
-        res.name(s.parse()?).table_opts(s.parse::<(WITH, _)>()?.1);
+        res.name(s.parse()?).table_opts(s.parse::<(WITH, Tag<TableOpts>)>()?.1);
         s.parse::<Option<Semicolon>>()?;
         Ok(res
             .build()
@@ -183,7 +202,8 @@ impl Display for AlterMaterializedViewStatement {
     }
 }
 
-#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq)]
+#[derive(ParseFromStr, Builder, Clone, Debug, ToTokens, PartialEq, Eq, Visitable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[parse_via(TaggedDropMaterializedViewStatement)]
 pub struct DropMaterializedViewStatement {
     #[builder(setter(name = "set_if_exists"), default)]
@@ -280,6 +300,9 @@ mod test {
         builder.primary_key(PrimaryKey::partition_key("column_1").clustering_columns(vec!["column 2", "column_3"]));
         let statement = builder.build().unwrap().to_string();
         assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+        let statement = builder.build().unwrap().with_synchronous_updates(true);
+        assert_eq!(statement.get_synchronous_updates(), Some(true));
+        assert_eq!(statement, statement.to_string().parse().unwrap());
     }
 
     #[test]
@@ -295,6 +318,14 @@ mod test {
         );
         let statement = builder.build().unwrap().to_string();
         assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
+        builder.table_opts(
+            crate::TableOptsBuilder::default()
+                .synchronous_updates(false)
+                .build()
+                .unwrap(),
+        );
+        let statement = builder.build().unwrap().to_string();
+        assert_eq!(builder.build().unwrap(), statement.parse().unwrap());
     }
 
     #[test]
@@ -0,0 +1,1297 @@
+use super::{
+    diff::same_type,
+    schema::ident,
+    ArithmeticOp,
+    Assignment,
+    BatchKind,
+    BatchStatement,
+    CollectionType,
+    CollectionTypeLiteral,
+    ColumnKind,
+    ColumnSchema,
+    Condition,
+    Constant,
+    CqlType,
+    DataManipulationStatement,
+    DeleteStatement,
+    FunctionCatalog,
+    IfClause,
+    InsertKind,
+    InsertStatement,
+    KeyspaceQualifiedName,
+    KeyspaceSchema,
+    Limit,
+    Literal,
+    ModificationStatement,
+    Name,
+    NativeType,
+    Operator,
+    Order,
+    Relation,
+    Schema,
+    SelectClause,
+    SelectStatement,
+    Selector,
+    SelectorKind,
+    SimpleSelection,
+    Statement,
+    TableSchema,
+    Term,
+    UpdateParameter,
+    UpdateStatement,
+    WhereClause,
+};
+use std::fmt::{
+    Display,
+    Formatter,
+};
+
+/// The maximum TTL accepted by the database, in seconds (20 years)
+const MAX_TTL: i32 = 630_720_000;
+
+/// A semantic problem found by validating a statement against a schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Diagnostic {
+    UnknownTable(KeyspaceQualifiedName),
+    ViewModification(KeyspaceQualifiedName),
+    UnknownColumn {
+        table: Name,
+        column: Name,
+    },
+    UnknownField {
+        column: Name,
+        field: Name,
+    },
+    DuplicateColumn(Name),
+    TypeMismatch {
+        column: Name,
+        expected: CqlType,
+        term: Term,
+    },
+    MissingPrimaryKeyColumn(Name),
+    PrimaryKeyAssignment(Name),
+    InvalidAssignment(String),
+    InvalidRestriction {
+        column: Name,
+        reason: String,
+    },
+    RequiresAllowFiltering(String),
+    InvalidSelection(String),
+    InvalidOrderBy(String),
+    InvalidGroupBy(String),
+    InvalidCounterOperation(String),
+    InvalidCondition(String),
+    InvalidUsing(String),
+    InvalidFunctionCall(String),
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownTable(t) => write!(f, "Unknown table {}", t),
+            Self::ViewModification(t) => write!(f, "Cannot directly modify materialized view {}", t),
+            Self::UnknownColumn { table, column } => write!(f, "Unknown column {} in table {}", column, table),
+            Self::UnknownField { column, field } => write!(f, "Unknown field {} of column {}", field, column),
+            Self::DuplicateColumn(c) => write!(f, "Column {} is specified more than once", c),
+            Self::TypeMismatch { column, expected, term } => {
+                write!(f, "Invalid value {} for column {} of type {}", term, column, expected)
+            }
+            Self::MissingPrimaryKeyColumn(c) => write!(f, "Missing value for primary key column {}", c),
+            Self::PrimaryKeyAssignment(c) => write!(f, "Primary key column {} cannot be updated", c),
+            Self::InvalidAssignment(r) => write!(f, "Invalid assignment: {}", r),
+            Self::InvalidRestriction { column, reason } => write!(f, "Invalid restriction on {}: {}", column, reason),
+            Self::RequiresAllowFiltering(r) => write!(f, "Query requires ALLOW FILTERING: {}", r),
+            Self::InvalidSelection(r) => write!(f, "Invalid selection: {}", r),
+            Self::InvalidOrderBy(r) => write!(f, "Invalid ORDER BY: {}", r),
+            Self::InvalidGroupBy(r) => write!(f, "Invalid GROUP BY: {}", r),
+            Self::InvalidCounterOperation(r) => write!(f, "Invalid counter operation: {}", r),
+            Self::InvalidCondition(r) => write!(f, "Invalid condition: {}", r),
+            Self::InvalidUsing(r) => write!(f, "Invalid USING clause: {}", r),
+            Self::InvalidFunctionCall(r) => write!(f, "Invalid function call: {}", r),
+        }
+    }
+}
+
+/// Statements which can be semantically validated against a schema.
+pub trait Validate {
+    fn validate_with(&self, schema: &Schema, diagnostics: &mut Vec<Diagnostic>);
+}
+
+impl Schema {
+    /// Validate a statement against the schema, returning every problem found.
+    pub fn validate(&self, statement: &impl Validate) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        statement.validate_with(self, &mut diagnostics);
+        diagnostics
+    }
+}
+
+/// The table targeted by a statement.
+struct Context<'a> {
+    schema: &'a Schema,
+    keyspace: &'a KeyspaceSchema,
+    table: &'a TableSchema,
+}
+
+impl<'a> Context<'a> {
+    fn resolve(
+        schema: &'a Schema,
+        name: &KeyspaceQualifiedName,
+        writable: bool,
+        d: &mut Vec<Diagnostic>,
+    ) -> Option<Self> {
+        let keyspace = schema.keyspace_of(name).ok().and_then(|ks| schema.keyspaces.get(&ks));
+        let keyspace = match keyspace {
+            Some(keyspace) => keyspace,
+            None => {
+                d.push(Diagnostic::UnknownTable(name.clone()));
+                return None;
+            }
+        };
+        let table = match (keyspace.table(name.name.clone()), keyspace.view(name.name.clone())) {
+            (Some(table), _) => table,
+            (None, Some(view)) => {
+                if writable {
+                    d.push(Diagnostic::ViewModification(name.clone()));
+                    return None;
+                }
+                &view.table
+            }
+            (None, None) => {
+                d.push(Diagnostic::UnknownTable(name.clone()));
+                return None;
+            }
+        };
+        Some(Self {
+            schema,
+            keyspace,
+            table,
+        })
+    }
+
+    fn column(&self, name: &Name, d: &mut Vec<Diagnostic>) -> Option<&'a ColumnSchema> {
+        let column = self.table.column(name.clone());
+        if column.is_none() {
+            d.push(Diagnostic::UnknownColumn {
+                table: self.table.name.clone(),
+                column: name.clone(),
+            });
+        }
+        column
+    }
+
+    fn is_counter_table(&self) -> bool {
+        self.table
+            .columns
+            .iter()
+            .any(|c| c.data_type == CqlType::Native(NativeType::Counter))
+    }
+
+    fn is_indexed(&self, column: &str) -> bool {
+        self.keyspace
+            .indexes
+            .values()
+            .any(|i| ident(&i.table) == ident(&self.table.name) && ident(i.column()) == column)
+    }
+
+    fn catalog(&self) -> FunctionCatalog<'a> {
+        FunctionCatalog::new(self.schema, self.keyspace, self.table)
+    }
+
+    fn check_term(&self, column: &Name, data_type: &CqlType, term: &Term, d: &mut Vec<Diagnostic>) {
+        let matches = match term {
+            Term::FunctionCall(f) => match self.catalog().resolve_call(f, Some(data_type)) {
+                Ok(f) => same_type(&f.return_type, data_type),
+                Err(e) => {
+                    d.push(Diagnostic::InvalidFunctionCall(e.to_string()));
+                    return;
+                }
+            },
+            t => term_matches(self.keyspace, data_type, t),
+        };
+        if !matches {
+            d.push(Diagnostic::TypeMismatch {
+                column: column.clone(),
+                expected: data_type.clone(),
+                term: term.clone(),
+            });
+        }
+    }
+
+    /// Check each value of an `IN` relation or condition.
+    fn check_in(&self, column: &Name, data_type: &CqlType, term: &Term, d: &mut Vec<Diagnostic>) {
+        match term {
+            Term::Literal(Literal::Tuple(t)) => {
+                t.elements.iter().for_each(|e| self.check_term(column, data_type, e, d))
+            }
+            Term::BindMarker(_) => (),
+            t => self.check_term(column, data_type, t, d),
+        }
+    }
+
+    /// Get the type of a column, element or field targeted by a selection, checking the selection itself.
+    fn selection_type(
+        &self,
+        selection: &SimpleSelection,
+        d: &mut Vec<Diagnostic>,
+    ) -> Option<(&'a ColumnSchema, CqlType)> {
+        match selection {
+            SimpleSelection::Column(c) => self.column(c, d).map(|col| (col, col.data_type.clone())),
+            SimpleSelection::Term(c, index) => {
+                let col = self.column(c, d)?;
+                let (key, value) = match &col.data_type {
+                    CqlType::Collection(t) => match t.as_ref() {
+                        CollectionType::List(v) => (CqlType::Native(NativeType::Int), v.clone()),
+                        CollectionType::Map(k, v) => (k.clone(), v.clone()),
+                        CollectionType::Set(_) => {
+                            d.push(Diagnostic::InvalidAssignment(format!(
+                                "elements of set column {} cannot be accessed by index",
+                                c
+                            )));
+                            return None;
+                        }
+                    },
+                    _ => {
+                        d.push(Diagnostic::InvalidAssignment(format!(
+                            "column {} is not a list or map",
+                            c
+                        )));
+                        return None;
+                    }
+                };
+                self.check_term(c, &key, index, d);
+                Some((col, value))
+            }
+            SimpleSelection::Field(c, field) => {
+                let col = self.column(c, d)?;
+                let udt = match &col.data_type {
+                    CqlType::UserDefined(t) => self.keyspace.user_type(t.name.clone())?,
+                    _ => {
+                        d.push(Diagnostic::InvalidAssignment(format!(
+                            "column {} is not a user defined type",
+                            c
+                        )));
+                        return None;
+                    }
+                };
+                match udt.field(field.clone()) {
+                    Some(f) => Some((col, f.data_type.clone())),
+                    None => {
+                        d.push(Diagnostic::UnknownField {
+                            column: c.clone(),
+                            field: field.clone(),
+                        });
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn collection(data_type: &CqlType) -> Option<&CollectionType> {
+    match data_type {
+        CqlType::Collection(c) => Some(c.as_ref()),
+        _ => None,
+    }
+}
+
+fn is_text(data_type: &CqlType) -> bool {
+    matches!(
+        data_type,
+        CqlType::Native(NativeType::Ascii | NativeType::Text | NativeType::Varchar)
+    )
+}
+
+fn constant_matches(constant: &Constant, data_type: &NativeType) -> bool {
+    use NativeType::*;
+    match constant {
+        Constant::Null => true,
+        Constant::String(_) => matches!(
+            data_type,
+            Ascii | Text | Varchar | Inet | Date | Time | Timestamp | Duration
+        ),
+        Constant::Integer(i) => match data_type {
+            Tinyint => i.parse::<i8>().is_ok(),
+            Smallint => i.parse::<i16>().is_ok(),
+            Int => i.parse::<i32>().is_ok(),
+            Bigint | Counter | Time | Timestamp => i.parse::<i64>().is_ok(),
+            Date => i.parse::<u32>().is_ok(),
+            Varint | Decimal | Float | Double => true,
+            _ => false,
+        },
+        Constant::Float(_) => matches!(data_type, Float | Double | Decimal),
+        Constant::Boolean(_) => matches!(data_type, Boolean),
+        Constant::Uuid(u) => matches!(data_type, Uuid) || (matches!(data_type, Timeuuid) && u.get_version_num() == 1),
+        Constant::Hex(_) | Constant::Blob(_) => matches!(data_type, Blob),
+    }
+}
+
+/// Whether a term may be assigned to a value of the given type.
+/// Function calls and bind markers cannot be checked without more context and are always accepted.
+pub(crate) fn term_matches(keyspace: &KeyspaceSchema, data_type: &CqlType, term: &Term) -> bool {
+    if let CqlType::Custom(_) = data_type {
+        return true;
+    }
+    match term {
+        Term::BindMarker(_) | Term::FunctionCall(_) => true,
+        Term::TypeHint { hint, .. } => same_type(hint, data_type),
+        Term::ArithmeticOp { lhs, rhs, .. } => {
+            matches!(
+                data_type,
+                CqlType::Native(
+                    NativeType::Tinyint
+                        | NativeType::Smallint
+                        | NativeType::Int
+                        | NativeType::Bigint
+                        | NativeType::Varint
+                        | NativeType::Decimal
+                        | NativeType::Float
+                        | NativeType::Double
+                        | NativeType::Counter
+                )
+            ) && lhs
+                .as_ref()
+                .map(|l| term_matches(keyspace, data_type, l))
+                .unwrap_or(true)
+                && term_matches(keyspace, data_type, rhs)
+        }
+        Term::Constant(Constant::Null) => true,
+        Term::Constant(c) => match data_type {
+            CqlType::Native(n) => constant_matches(c, n),
+            _ => false,
+        },
+        Term::Literal(l) => match (l, data_type) {
+            (Literal::Collection(c), CqlType::Collection(t)) => match (c, t.as_ref()) {
+                (CollectionTypeLiteral::List(l), CollectionType::List(t)) => {
+                    l.elements.iter().all(|e| term_matches(keyspace, t, e))
+                }
+                (CollectionTypeLiteral::Set(s), CollectionType::Set(t)) => {
+                    s.elements.iter().all(|e| term_matches(keyspace, t, e))
+                }
+                (CollectionTypeLiteral::Map(m), CollectionType::Map(k, v)) => m
+                    .elements
+                    .iter()
+                    .all(|(mk, mv)| term_matches(keyspace, k, mk) && term_matches(keyspace, v, mv)),
+                _ => false,
+            },
+            (Literal::UserDefined(u), CqlType::UserDefined(t)) => match keyspace.user_type(t.name.clone()) {
+                Some(udt) => u.fields.iter().all(|(name, value)| {
+                    udt.field(name.clone())
+                        .map(|f| term_matches(keyspace, &f.data_type, value))
+                        .unwrap_or(false)
+                }),
+                None => false,
+            },
+            (Literal::Tuple(t), CqlType::Tuple(types)) => {
+                t.elements.len() == types.len()
+                    && t.elements
+                        .iter()
+                        .zip(types.iter())
+                        .all(|(e, t)| term_matches(keyspace, t, e))
+            }
+            _ => false,
+        },
+    }
+}
+
+/// The columns restricted by a WHERE clause.
+#[derive(Default)]
+struct Restrictions {
+    columns: Vec<(String, Operator)>,
+    token: bool,
+}
+
+impl Restrictions {
+    fn ops<'a>(&'a self, column: &'a Name) -> impl Iterator<Item = Operator> + 'a {
+        let column = ident(column);
+        self.columns
+            .iter()
+            .filter(move |(c, _)| *c == column)
+            .map(|(_, op)| *op)
+    }
+
+    fn is_eq(&self, column: &Name) -> bool {
+        self.ops(column).any(|op| matches!(op, Operator::Equal | Operator::In))
+    }
+
+    fn is_restricted(&self, column: &Name) -> bool {
+        self.ops(column).next().is_some()
+    }
+}
+
+fn is_slice(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::GreaterThan | Operator::GreaterThanOrEqual | Operator::LessThan | Operator::LessThanOrEqual
+    )
+}
+
+fn check_where(ctx: &Context, where_clause: Option<&WhereClause>, d: &mut Vec<Diagnostic>) -> Restrictions {
+    let mut res = Restrictions::default();
+    for relation in where_clause.iter().flat_map(|w| w.relations.iter()) {
+        match relation {
+            Relation::Normal { column, operator, term } => {
+                let col = match ctx.column(column, d) {
+                    Some(col) => col,
+                    None => continue,
+                };
+                match operator {
+                    Operator::In => ctx.check_in(column, &col.data_type, term, d),
+                    Operator::Contains => match collection(&col.data_type) {
+                        Some(CollectionType::List(t) | CollectionType::Set(t) | CollectionType::Map(_, t)) => {
+                            ctx.check_term(column, t, term, d)
+                        }
+                        None => d.push(Diagnostic::InvalidRestriction {
+                            column: column.clone(),
+                            reason: "CONTAINS can only be used on collection columns".to_string(),
+                        }),
+                    },
+                    Operator::ContainsKey => match collection(&col.data_type) {
+                        Some(CollectionType::Map(k, _)) => ctx.check_term(column, k, term, d),
+                        _ => d.push(Diagnostic::InvalidRestriction {
+                            column: column.clone(),
+                            reason: "CONTAINS KEY can only be used on map columns".to_string(),
+                        }),
+                    },
+                    Operator::Like if !is_text(&col.data_type) => d.push(Diagnostic::InvalidRestriction {
+                        column: column.clone(),
+                        reason: "LIKE can only be used on text columns".to_string(),
+                    }),
+                    _ => ctx.check_term(column, &col.data_type, term, d),
+                }
+                res.columns.push((ident(column), *operator));
+            }
+            Relation::Tuple {
+                columns,
+                operator,
+                tuple_literal,
+            } => {
+                let cols = columns.iter().filter_map(|c| ctx.column(c, d)).collect::<Vec<_>>();
+                if cols.len() != columns.len() {
+                    continue;
+                }
+                let positions = cols
+                    .iter()
+                    .map(|c| {
+                        ctx.table
+                            .clustering_columns
+                            .iter()
+                            .position(|n| ident(n) == ident(&c.name))
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match positions {
+                    Some(p) if p.windows(2).all(|w| w[1] == w[0] + 1) => (),
+                    _ => d.push(Diagnostic::InvalidRestriction {
+                        column: columns[0].clone(),
+                        reason: "multi-column relations must use consecutive clustering columns".to_string(),
+                    }),
+                }
+                let types = CqlType::Tuple(cols.iter().map(|c| c.data_type.clone()).collect());
+                let term = Term::Literal(Literal::Tuple(tuple_literal.clone()));
+                if *operator == Operator::In {
+                    ctx.check_in(&columns[0], &types, &term, d);
+                } else {
+                    ctx.check_term(&columns[0], &types, &term, d);
+                }
+                for c in columns {
+                    res.columns.push((ident(c), *operator));
+                }
+            }
+            Relation::Token { columns, term, .. } => {
+                if !columns.iter().map(ident).eq(ctx.table.partition_key.iter().map(ident)) {
+                    d.push(Diagnostic::InvalidRestriction {
+                        column: columns
+                            .first()
+                            .cloned()
+                            .unwrap_or_else(|| ctx.table.partition_key[0].clone()),
+                        reason: "token() must be applied to the full partition key, in order".to_string(),
+                    });
+                }
+                ctx.check_term(&Name::from("token"), &CqlType::Native(NativeType::Bigint), term, d);
+                res.token = true;
+            }
+            Relation::MVExclusion { column } => d.push(Diagnostic::InvalidRestriction {
+                column: column.clone(),
+                reason: "IS NOT NULL can only be used when creating a materialized view".to_string(),
+            }),
+        }
+    }
+    res
+}
+
+/// Check the restrictions of a SELECT, returning the reasons it would require filtering.
+fn filtering_reasons(ctx: &Context, r: &Restrictions) -> Vec<String> {
+    let table = ctx.table;
+    let mut reasons = Vec::new();
+    let partition_restricted = table.partition_key.iter().all(|c| r.is_eq(c));
+    for c in table.partition_key.iter() {
+        if r.ops(c).any(|op| !matches!(op, Operator::Equal | Operator::In)) {
+            reasons.push(format!("partition key column {} is not restricted by = or IN", c));
+        }
+    }
+    if !partition_restricted && !r.token && table.partition_key.iter().any(|c| r.is_restricted(c)) {
+        reasons.push("the partition key is only partially restricted".to_string());
+    }
+    let mut prefix = true;
+    let mut reported_partition = false;
+    for c in table.clustering_columns.iter() {
+        let ops = r.ops(c).collect::<Vec<_>>();
+        if ops.is_empty() {
+            prefix = false;
+            continue;
+        }
+        if !partition_restricted && !reported_partition {
+            reasons.push(format!(
+                "clustering column {} is restricted without restricting the partition key",
+                c
+            ));
+            reported_partition = true;
+        }
+        if !prefix {
+            reasons.push(format!(
+                "clustering column {} is restricted but a preceding clustering column is not restricted by =",
+                c
+            ));
+        }
+        if ops
+            .iter()
+            .any(|op| !(matches!(op, Operator::Equal | Operator::In) || is_slice(*op)))
+        {
+            reasons.push(format!(
+                "clustering column {} is restricted by an unsupported operator",
+                c
+            ));
+        }
+        if ops.iter().any(|op| is_slice(*op)) {
+            prefix = false;
+        }
+    }
+    for (column, op) in r.columns.iter() {
+        let col = match table.column(Name::Quoted(column.clone())) {
+            Some(col) if !col.is_primary_key() => col,
+            _ => continue,
+        };
+        let indexed =
+            ctx.is_indexed(column) && matches!(op, Operator::Equal | Operator::Contains | Operator::ContainsKey);
+        if !indexed {
+            reasons.push(format!(
+                "column {} is not part of the primary key and is not indexed",
+                col.name
+            ));
+        }
+    }
+    reasons
+}
+
+/// Check the restrictions of an UPDATE or DELETE, which must target specific rows.
+fn check_modification_restrictions(
+    ctx: &Context,
+    r: &Restrictions,
+    full_clustering: bool,
+    conditional: bool,
+    d: &mut Vec<Diagnostic>,
+) {
+    let table = ctx.table;
+    if r.token {
+        d.push(Diagnostic::InvalidRestriction {
+            column: table.partition_key[0].clone(),
+            reason: "token() cannot be used to select rows to modify".to_string(),
+        });
+    }
+    for c in table.partition_key.iter().chain(table.clustering_columns.iter()) {
+        if conditional && r.ops(c).any(|op| op == Operator::In) {
+            d.push(Diagnostic::InvalidCondition(format!(
+                "IN cannot be used on primary key column {} with conditions",
+                c
+            )));
+        }
+    }
+    for c in table.partition_key.iter() {
+        if !r.is_eq(c) {
+            d.push(Diagnostic::MissingPrimaryKeyColumn(c.clone()));
+        }
+    }
+    let mut slice = None;
+    for c in table.clustering_columns.iter() {
+        let ops = r.ops(c).collect::<Vec<_>>();
+        if full_clustering {
+            if !r.is_eq(c) {
+                d.push(Diagnostic::MissingPrimaryKeyColumn(c.clone()));
+            }
+            continue;
+        }
+        if ops.is_empty() {
+            slice.get_or_insert(c);
+            continue;
+        }
+        if let Some(prev) = slice {
+            d.push(Diagnostic::InvalidRestriction {
+                column: c.clone(),
+                reason: format!("the preceding clustering column {} is not restricted by =", prev),
+            });
+        }
+        if ops.iter().any(|op| is_slice(*op)) {
+            slice.get_or_insert(c);
+        }
+    }
+    for (column, _) in r.columns.iter() {
+        if let Some(col) = table
+            .column(Name::Quoted(column.clone()))
+            .filter(|c| !c.is_primary_key())
+        {
+            d.push(Diagnostic::InvalidRestriction {
+                column: col.name.clone(),
+                reason: "only primary key columns can be restricted when modifying rows".to_string(),
+            });
+        }
+    }
+}
+
+fn check_using(
+    using: Option<&Vec<UpdateParameter>>,
+    allow_ttl: bool,
+    conditional: bool,
+    counter: bool,
+    d: &mut Vec<Diagnostic>,
+) {
+    let using = match using {
+        Some(using) => using,
+        None => return,
+    };
+    let (mut ttl, mut timestamp, mut timeout) = (0, 0, 0);
+    for param in using {
+        match param {
+            UpdateParameter::TTL(limit) => {
+                ttl += 1;
+                if !allow_ttl {
+                    d.push(Diagnostic::InvalidUsing("TTL is not supported here".to_string()));
+                } else if counter {
+                    d.push(Diagnostic::InvalidUsing(
+                        "TTL is not supported on counter tables".to_string(),
+                    ));
+                }
+                if let Limit::Literal(v) = limit {
+                    if *v < 0 || *v > MAX_TTL {
+                        d.push(Diagnostic::InvalidUsing(format!(
+                            "TTL must be between 0 and {}, found {}",
+                            MAX_TTL, v
+                        )));
+                    }
+                }
+            }
+            UpdateParameter::Timestamp(_) => {
+                timestamp += 1;
+                if conditional {
+                    d.push(Diagnostic::InvalidUsing(
+                        "USING TIMESTAMP cannot be used with conditions".to_string(),
+                    ));
+                } else if counter {
+                    d.push(Diagnostic::InvalidUsing(
+                        "USING TIMESTAMP is not supported on counter tables".to_string(),
+                    ));
+                }
+            }
+            UpdateParameter::Timeout(_) => timeout += 1,
+        }
+    }
+    for (count, name) in [(ttl, "TTL"), (timestamp, "TIMESTAMP"), (timeout, "TIMEOUT")] {
+        if count > 1 {
+            d.push(Diagnostic::InvalidUsing(format!(
+                "{} is specified more than once",
+                name
+            )));
+        }
+    }
+}
+
+fn check_conditions(ctx: &Context, if_clause: Option<&IfClause>, d: &mut Vec<Diagnostic>) {
+    let conditions = match if_clause {
+        Some(_) if ctx.is_counter_table() => {
+            d.push(Diagnostic::InvalidCondition(
+                "conditions are not supported on counter tables".to_string(),
+            ));
+            return;
+        }
+        Some(IfClause::Conditions(conditions)) => conditions,
+        _ => return,
+    };
+    for Condition { lhs, op, rhs } in conditions {
+        let (col, data_type) = match ctx.selection_type(lhs, d) {
+            Some(t) => t,
+            None => continue,
+        };
+        if col.is_primary_key() {
+            d.push(Diagnostic::InvalidCondition(format!(
+                "primary key column {} cannot be used in conditions",
+                col.name
+            )));
+            continue;
+        }
+        match op {
+            Operator::In => ctx.check_in(&col.name, &data_type, rhs, d),
+            Operator::Contains | Operator::ContainsKey => d.push(Diagnostic::InvalidCondition(format!(
+                "{} is not supported in conditions",
+                op
+            ))),
+            Operator::Like if !is_text(&data_type) => d.push(Diagnostic::InvalidCondition(format!(
+                "LIKE can only be used on text columns, found {}",
+                col.name
+            ))),
+            _ => ctx.check_term(&col.name, &data_type, rhs, d),
+        }
+    }
+}
+
+fn check_selector(ctx: &Context, selector: &Selector, distinct: bool, d: &mut Vec<Diagnostic>) {
+    match &selector.kind {
+        SelectorKind::Column(c) => {
+            if let Some(col) = ctx.column(c, d) {
+                if distinct && !matches!(col.kind, ColumnKind::PartitionKey | ColumnKind::Static) {
+                    d.push(Diagnostic::InvalidSelection(format!(
+                        "SELECT DISTINCT can only select partition key and static columns, found {}",
+                        c
+                    )));
+                }
+            }
+        }
+        SelectorKind::Cast(s, _) => check_selector(ctx, s, distinct, d),
+        SelectorKind::Function(f) => f.args.iter().for_each(|s| check_selector(ctx, s, distinct, d)),
+        SelectorKind::Term(_) | SelectorKind::Count => (),
+    }
+}
+
+impl Validate for SelectStatement {
+    fn validate_with(&self, schema: &Schema, d: &mut Vec<Diagnostic>) {
+        let ctx = match Context::resolve(schema, &self.from, false, d) {
+            Some(ctx) => ctx,
+            None => return,
+        };
+        let table = ctx.table;
+        if let SelectClause::Selectors(selectors) = &self.select_clause {
+            let catalog = ctx.catalog();
+            for selector in selectors {
+                let found = d.len();
+                check_selector(&ctx, selector, self.distinct, d);
+                if d.len() == found {
+                    if let Err(e) = catalog.selector_type(selector) {
+                        d.push(Diagnostic::InvalidSelection(e.to_string()));
+                    }
+                }
+            }
+        }
+        let r = check_where(&ctx, self.where_clause.as_ref(), d);
+        if !self.allow_filtering {
+            for reason in filtering_reasons(&ctx, &r) {
+                d.push(Diagnostic::RequiresAllowFiltering(reason));
+            }
+        }
+        if let Some(group_by) = &self.group_by_clause {
+            let key = table.partition_key.iter().chain(table.clustering_columns.iter());
+            if group_by.columns.len() > table.partition_key.len() + table.clustering_columns.len()
+                || !group_by
+                    .columns
+                    .iter()
+                    .map(ident)
+                    .eq(key.take(group_by.columns.len()).map(ident))
+            {
+                d.push(Diagnostic::InvalidGroupBy(
+                    "columns must be a prefix of the primary key, in order".to_string(),
+                ));
+            }
+        }
+        if let Some(order_by) = &self.order_by_clause {
+            let declared = |c: &Name| {
+                table
+                    .options
+                    .as_ref()
+                    .and_then(|o| o.clustering_order.as_ref())
+                    .and_then(|o| o.iter().find(|o| ident(&o.column) == ident(c)))
+                    .map(|o| o.order)
+                    .unwrap_or(Order::Ascending)
+            };
+            if order_by.columns.len() > table.clustering_columns.len()
+                || !order_by.columns.iter().map(|c| ident(&c.column)).eq(table
+                    .clustering_columns
+                    .iter()
+                    .take(order_by.columns.len())
+                    .map(ident))
+            {
+                d.push(Diagnostic::InvalidOrderBy(
+                    "columns must be a prefix of the clustering columns, in order".to_string(),
+                ));
+            } else {
+                let reversed = order_by
+                    .columns
+                    .iter()
+                    .map(|c| c.order != declared(&c.column))
+                    .collect::<Vec<_>>();
+                if reversed.iter().any(|r| *r != reversed[0]) {
+                    d.push(Diagnostic::InvalidOrderBy(
+                        "the order must match the table's clustering order or be fully reversed".to_string(),
+                    ));
+                }
+            }
+            if !table.partition_key.iter().all(|c| r.is_eq(c)) {
+                d.push(Diagnostic::InvalidOrderBy(
+                    "the partition key must be restricted by = or IN".to_string(),
+                ));
+            }
+        }
+    }
+}
+
+impl Validate for InsertStatement {
+    fn validate_with(&self, schema: &Schema, d: &mut Vec<Diagnostic>) {
+        let ctx = match Context::resolve(schema, &self.table, true, d) {
+            Some(ctx) => ctx,
+            None => return,
+        };
+        let counter = ctx.is_counter_table();
+        if counter {
+            d.push(Diagnostic::InvalidCounterOperation(
+                "INSERT is not supported on counter tables, use UPDATE".to_string(),
+            ));
+        }
+        check_using(self.using.as_ref(), true, self.if_not_exists, counter, d);
+        if let InsertKind::NameValue { names, values } = &self.kind {
+            let mut seen = Vec::new();
+            for (name, value) in names.iter().zip(values.elements.iter()) {
+                if seen.contains(&ident(name)) {
+                    d.push(Diagnostic::DuplicateColumn(name.clone()));
+                    continue;
+                }
+                seen.push(ident(name));
+                if let Some(col) = ctx.column(name, d) {
+                    if col.is_primary_key() && *value == Term::Constant(Constant::Null) {
+                        d.push(Diagnostic::MissingPrimaryKeyColumn(name.clone()));
+                    }
+                    ctx.check_term(name, &col.data_type, value, d);
+                }
+            }
+            for c in ctx
+                .table
+                .partition_key
+                .iter()
+                .chain(ctx.table.clustering_columns.iter())
+            {
+                if !seen.contains(&ident(c)) {
+                    d.push(Diagnostic::MissingPrimaryKeyColumn(c.clone()));
+                }
+            }
+        }
+    }
+}
+
+impl Validate for UpdateStatement {
+    fn validate_with(&self, schema: &Schema, d: &mut Vec<Diagnostic>) {
+        let ctx = match Context::resolve(schema, &self.table, true, d) {
+            Some(ctx) => ctx,
+            None => return,
+        };
+        let counter = ctx.is_counter_table();
+        let conditional = self.if_clause.is_some();
+        check_using(self.using.as_ref(), true, conditional, counter, d);
+        let mut only_static = true;
+        for assignment in self.set_clause.iter() {
+            let (assignee, col) = match assignment {
+                Assignment::Simple { selection, .. } => {
+                    let name = match selection {
+                        SimpleSelection::Column(c) | SimpleSelection::Term(c, _) | SimpleSelection::Field(c, _) => c,
+                    };
+                    (name, ctx.table.column(name.clone()))
+                }
+                Assignment::Arithmetic { assignee, .. } | Assignment::Append { assignee, .. } => {
+                    (assignee, ctx.table.column(assignee.clone()))
+                }
+            };
+            if let Some(col) = col {
+                only_static &= col.kind == ColumnKind::Static;
+                if col.is_primary_key() {
+                    d.push(Diagnostic::PrimaryKeyAssignment(assignee.clone()));
+                    continue;
+                }
+            }
+            match assignment {
+                Assignment::Simple { selection, term } => {
+                    if let Some((col, data_type)) = ctx.selection_type(selection, d) {
+                        if col.data_type == CqlType::Native(NativeType::Counter) {
+                            d.push(Diagnostic::InvalidCounterOperation(format!(
+                                "counter column {} can only be incremented or decremented",
+                                col.name
+                            )));
+                        } else {
+                            ctx.check_term(&col.name, &data_type, term, d);
+                        }
+                    }
+                }
+                Assignment::Arithmetic { assignee, lhs, op, rhs } => {
+                    if ident(assignee) != ident(lhs) {
+                        d.push(Diagnostic::InvalidAssignment(format!(
+                            "{} can only be assigned an operation on itself, found {}",
+                            assignee, lhs
+                        )));
+                    }
+                    let col = match ctx.column(assignee, d) {
+                        Some(col) => col,
+                        None => continue,
+                    };
+                    if !matches!(op, ArithmeticOp::Add | ArithmeticOp::Sub) {
+                        d.push(Diagnostic::InvalidAssignment(format!(
+                            "only + and - are supported when assigning to {}",
+                            assignee
+                        )));
+                        continue;
+                    }
+                    match (&col.data_type, collection(&col.data_type)) {
+                        (CqlType::Native(NativeType::Counter), _) => {
+                            ctx.check_term(assignee, &CqlType::Native(NativeType::Bigint), rhs, d)
+                        }
+                        (_, Some(CollectionType::Map(k, _))) if *op == ArithmeticOp::Sub => ctx.check_term(
+                            assignee,
+                            &CqlType::Collection(Box::new(CollectionType::Set(k.clone()))),
+                            rhs,
+                            d,
+                        ),
+                        (t, Some(_)) => ctx.check_term(assignee, t, rhs, d),
+                        (_, None) => d.push(Diagnostic::InvalidAssignment(format!(
+                            "{} {} ... is only supported on counter and collection columns",
+                            assignee, op
+                        ))),
+                    }
+                }
+                Assignment::Append { assignee, list, item } => {
+                    if ident(assignee) != ident(item) {
+                        d.push(Diagnostic::InvalidAssignment(format!(
+                            "{} can only be assigned an operation on itself, found {}",
+                            assignee, item
+                        )));
+                    }
+                    let col = match ctx.column(assignee, d) {
+                        Some(col) => col,
+                        None => continue,
+                    };
+                    match collection(&col.data_type) {
+                        Some(CollectionType::List(t)) => {
+                            list.elements.iter().for_each(|e| ctx.check_term(assignee, t, e, d))
+                        }
+                        _ => d.push(Diagnostic::InvalidAssignment(format!(
+                            "prepending is only supported on list columns, found {}",
+                            assignee
+                        ))),
+                    }
+                }
+            }
+        }
+        let r = check_where(&ctx, Some(&self.where_clause), d);
+        check_modification_restrictions(&ctx, &r, !only_static || conditional, conditional, d);
+        check_conditions(&ctx, self.if_clause.as_ref(), d);
+    }
+}
+
+impl Validate for DeleteStatement {
+    fn validate_with(&self, schema: &Schema, d: &mut Vec<Diagnostic>) {
+        let ctx = match Context::resolve(schema, &self.from, true, d) {
+            Some(ctx) => ctx,
+            None => return,
+        };
+        let conditional = self.if_clause.is_some();
+        check_using(self.using.as_ref(), false, conditional, false, d);
+        let mut only_static = true;
+        for selection in self.selections.iter().flatten() {
+            if let Some((col, _)) = ctx.selection_type(selection, d) {
+                only_static &= col.kind == ColumnKind::Static;
+                if col.is_primary_key() {
+                    d.push(Diagnostic::PrimaryKeyAssignment(col.name.clone()));
+                }
+            }
+        }
+        let full_clustering = conditional || (self.selections.is_some() && !only_static);
+        let r = check_where(&ctx, Some(&self.where_clause), d);
+        check_modification_restrictions(&ctx, &r, full_clustering, conditional, d);
+        check_conditions(&ctx, self.if_clause.as_ref(), d);
+    }
+}
+
+impl ModificationStatement {
+    fn using(&self) -> Option<&Vec<UpdateParameter>> {
+        match self {
+            Self::Insert(s) => s.using.as_ref(),
+            Self::Update(s) => s.using.as_ref(),
+            Self::Delete(s) => s.using.as_ref(),
+        }
+    }
+
+    fn is_conditional(&self) -> bool {
+        match self {
+            Self::Insert(s) => s.if_not_exists,
+            Self::Update(s) => s.if_clause.is_some(),
+            Self::Delete(s) => s.if_clause.is_some(),
+        }
+    }
+
+    fn table(&self) -> &KeyspaceQualifiedName {
+        match self {
+            Self::Insert(s) => &s.table,
+            Self::Update(s) => &s.table,
+            Self::Delete(s) => &s.from,
+        }
+    }
+
+    /// Get the values assigned to each partition key column, if they are all known.
+    fn partition(&self, table: &TableSchema) -> Option<Vec<Term>> {
+        let value = |c: &Name| match self {
+            Self::Insert(s) => match &s.kind {
+                InsertKind::NameValue { names, values } => names
+                    .iter()
+                    .position(|n| ident(n) == ident(c))
+                    .and_then(|i| values.elements.get(i).cloned()),
+                InsertKind::Json { .. } => None,
+            },
+            Self::Update(UpdateStatement { where_clause, .. }) | Self::Delete(DeleteStatement { where_clause, .. }) => {
+                where_clause.relations.iter().find_map(|r| match r {
+                    Relation::Normal {
+                        column,
+                        operator: Operator::Equal,
+                        term,
+                    } if ident(column) == ident(c) => Some(term.clone()),
+                    _ => None,
+                })
+            }
+        };
+        table.partition_key.iter().map(value).collect()
+    }
+}
+
+impl Validate for ModificationStatement {
+    fn validate_with(&self, schema: &Schema, d: &mut Vec<Diagnostic>) {
+        match self {
+            Self::Insert(s) => s.validate_with(schema, d),
+            Self::Update(s) => s.validate_with(schema, d),
+            Self::Delete(s) => s.validate_with(schema, d),
+        }
+    }
+}
+
+impl Validate for BatchStatement {
+    fn validate_with(&self, schema: &Schema, d: &mut Vec<Diagnostic>) {
+        check_using(self.using.as_ref(), false, false, false, d);
+        let batch_timestamp = self
+            .using
+            .iter()
+            .flatten()
+            .any(|p| matches!(p, UpdateParameter::Timestamp(_)));
+        let conditional = self.statements.iter().any(|s| s.is_conditional());
+        let mut partition = None;
+        for statement in self.statements.iter() {
+            statement.validate_with(schema, d);
+            if batch_timestamp
+                && statement
+                    .using()
+                    .iter()
+                    .flat_map(|u| u.iter())
+                    .any(|p| matches!(p, UpdateParameter::Timestamp(_)))
+            {
+                d.push(Diagnostic::InvalidUsing(
+                    "timestamps must be set on either the batch or its statements, not both".to_string(),
+                ));
+            }
+            let table = schema.table(statement.table().clone());
+            let is_counter = matches!(statement, ModificationStatement::Update(_))
+                && table
+                    .map(|t| {
+                        t.columns
+                            .iter()
+                            .any(|c| c.data_type == CqlType::Native(NativeType::Counter))
+                    })
+                    .unwrap_or(false);
+            if self.kind == BatchKind::Counter && !is_counter {
+                d.push(Diagnostic::InvalidCounterOperation(
+                    "counter batches can only contain counter updates".to_string(),
+                ));
+            } else if self.kind != BatchKind::Counter && is_counter {
+                d.push(Diagnostic::InvalidCounterOperation(
+                    "counter updates must be in a counter batch".to_string(),
+                ));
+            }
+            if let (true, Some(table)) = (conditional, table) {
+                let key = (ident(&table.name), statement.partition(table));
+                match &partition {
+                    None => partition = Some(key),
+                    Some(p) if *p != key => {
+                        d.push(Diagnostic::InvalidCondition(
+                            "batches with conditions must target a single partition of a single table".to_string(),
+                        ));
+                        partition = Some(key);
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+impl Validate for DataManipulationStatement {
+    fn validate_with(&self, schema: &Schema, d: &mut Vec<Diagnostic>) {
+        match self {
+            Self::Select(s) => s.validate_with(schema, d),
+            Self::Insert(s) => s.validate_with(schema, d),
+            Self::Update(s) => s.validate_with(schema, d),
+            Self::Delete(s) => s.validate_with(schema, d),
+            Self::Batch(s) => s.validate_with(schema, d),
+        }
+    }
+}
+
+impl Validate for Statement {
+    /// Only data manipulation statements are validated.
+    fn validate_with(&self, schema: &Schema, d: &mut Vec<Diagnostic>) {
+        if let Statement::DataManipulation(s) = self {
+            s.validate_with(schema, d);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        CREATE KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+        USE movies;
+        CREATE TYPE person (first_name text, last_name text);
+        CREATE TABLE nerd_movies (
+            movie text,
+            year int,
+            director person,
+            studio text STATIC,
+            rating float,
+            tags set<text>,
+            PRIMARY KEY ((movie), year)
+        ) WITH CLUSTERING ORDER BY (year DESC);
+        CREATE INDEX ON nerd_movies (rating);
+        CREATE TABLE views (movie text PRIMARY KEY, count counter);
+    "#;
+
+    fn validate(statement: &str) -> Vec<Diagnostic> {
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        schema.validate(&statement.parse::<Statement>().unwrap())
+    }
+
+    #[test]
+    fn test_valid_statements() {
+        for statement in [
+            "SELECT movie, director FROM nerd_movies WHERE movie = 'Serenity' AND year > 2000 ORDER BY year ASC",
+            "SELECT * FROM movies.nerd_movies WHERE rating = 5.0",
+            "SELECT * FROM nerd_movies WHERE token(movie) > 0",
+            "SELECT DISTINCT movie, studio FROM nerd_movies",
+            "SELECT max(rating), toJson(director), writetime(studio) FROM nerd_movies",
+            "SELECT * FROM nerd_movies WHERE movie IN ('Alien', 'Serenity') AND year = 2005",
+            "INSERT INTO nerd_movies (movie, year, director, tags) VALUES ('Serenity', 2005, {first_name: 'Joss'}, {'scifi'}) USING TTL 86400",
+            "UPDATE nerd_movies SET tags = tags + {'western'}, director.last_name = 'Whedon' WHERE movie = 'Serenity' AND year = 2005 IF rating = 5.0",
+            "UPDATE nerd_movies SET studio = 'Universal' WHERE movie = 'Serenity'",
+            "UPDATE nerd_movies SET tags = fromJson('[\"scifi\"]') WHERE movie = 'Serenity' AND year = 2005",
+            "UPDATE views SET count = count + 1 WHERE movie = 'Serenity'",
+            "DELETE FROM nerd_movies WHERE movie = 'Serenity' AND year > 2000",
+            "BEGIN COUNTER BATCH UPDATE views SET count = count + 1 WHERE movie = 'Serenity'; APPLY BATCH",
+            "BEGIN BATCH INSERT INTO nerd_movies (movie, year) VALUES ('Serenity', 2005) IF NOT EXISTS; UPDATE nerd_movies SET rating = 4.5 WHERE movie = 'Serenity' AND year = 2006; APPLY BATCH",
+        ] {
+            assert_eq!(validate(statement), vec![], "{}", statement);
+        }
+    }
+
+    #[test]
+    fn test_invalid_statements() {
+        let d = validate("SELECT title FROM nerd_movies WHERE movie = 1 AND studio = 'Universal'");
+        assert_eq!(
+            d,
+            vec![
+                Diagnostic::UnknownColumn {
+                    table: "nerd_movies".into(),
+                    column: "title".into()
+                },
+                Diagnostic::TypeMismatch {
+                    column: "movie".into(),
+                    expected: CqlType::Native(NativeType::Text),
+                    term: Term::Constant(Constant::Integer("1".to_string())),
+                },
+                Diagnostic::RequiresAllowFiltering(
+                    "column studio is not part of the primary key and is not indexed".to_string()
+                ),
+            ]
+        );
+        assert!(matches!(
+            validate("SELECT * FROM nerd_movies WHERE year = 2005")[..],
+            [Diagnostic::RequiresAllowFiltering(_)]
+        ));
+        assert!(validate("SELECT * FROM nerd_movies WHERE year = 2005 ALLOW FILTERING").is_empty());
+        assert!(matches!(
+            validate("SELECT * FROM nerd_movies WHERE movie = 'Serenity' ORDER BY year DESC, movie ASC")[..],
+            [Diagnostic::InvalidOrderBy(_)]
+        ));
+        assert!(matches!(
+            validate("SELECT * FROM unknown")[..],
+            [Diagnostic::UnknownTable(_)]
+        ));
+        assert_eq!(
+            validate("INSERT INTO nerd_movies (movie, rating) VALUES ('Serenity', 5.0) USING TTL -1"),
+            vec![
+                Diagnostic::InvalidUsing(format!("TTL must be between 0 and {}, found -1", MAX_TTL)),
+                Diagnostic::MissingPrimaryKeyColumn("year".into()),
+            ]
+        );
+        assert!(matches!(
+            validate("INSERT INTO views (movie, count) VALUES ('Serenity', 1)")[..],
+            [Diagnostic::InvalidCounterOperation(_), ..]
+        ));
+        assert!(matches!(
+            validate("UPDATE views SET count = 1 WHERE movie = 'Serenity'")[..],
+            [Diagnostic::InvalidCounterOperation(_)]
+        ));
+        assert!(matches!(
+            validate("UPDATE views USING TTL 10 SET count = count + 1 WHERE movie = 'Serenity' IF EXISTS")[..],
+            [Diagnostic::InvalidUsing(_), Diagnostic::InvalidCondition(_)]
+        ));
+        assert!(matches!(
+            validate("UPDATE nerd_movies SET rating = rating + 1.0 WHERE movie = 'Serenity' AND year = 2005")[..],
+            [Diagnostic::InvalidAssignment(_)]
+        ));
+        assert_eq!(
+            validate("UPDATE nerd_movies SET year = 2006, rating = 1.0 WHERE movie = 'Serenity'"),
+            vec![
+                Diagnostic::PrimaryKeyAssignment("year".into()),
+                Diagnostic::MissingPrimaryKeyColumn("year".into()),
+            ]
+        );
+        assert!(matches!(
+            validate("UPDATE nerd_movies USING TIMESTAMP 1 SET rating = 1.0 WHERE movie IN ('Alien', 'Serenity') AND year = 2005 IF director.age = 3")[..],
+            [
+                Diagnostic::InvalidUsing(_),
+                Diagnostic::InvalidCondition(_),
+                Diagnostic::UnknownField { .. }
+            ]
+        ));
+        assert!(matches!(
+            validate("DELETE FROM nerd_movies USING TTL 10 WHERE movie = 'Serenity' AND rating = 1.0")[..],
+            [Diagnostic::InvalidUsing(_), Diagnostic::InvalidRestriction { .. }]
+        ));
+        assert!(matches!(
+            validate("BEGIN BATCH UPDATE views SET count = count + 1 WHERE movie = 'Serenity'; APPLY BATCH")[..],
+            [Diagnostic::InvalidCounterOperation(_)]
+        ));
+        assert_eq!(
+            validate("SELECT sum(movie) FROM nerd_movies"),
+            vec![Diagnostic::InvalidSelection(
+                "Function sum expects a numeric argument, found TEXT".to_string()
+            )]
+        );
+        assert!(matches!(
+            validate("INSERT INTO nerd_movies (movie, year, rating) VALUES (now(), 2005, unknown(1))")[..],
+            [Diagnostic::TypeMismatch { .. }, Diagnostic::InvalidFunctionCall(_)]
+        ));
+        assert!(matches!(
+            validate("BEGIN BATCH INSERT INTO nerd_movies (movie, year) VALUES ('Serenity', 2005) IF NOT EXISTS; INSERT INTO nerd_movies (movie, year) VALUES ('Alien', 1979); APPLY BATCH")[..],
+            [Diagnostic::InvalidCondition(_)]
+        ));
+    }
+}
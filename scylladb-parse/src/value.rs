@@ -0,0 +1,1070 @@
+use super::{
+    schema::ident,
+    *,
+};
+use chrono::{
+    DateTime,
+    FixedOffset,
+    NaiveDate,
+    NaiveDateTime,
+    NaiveTime,
+};
+use std::net::{
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+};
+
+/// A value in the format of the CQL binary protocol, as bound to a statement or returned in a row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CqlValue {
+    Null,
+    Bytes(Vec<u8>),
+}
+
+impl CqlValue {
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Null => None,
+            Self::Bytes(b) => Some(b),
+        }
+    }
+
+    /// Render the value as a literal of the given type.
+    /// User defined types can only be rendered with a schema, see [`Schema::decode_value`].
+    pub fn to_term(&self, data_type: &CqlType) -> anyhow::Result<Term> {
+        Codec::default().decode(self, data_type)
+    }
+
+    /// Write the value with its length prefix
+    fn write(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Self::Null => buffer.extend((-1i32).to_be_bytes()),
+            Self::Bytes(b) => {
+                buffer.extend((b.len() as i32).to_be_bytes());
+                buffer.extend(b);
+            }
+        }
+    }
+
+    /// Read a value with its length prefix
+    fn read(bytes: &mut &[u8]) -> anyhow::Result<Self> {
+        let len = i32::from_be_bytes(take(bytes)?);
+        Ok(if len < 0 {
+            Self::Null
+        } else {
+            Self::Bytes(take_slice(bytes, len as usize)?.to_vec())
+        })
+    }
+}
+
+impl From<Vec<u8>> for CqlValue {
+    fn from(b: Vec<u8>) -> Self {
+        Self::Bytes(b)
+    }
+}
+
+impl From<Option<Vec<u8>>> for CqlValue {
+    fn from(b: Option<Vec<u8>>) -> Self {
+        b.map(Self::Bytes).unwrap_or(Self::Null)
+    }
+}
+
+impl Term {
+    /// Evaluate a literal term as a value of the given type.
+    /// User defined types can only be evaluated with a schema, see [`Schema::encode_term`].
+    pub fn to_cql_value(&self, data_type: &CqlType) -> anyhow::Result<CqlValue> {
+        Codec::default().encode(self, data_type)
+    }
+}
+
+impl Schema {
+    /// Evaluate a literal term as a value of the given type, resolving user defined types from the schema.
+    pub fn encode_term(&self, term: &Term, data_type: &CqlType) -> anyhow::Result<CqlValue> {
+        Codec::new(self).encode(term, data_type)
+    }
+
+    /// Render a value of the given type as a literal, resolving user defined types from the schema.
+    pub fn decode_value(&self, value: &CqlValue, data_type: &CqlType) -> anyhow::Result<Term> {
+        Codec::new(self).decode(value, data_type)
+    }
+
+    /// Evaluate the values extracted from a normalized statement using the inferred types of its bind markers.
+    /// Bind markers which were already present in the statement are returned as `None`, so that their values can
+    /// be supplied separately.
+    pub fn bind_values<T: InferBindMarkers>(
+        &self,
+        normalized: &NormalizedStatement<T>,
+    ) -> anyhow::Result<Vec<Option<CqlValue>>> {
+        let types = self.bind_marker_types(&normalized.statement)?;
+        if types.len() != normalized.values.len() {
+            anyhow::bail!(
+                "Expected {} bind marker values, found {}",
+                types.len(),
+                normalized.values.len()
+            );
+        }
+        types
+            .iter()
+            .zip(normalized.values.iter())
+            .map(|(t, v)| match v {
+                Term::BindMarker(_) => Ok(None),
+                v => self
+                    .encode_term(v, &t.data_type)
+                    .map(Some)
+                    .map_err(|e| anyhow::anyhow!("Invalid value for {}: {}", t.name, e)),
+            })
+            .collect()
+    }
+}
+
+impl<T> NormalizedStatement<T> {
+    /// Evaluate the extracted values without a schema. This is only possible when each extracted literal has a
+    /// single possible encoding whatever the type of its column, which is the case of booleans, uuids, blobs and
+    /// nulls. Bind markers which were already present in the statement are returned as `None`.
+    pub fn unambiguous_values(&self) -> anyhow::Result<Vec<Option<CqlValue>>> {
+        self.values
+            .iter()
+            .map(|v| {
+                let native = match v {
+                    Term::BindMarker(_) => return Ok(None),
+                    Term::Constant(Constant::Null) => return Ok(Some(CqlValue::Null)),
+                    Term::Constant(Constant::Boolean(_)) => NativeType::Boolean,
+                    Term::Constant(Constant::Uuid(_)) => NativeType::Uuid,
+                    Term::Constant(Constant::Hex(_) | Constant::Blob(_)) => NativeType::Blob,
+                    v => anyhow::bail!("The type of {} cannot be determined without a schema", v),
+                };
+                v.to_cql_value(&CqlType::Native(native)).map(Some)
+            })
+            .collect()
+    }
+}
+
+/// Converts between terms and values, with an optional schema to resolve user defined types.
+#[derive(Copy, Clone, Default)]
+struct Codec<'a> {
+    schema: Option<&'a Schema>,
+    /// The keyspace of the enclosing user defined type, against which nested type names are resolved
+    keyspace: Option<&'a Name>,
+}
+
+impl<'a> Codec<'a> {
+    fn new(schema: &'a Schema) -> Self {
+        Self {
+            schema: Some(schema),
+            keyspace: None,
+        }
+    }
+
+    fn user_type(&self, name: &UserDefinedType) -> anyhow::Result<(Self, &'a UserTypeSchema)> {
+        let schema = self
+            .schema
+            .ok_or_else(|| anyhow::anyhow!("Cannot resolve user defined type {} without a schema", name))?;
+        let keyspace = name
+            .keyspace
+            .as_ref()
+            .or(self.keyspace)
+            .or(schema.current_keyspace.as_ref())
+            .ok_or_else(|| anyhow::anyhow!("No keyspace specified for {} and no keyspace in use", name))?;
+        let keyspace = schema
+            .keyspaces
+            .get(&ident(keyspace))
+            .ok_or_else(|| anyhow::anyhow!("Keyspace {} does not exist", keyspace))?;
+        let user_type = keyspace
+            .user_type(name.name.clone())
+            .ok_or_else(|| anyhow::anyhow!("Unknown user defined type {}", name))?;
+        Ok((
+            Self {
+                schema: Some(schema),
+                keyspace: Some(&keyspace.name),
+            },
+            user_type,
+        ))
+    }
+
+    fn encode(&self, term: &Term, data_type: &CqlType) -> anyhow::Result<CqlValue> {
+        Ok(match term {
+            Term::Constant(Constant::Null) => CqlValue::Null,
+            Term::Constant(c) => CqlValue::Bytes(encode_constant(c, data_type)?),
+            Term::Literal(l) => CqlValue::Bytes(self.encode_literal(l, data_type)?),
+            Term::ArithmeticOp {
+                lhs: None,
+                op: ArithmeticOp::Sub,
+                rhs,
+            } => match &**rhs {
+                Term::Constant(Constant::Integer(n)) => {
+                    CqlValue::Bytes(encode_constant(&Constant::Integer(negate(n)), data_type)?)
+                }
+                Term::Constant(Constant::Float(n)) => {
+                    CqlValue::Bytes(encode_constant(&Constant::Float(negate(n)), data_type)?)
+                }
+                _ => anyhow::bail!("Cannot evaluate {} as a literal value", term),
+            },
+            _ => anyhow::bail!("Cannot evaluate {} as a literal value", term),
+        })
+    }
+
+    fn encode_element(&self, term: &Term, data_type: &CqlType, buffer: &mut Vec<u8>) -> anyhow::Result<()> {
+        match self.encode(term, data_type)? {
+            CqlValue::Null => anyhow::bail!("Collections cannot contain null values"),
+            value => value.write(buffer),
+        }
+        Ok(())
+    }
+
+    fn encode_literal(&self, literal: &Literal, data_type: &CqlType) -> anyhow::Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        match (data_type, literal) {
+            (CqlType::Collection(c), Literal::Collection(l)) => match (&**c, l) {
+                (CollectionType::List(e), CollectionTypeLiteral::List(l)) => {
+                    buffer.extend((l.elements.len() as i32).to_be_bytes());
+                    for t in l.elements.iter() {
+                        self.encode_element(t, e, &mut buffer)?;
+                    }
+                }
+                (CollectionType::Set(e), CollectionTypeLiteral::Set(s)) => {
+                    buffer.extend((s.elements.len() as i32).to_be_bytes());
+                    for t in s.elements.iter() {
+                        self.encode_element(t, e, &mut buffer)?;
+                    }
+                }
+                (CollectionType::Map(k, v), CollectionTypeLiteral::Map(m)) => {
+                    buffer.extend((m.elements.len() as i32).to_be_bytes());
+                    for (key, value) in m.elements.iter() {
+                        self.encode_element(key, k, &mut buffer)?;
+                        self.encode_element(value, v, &mut buffer)?;
+                    }
+                }
+                _ => anyhow::bail!("Invalid {} value {}", data_type, literal),
+            },
+            (CqlType::Tuple(types), Literal::Tuple(t)) => {
+                if t.elements.len() > types.len() {
+                    anyhow::bail!("Invalid {} value {}: too many elements", data_type, literal);
+                }
+                for (e, t) in t.elements.iter().zip(types.iter()) {
+                    self.encode(e, t)?.write(&mut buffer);
+                }
+            }
+            (CqlType::UserDefined(name), Literal::UserDefined(u)) => {
+                let (codec, user_type) = self.user_type(name)?;
+                if let Some(field) = u.fields.keys().find(|f| user_type.field((*f).clone()).is_none()) {
+                    anyhow::bail!("Unknown field {} in user defined type {}", field, name);
+                }
+                for field in user_type.fields.iter() {
+                    match u.fields.iter().find(|(n, _)| ident(n) == ident(&field.name)) {
+                        Some((_, t)) => codec.encode(t, &field.data_type)?.write(&mut buffer),
+                        None => CqlValue::Null.write(&mut buffer),
+                    }
+                }
+            }
+            _ => anyhow::bail!("Invalid {} value {}", data_type, literal),
+        }
+        Ok(buffer)
+    }
+
+    fn decode(&self, value: &CqlValue, data_type: &CqlType) -> anyhow::Result<Term> {
+        let bytes = match value {
+            CqlValue::Null => return Ok(Term::Constant(Constant::Null)),
+            CqlValue::Bytes(b) => b.as_slice(),
+        };
+        Ok(match data_type {
+            CqlType::Native(n) => Term::Constant(decode_constant(bytes, *n)?),
+            CqlType::Custom(_) => Term::Constant(Constant::Blob(bytes.to_vec())),
+            _ => {
+                let mut reader = bytes;
+                let literal = self.decode_literal(&mut reader, data_type)?;
+                if !reader.is_empty() {
+                    anyhow::bail!("Invalid {} value: {} trailing bytes", data_type, reader.len());
+                }
+                Term::Literal(literal)
+            }
+        })
+    }
+
+    fn decode_elements(&self, bytes: &mut &[u8], data_type: &CqlType) -> anyhow::Result<Vec<Term>> {
+        let len = i32::from_be_bytes(take(bytes)?);
+        (0..len.max(0))
+            .map(|_| self.decode(&CqlValue::read(bytes)?, data_type))
+            .collect()
+    }
+
+    fn decode_literal(&self, bytes: &mut &[u8], data_type: &CqlType) -> anyhow::Result<Literal> {
+        Ok(match data_type {
+            CqlType::Collection(c) => Literal::Collection(match &**c {
+                CollectionType::List(e) => CollectionTypeLiteral::List(ListLiteral {
+                    elements: self.decode_elements(bytes, e)?,
+                }),
+                CollectionType::Set(e) => CollectionTypeLiteral::Set(SetLiteral {
+                    elements: self.decode_elements(bytes, e)?.into_iter().collect(),
+                }),
+                CollectionType::Map(k, v) => {
+                    let len = i32::from_be_bytes(take(bytes)?);
+                    let mut elements = BTreeMap::new();
+                    for _ in 0..len.max(0) {
+                        let key = self.decode(&CqlValue::read(bytes)?, k)?;
+                        let value = self.decode(&CqlValue::read(bytes)?, v)?;
+                        elements.insert(key, value);
+                    }
+                    CollectionTypeLiteral::Map(MapLiteral { elements })
+                }
+            }),
+            // Values may omit trailing elements and fields
+            CqlType::Tuple(types) => {
+                let mut elements = Vec::new();
+                for t in types.iter() {
+                    if bytes.is_empty() {
+                        break;
+                    }
+                    elements.push(self.decode(&CqlValue::read(bytes)?, t)?);
+                }
+                Literal::Tuple(TupleLiteral { elements })
+            }
+            CqlType::UserDefined(name) => {
+                let (codec, user_type) = self.user_type(name)?;
+                let mut fields = BTreeMap::new();
+                for field in user_type.fields.iter() {
+                    if bytes.is_empty() {
+                        break;
+                    }
+                    fields.insert(
+                        field.name.clone(),
+                        codec.decode(&CqlValue::read(bytes)?, &field.data_type)?,
+                    );
+                }
+                Literal::UserDefined(UserDefinedTypeLiteral { fields })
+            }
+            CqlType::Native(_) | CqlType::Custom(_) => unreachable!(),
+        })
+    }
+}
+
+fn negate(n: &str) -> String {
+    match n.strip_prefix('-') {
+        Some(n) => n.to_string(),
+        None => format!("-{}", n),
+    }
+}
+
+fn take<const N: usize>(bytes: &mut &[u8]) -> anyhow::Result<[u8; N]> {
+    Ok(take_slice(bytes, N)?.try_into().unwrap())
+}
+
+fn take_slice<'a>(bytes: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+    if bytes.len() < len {
+        anyhow::bail!("Unexpected end of value: expected {} bytes, found {}", len, bytes.len());
+    }
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+/// Read a value which must be exactly `N` bytes long
+fn fixed<const N: usize>(bytes: &[u8], data_type: NativeType) -> anyhow::Result<[u8; N]> {
+    bytes.try_into().map_err(|_| {
+        anyhow::anyhow!(
+            "Invalid {} value: expected {} bytes, found {}",
+            data_type,
+            N,
+            bytes.len()
+        )
+    })
+}
+
+fn integer<T: FromStr>(value: &str, data_type: NativeType) -> anyhow::Result<T> {
+    value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid {} value {}: out of range", data_type, value))
+}
+
+fn unix_epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+}
+
+const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+fn encode_constant(constant: &Constant, data_type: &CqlType) -> anyhow::Result<Vec<u8>> {
+    let native = match (data_type, constant) {
+        (CqlType::Native(n), _) => *n,
+        (CqlType::Custom(_), Constant::Blob(b) | Constant::Hex(b)) => return Ok(b.clone()),
+        _ => anyhow::bail!("Invalid {} value {}", data_type, constant),
+    };
+    let invalid = |reason: &dyn Display| anyhow::anyhow!("Invalid {} value {}: {}", native, constant, reason);
+    Ok(match (native, constant) {
+        (NativeType::Ascii, Constant::String(s)) => {
+            if !s.value.is_ascii() {
+                return Err(invalid(&"non-ASCII characters"));
+            }
+            s.value.as_bytes().to_vec()
+        }
+        (NativeType::Text | NativeType::Varchar, Constant::String(s)) => s.value.as_bytes().to_vec(),
+        (NativeType::Bigint | NativeType::Counter, Constant::Integer(i)) => {
+            integer::<i64>(i, native)?.to_be_bytes().to_vec()
+        }
+        (NativeType::Int, Constant::Integer(i)) => integer::<i32>(i, native)?.to_be_bytes().to_vec(),
+        (NativeType::Smallint, Constant::Integer(i)) => integer::<i16>(i, native)?.to_be_bytes().to_vec(),
+        (NativeType::Tinyint, Constant::Integer(i)) => integer::<i8>(i, native)?.to_be_bytes().to_vec(),
+        (NativeType::Varint, Constant::Integer(i)) => encode_varint(i).map_err(|e| invalid(&e))?,
+        (NativeType::Decimal, Constant::Integer(f) | Constant::Float(f)) => {
+            encode_decimal(f).map_err(|e| invalid(&e))?
+        }
+        (NativeType::Double, Constant::Integer(f) | Constant::Float(f)) => {
+            f.parse::<f64>().map_err(|e| invalid(&e))?.to_be_bytes().to_vec()
+        }
+        (NativeType::Float, Constant::Integer(f) | Constant::Float(f)) => {
+            let f = f.parse::<f64>().map_err(|e| invalid(&e))?;
+            if f.is_finite() && (f as f32).is_infinite() {
+                return Err(invalid(&"out of range"));
+            }
+            (f as f32).to_be_bytes().to_vec()
+        }
+        (NativeType::Boolean, Constant::Boolean(b)) => vec![*b as u8],
+        (NativeType::Uuid, Constant::Uuid(u)) => u.as_bytes().to_vec(),
+        (NativeType::Timeuuid, Constant::Uuid(u)) => {
+            if u.get_version_num() != 1 {
+                return Err(invalid(&"not a time-based UUID"));
+            }
+            u.as_bytes().to_vec()
+        }
+        (NativeType::Blob, Constant::Blob(b) | Constant::Hex(b)) => b.clone(),
+        (NativeType::Inet, Constant::String(s)) => match s.value.parse::<IpAddr>().map_err(|e| invalid(&e))? {
+            IpAddr::V4(ip) => ip.octets().to_vec(),
+            IpAddr::V6(ip) => ip.octets().to_vec(),
+        },
+        (NativeType::Date, Constant::String(s)) => {
+            let days = (s.value.parse::<NaiveDate>().map_err(|e| invalid(&e))? - unix_epoch()).num_days();
+            ((days + (1i64 << 31)) as u32).to_be_bytes().to_vec()
+        }
+        (NativeType::Date, Constant::Integer(i)) => integer::<u32>(i, native)?.to_be_bytes().to_vec(),
+        (NativeType::Time, Constant::String(s)) => {
+            let time = s.value.parse::<NaiveTime>().map_err(|e| invalid(&e))?;
+            (time - NaiveTime::MIN)
+                .num_nanoseconds()
+                .unwrap()
+                .to_be_bytes()
+                .to_vec()
+        }
+        (NativeType::Time, Constant::Integer(i)) => {
+            let nanos = integer::<i64>(i, native)?;
+            if !(0..NANOS_PER_DAY).contains(&nanos) {
+                return Err(invalid(&"out of range"));
+            }
+            nanos.to_be_bytes().to_vec()
+        }
+        (NativeType::Timestamp, Constant::Integer(i)) => integer::<i64>(i, native)?.to_be_bytes().to_vec(),
+        (NativeType::Timestamp, Constant::String(s)) => parse_timestamp(&s.value)
+            .ok_or_else(|| invalid(&"expected a date or a date and time, with an optional time zone"))?
+            .to_be_bytes()
+            .to_vec(),
+        (NativeType::Duration, Constant::String(s)) => encode_duration(&s.value).map_err(|e| invalid(&e))?,
+        _ => anyhow::bail!("Invalid {} value {}", native, constant),
+    })
+}
+
+fn decode_constant(bytes: &[u8], data_type: NativeType) -> anyhow::Result<Constant> {
+    Ok(match data_type {
+        NativeType::Ascii => {
+            if !bytes.is_ascii() {
+                anyhow::bail!("Invalid {} value: non-ASCII characters", data_type);
+            }
+            Constant::String(String::from_utf8(bytes.to_vec())?.into())
+        }
+        NativeType::Text | NativeType::Varchar => Constant::String(
+            String::from_utf8(bytes.to_vec())
+                .map_err(|e| anyhow::anyhow!("Invalid {} value: {}", data_type, e))?
+                .into(),
+        ),
+        NativeType::Bigint | NativeType::Counter => {
+            Constant::Integer(i64::from_be_bytes(fixed(bytes, data_type)?).to_string())
+        }
+        NativeType::Int => Constant::Integer(i32::from_be_bytes(fixed(bytes, data_type)?).to_string()),
+        NativeType::Smallint => Constant::Integer(i16::from_be_bytes(fixed(bytes, data_type)?).to_string()),
+        NativeType::Tinyint => Constant::Integer(i8::from_be_bytes(fixed(bytes, data_type)?).to_string()),
+        NativeType::Varint => {
+            if bytes.is_empty() {
+                anyhow::bail!("Invalid {} value: empty", data_type);
+            }
+            Constant::Integer(decode_varint(bytes))
+        }
+        NativeType::Decimal => {
+            let mut reader = bytes;
+            let scale = i32::from_be_bytes(take(&mut reader)?);
+            if reader.is_empty() {
+                anyhow::bail!("Invalid {} value: missing unscaled value", data_type);
+            }
+            decode_decimal(&decode_varint(reader), scale)
+        }
+        NativeType::Double => {
+            let f = f64::from_be_bytes(fixed(bytes, data_type)?);
+            if !f.is_finite() {
+                anyhow::bail!("Cannot render {} value {} as a literal", data_type, f);
+            }
+            Constant::Float(format_cql_f64(f))
+        }
+        NativeType::Float => {
+            let f = f32::from_be_bytes(fixed(bytes, data_type)?);
+            if !f.is_finite() {
+                anyhow::bail!("Cannot render {} value {} as a literal", data_type, f);
+            }
+            Constant::Float(format_cql_f32(f))
+        }
+        NativeType::Boolean => Constant::Boolean(u8::from_be_bytes(fixed(bytes, data_type)?) != 0),
+        NativeType::Uuid | NativeType::Timeuuid => Constant::Uuid(Uuid::from_bytes(fixed(bytes, data_type)?)),
+        NativeType::Blob => Constant::Blob(bytes.to_vec()),
+        NativeType::Inet => {
+            let ip = match bytes.len() {
+                4 => IpAddr::V4(Ipv4Addr::from(fixed::<4>(bytes, data_type)?)),
+                16 => IpAddr::V6(Ipv6Addr::from(fixed::<16>(bytes, data_type)?)),
+                len => anyhow::bail!("Invalid {} value: expected 4 or 16 bytes, found {}", data_type, len),
+            };
+            Constant::String(ip.to_string().into())
+        }
+        NativeType::Date => {
+            let days = u32::from_be_bytes(fixed(bytes, data_type)?);
+            // Dates outside of the supported calendar range can only be written as integers
+            match unix_epoch().checked_add_signed(chrono::Duration::days(days as i64 - (1i64 << 31))) {
+                Some(date) => Constant::String(date.to_string().into()),
+                None => Constant::Integer(days.to_string()),
+            }
+        }
+        NativeType::Time => {
+            let nanos = i64::from_be_bytes(fixed(bytes, data_type)?);
+            if !(0..NANOS_PER_DAY).contains(&nanos) {
+                anyhow::bail!("Invalid {} value {}: out of range", data_type, nanos);
+            }
+            let time = NaiveTime::from_num_seconds_from_midnight_opt(
+                (nanos / 1_000_000_000) as u32,
+                (nanos % 1_000_000_000) as u32,
+            )
+            .unwrap();
+            Constant::String(time.to_string().into())
+        }
+        NativeType::Timestamp => {
+            let millis = i64::from_be_bytes(fixed(bytes, data_type)?);
+            match DateTime::from_timestamp_millis(millis) {
+                Some(ts) => Constant::String(format!("{}+0000", ts.naive_utc()).into()),
+                None => Constant::Integer(millis.to_string()),
+            }
+        }
+        NativeType::Duration => Constant::String(decode_duration(bytes)?.into()),
+    })
+}
+
+/// Parse a timestamp in one of the formats accepted by the database. Timestamps without a time zone are in UTC.
+fn parse_timestamp(s: &str) -> Option<i64> {
+    const FORMATS: [&str; 4] = [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ];
+    let s = s.trim();
+    if let Ok(ts) = DateTime::<FixedOffset>::parse_from_rfc3339(s) {
+        return Some(ts.timestamp_millis());
+    }
+    for format in FORMATS {
+        if let Ok(ts) = DateTime::<FixedOffset>::parse_from_str(s, &format!("{}%#z", format)) {
+            return Some(ts.timestamp_millis());
+        }
+        if let Ok(ts) = NaiveDateTime::parse_from_str(s, format) {
+            return Some(ts.and_utc().timestamp_millis());
+        }
+    }
+    s.parse::<NaiveDate>()
+        .ok()
+        .map(|d| d.and_time(NaiveTime::MIN).and_utc().timestamp_millis())
+}
+
+/// Encode a decimal integer of any size as a minimal big-endian two's complement integer.
+fn encode_varint(value: &str) -> anyhow::Result<Vec<u8>> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(d) => (true, d),
+        None => (false, value),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        anyhow::bail!("not an integer");
+    }
+    // Little-endian magnitude
+    let mut bytes = vec![0u8];
+    for d in digits.bytes() {
+        let mut carry = (d - b'0') as u32;
+        for b in bytes.iter_mut() {
+            let v = *b as u32 * 10 + carry;
+            *b = v as u8;
+            carry = v >> 8;
+        }
+        if carry > 0 {
+            bytes.push(carry as u8);
+        }
+    }
+    bytes.push(0);
+    if negative {
+        let mut carry = true;
+        for b in bytes.iter_mut() {
+            *b = !*b;
+            if carry {
+                (*b, carry) = b.overflowing_add(1);
+            }
+        }
+    }
+    while bytes.len() > 1 {
+        let (last, prev) = (bytes[bytes.len() - 1], bytes[bytes.len() - 2]);
+        if (last == 0 && prev & 0x80 == 0) || (last == 0xff && prev & 0x80 != 0) {
+            bytes.pop();
+        } else {
+            break;
+        }
+    }
+    bytes.reverse();
+    Ok(bytes)
+}
+
+fn decode_varint(bytes: &[u8]) -> String {
+    let negative = bytes.first().is_some_and(|b| b & 0x80 != 0);
+    let mut magnitude = bytes.to_vec();
+    if negative {
+        let mut carry = true;
+        for b in magnitude.iter_mut().rev() {
+            *b = !*b;
+            if carry {
+                (*b, carry) = b.overflowing_add(1);
+            }
+        }
+    }
+    let mut digits = Vec::new();
+    while magnitude.iter().any(|b| *b != 0) {
+        let mut rem = 0u32;
+        for b in magnitude.iter_mut() {
+            let v = rem << 8 | *b as u32;
+            *b = (v / 10) as u8;
+            rem = v % 10;
+        }
+        digits.push(b'0' + rem as u8);
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    if negative {
+        digits.push(b'-');
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap()
+}
+
+fn encode_decimal(value: &str) -> anyhow::Result<Vec<u8>> {
+    let (mantissa, exponent) = match value.find(['e', 'E']) {
+        Some(i) => (&value[..i], value[i + 1..].parse::<i64>()?),
+        None => (value, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let unscaled = format!("{}{}", int, frac);
+    let digits = unscaled.strip_prefix('-').unwrap_or(&unscaled);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        anyhow::bail!("not a decimal number");
+    }
+    let scale = i32::try_from(frac.len() as i64 - exponent).map_err(|_| anyhow::anyhow!("scale out of range"))?;
+    let mut res = scale.to_be_bytes().to_vec();
+    res.extend(encode_varint(&unscaled)?);
+    Ok(res)
+}
+
+/// Render a decimal so that it is encoded back with the same scale
+fn decode_decimal(unscaled: &str, scale: i32) -> Constant {
+    let (sign, digits) = match unscaled.strip_prefix('-') {
+        Some(d) => ("-", d),
+        None => ("", unscaled),
+    };
+    let len = digits.len() as i64;
+    let scale = scale as i64;
+    if scale == 0 {
+        Constant::Integer(unscaled.to_string())
+    } else if scale > 0 && scale < len {
+        let (int, frac) = digits.split_at((len - scale) as usize);
+        Constant::Float(format!("{}{}.{}", sign, int, frac))
+    } else {
+        Constant::Float(format!("{}0.{}E{}", sign, digits, len - scale))
+    }
+}
+
+/// Encode a duration as its months, days and nanoseconds, each as a zigzag encoded vint.
+fn encode_duration(value: &str) -> anyhow::Result<Vec<u8>> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(v) => (true, v),
+        None => (false, value),
+    };
+    let d = value.parse::<DurationLiteral>()?;
+    if d.months < 0 || d.days < 0 || d.nanos < 0 {
+        anyhow::bail!("out of range");
+    }
+    let sign = if negative { -1 } else { 1 };
+    let mut res = Vec::new();
+    for v in [sign * d.months as i64, sign * d.days as i64, sign * d.nanos] {
+        write_vint(v, &mut res);
+    }
+    Ok(res)
+}
+
+fn decode_duration(bytes: &[u8]) -> anyhow::Result<String> {
+    let mut reader = bytes;
+    let (months, days, nanos) = (
+        read_vint(&mut reader)?,
+        read_vint(&mut reader)?,
+        read_vint(&mut reader)?,
+    );
+    if !reader.is_empty() {
+        anyhow::bail!(
+            "Invalid {} value: {} trailing bytes",
+            NativeType::Duration,
+            reader.len()
+        );
+    }
+    let negative = months < 0 || days < 0 || nanos < 0;
+    if negative && (months > 0 || days > 0 || nanos > 0) {
+        anyhow::bail!(
+            "Invalid {} value: components have different signs",
+            NativeType::Duration
+        );
+    }
+    let d = DurationLiteral {
+        months: i32::try_from(months.abs())?,
+        days: i32::try_from(days.abs())?,
+        nanos: nanos
+            .checked_abs()
+            .ok_or_else(|| anyhow::anyhow!("Invalid duration value: out of range"))?,
+    };
+    Ok(format!("{}{}", if negative { "-" } else { "" }, d))
+}
+
+/// Write a zigzag encoded vint, whose first byte's leading ones count the extra bytes to read.
+fn write_vint(value: i64, buffer: &mut Vec<u8>) {
+    let v = ((value << 1) ^ (value >> 63)) as u64;
+    let size = (639 - 9 * (v | 1).leading_zeros() as usize) >> 6;
+    let bytes = v.to_be_bytes();
+    if size == 9 {
+        buffer.push(0xff);
+        buffer.extend(bytes);
+    } else {
+        let start = buffer.len();
+        buffer.extend(&bytes[8 - size..]);
+        buffer[start] |= !(0xffu8 >> (size - 1));
+    }
+}
+
+fn read_vint(bytes: &mut &[u8]) -> anyhow::Result<i64> {
+    let [first] = take::<1>(bytes)?;
+    let extra = first.leading_ones() as usize;
+    let mut v = if extra == 8 {
+        0
+    } else {
+        (first & (0xff >> extra)) as u64
+    };
+    for b in take_slice(bytes, extra)? {
+        v = v << 8 | *b as u64;
+    }
+    Ok((v >> 1) as i64 ^ -((v & 1) as i64))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round_trip(term: &str, data_type: &str) -> (Vec<u8>, String) {
+        let data_type = data_type.parse::<CqlType>().unwrap();
+        let value = term.parse::<Term>().unwrap().to_cql_value(&data_type).unwrap();
+        let rendered = value.to_term(&data_type).unwrap();
+        assert_eq!(rendered.to_cql_value(&data_type).unwrap(), value);
+        (value.as_bytes().unwrap().to_vec(), rendered.to_string())
+    }
+
+    fn error(term: &str, data_type: &str) -> String {
+        term.parse::<Term>()
+            .unwrap()
+            .to_cql_value(&data_type.parse().unwrap())
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_native_values() {
+        assert_eq!(round_trip("'abc'", "ascii"), (b"abc".to_vec(), "'abc'".to_string()));
+        assert_eq!(
+            round_trip("-2", "int"),
+            (vec![0xff, 0xff, 0xff, 0xfe], "-2".to_string())
+        );
+        assert_eq!(round_trip("-128", "tinyint"), (vec![0x80], "-128".to_string()));
+        assert_eq!(round_trip("1.5", "double").1, "1.5");
+        assert_eq!(
+            round_trip("1", "float"),
+            (1f32.to_be_bytes().to_vec(), "1.0".to_string())
+        );
+        assert_eq!(round_trip("true", "boolean"), (vec![1], "TRUE".to_string()));
+        assert_eq!(round_trip("0xcafe", "blob"), (vec![0xca, 0xfe], "0xcafe".to_string()));
+        assert_eq!(
+            round_trip("'127.0.0.1'", "inet"),
+            (vec![127, 0, 0, 1], "'127.0.0.1'".to_string())
+        );
+        assert_eq!(round_trip("'::1'", "inet").1, "'::1'");
+        assert_eq!(
+            round_trip("'1970-01-02'", "date"),
+            (vec![0x80, 0, 0, 1], "'1970-01-02'".to_string())
+        );
+        assert_eq!(round_trip("'1969-12-31'", "date").0, vec![0x7f, 0xff, 0xff, 0xff]);
+        assert_eq!(round_trip("'08:12:54.123456789'", "time").1, "'08:12:54.123456789'");
+        assert_eq!(
+            round_trip("'2011-02-03 04:05:00+0100'", "timestamp"),
+            (
+                1296702300000i64.to_be_bytes().to_vec(),
+                "'2011-02-03 03:05:00+0000'".to_string()
+            )
+        );
+        assert_eq!(
+            round_trip("'2011-02-03T04:05:06.789Z'", "timestamp").1,
+            "'2011-02-03 04:05:06.789+0000'"
+        );
+        assert_eq!(
+            round_trip("'2011-02-03'", "timestamp").0,
+            1296691200000i64.to_be_bytes().to_vec()
+        );
+        assert_eq!(round_trip("1296691200000", "timestamp").1, "'2011-02-03 00:00:00+0000'");
+        assert_eq!(
+            round_trip("'1h30m'", "duration"),
+            (
+                vec![0, 0, 0xfc, 0x09, 0xd2, 0x92, 0x29, 0xe0, 0],
+                "'5400000000000ns'".to_string()
+            )
+        );
+        assert_eq!(
+            round_trip("'-P1Y2D'", "duration"),
+            (vec![23, 3, 0], "'-12mo2d'".to_string())
+        );
+        assert_eq!(
+            round_trip("123456789012345678901234567890", "varint").1,
+            "123456789012345678901234567890"
+        );
+        assert_eq!(round_trip("-129", "varint").0, vec![0xff, 0x7f]);
+        assert_eq!(round_trip("128", "varint").0, vec![0x00, 0x80]);
+        assert_eq!(round_trip("0", "varint").0, vec![0]);
+        for invalid in ["+5", "", "-", "1a", "--1", "١"] {
+            assert!(encode_varint(invalid).is_err(), "{}", invalid);
+        }
+        assert_eq!(
+            round_trip("-12.50", "decimal"),
+            (vec![0, 0, 0, 2, 0xfb, 0x1e], "-12.50".to_string())
+        );
+        assert_eq!(
+            round_trip("1.5E3", "decimal"),
+            (vec![0xff, 0xff, 0xff, 0xfe, 15], "0.15E4".to_string())
+        );
+        assert_eq!(round_trip("0.001", "decimal").1, "0.1E-2");
+        assert_eq!(round_trip("42", "decimal").1, "42");
+        assert_eq!(round_trip("-5", "bigint").0, (-5i64).to_be_bytes().to_vec());
+        assert_eq!(
+            Term::negative(Term::constant(5))
+                .to_cql_value(&NativeType::Int.into())
+                .unwrap(),
+            CqlValue::Bytes((-5i32).to_be_bytes().to_vec())
+        );
+        assert_eq!(
+            "NULL"
+                .parse::<Term>()
+                .unwrap()
+                .to_cql_value(&NativeType::Int.into())
+                .unwrap(),
+            CqlValue::Null
+        );
+        assert_eq!(
+            CqlValue::Null.to_term(&NativeType::Int.into()).unwrap().to_string(),
+            "NULL"
+        );
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        assert_eq!(error("128", "tinyint"), "Invalid TINYINT value 128: out of range");
+        assert_eq!(error("2147483648", "int"), "Invalid INT value 2147483648: out of range");
+        assert_eq!(error("1.0e39", "float"), "Invalid FLOAT value 1.0e39: out of range");
+        assert_eq!(error("'é'", "ascii"), "Invalid ASCII value 'é': non-ASCII characters");
+        assert_eq!(error("'cafe'", "blob"), "Invalid BLOB value 'cafe'");
+        assert_eq!(error("1.5", "int"), "Invalid INT value 1.5");
+        assert!(error("'2021-02-30'", "date").starts_with("Invalid DATE value '2021-02-30': "));
+        assert_eq!(error("-1", "date"), "Invalid DATE value -1: out of range");
+        assert!(error("'25:00:00'", "time").starts_with("Invalid TIME value '25:00:00': "));
+        assert_eq!(
+            error("86400000000000", "time"),
+            "Invalid TIME value 86400000000000: out of range"
+        );
+        assert!(error("'yesterday'", "timestamp").starts_with("Invalid TIMESTAMP value 'yesterday': "));
+        assert!(error("'1h3x'", "duration").starts_with("Invalid DURATION value '1h3x': "));
+        assert!(error("'localhost'", "inet").starts_with("Invalid INET value 'localhost': "));
+        assert_eq!(
+            error("a8098c1a-f86e-41ab-ba49-b1a8f4ba8f0b", "timeuuid"),
+            "Invalid TIMEUUID value a8098c1a-f86e-41ab-ba49-b1a8f4ba8f0b: not a time-based UUID"
+        );
+        assert_eq!(
+            error("[1, NULL]", "list<int>"),
+            "Collections cannot contain null values"
+        );
+        assert_eq!(
+            error("(1, 2, 3)", "tuple<int, int>"),
+            "Invalid TUPLE<INT, INT> value (1, 2, 3): too many elements"
+        );
+        assert_eq!(error("[1]", "set<int>"), "Invalid SET<INT> value [1]");
+        assert_eq!(error("now()", "timeuuid"), "Cannot evaluate now() as a literal value");
+        assert_eq!(
+            error("{first_name: 'a'}", "person"),
+            "Cannot resolve user defined type person without a schema"
+        );
+
+        let int = CqlType::from(NativeType::Int);
+        assert_eq!(
+            CqlValue::Bytes(vec![0, 1]).to_term(&int).unwrap_err().to_string(),
+            "Invalid INT value: expected 4 bytes, found 2"
+        );
+        assert_eq!(
+            CqlValue::Bytes(f64::NAN.to_be_bytes().to_vec())
+                .to_term(&NativeType::Double.into())
+                .unwrap_err()
+                .to_string(),
+            "Cannot render DOUBLE value NaN as a literal"
+        );
+        assert_eq!(
+            CqlValue::Bytes(vec![0, 0, 0, 1, 0, 0, 0, 1, 0])
+                .to_term(&"list<int>".parse().unwrap())
+                .unwrap_err()
+                .to_string(),
+            "Invalid INT value: expected 4 bytes, found 1"
+        );
+        assert_eq!(
+            CqlValue::Bytes(vec![2, 1, 0])
+                .to_term(&NativeType::Duration.into())
+                .unwrap_err()
+                .to_string(),
+            "Invalid DURATION value: components have different signs"
+        );
+    }
+
+    #[test]
+    fn test_literal_values() {
+        assert_eq!(
+            round_trip("[1, 2]", "list<int>"),
+            (
+                vec![0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2],
+                "[1, 2]".to_string()
+            )
+        );
+        assert_eq!(round_trip("{'a', 'b'}", "set<text>").1, "{'a', 'b'}");
+        assert_eq!(round_trip("{'a': 1}", "map<text, bigint>").1, "{'a': 1}");
+        assert_eq!(
+            CqlValue::Bytes(vec![0, 0, 0, 0])
+                .to_term(&"list<text>".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "[]"
+        );
+        assert_eq!(
+            round_trip("(1, 'a', NULL)", "tuple<int, text, int>").1,
+            "(1, 'a', NULL)"
+        );
+        assert_eq!(
+            round_trip("{'k': [(1, '2021-01-01')]}", "map<text, list<tuple<int, date>>>").1,
+            "{'k': [(1, '2021-01-01')]}"
+        );
+
+        let schema = Schema::from_script(
+            r#"
+            CREATE KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+            CREATE KEYSPACE other WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+            CREATE TYPE movies.name (first text, last text);
+            CREATE TYPE movies.person (name name, age int, emails set<text>);
+            USE other;
+            "#,
+        )
+        .unwrap();
+        let person = "movies.person".parse::<CqlType>().unwrap();
+        let value = schema
+            .encode_term(&"{age: 42, name: {last: 'b', first: 'a'}}".parse().unwrap(), &person)
+            .unwrap();
+        assert_eq!(
+            value.as_bytes().unwrap(),
+            vec![0, 0, 0, 10, 0, 0, 0, 1, b'a', 0, 0, 0, 1, b'b', 0, 0, 0, 4, 0, 0, 0, 42, 0xff, 0xff, 0xff, 0xff]
+        );
+        assert_eq!(
+            schema.decode_value(&value, &person).unwrap().to_string(),
+            "{age: 42, emails: NULL, name: {first: 'a', last: 'b'}}"
+        );
+        assert_eq!(
+            schema
+                .encode_term(&"{title: 'a'}".parse().unwrap(), &person)
+                .unwrap_err()
+                .to_string(),
+            "Unknown field title in user defined type movies.person"
+        );
+        assert_eq!(
+            schema
+                .encode_term(&"{first: 'a'}".parse().unwrap(), &"name".parse().unwrap())
+                .unwrap_err()
+                .to_string(),
+            "Unknown user defined type name"
+        );
+    }
+
+    #[test]
+    fn test_bind_values() {
+        let schema = Schema::from_script(
+            r#"
+            CREATE KEYSPACE movies WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+            USE movies;
+            CREATE TABLE nerd_movies (movie text, year int, released date, tags set<text>, PRIMARY KEY (movie, year));
+            "#,
+        )
+        .unwrap();
+        let normalized = "INSERT INTO nerd_movies (movie, year, released, tags) VALUES ('Alien', ?, '1979-05-25', {'scifi'}) USING TTL 86400"
+            .parse::<DataManipulationStatement>()
+            .unwrap()
+            .normalize();
+        let values = schema.bind_values(&normalized).unwrap();
+        assert_eq!(
+            values,
+            vec![
+                Some(CqlValue::Bytes(b"Alien".to_vec())),
+                None,
+                Some(CqlValue::Bytes(vec![0x80, 0, 0x0d, 0x67])),
+                Some(CqlValue::Bytes(vec![
+                    0, 0, 0, 1, 0, 0, 0, 5, b's', b'c', b'i', b'f', b'i'
+                ])),
+                Some(CqlValue::Bytes(86400i32.to_be_bytes().to_vec())),
+            ]
+        );
+        let normalized = "INSERT INTO nerd_movies (movie, year) VALUES ('Alien', 1.0e100)"
+            .parse::<DataManipulationStatement>()
+            .unwrap()
+            .normalize();
+        assert!(schema.bind_values(&normalized).is_err());
+    }
+
+    #[test]
+    fn test_unambiguous_values() {
+        let normalized = "UPDATE t SET flag = true, data = 0xcafe, owner = null WHERE id = f81d4fae-7dec-11d0-a765-00a0c91e6bf6 AND part = ?"
+            .parse::<DataManipulationStatement>()
+            .unwrap()
+            .normalize();
+        assert_eq!(
+            normalized.unambiguous_values().unwrap(),
+            vec![
+                Some(CqlValue::Bytes(vec![1])),
+                Some(CqlValue::Bytes(vec![0xca, 0xfe])),
+                Some(CqlValue::Null),
+                Some(CqlValue::Bytes(vec![
+                    0xf8, 0x1d, 0x4f, 0xae, 0x7d, 0xec, 0x11, 0xd0, 0xa7, 0x65, 0x00, 0xa0, 0xc9, 0x1e, 0x6b, 0xf6
+                ])),
+                None,
+            ]
+        );
+        // Integers may be bound to any of the integer types
+        let normalized = "SELECT * FROM t WHERE id = 1"
+            .parse::<DataManipulationStatement>()
+            .unwrap()
+            .normalize();
+        assert!(normalized.unambiguous_values().is_err());
+    }
+}
@@ -0,0 +1,416 @@
+//! Traversal of statement syntax trees, in the style of `syn::visit`.
+//!
+//! Implement [`Visit`] or [`VisitMut`] and override the methods for the nodes of interest. The default
+//! implementations walk into the node's children, so an overriding method should call `node.walk(self)` (or
+//! `node.walk_mut(self)`) itself to keep descending.
+
+use super::*;
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+    HashMap,
+};
+
+/// A syntax tree node which can be traversed by a [`Visit`] or [`VisitMut`] visitor.
+pub trait Visitable {
+    /// Visit this node with the visitor method matching its type.
+    fn accept<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V);
+
+    /// Visit this node with the mutable visitor method matching its type.
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V);
+
+    /// Visit each child of this node.
+    fn walk<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V);
+
+    /// Mutably visit each child of this node.
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V);
+}
+
+macro_rules! visitors {
+    ($($t:ident => $visit:ident, $visit_mut:ident;)*) => {
+        /// A visitor over a borrowed syntax tree.
+        pub trait Visit<'ast> {
+            $(
+                fn $visit(&mut self, node: &'ast $t) {
+                    node.walk(self)
+                }
+            )*
+        }
+
+        /// A visitor which can modify a syntax tree in place.
+        pub trait VisitMut {
+            $(
+                fn $visit_mut(&mut self, node: &mut $t) {
+                    node.walk_mut(self)
+                }
+            )*
+        }
+    };
+}
+
+visitors! {
+    AlterKeyspaceStatement => visit_alter_keyspace_statement, visit_alter_keyspace_statement_mut;
+    AlterMaterializedViewStatement => visit_alter_materialized_view_statement, visit_alter_materialized_view_statement_mut;
+    AlterRoleStatement => visit_alter_role_statement, visit_alter_role_statement_mut;
+    AlterServiceLevelStatement => visit_alter_service_level_statement, visit_alter_service_level_statement_mut;
+    AlterTableInstruction => visit_alter_table_instruction, visit_alter_table_instruction_mut;
+    AlterTableStatement => visit_alter_table_statement, visit_alter_table_statement_mut;
+    AlterTypeInstruction => visit_alter_type_instruction, visit_alter_type_instruction_mut;
+    AlterUserDefinedTypeStatement => visit_alter_user_defined_type_statement, visit_alter_user_defined_type_statement_mut;
+    AlterUserStatement => visit_alter_user_statement, visit_alter_user_statement_mut;
+    ArithmeticOp => visit_arithmetic_op, visit_arithmetic_op_mut;
+    Assignment => visit_assignment, visit_assignment_mut;
+    AttachServiceLevelStatement => visit_attach_service_level_statement, visit_attach_service_level_statement_mut;
+    BatchKind => visit_batch_kind, visit_batch_kind_mut;
+    BatchStatement => visit_batch_statement, visit_batch_statement_mut;
+    BindMarker => visit_bind_marker, visit_bind_marker_mut;
+    Caching => visit_caching, visit_caching_mut;
+    CdcDelta => visit_cdc_delta, visit_cdc_delta_mut;
+    CdcPreimage => visit_cdc_preimage, visit_cdc_preimage_mut;
+    ChangeDataCapture => visit_change_data_capture, visit_change_data_capture_mut;
+    CollectionType => visit_collection_type, visit_collection_type_mut;
+    CollectionTypeLiteral => visit_collection_type_literal, visit_collection_type_literal_mut;
+    ColumnDefault => visit_column_default, visit_column_default_mut;
+    ColumnDefinition => visit_column_definition, visit_column_definition_mut;
+    ColumnOrder => visit_column_order, visit_column_order_mut;
+    Compaction => visit_compaction, visit_compaction_mut;
+    Compression => visit_compression, visit_compression_mut;
+    Condition => visit_condition, visit_condition_mut;
+    Constant => visit_constant, visit_constant_mut;
+    CqlType => visit_cql_type, visit_cql_type_mut;
+    CreateAggregateFunctionStatement => visit_create_aggregate_function_statement, visit_create_aggregate_function_statement_mut;
+    CreateFunctionStatement => visit_create_function_statement, visit_create_function_statement_mut;
+    CreateIndexStatement => visit_create_index_statement, visit_create_index_statement_mut;
+    CreateKeyspaceStatement => visit_create_keyspace_statement, visit_create_keyspace_statement_mut;
+    CreateMaterializedViewStatement => visit_create_materialized_view_statement, visit_create_materialized_view_statement_mut;
+    CreateRoleStatement => visit_create_role_statement, visit_create_role_statement_mut;
+    CreateServiceLevelStatement => visit_create_service_level_statement, visit_create_service_level_statement_mut;
+    CreateTableStatement => visit_create_table_statement, visit_create_table_statement_mut;
+    CreateTriggerStatement => visit_create_trigger_statement, visit_create_trigger_statement_mut;
+    CreateUserDefinedTypeStatement => visit_create_user_defined_type_statement, visit_create_user_defined_type_statement_mut;
+    CreateUserStatement => visit_create_user_statement, visit_create_user_statement_mut;
+    DataDefinitionStatement => visit_data_definition_statement, visit_data_definition_statement_mut;
+    DataManipulationStatement => visit_data_manipulation_statement, visit_data_manipulation_statement_mut;
+    DeleteStatement => visit_delete_statement, visit_delete_statement_mut;
+    DescribeKeyspaceStatement => visit_describe_keyspace_statement, visit_describe_keyspace_statement_mut;
+    DescribeMaterializedViewStatement => visit_describe_materialized_view_statement, visit_describe_materialized_view_statement_mut;
+    DescribeSchemaStatement => visit_describe_schema_statement, visit_describe_schema_statement_mut;
+    DescribeStatement => visit_describe_statement, visit_describe_statement_mut;
+    DescribeTableStatement => visit_describe_table_statement, visit_describe_table_statement_mut;
+    DetachServiceLevelStatement => visit_detach_service_level_statement, visit_detach_service_level_statement_mut;
+    DropAggregateFunctionStatement => visit_drop_aggregate_function_statement, visit_drop_aggregate_function_statement_mut;
+    DropFunctionStatement => visit_drop_function_statement, visit_drop_function_statement_mut;
+    DropIndexStatement => visit_drop_index_statement, visit_drop_index_statement_mut;
+    DropKeyspaceStatement => visit_drop_keyspace_statement, visit_drop_keyspace_statement_mut;
+    DropMaterializedViewStatement => visit_drop_materialized_view_statement, visit_drop_materialized_view_statement_mut;
+    DropRoleStatement => visit_drop_role_statement, visit_drop_role_statement_mut;
+    DropServiceLevelStatement => visit_drop_service_level_statement, visit_drop_service_level_statement_mut;
+    DropTableStatement => visit_drop_table_statement, visit_drop_table_statement_mut;
+    DropTriggerStatement => visit_drop_trigger_statement, visit_drop_trigger_statement_mut;
+    DropUserDefinedTypeStatement => visit_drop_user_defined_type_statement, visit_drop_user_defined_type_statement_mut;
+    DropUserStatement => visit_drop_user_statement, visit_drop_user_statement_mut;
+    DurationLiteral => visit_duration_literal, visit_duration_literal_mut;
+    FieldDefinition => visit_field_definition, visit_field_definition_mut;
+    FunctionCall => visit_function_call, visit_function_call_mut;
+    FunctionDeclaration => visit_function_declaration, visit_function_declaration_mut;
+    FunctionReference => visit_function_reference, visit_function_reference_mut;
+    FunctionSignature => visit_function_signature, visit_function_signature_mut;
+    GrantPermissionStatement => visit_grant_permission_statement, visit_grant_permission_statement_mut;
+    GrantRoleStatement => visit_grant_role_statement, visit_grant_role_statement_mut;
+    GroupByClause => visit_group_by_clause, visit_group_by_clause_mut;
+    IfClause => visit_if_clause, visit_if_clause_mut;
+    IndexClass => visit_index_class, visit_index_class_mut;
+    IndexIdentifier => visit_index_identifier, visit_index_identifier_mut;
+    IndexQualifier => visit_index_qualifier, visit_index_qualifier_mut;
+    InsertKind => visit_insert_kind, visit_insert_kind_mut;
+    InsertStatement => visit_insert_statement, visit_insert_statement_mut;
+    JavaTimeUnit => visit_java_time_unit, visit_java_time_unit_mut;
+    Keys => visit_keys, visit_keys_mut;
+    KeyspaceOpts => visit_keyspace_opts, visit_keyspace_opts_mut;
+    KeyspaceQualifiedName => visit_keyspace_qualified_name, visit_keyspace_qualified_name_mut;
+    LeveledCompactionStrategy => visit_leveled_compaction_strategy, visit_leveled_compaction_strategy_mut;
+    Limit => visit_limit, visit_limit_mut;
+    ListAttachedServiceLevelsStatement => visit_list_attached_service_levels_statement, visit_list_attached_service_levels_statement_mut;
+    ListLiteral => visit_list_literal, visit_list_literal_mut;
+    ListPermissionsStatement => visit_list_permissions_statement, visit_list_permissions_statement_mut;
+    ListRolesStatement => visit_list_roles_statement, visit_list_roles_statement_mut;
+    ListServiceLevelsStatement => visit_list_service_levels_statement, visit_list_service_levels_statement_mut;
+    ListUsersStatement => visit_list_users_statement, visit_list_users_statement_mut;
+    LitStr => visit_lit_str, visit_lit_str_mut;
+    LitStrKind => visit_lit_str_kind, visit_lit_str_kind_mut;
+    Literal => visit_literal, visit_literal_mut;
+    MapLiteral => visit_map_literal, visit_map_literal_mut;
+    MaterializedViewStatement => visit_materialized_view_statement, visit_materialized_view_statement_mut;
+    ModificationStatement => visit_modification_statement, visit_modification_statement_mut;
+    Name => visit_name, visit_name_mut;
+    NativeType => visit_native_type, visit_native_type_mut;
+    OnNullInput => visit_on_null_input, visit_on_null_input_mut;
+    Operator => visit_operator, visit_operator_mut;
+    Order => visit_order, visit_order_mut;
+    OrderByClause => visit_order_by_clause, visit_order_by_clause_mut;
+    PartitionKey => visit_partition_key, visit_partition_key_mut;
+    PerPartitionRateLimit => visit_per_partition_rate_limit, visit_per_partition_rate_limit_mut;
+    Permission => visit_permission, visit_permission_mut;
+    PermissionKind => visit_permission_kind, visit_permission_kind_mut;
+    PermissionStatement => visit_permission_statement, visit_permission_statement_mut;
+    PrimaryKey => visit_primary_key, visit_primary_key_mut;
+    Relation => visit_relation, visit_relation_mut;
+    Replication => visit_replication, visit_replication_mut;
+    Resource => visit_resource, visit_resource_mut;
+    RevokePermissionStatement => visit_revoke_permission_statement, visit_revoke_permission_statement_mut;
+    RevokeRoleStatement => visit_revoke_role_statement, visit_revoke_role_statement_mut;
+    RoleOpt => visit_role_opt, visit_role_opt_mut;
+    RoleStatement => visit_role_statement, visit_role_statement_mut;
+    RowsPerPartition => visit_rows_per_partition, visit_rows_per_partition_mut;
+    SecondaryIndexStatement => visit_secondary_index_statement, visit_secondary_index_statement_mut;
+    SelectClause => visit_select_clause, visit_select_clause_mut;
+    SelectStatement => visit_select_statement, visit_select_statement_mut;
+    Selector => visit_selector, visit_selector_mut;
+    SelectorFunction => visit_selector_function, visit_selector_function_mut;
+    SelectorKind => visit_selector_kind, visit_selector_kind_mut;
+    ServiceLevelOpt => visit_service_level_opt, visit_service_level_opt_mut;
+    ServiceLevelStatement => visit_service_level_statement, visit_service_level_statement_mut;
+    SetLiteral => visit_set_literal, visit_set_literal_mut;
+    SimpleSelection => visit_simple_selection, visit_simple_selection_mut;
+    SizeTieredCompactionStrategy => visit_size_tiered_compaction_strategy, visit_size_tiered_compaction_strategy_mut;
+    SpeculativeRetry => visit_speculative_retry, visit_speculative_retry_mut;
+    Statement => visit_statement, visit_statement_mut;
+    StatementOpt => visit_statement_opt, visit_statement_opt_mut;
+    StatementOptValue => visit_statement_opt_value, visit_statement_opt_value_mut;
+    TableOpts => visit_table_opts, visit_table_opts_mut;
+    Term => visit_term, visit_term_mut;
+    TimeWindowCompactionStrategy => visit_time_window_compaction_strategy, visit_time_window_compaction_strategy_mut;
+    TombstoneGc => visit_tombstone_gc, visit_tombstone_gc_mut;
+    TombstoneGcMode => visit_tombstone_gc_mode, visit_tombstone_gc_mode_mut;
+    TriggerStatement => visit_trigger_statement, visit_trigger_statement_mut;
+    TruncateStatement => visit_truncate_statement, visit_truncate_statement_mut;
+    TupleLiteral => visit_tuple_literal, visit_tuple_literal_mut;
+    UpdateParameter => visit_update_parameter, visit_update_parameter_mut;
+    UpdateStatement => visit_update_statement, visit_update_statement_mut;
+    UseStatement => visit_use_statement, visit_use_statement_mut;
+    UserDefinedFunctionStatement => visit_user_defined_function_statement, visit_user_defined_function_statement_mut;
+    UserDefinedTypeLiteral => visit_user_defined_type_literal, visit_user_defined_type_literal_mut;
+    UserDefinedTypeStatement => visit_user_defined_type_statement, visit_user_defined_type_statement_mut;
+    UserStatement => visit_user_statement, visit_user_statement_mut;
+    WhereClause => visit_where_clause, visit_where_clause_mut;
+    WorkloadType => visit_workload_type, visit_workload_type_mut;
+}
+
+macro_rules! leaf {
+    ($($t:ty),*) => {
+        $(
+            impl Visitable for $t {
+                fn accept<'ast, V: Visit<'ast> + ?Sized>(&'ast self, _: &mut V) {}
+
+                fn accept_mut<V: VisitMut + ?Sized>(&mut self, _: &mut V) {}
+
+                fn walk<'ast, V: Visit<'ast> + ?Sized>(&'ast self, _: &mut V) {}
+
+                fn walk_mut<V: VisitMut + ?Sized>(&mut self, _: &mut V) {}
+            }
+        )*
+    };
+}
+
+leaf!(
+    bool,
+    u8,
+    i8,
+    i16,
+    i32,
+    i64,
+    u32,
+    u64,
+    usize,
+    f32,
+    f64,
+    String,
+    uuid::Uuid
+);
+
+impl<T: Visitable> Visitable for Box<T> {
+    fn accept<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        self.as_ref().accept(visitor)
+    }
+
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.as_mut().accept_mut(visitor)
+    }
+
+    fn walk<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        self.as_ref().walk(visitor)
+    }
+
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.as_mut().walk_mut(visitor)
+    }
+}
+
+macro_rules! container {
+    ($($t:ident<$($p:ident),+> [$($bounds:tt)*]),* $(,)?) => {
+        $(
+            impl<$($p: Visitable),+> Visitable for $t<$($p),+> where $($bounds)* {
+                fn accept<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+                    self.walk(visitor)
+                }
+
+                fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                    self.walk_mut(visitor)
+                }
+
+                fn walk<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+                    container!(@walk $t self visitor)
+                }
+
+                fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                    container!(@walk_mut $t self visitor)
+                }
+            }
+        )*
+    };
+    (@walk BTreeMap $self:ident $visitor:ident) => {
+        $self.iter().for_each(|(k, v)| {
+            k.accept($visitor);
+            v.accept($visitor);
+        })
+    };
+    (@walk HashMap $self:ident $visitor:ident) => {
+        $self.iter().for_each(|(k, v)| {
+            k.accept($visitor);
+            v.accept($visitor);
+        })
+    };
+    (@walk $t:ident $self:ident $visitor:ident) => {
+        $self.iter().for_each(|v| v.accept($visitor))
+    };
+    // Keys cannot be modified in place, so ordered collections are rebuilt
+    (@walk_mut BTreeSet $self:ident $visitor:ident) => {
+        *$self = std::mem::take($self)
+            .into_iter()
+            .map(|mut v| {
+                v.accept_mut($visitor);
+                v
+            })
+            .collect()
+    };
+    (@walk_mut BTreeMap $self:ident $visitor:ident) => {
+        *$self = std::mem::take($self)
+            .into_iter()
+            .map(|(mut k, mut v)| {
+                k.accept_mut($visitor);
+                v.accept_mut($visitor);
+                (k, v)
+            })
+            .collect()
+    };
+    (@walk_mut HashMap $self:ident $visitor:ident) => {
+        *$self = std::mem::take($self)
+            .into_iter()
+            .map(|(mut k, mut v)| {
+                k.accept_mut($visitor);
+                v.accept_mut($visitor);
+                (k, v)
+            })
+            .collect()
+    };
+    (@walk_mut $t:ident $self:ident $visitor:ident) => {
+        $self.iter_mut().for_each(|v| v.accept_mut($visitor))
+    };
+}
+
+container!(
+    Option<T> [],
+    Vec<T> [],
+    BTreeSet<T> [T: Ord],
+    BTreeMap<K, T> [K: Ord],
+    HashMap<K, T> [K: std::hash::Hash + Eq],
+);
+
+impl<A: Visitable, B: Visitable> Visitable for (A, B) {
+    fn accept<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        self.walk(visitor)
+    }
+
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.walk_mut(visitor)
+    }
+
+    fn walk<'ast, V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        self.0.accept(visitor);
+        self.1.accept(visitor);
+    }
+
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.0.accept_mut(visitor);
+        self.1.accept_mut(visitor);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::KeyspaceQualifyExt;
+
+    #[derive(Default)]
+    struct Collector<'ast> {
+        tables: Vec<&'ast KeyspaceQualifiedName>,
+        bind_markers: usize,
+        constants: Vec<&'ast Constant>,
+    }
+
+    impl<'ast> Visit<'ast> for Collector<'ast> {
+        fn visit_keyspace_qualified_name(&mut self, node: &'ast KeyspaceQualifiedName) {
+            self.tables.push(node);
+        }
+
+        fn visit_bind_marker(&mut self, node: &'ast BindMarker) {
+            self.bind_markers += 1;
+            node.walk(self)
+        }
+
+        fn visit_constant(&mut self, node: &'ast Constant) {
+            self.constants.push(node);
+        }
+    }
+
+    struct RenameKeyspace;
+
+    impl VisitMut for RenameKeyspace {
+        fn visit_keyspace_qualified_name_mut(&mut self, node: &mut KeyspaceQualifiedName) {
+            node.keyspace.replace("test_keyspace".into());
+        }
+    }
+
+    #[test]
+    fn test_visit() {
+        let statement = "BEGIN BATCH
+            INSERT INTO my_keyspace.my_table (a, b) VALUES (?, 1);
+            UPDATE my_table SET b = :b WHERE a IN ('x', 'y');
+            APPLY BATCH"
+            .parse::<Statement>()
+            .unwrap();
+        let mut collector = Collector::default();
+        statement.accept(&mut collector);
+        assert_eq!(
+            collector.tables,
+            vec![&"my_keyspace".dot("my_table"), &"my_table".into()]
+        );
+        assert_eq!(collector.bind_markers, 2);
+        assert_eq!(collector.constants.len(), 3);
+    }
+
+    #[test]
+    fn test_visit_mut() {
+        let mut statement = "BEGIN BATCH
+            DELETE FROM my_keyspace.my_table WHERE a = 1;
+            INSERT INTO my_table (a, b) VALUES (2, 3);
+            APPLY BATCH"
+            .parse::<Statement>()
+            .unwrap();
+        statement.accept_mut(&mut RenameKeyspace);
+        assert_eq!(
+            statement.to_string(),
+            "BEGIN BATCH DELETE FROM test_keyspace.my_table WHERE a = 1; INSERT INTO test_keyspace.my_table (a, b) VALUES (2, 3) APPLY BATCH"
+        );
+    }
+}
@@ -103,6 +103,7 @@ pub struct BatchCollector<'a, S, Type: Copy + Into<u8>, Stage: Copy> {
     builder: BatchBuilder<Type, Stage>,
     map: HashMap<[u8; 16], ModificationStatement>,
     keyspace: &'a S,
+    timeout: Option<Duration>,
 }
 
 impl<'a, S: Keyspace + Clone> BatchCollector<'a, S, BatchTypeUnset, BatchType> {
@@ -115,6 +116,7 @@ impl<'a, S: Keyspace + Clone> BatchCollector<'a, S, BatchTypeUnset, BatchType> {
             builder: crate::cql::Batch::new(),
             map: HashMap::new(),
             keyspace,
+            timeout: None,
         }
     }
 
@@ -127,6 +129,7 @@ impl<'a, S: Keyspace + Clone> BatchCollector<'a, S, BatchTypeUnset, BatchType> {
             builder: crate::cql::Batch::with_capacity(capacity),
             map: HashMap::new(),
             keyspace,
+            timeout: None,
         }
     }
 
@@ -135,22 +138,27 @@ impl<'a, S: Keyspace + Clone> BatchCollector<'a, S, BatchTypeUnset, BatchType> {
         self,
         batch_type: Type,
     ) -> BatchCollector<'a, S, Type, BatchStatementOrId> {
-        Self::step(self.builder.batch_type(batch_type), self.map, self.keyspace)
+        Self::step(
+            self.builder.batch_type(batch_type),
+            self.map,
+            self.keyspace,
+            self.timeout,
+        )
     }
 
     /// Specify the batch type as Logged
     pub fn logged(self) -> BatchCollector<'a, S, BatchTypeLogged, BatchStatementOrId> {
-        Self::step(self.builder.logged(), self.map, self.keyspace)
+        Self::step(self.builder.logged(), self.map, self.keyspace, self.timeout)
     }
 
     /// Specify the batch type as Unlogged
     pub fn unlogged(self) -> BatchCollector<'a, S, BatchTypeUnlogged, BatchStatementOrId> {
-        Self::step(self.builder.unlogged(), self.map, self.keyspace)
+        Self::step(self.builder.unlogged(), self.map, self.keyspace, self.timeout)
     }
 
     /// Specify the batch type as Counter
     pub fn counter(self) -> BatchCollector<'a, S, BatchTypeCounter, BatchStatementOrId> {
-        Self::step(self.builder.counter(), self.map, self.keyspace)
+        Self::step(self.builder.counter(), self.map, self.keyspace, self.timeout)
     }
 }
 
@@ -176,7 +184,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchSt
         let mut builder = S::bind_values(builder, key, value);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append an unprepared insert query using the statement defined in the `Insert` impl.
@@ -191,7 +199,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchSt
         let mut builder = S::bind_values(builder, key, value);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append a prepared insert query using the statement defined in the `Insert` impl.
@@ -212,7 +220,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchSt
         let mut builder = S::bind_values(builder, key, value);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append an update query using the default query type defined in the `UpdateBatch` impl
@@ -237,7 +245,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchSt
         let mut builder = S::bind_values(builder, key, variables, values);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append an unprepared update query using the statement defined in the `Update` impl.
@@ -252,7 +260,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchSt
         let mut builder = S::bind_values(builder, key, variables, values);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append a prepared update query using the statement defined in the `Update` impl.
@@ -279,7 +287,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchSt
         let mut builder = S::bind_values(builder, key, variables, values);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append a delete query using the default query type defined in the `DeleteBatch` impl
@@ -304,7 +312,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchSt
         let mut builder = S::bind_values(builder, key, variables);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append an unprepared delete query using the statement defined in the `Delete` impl.
@@ -319,7 +327,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchSt
         let mut builder = S::bind_values(builder, key, variables);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append a prepared delete query using the statement defined in the `Delete` impl.
@@ -341,7 +349,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchSt
         let mut builder = S::bind_values(builder, key, variables);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 }
 
@@ -367,7 +375,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
         let mut builder = S::bind_values(builder, key, value);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append an unprepared insert query using the statement defined in the `Insert` impl.
@@ -382,7 +390,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
         let mut builder = S::bind_values(builder, key, value);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append a prepared insert query using the statement defined in the `Insert` impl.
@@ -403,7 +411,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
         let mut builder = S::bind_values(builder, key, value);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append an update query using the default query type defined in the `UpdateBatch` impl
@@ -428,7 +436,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
         let mut builder = S::bind_values(builder, key, variables, values);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append an unprepared update query using the statement defined in the `Update` impl.
@@ -443,7 +451,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
         let mut builder = S::bind_values(builder, key, variables, values);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append a prepared update query using the statement defined in the `Update` impl.
@@ -470,7 +478,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
         let mut builder = S::bind_values(builder, key, variables, values);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append a delete query using the default query type defined in the `DeleteBatch` impl
@@ -495,7 +503,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
         let mut builder = S::bind_values(builder, key, variables);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append an unprepared delete query using the statement defined in the `Delete` impl.
@@ -510,7 +518,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
         let mut builder = S::bind_values(builder, key, variables);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Append a prepared delete query using the statement defined in the `Delete` impl.
@@ -532,12 +540,17 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
         let mut builder = S::bind_values(builder, key, variables);
         builder.commit_value_count();
 
-        Self::step(builder, self.map, self.keyspace)
+        Self::step(builder, self.map, self.keyspace, self.timeout)
     }
 
     /// Set the consistency for this batch
     pub fn consistency(self, consistency: Consistency) -> BatchCollector<'a, S, Type, BatchFlags> {
-        Self::step(self.builder.consistency(consistency), self.map, self.keyspace)
+        Self::step(
+            self.builder.consistency(consistency),
+            self.map,
+            self.keyspace,
+            self.timeout,
+        )
     }
 
     /// Set the serial consistency for the batch
@@ -548,6 +561,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
                 .serial_consistency(consistency),
             self.map,
             self.keyspace,
+            self.timeout,
         )
     }
     /// Set the timestamp for the batch
@@ -556,6 +570,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
             self.builder.consistency(Consistency::Quorum).timestamp(timestamp),
             self.map,
             self.keyspace,
+            self.timeout,
         )
     }
     /// Build the LWT batch request using the current collector
@@ -565,6 +580,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
             map: self.map,
             payload: self.builder.consistency(Consistency::Quorum).build()?.0.into(),
             keyspace: self.keyspace.name().into(),
+            timeout: self.timeout,
         })
     }
     /// Build the batch request using the current collector
@@ -574,6 +590,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
             map: self.map,
             payload: self.builder.consistency(Consistency::Quorum).build()?.0.into(),
             keyspace: self.keyspace.name().into(),
+            timeout: self.timeout,
         })
     }
 }
@@ -581,11 +598,16 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchVa
 impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchFlags> {
     /// Set the serial consistency for the batch
     pub fn serial_consistency(self, consistency: Consistency) -> BatchCollector<'a, S, Type, BatchTimestamp> {
-        Self::step(self.builder.serial_consistency(consistency), self.map, self.keyspace)
+        Self::step(
+            self.builder.serial_consistency(consistency),
+            self.map,
+            self.keyspace,
+            self.timeout,
+        )
     }
     /// Set the timestamp for the batch
     pub fn timestamp(self, timestamp: i64) -> BatchCollector<'a, S, Type, BatchBuild> {
-        Self::step(self.builder.timestamp(timestamp), self.map, self.keyspace)
+        Self::step(self.builder.timestamp(timestamp), self.map, self.keyspace, self.timeout)
     }
     /// Build the LWT batch request using the current collector
     pub fn build_lwt(self) -> anyhow::Result<LwtBatchRequest> {
@@ -594,6 +616,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchFl
             map: self.map,
             payload: self.builder.build()?.0.into(),
             keyspace: self.keyspace.name().into(),
+            timeout: self.timeout,
         })
     }
     /// Build the batch request using the current collector
@@ -603,6 +626,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchFl
             map: self.map,
             payload: self.builder.build()?.0.into(),
             keyspace: self.keyspace.name().into(),
+            timeout: self.timeout,
         })
     }
 }
@@ -610,7 +634,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchFl
 impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchTimestamp> {
     /// Set the timestamp for the batch
     pub fn timestamp(self, timestamp: i64) -> BatchCollector<'a, S, Type, BatchBuild> {
-        Self::step(self.builder.timestamp(timestamp), self.map, self.keyspace)
+        Self::step(self.builder.timestamp(timestamp), self.map, self.keyspace, self.timeout)
     }
     /// Build the batch request using the current collector
     pub fn build_lwt(self) -> anyhow::Result<LwtBatchRequest> {
@@ -619,6 +643,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchTi
             map: self.map,
             payload: self.builder.build()?.0.into(),
             keyspace: self.keyspace.name().into(),
+            timeout: self.timeout,
         })
     }
     /// Build the batch request using the current collector
@@ -628,6 +653,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchTi
             map: self.map,
             payload: self.builder.build()?.0.into(),
             keyspace: self.keyspace.name().into(),
+            timeout: self.timeout,
         })
     }
 }
@@ -640,6 +666,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchBu
             map: self.map,
             payload: self.builder.build()?.0.into(),
             keyspace: self.keyspace.name().into(),
+            timeout: self.timeout,
         })
     }
     /// Build the batch request using the current collector
@@ -649,6 +676,7 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>> BatchCollector<'a, S, Type, BatchBu
             map: self.map,
             payload: self.builder.build()?.0.into(),
             keyspace: self.keyspace.name().into(),
+            timeout: self.timeout,
         })
     }
 }
@@ -658,8 +686,20 @@ impl<'a, S: Keyspace, Type: Copy + Into<u8>, Stage: Copy> BatchCollector<'a, S,
         builder: BatchBuilder<NextType, NextStage>,
         map: HashMap<[u8; 16], ModificationStatement>,
         keyspace: &'a S,
+        timeout: Option<Duration>,
     ) -> BatchCollector<'a, S, NextType, NextStage> {
-        BatchCollector { builder, map, keyspace }
+        BatchCollector {
+            builder,
+            map,
+            keyspace,
+            timeout,
+        }
+    }
+    /// Override the default client side timeout of the request, once it expires the worker gets a
+    /// `WorkerError::Timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.replace(timeout);
+        self
     }
 }
 
@@ -687,6 +727,7 @@ pub struct BatchRequest {
     payload: Vec<u8>,
     map: HashMap<[u8; 16], ModificationStatement>,
     keyspace: Option<String>,
+    timeout: Option<Duration>,
 }
 
 impl Request for BatchRequest {
@@ -709,6 +750,10 @@ impl Request for BatchRequest {
     fn keyspace(&self) -> Option<String> {
         self.keyspace.clone()
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
 }
 
 impl SendRequestExt for BatchRequest {
@@ -761,6 +806,7 @@ pub struct LwtBatchRequest {
     payload: Vec<u8>,
     map: HashMap<[u8; 16], ModificationStatement>,
     keyspace: Option<String>,
+    timeout: Option<Duration>,
 }
 
 impl Request for LwtBatchRequest {
@@ -783,6 +829,10 @@ impl Request for LwtBatchRequest {
     fn keyspace(&self) -> Option<String> {
         self.keyspace.clone()
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
 }
 
 impl SendRequestExt for LwtBatchRequest {
@@ -142,6 +142,7 @@ pub trait GetStaticDeleteRequest<K, V>: Keyspace {
             builder: Self::QueryOrPrepared::encode_statement(Query::new(), &statement.to_string()),
             statement,
             _marker: PhantomData,
+            timeout: None,
         }
     }
 
@@ -212,6 +213,7 @@ pub trait GetStaticDeleteRequest<K, V>: Keyspace {
             builder: QueryStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
             _marker: PhantomData,
+            timeout: None,
         }
     }
 
@@ -282,6 +284,7 @@ pub trait GetStaticDeleteRequest<K, V>: Keyspace {
             builder: PreparedStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
             _marker: PhantomData,
+            timeout: None,
         }
     }
 }
@@ -366,6 +369,7 @@ pub trait GetDynamicDeleteRequest: Keyspace {
             key,
             variables,
             _marker: PhantomData,
+            timeout: None,
         }
     }
 
@@ -408,6 +412,7 @@ pub trait GetDynamicDeleteRequest: Keyspace {
             key,
             variables,
             _marker: PhantomData,
+            timeout: None,
         }
     }
 }
@@ -526,6 +531,7 @@ impl AsDynamicDeleteRequest for DeleteStatement {
             statement: self,
             key,
             variables,
+            timeout: None,
         }
     }
 
@@ -549,6 +555,7 @@ impl AsDynamicDeleteRequest for DeleteStatement {
             statement: self,
             key,
             variables,
+            timeout: None,
         }
     }
 }
@@ -559,9 +566,19 @@ pub struct DeleteBuilder<'a, S, K: ?Sized, V: ?Sized, D, Stage, T> {
     pub(crate) key: &'a K,
     pub(crate) variables: &'a V,
     pub(crate) builder: QueryBuilder<Stage>,
+    pub(crate) timeout: Option<Duration>,
     pub(crate) _marker: PhantomData<fn(D, T) -> (D, T)>,
 }
 
+impl<'a, S, K: ?Sized, V: ?Sized, D, Stage, T> DeleteBuilder<'a, S, K, V, D, Stage, T> {
+    /// Override the default client side timeout of the request, once it expires the worker gets a
+    /// `WorkerError::Timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.replace(timeout);
+        self
+    }
+}
+
 impl<'a, S: Delete<K, V, D>, K: TokenEncoder, V, D> DeleteBuilder<'a, S, K, V, D, QueryConsistency, StaticRequest> {
     pub fn consistency(self, consistency: Consistency) -> DeleteBuilder<'a, S, K, V, D, QueryValues, StaticRequest> {
         DeleteBuilder {
@@ -575,6 +592,7 @@ impl<'a, S: Delete<K, V, D>, K: TokenEncoder, V, D> DeleteBuilder<'a, S, K, V, D
                 &self.key,
                 &self.variables,
             ),
+            timeout: self.timeout,
         }
     }
 
@@ -591,6 +609,7 @@ impl<'a, S: Delete<K, V, D>, K: TokenEncoder, V, D> DeleteBuilder<'a, S, K, V, D
             )
             .timestamp(timestamp),
             _marker: self._marker,
+            timeout: self.timeout,
         }
     }
 
@@ -606,6 +625,7 @@ impl<'a, S: Delete<K, V, D>, K: TokenEncoder, V, D> DeleteBuilder<'a, S, K, V, D
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -622,6 +642,7 @@ impl<'a, S: Delete<K, V, D>, K: TokenEncoder, V, D> DeleteBuilder<'a, S, K, V, D
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -657,6 +678,7 @@ impl<'a, S: Keyspace, D>
             key: self.key,
             variables: self.variables,
             builder: self.builder.consistency(consistency).bind_values().bind(self.key),
+            timeout: self.timeout,
         }
     }
 
@@ -684,6 +706,7 @@ impl<'a, S: Keyspace, D>
                 .bind(self.key)
                 .timestamp(timestamp),
             _marker: self._marker,
+            timeout: self.timeout,
         }
     }
     pub fn build_lwt(self) -> anyhow::Result<LwtDeleteRequest> {
@@ -698,6 +721,7 @@ impl<'a, S: Keyspace, D>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -713,6 +737,7 @@ impl<'a, S: Keyspace, D>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -727,6 +752,7 @@ impl<'a, S, K: ?Sized, V: ?Sized, D, T> DeleteBuilder<'a, S, K, V, D, QueryValue
             variables: self.variables,
             builder: self.builder.timestamp(timestamp),
             _marker: self._marker,
+            timeout: self.timeout,
         }
     }
 }
@@ -739,6 +765,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, D, T> DeleteBuilder<'a, S, K, V
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -749,6 +776,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, D, T> DeleteBuilder<'a, S, K, V
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -762,6 +790,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, D, T> DeleteBuilder<'a, S, K, V
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -816,6 +845,14 @@ impl Request for DeleteRequest {
     fn keyspace(&self) -> Option<String> {
         self.0.keyspace()
     }
+
+    fn table(&self) -> Option<String> {
+        self.0.table()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.0.timeout()
+    }
 }
 
 impl SendRequestExt for DeleteRequest {
@@ -877,6 +914,14 @@ impl Request for LwtDeleteRequest {
     fn keyspace(&self) -> Option<String> {
         self.0.keyspace()
     }
+
+    fn table(&self) -> Option<String> {
+        self.0.table()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.0.timeout()
+    }
 }
 
 impl SendRequestExt for LwtDeleteRequest {
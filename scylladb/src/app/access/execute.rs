@@ -31,6 +31,8 @@ where
             builder: QueryStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
             variables: &[],
+            normalized: None,
+            timeout: None,
         }
     }
 
@@ -62,6 +64,8 @@ where
             builder: QueryStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
             variables,
+            normalized: None,
+            timeout: None,
         }
     }
 }
@@ -70,19 +74,134 @@ impl<T: Into<Statement>> AsDynamicExecuteRequest for T {}
 pub struct ExecuteBuilder<'a, V: ?Sized, Stage> {
     pub(crate) statement: Statement,
     pub(crate) variables: &'a V,
+    pub(crate) normalized: Option<Vec<Option<CqlValue>>>,
     pub(crate) builder: QueryBuilder<Stage>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl<'a, V: ?Sized, Stage> ExecuteBuilder<'a, V, Stage> {
+    /// Override the default client side timeout of the request, once it expires the worker gets a
+    /// `WorkerError::Timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.replace(timeout);
+        self
+    }
+}
+
+/// Bind the variables, interleaving them with the literals extracted by normalization if any
+fn bind_variables<'a>(
+    builder: QueryBuilder<QueryValues>,
+    variables: &'a [&'a dyn BindableValue<QueryBuilder<QueryValues>>],
+    normalized: Option<&Vec<Option<CqlValue>>>,
+) -> QueryBuilder<QueryValues> {
+    match normalized {
+        Some(values) => {
+            let mut variables = variables.iter();
+            values.iter().fold(builder, |builder, value| match value {
+                Some(literal) => builder.value(literal),
+                None => match variables.next() {
+                    Some(variable) => builder.bind(variable),
+                    None => builder.unset_value(),
+                },
+            })
+        }
+        None => builder.bind(variables),
+    }
+}
+
+/// Count the bind markers of a statement
+fn bind_marker_count(statement: &Statement) -> usize {
+    struct Count(usize);
+    impl<'ast> Visit<'ast> for Count {
+        fn visit_bind_marker(&mut self, _: &'ast BindMarker) {
+            self.0 += 1;
+        }
+    }
+    let mut count = Count(0);
+    statement.accept(&mut count);
+    count.0
+}
+
+impl<'a, Stage> ExecuteBuilder<'a, [&'a dyn BindableValue<QueryBuilder<QueryValues>>], Stage> {
+    /// Check that exactly one variable was provided for each bind marker of the statement
+    fn check_variables(&self) -> anyhow::Result<()> {
+        let expected = match self.normalized.as_ref() {
+            Some(values) => values.iter().filter(|v| v.is_none()).count(),
+            None => bind_marker_count(&self.statement),
+        };
+        if self.variables.len() != expected {
+            anyhow::bail!(
+                "Expected {} variables for the bind markers of {}, found {}",
+                expected,
+                self.statement,
+                self.variables.len()
+            );
+        }
+        Ok(())
+    }
 }
 
 impl<'a> ExecuteBuilder<'a, [&'a dyn BindableValue<QueryBuilder<QueryValues>>], QueryConsistency> {
+    /// Replace the literals of a data manipulation statement with bind markers and bind them as values, so that
+    /// statements which only differ by their literals share the same prepared statement id. Any variables are
+    /// bound in place of the bind markers which were already present.
+    ///
+    /// The literals are encoded as the types of the columns they are bound to, which are looked up in the given
+    /// schema. If the types cannot be inferred, the literals are only extracted when their encoding does not depend
+    /// on their type, i.e. when they are all booleans, uuids, blobs or nulls. Otherwise the statement is left
+    /// unchanged and the reason is logged, as are other statements.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use scylladb::prelude::*;
+    /// let schema = Schema::from_script(
+    ///     "CREATE KEYSPACE my_keyspace WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+    ///     CREATE TABLE my_keyspace.my_table (key text, val bigint, PRIMARY KEY (key, val));",
+    /// )?;
+    /// parse_statement!("SELECT * FROM my_keyspace.my_table WHERE key = 'my_key' AND val > ?")
+    ///     .execute_with_vars(&[&10i64])
+    ///     .normalize(&schema)
+    ///     .build()?
+    ///     .get_local_blocking()?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn normalize(mut self, schema: &Schema) -> Self {
+        if self.normalized.is_some() {
+            return self;
+        }
+        if let Ok(statement) = DataManipulationStatement::try_from(self.statement.clone()) {
+            let normalized = statement.normalize();
+            match schema.bind_values(&normalized).or_else(|e| {
+                normalized
+                    .unambiguous_values()
+                    .map_err(|fallback| anyhow::anyhow!("{}, and {}", e, fallback))
+            }) {
+                Ok(values) => {
+                    self.builder = QueryStatement::encode_statement(Query::new(), &normalized.text());
+                    self.statement = normalized.statement.into();
+                    self.normalized = Some(values);
+                }
+                Err(e) => log::warn!("Unable to normalize {}: {}", self.statement, e),
+            }
+        }
+        self
+    }
+
     pub fn consistency(
         self,
         consistency: Consistency,
     ) -> ExecuteBuilder<'a, [&'a dyn BindableValue<QueryBuilder<QueryValues>>], QueryValues> {
-        let builder = self.builder.consistency(consistency).bind_values().bind(self.variables);
+        let builder = bind_variables(
+            self.builder.consistency(consistency).bind_values(),
+            self.variables,
+            self.normalized.as_ref(),
+        );
         ExecuteBuilder {
             statement: self.statement,
             variables: self.variables,
+            normalized: self.normalized,
             builder,
+            timeout: self.timeout,
         }
     }
 
@@ -91,29 +210,33 @@ impl<'a> ExecuteBuilder<'a, [&'a dyn BindableValue<QueryBuilder<QueryValues>>],
         timestamp: i64,
     ) -> ExecuteBuilder<'a, [&'a dyn BindableValue<QueryBuilder<QueryValues>>], QueryBuild> {
         ExecuteBuilder {
+            builder: bind_variables(
+                self.builder.consistency(Consistency::Quorum).bind_values(),
+                self.variables,
+                self.normalized.as_ref(),
+            )
+            .timestamp(timestamp),
             statement: self.statement,
             variables: self.variables,
-            builder: self
-                .builder
-                .consistency(Consistency::Quorum)
-                .bind_values()
-                .bind(self.variables)
-                .timestamp(timestamp),
+            normalized: self.normalized,
+            timeout: self.timeout,
         }
     }
 
     pub fn build(self) -> anyhow::Result<ExecuteRequest> {
-        let query = self
-            .builder
-            .consistency(Consistency::Quorum)
-            .bind_values()
-            .bind(self.variables)
-            .build()?;
+        self.check_variables()?;
+        let query = bind_variables(
+            self.builder.consistency(Consistency::Quorum).bind_values(),
+            self.variables,
+            self.normalized.as_ref(),
+        )
+        .build()?;
         // create the request
         Ok(ExecuteRequest {
             token: rand::random(),
             payload: query.into(),
             statement: self.statement,
+            timeout: self.timeout,
         })
     }
 }
@@ -126,29 +249,35 @@ impl<'a> ExecuteBuilder<'a, [&'a dyn BindableValue<QueryBuilder<QueryValues>>],
         ExecuteBuilder {
             statement: self.statement,
             variables: self.variables,
+            normalized: self.normalized,
             builder: self.builder.timestamp(timestamp),
+            timeout: self.timeout,
         }
     }
 
     pub fn build(self) -> anyhow::Result<ExecuteRequest> {
+        self.check_variables()?;
         let query = self.builder.build()?;
         // create the request
         Ok(ExecuteRequest {
             token: rand::random(),
             payload: query.into(),
             statement: self.statement,
+            timeout: self.timeout,
         })
     }
 }
 
-impl<'a, V: ?Sized> ExecuteBuilder<'a, V, QueryBuild> {
+impl<'a> ExecuteBuilder<'a, [&'a dyn BindableValue<QueryBuilder<QueryValues>>], QueryBuild> {
     pub fn build(self) -> anyhow::Result<ExecuteRequest> {
+        self.check_variables()?;
         let query = self.builder.build()?;
         // create the request
         Ok(ExecuteRequest {
             token: rand::random(),
             payload: query.into(),
             statement: self.statement,
+            timeout: self.timeout,
         })
     }
 }
@@ -159,6 +288,7 @@ pub struct ExecuteRequest {
     pub(crate) token: i64,
     pub(crate) payload: Vec<u8>,
     pub(crate) statement: Statement,
+    pub(crate) timeout: Option<Duration>,
 }
 
 impl From<CommonRequest> for ExecuteRequest {
@@ -167,6 +297,7 @@ impl From<CommonRequest> for ExecuteRequest {
             token: req.token,
             payload: req.payload,
             statement: req.statement.into(),
+            timeout: req.timeout,
         }
     }
 }
@@ -178,6 +309,7 @@ impl TryFrom<ExecuteRequest> for CommonRequest {
             token: req.token,
             payload: req.payload,
             statement: req.statement.try_into()?,
+            timeout: req.timeout,
         })
     }
 }
@@ -207,6 +339,24 @@ impl Request for ExecuteRequest {
     fn keyspace(&self) -> Option<String> {
         self.statement.get_keyspace()
     }
+
+    fn table(&self) -> Option<String> {
+        match &self.statement {
+            Statement::DataManipulation(statement) => table_name(statement),
+            _ => None,
+        }
+    }
+
+    fn is_idempotent(&self) -> bool {
+        matches!(
+            self.statement,
+            Statement::DataManipulation(DataManipulationStatement::Select(_))
+        )
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
 }
 
 impl SendRequestExt for ExecuteRequest {
@@ -218,3 +368,165 @@ impl SendRequestExt for ExecuteRequest {
         BasicRetryWorker::new(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cql::Binder;
+    use chrono::{
+        NaiveDate,
+        NaiveTime,
+    };
+    use std::net::{
+        IpAddr,
+        Ipv4Addr,
+    };
+
+    const SCHEMA: &str = r#"
+        CREATE KEYSPACE ks WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+        CREATE TYPE ks.person (last_name text, first_name text, age smallint);
+        CREATE TABLE ks.all_types (
+            id bigint PRIMARY KEY,
+            s smallint,
+            t tinyint,
+            v varint,
+            f float,
+            ts timestamp,
+            d date,
+            tm time,
+            ip inet,
+            u uuid,
+            p person
+        );
+        CREATE TABLE ks.counters (id int PRIMARY KEY, c counter);
+    "#;
+
+    fn query(statement: &str) -> QueryBuilder<QueryValues> {
+        QueryStatement::encode_statement(Query::new(), statement)
+            .consistency(Consistency::Quorum)
+            .bind_values()
+    }
+
+    #[test]
+    fn normalize_encodes_literals_as_column_types() {
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        let request = "INSERT INTO ks.all_types (id, s, t, v, f, ts, d, tm, ip, u, p)
+            VALUES (1, 2, 3, 12345678901234567890, 1.5, '2021-01-01 00:00:00', '2021-01-01', '08:12:54', '127.0.0.1',
+            f81d4fae-7dec-11d0-a765-00a0c91e6bf6, {first_name: 'Joss', last_name: 'Whedon', age: ?})"
+            .parse::<Statement>()
+            .unwrap()
+            .execute_with_vars(&[&57i16])
+            .normalize(&schema)
+            .build()
+            .unwrap();
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        let expected = query(
+            "INSERT INTO ks.all_types (id, s, t, v, f, ts, d, tm, ip, u, p) \
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, {age: ?, first_name: ?, last_name: ?})",
+        )
+        .value(1i64)
+        .value(2i16)
+        .value(3i8)
+        .value(&[0x00u8, 0xab, 0x54, 0xa9, 0x8c, 0xeb, 0x1f, 0x0a, 0xd2][..])
+        .value(1.5f32)
+        .value(date.and_hms_opt(0, 0, 0).unwrap())
+        .value(date)
+        .value(NaiveTime::from_hms_opt(8, 12, 54).unwrap())
+        .value(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .value(
+            &[
+                0xf8u8, 0x1d, 0x4f, 0xae, 0x7d, 0xec, 0x11, 0xd0, 0xa7, 0x65, 0x00, 0xa0, 0xc9, 0x1e, 0x6b, 0xf6,
+            ][..],
+        )
+        .value(57i16)
+        .value("Joss")
+        .value("Whedon")
+        .build()
+        .unwrap();
+        assert_eq!(request.payload, expected.0);
+
+        let request = "UPDATE ks.counters SET c = c + 5 WHERE id = 1"
+            .parse::<Statement>()
+            .unwrap()
+            .execute()
+            .normalize(&schema)
+            .build()
+            .unwrap();
+        let expected = query("UPDATE ks.counters SET c = c + ? WHERE id = ?")
+            .value(5i64)
+            .value(1i32)
+            .build()
+            .unwrap();
+        assert_eq!(request.payload, expected.0);
+    }
+
+    #[test]
+    fn normalize_without_schema_types() {
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        // The table is unknown, so the literals are left in the statement
+        let request = "SELECT * FROM ks.unknown WHERE id = 1"
+            .parse::<Statement>()
+            .unwrap()
+            .execute()
+            .normalize(&schema)
+            .build()
+            .unwrap();
+        let expected = query("SELECT * FROM ks.unknown WHERE id = 1").build().unwrap();
+        assert_eq!(request.payload, expected.0);
+
+        // Booleans and uuids have a single encoding, so they can be extracted without knowing the column types
+        let request = "UPDATE ks.unknown SET flag = true WHERE id = f81d4fae-7dec-11d0-a765-00a0c91e6bf6 AND part = ?"
+            .parse::<Statement>()
+            .unwrap()
+            .execute_with_vars(&[&1i32])
+            .normalize(&schema)
+            .build()
+            .unwrap();
+        let expected = query("UPDATE ks.unknown SET flag = ? WHERE id = ? AND part = ?")
+            .value(true)
+            .value(
+                &[
+                    0xf8u8, 0x1d, 0x4f, 0xae, 0x7d, 0xec, 0x11, 0xd0, 0xa7, 0x65, 0x00, 0xa0, 0xc9, 0x1e, 0x6b, 0xf6,
+                ][..],
+            )
+            .value(1i32)
+            .build()
+            .unwrap();
+        assert_eq!(request.payload, expected.0);
+    }
+
+    #[test]
+    fn variables_must_match_bind_markers() {
+        let schema = Schema::from_script(SCHEMA).unwrap();
+        let statement = "SELECT * FROM ks.all_types WHERE id = ? AND s = ?"
+            .parse::<Statement>()
+            .unwrap();
+        assert!(statement.clone().execute_with_vars(&[&1i64, &2i16]).build().is_ok());
+        assert!(statement.clone().execute_with_vars(&[&1i64]).build().is_err());
+        assert!(statement
+            .clone()
+            .execute_with_vars(&[&1i64, &2i16, &3i8])
+            .consistency(Consistency::One)
+            .build()
+            .is_err());
+        assert!(statement.execute().timestamp(0).build().is_err());
+
+        // Only the bind markers which were already present take variables once the literals are extracted
+        let statement = "SELECT * FROM ks.all_types WHERE id = 1 AND s = ?"
+            .parse::<Statement>()
+            .unwrap();
+        assert!(statement
+            .clone()
+            .execute_with_vars(&[&2i16])
+            .normalize(&schema)
+            .build()
+            .is_ok());
+        assert!(statement
+            .clone()
+            .execute_with_vars(&[&1i64, &2i16])
+            .normalize(&schema)
+            .build()
+            .is_err());
+        assert!(statement.execute().normalize(&schema).build().is_err());
+    }
+}
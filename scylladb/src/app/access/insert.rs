@@ -152,6 +152,7 @@ pub trait GetStaticInsertRequest<K, V>: Keyspace {
             builder: Self::QueryOrPrepared::encode_statement(Query::new(), &statement.to_string()),
             statement,
             _marker: StaticRequest,
+            timeout: None,
         }
     }
 
@@ -229,6 +230,7 @@ pub trait GetStaticInsertRequest<K, V>: Keyspace {
             builder: QueryStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
             _marker: StaticRequest,
+            timeout: None,
         }
     }
 
@@ -306,6 +308,7 @@ pub trait GetStaticInsertRequest<K, V>: Keyspace {
             builder: PreparedStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
             _marker: StaticRequest,
+            timeout: None,
         }
     }
 }
@@ -389,6 +392,7 @@ pub trait GetDynamicInsertRequest: Keyspace {
             key,
             values,
             _marker: DynamicRequest,
+            timeout: None,
         }
     }
 
@@ -430,6 +434,7 @@ pub trait GetDynamicInsertRequest: Keyspace {
             key,
             values,
             _marker: DynamicRequest,
+            timeout: None,
         }
     }
 }
@@ -545,6 +550,7 @@ impl AsDynamicInsertRequest for InsertStatement {
             statement: self,
             key,
             values,
+            timeout: None,
         }
     }
 
@@ -567,6 +573,7 @@ impl AsDynamicInsertRequest for InsertStatement {
             statement: self,
             key,
             values,
+            timeout: None,
         }
     }
 }
@@ -577,9 +584,19 @@ pub struct InsertBuilder<'a, S, K: ?Sized, V: ?Sized, Stage, T> {
     pub(crate) key: &'a K,
     pub(crate) values: &'a V,
     pub(crate) builder: QueryBuilder<Stage>,
+    pub(crate) timeout: Option<Duration>,
     pub(crate) _marker: T,
 }
 
+impl<'a, S, K: ?Sized, V: ?Sized, Stage, T> InsertBuilder<'a, S, K, V, Stage, T> {
+    /// Override the default client side timeout of the request, once it expires the worker gets a
+    /// `WorkerError::Timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.replace(timeout);
+        self
+    }
+}
+
 impl<'a, S: Insert<K, V>, K: TokenEncoder, V> InsertBuilder<'a, S, K, V, QueryConsistency, StaticRequest> {
     pub fn consistency(self, consistency: Consistency) -> InsertBuilder<'a, S, K, V, QueryValues, StaticRequest> {
         InsertBuilder {
@@ -593,6 +610,7 @@ impl<'a, S: Insert<K, V>, K: TokenEncoder, V> InsertBuilder<'a, S, K, V, QueryCo
                 &self.key,
                 &self.values,
             ),
+            timeout: self.timeout,
         }
     }
 
@@ -609,6 +627,7 @@ impl<'a, S: Insert<K, V>, K: TokenEncoder, V> InsertBuilder<'a, S, K, V, QueryCo
             )
             .timestamp(timestamp),
             _marker: self._marker,
+            timeout: self.timeout,
         }
     }
     pub fn build_lwt(self) -> anyhow::Result<LwtInsertRequest> {
@@ -622,6 +641,7 @@ impl<'a, S: Insert<K, V>, K: TokenEncoder, V> InsertBuilder<'a, S, K, V, QueryCo
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -636,6 +656,7 @@ impl<'a, S: Insert<K, V>, K: TokenEncoder, V> InsertBuilder<'a, S, K, V, QueryCo
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -653,6 +674,7 @@ impl<'a, S: Insert<K, V>, K: TokenEncoder, V> InsertBuilder<'a, S, K, V, QueryCo
                     token: self.key.token(),
                     payload: query.into(),
                     statement: self.statement.into(),
+                    timeout: self.timeout,
                 }
                 .into(),
             ))
@@ -663,6 +685,7 @@ impl<'a, S: Insert<K, V>, K: TokenEncoder, V> InsertBuilder<'a, S, K, V, QueryCo
                     token: self.key.token(),
                     payload: query.into(),
                     statement: self.statement.into(),
+                    timeout: self.timeout,
                 }
                 .into(),
             ))
@@ -707,6 +730,7 @@ impl<'a, S: Keyspace>
             key: self.key,
             values: self.values,
             builder: self.builder,
+            timeout: self.timeout,
         }
     }
 
@@ -734,6 +758,7 @@ impl<'a, S: Keyspace>
             key: self.key,
             values: self.values,
             builder,
+            timeout: self.timeout,
         }
     }
 
@@ -761,6 +786,7 @@ impl<'a, S: Keyspace>
                 .bind(self.values)
                 .timestamp(timestamp),
             _marker: self._marker,
+            timeout: self.timeout,
         }
     }
     pub fn build_lwt(self) -> anyhow::Result<LwtInsertRequest> {
@@ -776,6 +802,7 @@ impl<'a, S: Keyspace>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -792,6 +819,7 @@ impl<'a, S: Keyspace>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -829,6 +857,7 @@ impl<'a, S: Keyspace>
                 self.key,
                 self.values,
             ),
+            timeout: self.timeout,
         }
     }
 
@@ -855,6 +884,7 @@ impl<'a, S: Keyspace>
             )
             .timestamp(timestamp),
             _marker: DynamicRequest,
+            timeout: self.timeout,
         }
     }
 
@@ -870,6 +900,7 @@ impl<'a, S: Keyspace>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -886,6 +917,7 @@ impl<'a, S: Keyspace>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -900,6 +932,7 @@ impl<'a, S, K: ?Sized, V: ?Sized, T> InsertBuilder<'a, S, K, V, QueryValues, T>
             values: self.values,
             builder: self.builder.timestamp(timestamp),
             _marker: self._marker,
+            timeout: self.timeout,
         }
     }
 }
@@ -912,6 +945,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, T> InsertBuilder<'a, S, K, V, Q
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -923,6 +957,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, T> InsertBuilder<'a, S, K, V, Q
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -936,6 +971,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, T> InsertBuilder<'a, S, K, V, Q
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -947,6 +983,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, T> InsertBuilder<'a, S, K, V, Q
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -1010,6 +1047,14 @@ impl Request for InsertRequest {
     fn keyspace(&self) -> Option<String> {
         self.0.keyspace()
     }
+
+    fn table(&self) -> Option<String> {
+        self.0.table()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.0.timeout()
+    }
 }
 
 impl SendRequestExt for InsertRequest {
@@ -1071,6 +1116,14 @@ impl Request for LwtInsertRequest {
     fn keyspace(&self) -> Option<String> {
         self.0.keyspace()
     }
+
+    fn table(&self) -> Option<String> {
+        self.0.table()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.0.timeout()
+    }
 }
 
 impl SendRequestExt for LwtInsertRequest {
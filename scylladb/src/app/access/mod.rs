@@ -25,7 +25,12 @@ pub(crate) mod execute;
 pub(crate) mod prepare;
 
 use super::{
-    worker::BasicRetryWorker,
+    worker::{
+        send_speculative,
+        BasicRetryWorker,
+        SentNotifyWorker,
+        SpeculativeExecution,
+    },
     Worker,
     WorkerError,
 };
@@ -33,6 +38,7 @@ pub use crate::{
     app::{
         ring::{
             shared::SharedRing,
+            LoadBalancingPolicy,
             RingSendError,
         },
         stage::reporter::ReporterEvent,
@@ -104,6 +110,10 @@ pub use select::{
     Select,
     SelectRequest,
 };
+use serde::{
+    Deserialize,
+    Serialize,
+};
 pub use std::{
     borrow::Cow,
     convert::{
@@ -118,6 +128,7 @@ use std::{
         Deref,
         DerefMut,
     },
+    time::Duration,
 };
 use thiserror::Error;
 pub use update::{
@@ -139,7 +150,7 @@ impl<B: Binder, T: TokenEncoder + BindableValue<B>> BindableToken<B> for T {}
 /// The possible request types
 #[allow(missing_docs)]
 #[repr(u8)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RequestType {
     Insert = 0,
     Update = 1,
@@ -186,6 +197,22 @@ pub trait Request {
 
     /// get the keyspace of the request
     fn keyspace(&self) -> Option<String>;
+
+    /// get the table of the request, used to route the requests of the tablets based keyspaces
+    fn table(&self) -> Option<String> {
+        None
+    }
+
+    /// Check if the request can be safely retried, as applying it more than once has the same effect as applying it
+    /// once. Used by the retry policy to decide whether a write which may have been applied can be retried.
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+
+    /// Get the client side timeout of the request, if it overrides the default one
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
 }
 
 /// Extension trait which provides helper functions for sending requests and retrieving their responses
@@ -201,6 +228,17 @@ pub trait SendRequestExt: 'static + Request + Debug + Send + Sync + Sized {
     /// Create a worker containing this request
     fn worker(self) -> Box<Self::Worker>;
 
+    /// Mark this request as idempotent, so the retry policy may retry it on write timeouts and lost connections
+    fn idempotent(self) -> Idempotent<Self> {
+        Idempotent(self)
+    }
+
+    /// Override the default client side timeout of this request, once it expires the worker gets a
+    /// `WorkerError::Timeout`
+    fn with_timeout(self, timeout: Duration) -> WithTimeout<Self> {
+        WithTimeout { request: self, timeout }
+    }
+
     /// Send this request to a specific reporter, without waiting for a response
     fn send_to_reporter(self, reporter: &ReporterHandle) -> Result<DecodeResult<Self::Marker>, RequestError> {
         self.worker().send_to_reporter(reporter)?;
@@ -219,8 +257,11 @@ pub trait SendRequestExt: 'static + Request + Debug + Send + Sync + Sized {
 
     /// Send this request to the local datacenter, without waiting for a response
     fn send_local(self) -> Result<DecodeResult<Self::Marker>, RequestError> {
-        send_local(
+        send_speculative(
+            true,
+            SpeculativeExecution::of(&self),
             self.keyspace().as_ref().map(|s| s.as_str()),
+            self.table().as_ref().map(|s| s.as_str()),
             self.token(),
             self.payload(),
             self.worker(),
@@ -235,16 +276,36 @@ pub trait SendRequestExt: 'static + Request + Debug + Send + Sync + Sized {
     ) -> Result<DecodeResult<Self::Marker>, RequestError> {
         send_local(
             self.keyspace().as_ref().map(|s| s.as_str()),
+            self.table().as_ref().map(|s| s.as_str()),
             self.token(),
             self.payload(),
             worker,
         )?;
         Ok(DecodeResult::new(Self::Marker::new(), Self::TYPE))
     }
+    /// Send this request to the local datacenter, to the replica picked by the given load balancing policy instead of
+    /// the configured one, without waiting for a response
+    fn send_local_with_policy(
+        self,
+        policy: &dyn LoadBalancingPolicy,
+    ) -> Result<DecodeResult<Self::Marker>, RequestError> {
+        send_local_with_policy(
+            self.keyspace().as_ref().map(|s| s.as_str()),
+            self.table().as_ref().map(|s| s.as_str()),
+            self.token(),
+            self.payload(),
+            self.worker(),
+            policy,
+        )?;
+        Ok(DecodeResult::new(Self::Marker::new(), Self::TYPE))
+    }
     /// Send this request to a global datacenter, without waiting for a response
     fn send_global(self) -> Result<DecodeResult<Self::Marker>, RequestError> {
-        send_global(
+        send_speculative(
+            false,
+            SpeculativeExecution::of(&self),
             self.keyspace().as_ref().map(|s| s.as_str()),
+            self.table().as_ref().map(|s| s.as_str()),
             self.token(),
             self.payload(),
             self.worker(),
@@ -259,12 +320,72 @@ pub trait SendRequestExt: 'static + Request + Debug + Send + Sync + Sized {
     ) -> Result<DecodeResult<Self::Marker>, RequestError> {
         send_global(
             self.keyspace().as_ref().map(|s| s.as_str()),
+            self.table().as_ref().map(|s| s.as_str()),
             self.token(),
             self.payload(),
             worker,
         )?;
         Ok(DecodeResult::new(Self::Marker::new(), Self::TYPE))
     }
+
+    /// Send this request to a global datacenter, to the replica picked by the given load balancing policy instead of
+    /// the configured one, without waiting for a response
+    fn send_global_with_policy(
+        self,
+        policy: &dyn LoadBalancingPolicy,
+    ) -> Result<DecodeResult<Self::Marker>, RequestError> {
+        send_global_with_policy(
+            self.keyspace().as_ref().map(|s| s.as_str()),
+            self.table().as_ref().map(|s| s.as_str()),
+            self.token(),
+            self.payload(),
+            self.worker(),
+            policy,
+        )?;
+        Ok(DecodeResult::new(Self::Marker::new(), Self::TYPE))
+    }
+    /// Send this request to the local datacenter, awaiting the reporter capacity when its queue is enabled, see
+    /// `BackpressureConfig`. Returns once the request is sent, or with the `WorkerError::Overload` if it could not be
+    /// sent within the queue max wait
+    async fn send_local_async(self) -> Result<DecodeResult<Self::Marker>, RequestError> {
+        let (keyspace, table, token, payload) = (self.keyspace(), self.table(), self.token(), self.payload());
+        let execution = SpeculativeExecution::of(&self);
+        let (worker, sent) = SentNotifyWorker::new(self.worker());
+        send_speculative(
+            true,
+            execution,
+            keyspace.as_deref(),
+            table.as_deref(),
+            token,
+            payload,
+            worker,
+        )?;
+        sent.await
+            .map_err(|e| anyhow::anyhow!("No notification from worker: {}", e))??;
+        Ok(DecodeResult::new(Self::Marker::new(), Self::TYPE))
+    }
+
+    /// Send this request to a global datacenter, awaiting the reporter capacity when its queue is enabled, see
+    /// `BackpressureConfig`. Returns once the request is sent, or with the `WorkerError::Overload` if it could not be
+    /// sent within the queue max wait
+    async fn send_global_async(self) -> Result<DecodeResult<Self::Marker>, RequestError> {
+        let (keyspace, table, token, payload) = (self.keyspace(), self.table(), self.token(), self.payload());
+        let execution = SpeculativeExecution::of(&self);
+        let (worker, sent) = SentNotifyWorker::new(self.worker());
+        send_speculative(
+            false,
+            execution,
+            keyspace.as_deref(),
+            table.as_deref(),
+            token,
+            payload,
+            worker,
+        )?;
+        sent.await
+            .map_err(|e| anyhow::anyhow!("No notification from worker: {}", e))??;
+        Ok(DecodeResult::new(Self::Marker::new(), Self::TYPE))
+    }
+
     /// Send this request to the local datacenter and await the response asynchronously
     async fn get_local(self) -> Result<<Self::Marker as Marker>::Output, RequestError>
     where
@@ -380,6 +501,7 @@ pub struct CommonRequest {
     pub(crate) token: i64,
     pub(crate) payload: Vec<u8>,
     pub(crate) statement: DataManipulationStatement,
+    pub(crate) timeout: Option<Duration>,
 }
 
 impl Into<Vec<u8>> for CommonRequest {
@@ -395,6 +517,7 @@ impl CommonRequest {
             token: 0,
             payload,
             statement,
+            timeout: None,
         }
     }
 }
@@ -424,6 +547,147 @@ impl Request for CommonRequest {
     fn keyspace(&self) -> Option<String> {
         self.statement.get_keyspace()
     }
+
+    fn table(&self) -> Option<String> {
+        table_name(&self.statement)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+/// A request which is marked as idempotent, see `SendRequestExt::idempotent`
+#[derive(Debug, Clone)]
+pub struct Idempotent<R>(pub R);
+
+impl<R> Deref for Idempotent<R> {
+    type Target = R;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<R> DerefMut for Idempotent<R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<R: Request> Request for Idempotent<R> {
+    fn token(&self) -> i64 {
+        self.0.token()
+    }
+
+    fn statement(&self) -> Statement {
+        self.0.statement()
+    }
+
+    fn statement_by_id(&self, id: &[u8; 16]) -> Option<DataManipulationStatement> {
+        self.0.statement_by_id(id)
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        self.0.payload()
+    }
+
+    fn keyspace(&self) -> Option<String> {
+        self.0.keyspace()
+    }
+
+    fn table(&self) -> Option<String> {
+        self.0.table()
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.0.timeout()
+    }
+}
+
+// The worker holds the wrapper, so that retrying it keeps the request idempotent, thus only the requests of the
+// basic retry worker can be wrapped
+impl<R: SendRequestExt<Worker = BasicRetryWorker<R>>> SendRequestExt for Idempotent<R> {
+    type Marker = R::Marker;
+    type Worker = BasicRetryWorker<Self>;
+    const TYPE: RequestType = R::TYPE;
+
+    fn worker(self) -> Box<Self::Worker> {
+        BasicRetryWorker::new(self)
+    }
+}
+
+/// A request with its own client side timeout, see `SendRequestExt::with_timeout`
+#[derive(Debug, Clone)]
+pub struct WithTimeout<R> {
+    /// The request
+    pub request: R,
+    /// The client side timeout of the request
+    pub timeout: Duration,
+}
+
+impl<R> Deref for WithTimeout<R> {
+    type Target = R;
+
+    fn deref(&self) -> &Self::Target {
+        &self.request
+    }
+}
+
+impl<R> DerefMut for WithTimeout<R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.request
+    }
+}
+
+impl<R: Request> Request for WithTimeout<R> {
+    fn token(&self) -> i64 {
+        self.request.token()
+    }
+
+    fn statement(&self) -> Statement {
+        self.request.statement()
+    }
+
+    fn statement_by_id(&self, id: &[u8; 16]) -> Option<DataManipulationStatement> {
+        self.request.statement_by_id(id)
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        self.request.payload()
+    }
+
+    fn keyspace(&self) -> Option<String> {
+        self.request.keyspace()
+    }
+
+    fn table(&self) -> Option<String> {
+        self.request.table()
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.request.is_idempotent()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        Some(self.timeout)
+    }
+}
+
+// Like `Idempotent`, the worker holds the wrapper so that retries keep the timeout, thus only the requests of the
+// basic retry worker can be wrapped
+impl<R: SendRequestExt<Worker = BasicRetryWorker<R>>> SendRequestExt for WithTimeout<R> {
+    type Marker = R::Marker;
+    type Worker = BasicRetryWorker<Self>;
+    const TYPE: RequestType = R::TYPE;
+
+    fn worker(self) -> Box<Self::Worker> {
+        BasicRetryWorker::new(self)
+    }
 }
 
 /// Defines two helper methods to specify statement / id
@@ -617,26 +881,73 @@ impl<V> DecodeResult<DecodeRows<V>> {
 #[inline]
 pub fn send_local(
     keyspace: Option<&str>,
+    table: Option<&str>,
     token: i64,
     payload: Vec<u8>,
     worker: Box<dyn Worker>,
 ) -> Result<(), RingSendError> {
     let request = ReporterEvent::Request { worker, payload };
 
-    SharedRing::send_local_random_replica(keyspace, token, request)
+    SharedRing::send_local_replica(keyspace, table, token, request, None)
+}
+
+/// Send a local request to the Ring, to the replica picked by the given load balancing policy
+#[inline]
+pub fn send_local_with_policy(
+    keyspace: Option<&str>,
+    table: Option<&str>,
+    token: i64,
+    payload: Vec<u8>,
+    worker: Box<dyn Worker>,
+    policy: &dyn LoadBalancingPolicy,
+) -> Result<(), RingSendError> {
+    let request = ReporterEvent::Request { worker, payload };
+
+    SharedRing::send_local_replica(keyspace, table, token, request, Some(policy))
 }
 
 /// Send a global request to the Ring
 #[inline]
 pub fn send_global(
     keyspace: Option<&str>,
+    table: Option<&str>,
+    token: i64,
+    payload: Vec<u8>,
+    worker: Box<dyn Worker>,
+) -> Result<(), RingSendError> {
+    let request = ReporterEvent::Request { worker, payload };
+
+    SharedRing::send_global_replica(keyspace, table, token, request, None)
+}
+
+/// Send a global request to the Ring, to the replica picked by the given load balancing policy
+#[inline]
+pub fn send_global_with_policy(
+    keyspace: Option<&str>,
+    table: Option<&str>,
     token: i64,
     payload: Vec<u8>,
     worker: Box<dyn Worker>,
+    policy: &dyn LoadBalancingPolicy,
 ) -> Result<(), RingSendError> {
     let request = ReporterEvent::Request { worker, payload };
 
-    SharedRing::send_global_random_replica(keyspace, token, request)
+    SharedRing::send_global_replica(keyspace, table, token, request, Some(policy))
+}
+
+/// Get the table name of the statement as stored by scylla, batches do not target a single table
+pub(crate) fn table_name(statement: &DataManipulationStatement) -> Option<String> {
+    let table = match statement {
+        DataManipulationStatement::Select(s) => &s.from.name,
+        DataManipulationStatement::Insert(s) => &s.table.name,
+        DataManipulationStatement::Update(s) => &s.table.name,
+        DataManipulationStatement::Delete(s) => &s.from.name,
+        DataManipulationStatement::Batch(_) => return None,
+    };
+    Some(match table {
+        Name::Quoted(name) => name.clone(),
+        Name::Unquoted(name) => name.to_lowercase(),
+    })
 }
 
 impl<T> Deref for DecodeResult<T> {
@@ -850,6 +1161,32 @@ mod tests {
         let _res = req.clone().send_local().ok();
     }
 
+    #[test]
+    fn test_request_timeout() {
+        let keyspace = MyKeyspace::new();
+        let timeout = Duration::from_millis(250);
+        let req = keyspace.select::<i32>(&3, &()).build().unwrap();
+        assert_eq!(req.timeout(), None);
+        let req = keyspace.select::<i32>(&3, &()).timeout(timeout).build().unwrap();
+        assert_eq!(req.timeout(), Some(timeout));
+        let req = keyspace.insert(&3, &8.0).timeout(timeout).build().unwrap();
+        assert_eq!(req.timeout(), Some(timeout));
+        let req = keyspace.update(&3, &(), &8.0).timeout(timeout).build().unwrap();
+        assert_eq!(req.timeout(), Some(timeout));
+        let req = keyspace.delete::<f32>(&3, &()).timeout(timeout).build().unwrap();
+        assert_eq!(req.timeout(), Some(timeout));
+        let req = keyspace
+            .batch()
+            .logged()
+            .timeout(timeout)
+            .insert(&3, &9.0)
+            .build()
+            .unwrap();
+        assert_eq!(req.timeout(), Some(timeout));
+        let req = keyspace.select::<i32>(&3, &()).build().unwrap().with_timeout(timeout);
+        assert_eq!(req.timeout(), Some(timeout));
+    }
+
     #[ignore]
     #[tokio::test]
     async fn test_insert2() {
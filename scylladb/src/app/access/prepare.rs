@@ -330,6 +330,14 @@ impl Request for PrepareRequest {
     fn keyspace(&self) -> Option<String> {
         self.statement.get_keyspace()
     }
+
+    fn table(&self) -> Option<String> {
+        table_name(&self.statement)
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
 }
 
 #[async_trait::async_trait]
@@ -140,6 +140,7 @@ pub trait GetStaticSelectRequest<K, V>: Keyspace {
             variables,
             builder: Self::QueryOrPrepared::encode_statement(Query::new(), &statement.to_string()),
             statement,
+            timeout: None,
         }
     }
 
@@ -210,6 +211,7 @@ pub trait GetStaticSelectRequest<K, V>: Keyspace {
             variables,
             builder: QueryStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
+            timeout: None,
         }
     }
 
@@ -280,6 +282,7 @@ pub trait GetStaticSelectRequest<K, V>: Keyspace {
             variables,
             builder: PreparedStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
+            timeout: None,
         }
     }
 }
@@ -365,6 +368,7 @@ pub trait GetDynamicSelectRequest: Keyspace {
             statement,
             key,
             variables,
+            timeout: None,
         }
     }
 
@@ -407,6 +411,7 @@ pub trait GetDynamicSelectRequest: Keyspace {
             statement,
             key,
             variables,
+            timeout: None,
         }
     }
 }
@@ -529,6 +534,7 @@ impl AsDynamicSelectRequest for SelectStatement {
             statement: self,
             key,
             variables,
+            timeout: None,
         }
     }
 
@@ -552,6 +558,7 @@ impl AsDynamicSelectRequest for SelectStatement {
             statement: self,
             key,
             variables,
+            timeout: None,
         }
     }
 }
@@ -562,9 +569,19 @@ pub struct SelectBuilder<'a, S, K: ?Sized, V: ?Sized, O, Stage, T> {
     pub(crate) key: &'a K,
     pub(crate) variables: &'a V,
     pub(crate) builder: QueryBuilder<Stage>,
+    pub(crate) timeout: Option<Duration>,
     pub(crate) _marker: T,
 }
 
+impl<'a, S, K: ?Sized, V: ?Sized, O, Stage, T> SelectBuilder<'a, S, K, V, O, Stage, T> {
+    /// Override the default client side timeout of the request, once it expires the worker gets a
+    /// `WorkerError::Timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.replace(timeout);
+        self
+    }
+}
+
 impl<'a, S: Select<K, V, O>, K: TokenEncoder, V, O> SelectBuilder<'a, S, K, V, O, QueryConsistency, StaticRequest> {
     pub fn consistency(self, consistency: Consistency) -> SelectBuilder<'a, S, K, V, O, QueryValues, StaticRequest> {
         SelectBuilder {
@@ -578,6 +595,7 @@ impl<'a, S: Select<K, V, O>, K: TokenEncoder, V, O> SelectBuilder<'a, S, K, V, O
                 &self.key,
                 &self.variables,
             ),
+            timeout: self.timeout,
         }
     }
 
@@ -594,6 +612,7 @@ impl<'a, S: Select<K, V, O>, K: TokenEncoder, V, O> SelectBuilder<'a, S, K, V, O
                 &self.variables,
             )
             .page_size(page_size),
+            timeout: self.timeout,
         }
     }
     /// Set the paging state.
@@ -613,6 +632,7 @@ impl<'a, S: Select<K, V, O>, K: TokenEncoder, V, O> SelectBuilder<'a, S, K, V, O
                 &self.variables,
             )
             .paging_state(paging_state),
+            timeout: self.timeout,
         }
     }
     pub fn timestamp(self, timestamp: i64) -> SelectBuilder<'a, S, K, V, O, QueryBuild, StaticRequest> {
@@ -628,6 +648,7 @@ impl<'a, S: Select<K, V, O>, K: TokenEncoder, V, O> SelectBuilder<'a, S, K, V, O
                 &self.variables,
             )
             .timestamp(timestamp),
+            timeout: self.timeout,
         }
     }
 
@@ -643,6 +664,7 @@ impl<'a, S: Select<K, V, O>, K: TokenEncoder, V, O> SelectBuilder<'a, S, K, V, O
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -687,6 +709,7 @@ impl<'a, S: Keyspace, O>
             key: self.key,
             variables: self.variables,
             builder: self.builder,
+            timeout: self.timeout,
         }
     }
 
@@ -715,6 +738,7 @@ impl<'a, S: Keyspace, O>
             key: self.key,
             variables: self.variables,
             builder,
+            timeout: self.timeout,
         }
     }
 
@@ -743,6 +767,7 @@ impl<'a, S: Keyspace, O>
                 .bind(self.key)
                 .bind(self.variables)
                 .page_size(page_size),
+            timeout: self.timeout,
         }
     }
     /// Set the paging state.
@@ -771,6 +796,7 @@ impl<'a, S: Keyspace, O>
                 .bind(self.key)
                 .bind(self.variables)
                 .paging_state(paging_state),
+            timeout: self.timeout,
         }
     }
     pub fn timestamp(
@@ -798,6 +824,7 @@ impl<'a, S: Keyspace, O>
                 .bind(self.key)
                 .bind(self.variables)
                 .timestamp(timestamp),
+            timeout: self.timeout,
         }
     }
 
@@ -814,6 +841,7 @@ impl<'a, S: Keyspace, O>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -853,6 +881,7 @@ impl<'a, S: Keyspace, O>
                 self.key,
                 self.variables,
             ),
+            timeout: self.timeout,
         }
     }
 
@@ -880,6 +909,7 @@ impl<'a, S: Keyspace, O>
                 self.variables,
             )
             .page_size(page_size),
+            timeout: self.timeout,
         }
     }
     /// Set the paging state.
@@ -907,6 +937,7 @@ impl<'a, S: Keyspace, O>
                 self.variables,
             )
             .paging_state(paging_state),
+            timeout: self.timeout,
         }
     }
     pub fn timestamp(
@@ -933,6 +964,7 @@ impl<'a, S: Keyspace, O>
                 self.variables,
             )
             .timestamp(timestamp),
+            timeout: self.timeout,
         }
     }
 
@@ -949,6 +981,7 @@ impl<'a, S: Keyspace, O>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -963,6 +996,7 @@ impl<'a, S, K: ?Sized, V: ?Sized, O, T> SelectBuilder<'a, S, K, V, O, QueryValue
             key: self.key,
             variables: self.variables,
             builder: self.builder.page_size(page_size),
+            timeout: self.timeout,
         }
     }
     pub fn paging_state(
@@ -976,6 +1010,7 @@ impl<'a, S, K: ?Sized, V: ?Sized, O, T> SelectBuilder<'a, S, K, V, O, QueryValue
             key: self.key,
             variables: self.variables,
             builder: self.builder.paging_state(paging_state),
+            timeout: self.timeout,
         }
     }
     pub fn timestamp(self, timestamp: i64) -> SelectBuilder<'a, S, K, V, O, QueryBuild, T> {
@@ -986,6 +1021,7 @@ impl<'a, S, K: ?Sized, V: ?Sized, O, T> SelectBuilder<'a, S, K, V, O, QueryValue
             key: self.key,
             variables: self.variables,
             builder: self.builder.timestamp(timestamp),
+            timeout: self.timeout,
         }
     }
 }
@@ -998,6 +1034,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, O: RowsDecoder, T> SelectBuilde
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -1011,6 +1048,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, O: RowsDecoder, T> SelectBuilde
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -1028,6 +1066,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, O, T> SelectBuilder<'a, S, K, V
             key: self.key,
             variables: self.variables,
             builder: self.builder.paging_state(paging_state),
+            timeout: self.timeout,
         }
     }
 
@@ -1039,6 +1078,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, O, T> SelectBuilder<'a, S, K, V
             key: self.key,
             variables: self.variables,
             builder: self.builder.timestamp(timestamp),
+            timeout: self.timeout,
         }
     }
 
@@ -1049,6 +1089,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, O, T> SelectBuilder<'a, S, K, V
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -1063,6 +1104,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, O, T> SelectBuilder<'a, S, K, V
             key: self.key,
             variables: self.variables,
             builder: self.builder.timestamp(timestamp),
+            timeout: self.timeout,
         }
     }
 
@@ -1073,6 +1115,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, O, T> SelectBuilder<'a, S, K, V
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -1147,6 +1190,16 @@ impl<O: 'static> Request for SelectRequest<O> {
     fn keyspace(&self) -> Option<String> {
         self.inner.keyspace()
     }
+    fn table(&self) -> Option<String> {
+        self.inner.table()
+    }
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout()
+    }
 }
 
 impl<O> SelectRequest<O> {
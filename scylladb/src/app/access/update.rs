@@ -178,6 +178,7 @@ pub trait GetStaticUpdateRequest<K, V, U>: Keyspace {
             builder: Self::QueryOrPrepared::encode_statement(Query::new(), &statement.to_string()),
             statement,
             _marker: StaticRequest,
+            timeout: None,
         }
     }
 
@@ -267,6 +268,7 @@ pub trait GetStaticUpdateRequest<K, V, U>: Keyspace {
             builder: QueryStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
             _marker: StaticRequest,
+            timeout: None,
         }
     }
 
@@ -356,6 +358,7 @@ pub trait GetStaticUpdateRequest<K, V, U>: Keyspace {
             builder: PreparedStatement::encode_statement(Query::new(), &statement.to_string()),
             statement,
             _marker: StaticRequest,
+            timeout: None,
         }
     }
 }
@@ -448,6 +451,7 @@ pub trait GetDynamicUpdateRequest: Keyspace {
             variables,
             values: &(),
             _marker: DynamicRequest,
+            timeout: None,
         }
     }
 
@@ -494,6 +498,7 @@ pub trait GetDynamicUpdateRequest: Keyspace {
             variables,
             values: &(),
             _marker: DynamicRequest,
+            timeout: None,
         }
     }
 }
@@ -629,6 +634,7 @@ impl AsDynamicUpdateRequest for UpdateStatement {
             key,
             values: &(),
             variables,
+            timeout: None,
         }
     }
 
@@ -653,6 +659,7 @@ impl AsDynamicUpdateRequest for UpdateStatement {
             key,
             values: &(),
             variables,
+            timeout: None,
         }
     }
 }
@@ -664,9 +671,19 @@ pub struct UpdateBuilder<'a, S, K: ?Sized, V: ?Sized, U: ?Sized, Stage, T> {
     pub(crate) variables: &'a V,
     pub(crate) values: &'a U,
     pub(crate) builder: QueryBuilder<Stage>,
+    pub(crate) timeout: Option<Duration>,
     pub(crate) _marker: T,
 }
 
+impl<'a, S, K: ?Sized, V: ?Sized, U: ?Sized, Stage, T> UpdateBuilder<'a, S, K, V, U, Stage, T> {
+    /// Override the default client side timeout of the request, once it expires the worker gets a
+    /// `WorkerError::Timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.replace(timeout);
+        self
+    }
+}
+
 impl<'a, S: Update<K, V, U>, K: TokenEncoder, V, U> UpdateBuilder<'a, S, K, V, U, QueryConsistency, StaticRequest> {
     pub fn consistency(self, consistency: Consistency) -> UpdateBuilder<'a, S, K, V, U, QueryValues, StaticRequest> {
         UpdateBuilder {
@@ -682,6 +699,7 @@ impl<'a, S: Update<K, V, U>, K: TokenEncoder, V, U> UpdateBuilder<'a, S, K, V, U
                 &self.variables,
                 &self.values,
             ),
+            timeout: self.timeout,
         }
     }
 
@@ -700,6 +718,7 @@ impl<'a, S: Update<K, V, U>, K: TokenEncoder, V, U> UpdateBuilder<'a, S, K, V, U
             )
             .timestamp(timestamp),
             _marker: self._marker,
+            timeout: self.timeout,
         }
     }
 
@@ -716,6 +735,7 @@ impl<'a, S: Update<K, V, U>, K: TokenEncoder, V, U> UpdateBuilder<'a, S, K, V, U
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -733,6 +753,7 @@ impl<'a, S: Update<K, V, U>, K: TokenEncoder, V, U> UpdateBuilder<'a, S, K, V, U
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -778,6 +799,7 @@ impl<'a, S: Keyspace>
             variables: self.variables,
             values: self.values,
             builder: self.builder,
+            timeout: self.timeout,
         }
     }
 
@@ -807,6 +829,7 @@ impl<'a, S: Keyspace>
             variables: self.variables,
             values: self.values,
             builder,
+            timeout: self.timeout,
         }
     }
 
@@ -836,6 +859,7 @@ impl<'a, S: Keyspace>
                 .bind(self.key)
                 .timestamp(timestamp),
             _marker: self._marker,
+            timeout: self.timeout,
         }
     }
 
@@ -852,6 +876,7 @@ impl<'a, S: Keyspace>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -869,6 +894,7 @@ impl<'a, S: Keyspace>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -909,6 +935,7 @@ impl<'a, S: Keyspace>
                 self.key,
                 self.variables,
             ),
+            timeout: self.timeout,
         }
     }
 
@@ -937,6 +964,7 @@ impl<'a, S: Keyspace>
             )
             .timestamp(timestamp),
             _marker: DynamicRequest,
+            timeout: self.timeout,
         }
     }
     pub fn build_lwt(self) -> anyhow::Result<LwtUpdateRequest> {
@@ -951,6 +979,7 @@ impl<'a, S: Keyspace>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -966,6 +995,7 @@ impl<'a, S: Keyspace>
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -981,6 +1011,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, U: ?Sized, T> UpdateBuilder<'a,
             values: self.values,
             builder: self.builder.timestamp(timestamp),
             _marker: self._marker,
+            timeout: self.timeout,
         }
     }
 
@@ -991,6 +1022,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, U: ?Sized, T> UpdateBuilder<'a,
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -1002,6 +1034,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, U: ?Sized, T> UpdateBuilder<'a,
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -1015,6 +1048,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, U: ?Sized, T> UpdateBuilder<'a,
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -1025,6 +1059,7 @@ impl<'a, S, K: TokenEncoder + ?Sized, V: ?Sized, U: ?Sized, T> UpdateBuilder<'a,
             token: self.key.token(),
             payload: query.into(),
             statement: self.statement.into(),
+            timeout: self.timeout,
         }
         .into())
     }
@@ -1079,6 +1114,14 @@ impl Request for UpdateRequest {
     fn keyspace(&self) -> Option<String> {
         self.0.keyspace()
     }
+
+    fn table(&self) -> Option<String> {
+        self.0.table()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.0.timeout()
+    }
 }
 
 impl SendRequestExt for UpdateRequest {
@@ -1140,6 +1183,14 @@ impl Request for LwtUpdateRequest {
     fn keyspace(&self) -> Option<String> {
         self.0.keyspace()
     }
+
+    fn table(&self) -> Option<String> {
+        self.0.table()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.0.timeout()
+    }
 }
 
 impl SendRequestExt for LwtUpdateRequest {
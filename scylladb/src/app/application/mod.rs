@@ -1,4 +1,14 @@
-use super::cluster::Cluster;
+use super::{
+    access::RequestType,
+    cluster::Cluster,
+    ring::LoadBalancing,
+    worker::{
+        set_retry_policy,
+        set_speculative_execution,
+        RetryPolicyConfig,
+        SpeculativeExecutionConfig,
+    },
+};
 use crate::app::cluster::ClusterEvent;
 pub(crate) use crate::cql::PasswordAuth;
 use async_trait::async_trait;
@@ -27,6 +37,7 @@ use std::{
         HashSet,
     },
     net::SocketAddr,
+    time::Duration,
 };
 /// Scylla handle
 pub type ScyllaHandle = UnboundedHandle<ScyllaEvent>;
@@ -70,6 +81,47 @@ pub struct KeyspaceConfig {
     pub data_centers: HashMap<DatacenterName, DatacenterConfig>,
 }
 
+/// Configuration for discovering the cluster nodes from the system.peers of the contact points
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct PeerDiscoveryConfig {
+    /// The datacenters of the discovered nodes to start, or all datacenters if empty
+    pub data_centers: HashSet<DatacenterName>,
+    /// The maximum number of discovered nodes to start outside of the local datacenter
+    pub max_remote_nodes: Option<usize>,
+    /// The interval in seconds to refresh the discovered nodes, to catch any missed change
+    pub refresh_interval: u64,
+}
+
+impl Default for PeerDiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            data_centers: HashSet::new(),
+            max_remote_nodes: None,
+            refresh_interval: 60,
+        }
+    }
+}
+
+/// Configuration of the reporters queue, where the requests wait for a free stream instead of failing with
+/// `WorkerError::Overload`
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct BackpressureConfig {
+    /// The maximum number of requests queued by each reporter
+    pub queue_size: usize,
+    /// The maximum time in milliseconds a request waits for a free stream, before failing with
+    /// `WorkerError::Overload`
+    pub max_wait: u64,
+}
+
+impl Default for BackpressureConfig {
+    fn default() -> Self {
+        Self {
+            queue_size: 1024,
+            max_wait: 1000,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 /// Application state
 pub struct Scylla {
@@ -78,7 +130,8 @@ pub struct Scylla {
     /// The initial scylla nodes
     pub nodes: HashSet<SocketAddr>,
     /// Keyspace definition for this cluster, keyed by the network
-    /// they will pull data from
+    /// they will pull data from. The replication of any other keyspace
+    /// is discovered from system_schema.keyspaces, these override it
     pub keyspaces: HashSet<KeyspaceConfig>,
     /// Reporter count per stage
     pub reporter_count: u8,
@@ -90,6 +143,26 @@ pub struct Scylla {
     pub send_buffer_size: Option<u32>,
     /// Default cql authentication
     pub authenticator: PasswordAuth,
+    /// Optional discovery of the cluster nodes, the initial nodes are used as contact points
+    pub peer_discovery: Option<PeerDiscoveryConfig>,
+    /// The load balancing policy which picks the replica of the requests
+    #[serde(default)]
+    pub load_balancing: LoadBalancing,
+    /// The retry policy which decides whether the failed requests are retried
+    #[serde(default)]
+    pub retry_policy: RetryPolicyConfig,
+    /// The default client side timeout of the requests in milliseconds, None to wait for the responses forever
+    #[serde(default = "Scylla::default_request_timeout")]
+    pub request_timeout: Option<u64>,
+    /// Optional queue of the requests waiting for a free stream, otherwise they fail with `WorkerError::Overload`
+    #[serde(default)]
+    pub backpressure: Option<BackpressureConfig>,
+    /// The speculative execution of the idempotent requests, by request type
+    #[serde(default)]
+    pub speculative_execution: HashMap<RequestType, SpeculativeExecutionConfig>,
+    /// The delay in milliseconds the cluster waits for the topology to settle before rebuilding the ring
+    #[serde(default = "Scylla::default_ring_rebuild_delay")]
+    pub ring_rebuild_delay: u64,
 }
 
 impl Default for Scylla {
@@ -103,6 +176,13 @@ impl Default for Scylla {
             recv_buffer_size: None,
             send_buffer_size: None,
             authenticator: PasswordAuth::default(),
+            peer_discovery: None,
+            load_balancing: LoadBalancing::default(),
+            retry_policy: RetryPolicyConfig::default(),
+            request_timeout: Self::default_request_timeout(),
+            backpressure: None,
+            speculative_execution: HashMap::new(),
+            ring_rebuild_delay: Self::default_ring_rebuild_delay(),
         }
     }
 }
@@ -119,6 +199,13 @@ impl Scylla {
             recv_buffer_size: None,
             send_buffer_size: None,
             authenticator: password_auth,
+            peer_discovery: None,
+            load_balancing: LoadBalancing::default(),
+            retry_policy: RetryPolicyConfig::default(),
+            request_timeout: Self::default_request_timeout(),
+            backpressure: None,
+            speculative_execution: HashMap::new(),
+            ring_rebuild_delay: Self::default_ring_rebuild_delay(),
         }
     }
     /// Insert scylla node
@@ -131,6 +218,51 @@ impl Scylla {
         self.nodes.remove(&node);
         self
     }
+    /// Enable the discovery of the cluster nodes from the initial nodes
+    pub fn peer_discovery(&mut self, peer_discovery: PeerDiscoveryConfig) -> &mut Self {
+        self.peer_discovery.replace(peer_discovery);
+        self
+    }
+    /// Enable the queue of the requests waiting for a free stream
+    pub fn backpressure(&mut self, backpressure: BackpressureConfig) -> &mut Self {
+        self.backpressure.replace(backpressure);
+        self
+    }
+    /// Enable the speculative execution of the idempotent requests of the request type
+    pub fn speculative_execution(
+        &mut self,
+        request_type: RequestType,
+        speculative_execution: SpeculativeExecutionConfig,
+    ) -> &mut Self {
+        self.speculative_execution.insert(request_type, speculative_execution);
+        self
+    }
+    /// Set the load balancing policy of the requests
+    pub fn load_balancing(&mut self, load_balancing: LoadBalancing) -> &mut Self {
+        self.load_balancing = load_balancing;
+        self
+    }
+    /// Set the retry policy of the failed requests
+    pub fn retry_policy(&mut self, retry_policy: RetryPolicyConfig) -> &mut Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+    /// Set the default client side timeout of the requests
+    pub fn request_timeout(&mut self, request_timeout: Option<Duration>) -> &mut Self {
+        self.request_timeout = request_timeout.map(|timeout| timeout.as_millis() as u64);
+        self
+    }
+    fn default_request_timeout() -> Option<u64> {
+        Some(12000)
+    }
+    /// Set the delay the cluster waits for the topology to settle before rebuilding the ring
+    pub fn ring_rebuild_delay(&mut self, ring_rebuild_delay: Duration) -> &mut Self {
+        self.ring_rebuild_delay = ring_rebuild_delay.as_millis() as u64;
+        self
+    }
+    fn default_ring_rebuild_delay() -> u64 {
+        1000
+    }
     /// Insert keyspace into the config
     pub fn insert_keyspace(&mut self, keyspace: KeyspaceConfig) -> &mut Self {
         self.keyspaces.insert(keyspace);
@@ -188,6 +320,8 @@ where
         // todo add scylla banner
         // publish scylla as config
         rt.add_resource(self.clone()).await;
+        set_retry_policy(self.retry_policy.policy());
+        set_speculative_execution(self.speculative_execution.clone());
         let cluster = Cluster::new();
         let cluster_handle = rt.start("cluster".to_string(), cluster).await?;
         if rt.microservices_all(|ms| ms.is_idle()) {
@@ -201,6 +335,8 @@ where
             match event {
                 ScyllaEvent::UpdateState(new_state) => {
                     *self = new_state;
+                    set_retry_policy(self.retry_policy.policy());
+                    set_speculative_execution(self.speculative_execution.clone());
                     rt.publish(self.clone()).await;
                 }
                 ScyllaEvent::GetClusterHandle(oneshot) => {
@@ -1,6 +1,7 @@
 use super::{
     node::Node,
     KeyspaceConfig,
+    PeerDiscoveryConfig,
     Scylla,
     ScyllaEvent,
     ScyllaHandle,
@@ -9,9 +10,17 @@ use crate::{
     app::ring::{
         Registry,
         ReplicationInfo,
+        ReplicationStrategy,
         SharedRing,
+        Tablets,
+    },
+    cql::{
+        Cql,
+        CqlBuilder,
+        HostId,
+        Peer,
+        SCHEMA_CHANGE,
     },
-    cql::CqlBuilder,
 };
 use std::sync::Arc;
 use thiserror::Error;
@@ -43,7 +52,10 @@ use overclock::{
 };
 
 use std::{
-    collections::HashMap,
+    collections::{
+        HashMap,
+        HashSet,
+    },
     convert::TryFrom,
     net::SocketAddr,
 };
@@ -53,7 +65,16 @@ pub(crate) type Nodes = HashMap<SocketAddr, NodeInfo>;
 /// Cluster state
 pub struct Cluster {
     nodes: Nodes,
+    /// Manually configured keyspaces, which override the discovered ones
     keyspaces: HashMap<String, ReplicationInfo>,
+    /// Keyspaces replication discovered from system_schema.keyspaces
+    discovered_keyspaces: HashMap<String, ReplicationStrategy>,
+    /// Nodes removed by the admin, which must not be started by the peer discovery
+    removed_nodes: HashSet<SocketAddr>,
+    /// Nodes started by the peer discovery, which get stopped once they are no longer listed in system.peers
+    discovered_nodes: HashSet<SocketAddr>,
+    /// The generation of the latest scheduled ring rebuild, the outdated rebuilds are skipped
+    ring_generation: u64,
 }
 
 /// Cluster Event type
@@ -62,6 +83,10 @@ pub enum ClusterEvent {
     Topology(Topology, Option<TopologyResponder>),
     /// Used by the Node to keep the cluster up to date with its service
     Microservice(ScopeId, Service, Option<ActorResult<()>>),
+    /// Rebuild the ring once the topology settled, only if no later rebuild got scheduled
+    RebuildRing(u64),
+    /// Used by the schema changes listener to get the addresses of the current nodes
+    Nodes(tokio::sync::oneshot::Sender<Vec<SocketAddr>>),
     /// Shutdown signal
     Shutdown,
 }
@@ -92,7 +117,12 @@ pub enum Topology {
     UpsertKeyspace(KeyspaceConfig),
     /// Remove keyspace by its name
     RemoveKeyspace(String),
-    /// Used by Scylla/dashboard to build new ring and expose the recent cluster topology
+    /// Rediscover the keyspaces replication from system_schema, used on schema change
+    RefreshKeyspaces,
+    /// Discover and start the nodes listed in system.peers, used to refresh the cluster nodes periodically
+    DiscoverPeers,
+    /// Used by Scylla/dashboard to build new ring from the healthy nodes right away, otherwise the ring gets rebuilt
+    /// once the topology settles
     BuildRing,
 }
 
@@ -132,7 +162,17 @@ impl Cluster {
     pub fn new() -> Self {
         let nodes = HashMap::new();
         let keyspaces = HashMap::new();
-        Self { nodes, keyspaces }
+        let discovered_keyspaces = HashMap::new();
+        let removed_nodes = HashSet::new();
+        let discovered_nodes = HashSet::new();
+        Self {
+            nodes,
+            keyspaces,
+            discovered_keyspaces,
+            removed_nodes,
+            discovered_nodes,
+            ring_generation: 0,
+        }
     }
 }
 
@@ -145,6 +185,10 @@ pub struct NodeInfo {
     pub(crate) address: SocketAddr,
     /// in which data_center the scylla node exist
     pub(crate) data_center: String,
+    /// in which rack of the data_center the scylla node exist
+    pub(crate) rack: String,
+    /// The host id of the node, which identifies the tablet replicas
+    pub(crate) host_id: Option<HostId>,
     /// The tokens of all nodes shards.
     pub(crate) tokens: Vec<i64>,
     /// the shard_count in scylla node.
@@ -166,7 +210,7 @@ impl Actor<ScyllaHandle> for Cluster {
         let parent_id = rt
             .parent_id()
             .ok_or_else(|| ActorError::exit_msg("cluster without scylla supervisor"))?;
-        let scylla = rt
+        let mut scylla = rt
             .lookup::<Scylla>(parent_id)
             .await
             .ok_or_else(|| ActorError::exit_msg("cluster unables to lookup for scylla as config"))?;
@@ -190,15 +234,30 @@ impl Actor<ScyllaHandle> for Cluster {
             }
             self.keyspaces.insert(name.clone(), info);
         }
+        if let Some(peer_discovery) = scylla.peer_discovery.clone() {
+            match self.discover_peers(rt, &mut scylla, &peer_discovery).await {
+                Ok((started, _)) => log::info!("Started {} discovered nodes", started),
+                Err(e) => log::warn!("Unable to discover peers, error: {}", e),
+            }
+            Self::refresh_peers(rt.handle().clone(), peer_discovery.refresh_interval);
+        }
+        if let Err(e) = self.discover_keyspaces(&scylla).await {
+            log::warn!("Unable to discover keyspaces replication, error: {}", e);
+        }
+        if let Err(e) = self.discover_tablets(&scylla).await {
+            log::warn!("Unable to discover tablets, error: {}", e);
+        }
+        Self::listen_schema_changes(rt.handle().clone(), scylla.clone());
         if self.nodes.is_empty() {
             rt.update_status(ServiceStatus::Idle).await;
         } else {
             SharedRing::new(
                 &scylla.local_dc,
                 reporters_registry.read().await.clone(),
-                self.keyspaces.clone(),
+                self.replication(&scylla),
                 scylla.reporter_count,
                 &self.nodes,
+                &scylla.load_balancing,
             )
             .commit();
         }
@@ -234,6 +293,63 @@ impl Actor<ScyllaHandle> for Cluster {
                             self.keyspaces.remove(&name);
                             scylla.remove_keyspace(&name);
                         }
+                        Topology::RefreshKeyspaces => {
+                            if let Err(e) = self.discover_keyspaces(&scylla).await {
+                                log::warn!("Unable to refresh keyspaces replication, error: {}", e);
+                                if let Some(responder) = responder_opt.take() {
+                                    let error_response: Result<Topology, _> = Err(TopologyErr::new(format!(
+                                        "Unable to refresh keyspaces replication, error: {}",
+                                        e
+                                    )));
+                                    responder.reply(error_response).await.ok();
+                                }
+                                continue;
+                            }
+                            log::info!("Refreshed keyspaces replication");
+                            if let Err(e) = self.discover_tablets(&scylla).await {
+                                log::warn!("Unable to refresh tablets, error: {}", e);
+                            }
+                            // expose the changes only if the ring is already stable, otherwise the admin
+                            // supposed to rebuild the ring
+                            if rt.service().is_running() {
+                                let maybe_unstable_registry = registry.read().await.clone();
+                                self.build_healthy_ring(maybe_unstable_registry, &scylla);
+                            }
+                            if let Some(responder) = responder_opt.take() {
+                                let ok_response: Result<_, TopologyErr> = Ok(Topology::RefreshKeyspaces);
+                                responder.reply(ok_response).await.ok();
+                            }
+                        }
+                        Topology::DiscoverPeers => {
+                            let peer_discovery = scylla.peer_discovery.clone().unwrap_or_default();
+                            match self.discover_peers(rt, &mut scylla, &peer_discovery).await {
+                                Ok((started, stopped)) => {
+                                    if started + stopped > 0 {
+                                        log::info!(
+                                            "Started {} discovered nodes, and stopped {} unlisted nodes",
+                                            started,
+                                            stopped
+                                        );
+                                        rt.supervisor_handle()
+                                            .send(ScyllaEvent::UpdateState(scylla.clone()))
+                                            .ok();
+                                        self.schedule_ring_rebuild(rt.handle().clone(), scylla.ring_rebuild_delay);
+                                    }
+                                    if let Some(responder) = responder_opt.take() {
+                                        let ok_response: Result<_, TopologyErr> = Ok(Topology::DiscoverPeers);
+                                        responder.reply(ok_response).await.ok();
+                                    }
+                                }
+                                Err(e) => {
+                                    log::warn!("Unable to discover peers, error: {}", e);
+                                    if let Some(responder) = responder_opt.take() {
+                                        let error_response: Result<Topology, _> =
+                                            Err(TopologyErr::new(format!("Unable to discover peers, error: {}", e)));
+                                        responder.reply(error_response).await.ok();
+                                    }
+                                }
+                            }
+                        }
                         Topology::AddNode(address) => {
                             if self.nodes.contains_key(&address) {
                                 if let Some(responder) = responder_opt.take() {
@@ -276,25 +392,25 @@ impl Actor<ScyllaHandle> for Cluster {
                                                     msb: cqlconn.msb(),
                                                     shard_count,
                                                     data_center: dc,
+                                                    rack: cqlconn.take_rack().unwrap_or_default(),
+                                                    host_id: cqlconn.take_host_id(),
                                                     tokens,
                                                 };
                                                 // add node_info to nodes
                                                 self.nodes.insert(address.clone(), node_info);
                                                 scylla.nodes.insert(address);
+                                                self.removed_nodes.remove(&address);
                                                 log::info!("Added {} node!", address);
+                                                self.schedule_ring_rebuild(
+                                                    rt.handle().clone(),
+                                                    scylla.ring_rebuild_delay,
+                                                );
                                                 if let Some(responder) = responder_opt.take() {
-                                                    rt.update_status(ServiceStatus::Maintenance).await;
-                                                    log::info!("Cluster is Maintenance");
+                                                    // added by the admin, so the peer discovery must not stop it
+                                                    self.discovered_nodes.remove(&address);
                                                     let ok_response: Result<_, TopologyErr> =
                                                         Ok(Topology::AddNode(address));
                                                     responder.reply(ok_response).await.ok();
-                                                } else {
-                                                    if !rt.service().is_maintenance() {
-                                                        let maybe_unstable_registry = registry.read().await.clone();
-                                                        log::warn!("Rebuilding healthy ring");
-                                                        self.build_healthy_ring(maybe_unstable_registry, &scylla);
-                                                        self.update_service_status(rt).await;
-                                                    } // else the admin supposed to rebuild the ring
                                                 }
                                             }
                                             Err(err) => {
@@ -340,14 +456,14 @@ impl Actor<ScyllaHandle> for Cluster {
                             // get and remove node_info
                             if let Some(node_info) = self.nodes.get(&address) {
                                 if let Some(join_handle) = rt.shutdown_child(&node_info.scope_id).await {
-                                    // update status to maintenance, as this is a topology event
-                                    rt.update_status(ServiceStatus::Maintenance).await;
-                                    log::info!("Cluster is Maintenance");
                                     // Await till it gets shutdown, it forces sync shutdown
                                     join_handle.await.ok();
                                     self.nodes.remove(&address);
+                                    self.removed_nodes.insert(address);
+                                    self.discovered_nodes.remove(&address);
                                     scylla.nodes.remove(&address);
                                     log::info!("Removed {} node!", address);
+                                    self.schedule_ring_rebuild(rt.handle().clone(), scylla.ring_rebuild_delay);
                                     let ok_response: Result<_, TopologyErr> = Ok(Topology::RemoveNode(address));
                                     responder.reply(ok_response).await.ok();
                                 };
@@ -365,51 +481,11 @@ impl Actor<ScyllaHandle> for Cluster {
                             let responder = responder_opt
                                 .take()
                                 .ok_or_else(|| ActorError::exit_msg("cannot build ring without responder"))?;
-                            // re/build
-                            let status_change;
-                            if self.nodes.is_empty() {
-                                SharedRing::drop();
-                                status_change = ServiceStatus::Idle;
-                            } else {
-                                let registry_snapshot = registry.read().await.clone();
-                                // compute total shards count for all nodes
-                                let mut total_shard_count = 0;
-                                self.nodes
-                                    .iter()
-                                    .for_each(|(_addr, node_info)| total_shard_count += node_info.shard_count as usize);
-                                // ensure all nodes are running
-                                if rt.microservices_any(|node| !node.is_running())
-                                    || self.nodes.len() != rt.service().microservices().len()
-                                    || registry_snapshot.len() != total_shard_count
-                                {
-                                    log::error!(
-                                        "Unstable cluster, cannot build ring!, fix this by removing any dead node(s)"
-                                    );
-                                    // the cluster in critical state, we cannot rebuild new ring
-                                    if let Some(responder) = responder_opt.take() {
-                                        let error_response: Result<Topology, _> =
-                                            Err(TopologyErr::new(format!("Unstable cluster, unable to build ring")));
-                                        responder.reply(error_response).await.ok();
-                                    }
-                                    continue;
-                                }
-                                SharedRing::new(
-                                    &scylla.local_dc,
-                                    registry_snapshot,
-                                    self.keyspaces.clone(),
-                                    scylla.reporter_count,
-                                    &self.nodes,
-                                )
-                                .commit();
-                                status_change = ServiceStatus::Running;
-                            }
+                            // rebuild from the healthy nodes, the unhealthy ones are excluded from the replicas
+                            self.rebuild_ring(rt, &registry, &scylla).await;
                             rt.supervisor_handle()
                                 .send(ScyllaEvent::UpdateState(scylla.clone()))
                                 .ok();
-                            if rt.service().status() != &status_change {
-                                log::info!("Cluster is {}", status_change);
-                            }
-                            rt.update_status(status_change).await;
                             let ok_response: Result<_, TopologyErr> = Ok(Topology::BuildRing);
                             responder.reply(ok_response).await.ok();
                         }
@@ -429,10 +505,8 @@ impl Actor<ScyllaHandle> for Cluster {
                             rt.remove_microservice(scope_id);
                         }
                         if !rt.service().is_stopping() && self.nodes.contains_key(&address) {
-                            {
-                                let maybe_unstable_registry = registry.read().await.clone();
-                                self.build_healthy_ring(maybe_unstable_registry, &scylla);
-                            }
+                            // exclude the node from the ring until it comes up
+                            self.schedule_ring_rebuild(rt.handle().clone(), scylla.ring_rebuild_delay);
                             if let Err(ActorError {
                                 source: _,
                                 request: Some(ActorRequest::Restart(_)),
@@ -443,7 +517,17 @@ impl Actor<ScyllaHandle> for Cluster {
                             }
                         }
                     } else {
+                        let came_up = service.is_running()
+                            && !rt
+                                .service()
+                                .microservices()
+                                .get(&scope_id)
+                                .is_some_and(|node| node.is_running());
                         rt.upsert_microservice(scope_id, service);
+                        if came_up && !rt.service().is_stopping() {
+                            // include the node in the ring
+                            self.schedule_ring_rebuild(rt.handle().clone(), scylla.ring_rebuild_delay);
+                        }
                     }
                     if rt.service().is_maintenance() || rt.service().is_stopping() {
                         rt.update_status(rt.service().status().clone()).await;
@@ -454,11 +538,22 @@ impl Actor<ScyllaHandle> for Cluster {
                         self.update_service_status(rt).await;
                     }
                 }
+                ClusterEvent::RebuildRing(generation) => {
+                    // skip the outdated rebuild, as a later topology change scheduled another one
+                    if generation == self.ring_generation && !rt.service().is_stopping() {
+                        log::info!("Rebuilding the ring, as the topology settled");
+                        self.rebuild_ring(rt, &registry, &scylla).await;
+                    }
+                }
+                ClusterEvent::Nodes(tx) => {
+                    tx.send(self.nodes.keys().cloned().collect()).ok();
+                }
                 ClusterEvent::Shutdown => {
                     log::warn!("Cluster is Stopping");
                     // stop all the children/nodes
                     rt.stop().await;
                     SharedRing::drop();
+                    Tablets::drop();
                     if rt.microservices_stopped() {
                         rt.inbox_mut().close();
                     }
@@ -510,6 +605,8 @@ impl Cluster {
                 msb: cqlconn.msb(),
                 shard_count,
                 data_center: dc,
+                rack: cqlconn.take_rack().unwrap_or_default(),
+                host_id: cqlconn.take_host_id(),
                 tokens,
             };
             // add node_info to nodes
@@ -521,6 +618,249 @@ impl Cluster {
         }
         Ok(())
     }
+    /// Compute the keyspaces replication info, the manually configured keyspaces override the discovered ones
+    fn replication(&self, scylla: &Scylla) -> HashMap<String, ReplicationInfo> {
+        let mut keyspaces: HashMap<String, ReplicationInfo> = self
+            .discovered_keyspaces
+            .iter()
+            .filter_map(|(name, strategy)| {
+                strategy
+                    .replication_info(&scylla.local_dc, &self.nodes)
+                    .map(|info| (name.clone(), info))
+            })
+            .collect();
+        keyspaces.extend(self.keyspaces.clone());
+        keyspaces
+    }
+    /// Connect to a node without fetching its tokens, to query the system tables or listen for events
+    async fn control_connection(address: SocketAddr, scylla: &Scylla) -> anyhow::Result<Cql> {
+        CqlBuilder::new()
+            .address(address)
+            .recv_buffer_size(scylla.recv_buffer_size)
+            .send_buffer_size(scylla.send_buffer_size)
+            .authenticator(scylla.authenticator.clone())
+            .build()
+            .await
+    }
+    /// Fetch the peers from system.peers of the first reachable node, along with the address of that node
+    async fn fetch_peers(&self, scylla: &Scylla) -> anyhow::Result<(SocketAddr, Vec<Peer>)> {
+        let mut error = anyhow::anyhow!("No nodes to discover the peers from");
+        for address in self.nodes.keys() {
+            match Self::control_connection(*address, scylla).await {
+                Ok(mut cqlconn) => match cqlconn.fetch_peers().await {
+                    Ok(peers) => return Ok((*address, peers)),
+                    Err(e) => error = e,
+                },
+                Err(e) => error = e,
+            }
+            log::warn!("Unable to fetch peers from {} node, error: {}", address, error);
+        }
+        Err(error)
+    }
+    /// Start the discovered peers which are not yet part of the cluster, and stop the previously discovered nodes
+    /// which are no longer listed. Returns the number of started and stopped nodes
+    async fn discover_peers(
+        &mut self,
+        rt: &mut Rt<Self, ScyllaHandle>,
+        scylla: &mut Scylla,
+        peer_discovery: &PeerDiscoveryConfig,
+    ) -> anyhow::Result<(usize, usize)> {
+        let (fetched_from, peers) = self.fetch_peers(scylla).await?;
+        let mut stopped = 0;
+        for address in unlisted_nodes(&self.discovered_nodes, &fetched_from, &peers) {
+            log::info!("Stopping {} node, as it is no longer listed in system.peers", address);
+            if self.stop_node(rt, address, scylla).await {
+                stopped += 1;
+            }
+        }
+        let remote_nodes = self
+            .nodes
+            .values()
+            .filter(|node_info| node_info.data_center != scylla.local_dc)
+            .count();
+        let addresses = select_peers(
+            peers,
+            |address| self.nodes.contains_key(address) || self.removed_nodes.contains(address),
+            remote_nodes,
+            &scylla.local_dc,
+            peer_discovery,
+        );
+        let mut started = 0;
+        for address in addresses {
+            log::info!("Starting discovered node: {}", address);
+            match self.start_node(rt, address, scylla).await {
+                Ok(()) => {
+                    scylla.nodes.insert(address);
+                    self.discovered_nodes.insert(address);
+                    started += 1;
+                }
+                Err(e) => log::warn!("Unable to start discovered node: {}, error: {}", address, e),
+            }
+        }
+        Ok((started, stopped))
+    }
+    /// Shutdown a node and remove it from the cluster, returns whether it got stopped
+    async fn stop_node(&mut self, rt: &mut Rt<Self, ScyllaHandle>, address: SocketAddr, scylla: &mut Scylla) -> bool {
+        if let Some(node_info) = self.nodes.get(&address) {
+            if let Some(join_handle) = rt.shutdown_child(&node_info.scope_id).await {
+                join_handle.await.ok();
+                self.nodes.remove(&address);
+                self.discovered_nodes.remove(&address);
+                scylla.nodes.remove(&address);
+                self.schedule_ring_rebuild(rt.handle().clone(), scylla.ring_rebuild_delay);
+                return true;
+            }
+        }
+        false
+    }
+    fn refresh_peers(my_handle: UnboundedHandle<ClusterEvent>, refresh_interval: u64) {
+        let refresh_peers_task = async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(refresh_interval)).await;
+                if my_handle
+                    .send(ClusterEvent::Topology(Topology::DiscoverPeers, None))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        };
+        overclock::spawn_task("cluster peers refresher", refresh_peers_task);
+    }
+    /// Discover the tablets of the tablets based keyspaces from system.tablets of the first reachable node
+    async fn discover_tablets(&self, scylla: &Scylla) -> anyhow::Result<()> {
+        let mut error = anyhow::anyhow!("No nodes to discover the tablets from");
+        for address in self.nodes.keys() {
+            match Self::control_connection(*address, scylla).await {
+                Ok(mut cqlconn) => match cqlconn.fetch_tablets().await {
+                    Ok(tablets) => {
+                        Tablets::new(tablets).commit();
+                        return Ok(());
+                    }
+                    Err(e) => error = e,
+                },
+                Err(e) => error = e,
+            }
+            log::warn!("Unable to fetch tablets from {} node, error: {}", address, error);
+        }
+        Err(error)
+    }
+    /// Discover the keyspaces replication from system_schema.keyspaces of the first reachable node
+    async fn discover_keyspaces(&mut self, scylla: &Scylla) -> anyhow::Result<()> {
+        let mut error = anyhow::anyhow!("No nodes to discover the keyspaces from");
+        let addresses: Vec<SocketAddr> = self.nodes.keys().cloned().collect();
+        for address in addresses {
+            match Self::fetch_replication(address, scylla).await {
+                Ok(discovered_keyspaces) => {
+                    self.discovered_keyspaces = discovered_keyspaces;
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Unable to fetch keyspaces replication from {} node, error: {}",
+                        address,
+                        e
+                    );
+                    error = e;
+                }
+            }
+        }
+        Err(error)
+    }
+    async fn fetch_replication(
+        address: SocketAddr,
+        scylla: &Scylla,
+    ) -> anyhow::Result<HashMap<String, ReplicationStrategy>> {
+        let mut cqlconn = Self::control_connection(address, scylla).await?;
+        Ok(cqlconn
+            .fetch_replication()
+            .await?
+            .into_iter()
+            .filter_map(
+                |(name, replication)| match ReplicationStrategy::try_from(&replication) {
+                    Ok(strategy) => Some((name, strategy)),
+                    Err(e) => {
+                        log::warn!("Skipping {} keyspace replication, error: {}", name, e);
+                        None
+                    }
+                },
+            )
+            .collect())
+    }
+    /// Listen for schema changes on a control connection, and refresh the keyspaces replication on any keyspace
+    /// change. The control connection reconnects after 5 seconds if it fails, to the node which follows the failed
+    /// one among the current nodes of the cluster.
+    fn listen_schema_changes(my_handle: UnboundedHandle<ClusterEvent>, scylla: Scylla) {
+        let listen_schema_changes_task = async move {
+            let mut last_address: Option<SocketAddr> = None;
+            loop {
+                let (tx, rx) = tokio::sync::oneshot::channel();
+                if my_handle.send(ClusterEvent::Nodes(tx)).is_err() {
+                    break;
+                }
+                let mut addresses = match rx.await {
+                    Ok(addresses) => addresses,
+                    Err(_) => break,
+                };
+                addresses.sort();
+                let next_address = addresses
+                    .iter()
+                    .find(|address| Some(**address) > last_address)
+                    .or_else(|| addresses.first())
+                    .cloned();
+                if let Some(address) = next_address {
+                    last_address = Some(address);
+                    let result: anyhow::Result<()> = async {
+                        let mut cqlconn = Self::control_connection(address, &scylla).await?;
+                        cqlconn.register(&[SCHEMA_CHANGE]).await?;
+                        log::info!("Listening for schema changes on {} node", address);
+                        loop {
+                            let event = cqlconn.next_schema_change().await?;
+                            if event.is_keyspace_change() {
+                                log::info!("Keyspace {} got {}", event.keyspace, event.change_type);
+                                my_handle
+                                    .send(ClusterEvent::Topology(Topology::RefreshKeyspaces, None))
+                                    .map_err(|_| anyhow::anyhow!("closed cluster handle"))?;
+                            }
+                        }
+                    }
+                    .await;
+                    if my_handle.is_closed() {
+                        break;
+                    }
+                    if let Err(e) = result {
+                        log::warn!("Schema changes listener on {} node failed, error: {}", address, e);
+                    }
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            }
+        };
+        overclock::spawn_task("cluster schema changes listener", listen_schema_changes_task);
+    }
+    /// Schedule a ring rebuild after the delay in milliseconds, any topology change within the delay postpones it
+    fn schedule_ring_rebuild(&mut self, my_handle: UnboundedHandle<ClusterEvent>, delay: u64) {
+        self.ring_generation += 1;
+        let generation = self.ring_generation;
+        let rebuild_ring_task = async move {
+            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            my_handle.send(ClusterEvent::RebuildRing(generation)).ok();
+        };
+        overclock::spawn_task("cluster ring rebuilder", rebuild_ring_task);
+    }
+    /// Rebuild the ring from the healthy nodes and update the cluster status
+    async fn rebuild_ring(&mut self, rt: &mut Rt<Self, ScyllaHandle>, registry: &RwLock<Registry>, scylla: &Scylla) {
+        if self.nodes.is_empty() {
+            SharedRing::drop();
+            if !rt.service().is_idle() {
+                log::warn!("Cluster is Idle");
+            }
+            rt.update_status(ServiceStatus::Idle).await;
+        } else {
+            let maybe_unstable_registry = registry.read().await.clone();
+            self.build_healthy_ring(maybe_unstable_registry, scylla);
+            self.update_service_status(rt).await;
+        }
+    }
     fn restart_node(my_handle: UnboundedHandle<ClusterEvent>, address: SocketAddr) {
         let restart_node_task = async move {
             log::warn!("After 5 seconds will try to restart/reconnect {}", address);
@@ -587,9 +927,10 @@ impl Cluster {
             SharedRing::new(
                 &scylla.local_dc,
                 registry,
-                self.keyspaces.clone(),
+                self.replication(&scylla),
                 scylla.reporter_count,
-                &self.nodes,
+                &healthy_nodes,
+                &scylla.load_balancing,
             )
             .commit();
             if self.nodes.len() != healthy_nodes.len() {
@@ -601,9 +942,55 @@ impl Cluster {
     }
 }
 
+/// Select the peers to start: the ones which are neither skipped nor outside the configured datacenters, without
+/// exceeding the max remote nodes given the number of remote nodes already in the cluster
+fn select_peers(
+    peers: Vec<Peer>,
+    skip: impl Fn(&SocketAddr) -> bool,
+    mut remote_nodes: usize,
+    local_dc: &str,
+    peer_discovery: &PeerDiscoveryConfig,
+) -> Vec<SocketAddr> {
+    let mut addresses = Vec::new();
+    for Peer {
+        address, data_center, ..
+    } in peers
+    {
+        if skip(&address) {
+            continue;
+        }
+        if !peer_discovery.data_centers.is_empty() && !peer_discovery.data_centers.contains(&data_center) {
+            continue;
+        }
+        if data_center != local_dc {
+            if peer_discovery.max_remote_nodes.is_some_and(|max| remote_nodes >= max) {
+                continue;
+            }
+            remote_nodes += 1;
+        }
+        addresses.push(address);
+    }
+    addresses
+}
+
+/// Get the discovered nodes which are no longer listed in the peers fetched from the given node.
+/// Note: system.peers doesn't list the node it got queried from, and the contact points and admin added nodes are
+/// not part of the discovered nodes, so neither gets stopped
+fn unlisted_nodes(
+    discovered_nodes: &HashSet<SocketAddr>,
+    fetched_from: &SocketAddr,
+    peers: &[Peer],
+) -> Vec<SocketAddr> {
+    discovered_nodes
+        .iter()
+        .filter(|address| *address != fetched_from && !peers.iter().any(|peer| &peer.address == *address))
+        .copied()
+        .collect()
+}
+
 #[async_trait]
 /// The public interface of cluster handle, it enables adding/removing and building ring.
-/// Note: you must invoke build ring to expose the changes
+/// Note: the ring gets rebuilt once the topology settles, build ring exposes the changes right away
 pub trait ClusterHandleExt {
     /// Add scylla node to the cluster,
     async fn add_node(&self, node: SocketAddr) -> TopologyResponse;
@@ -613,7 +1000,11 @@ pub trait ClusterHandleExt {
     async fn upsert_keyspace(&self, keyspace_config: KeyspaceConfig) -> TopologyResponse;
     /// remove keyspace
     async fn remove_keyspace(&self, keyspace_name: &str) -> TopologyResponse;
-    /// Build ring with uniform replication factor
+    /// Rediscover the keyspaces replication from system_schema
+    async fn refresh_keyspaces(&self) -> TopologyResponse;
+    /// Discover and start the nodes listed in system.peers
+    async fn discover_peers(&self) -> TopologyResponse;
+    /// Build ring from the healthy nodes
     async fn build_ring(&self) -> TopologyResponse;
 }
 
@@ -669,6 +1060,22 @@ impl ClusterHandleExt for UnboundedHandle<ClusterEvent> {
             ))
         })?
     }
+    async fn refresh_keyspaces(&self) -> TopologyResponse {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let event = ClusterEvent::Topology(Topology::RefreshKeyspaces, Some(TopologyResponder::OneShot(tx)));
+        self.send(event)
+            .map_err(|_| TopologyErr::new("Unable to refresh keyspaces, error: closed cluster handle".to_string()))?;
+        rx.await
+            .map_err(|_| TopologyErr::new("Unable to refresh keyspaces, error: closed oneshot receiver".to_string()))?
+    }
+    async fn discover_peers(&self) -> TopologyResponse {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let event = ClusterEvent::Topology(Topology::DiscoverPeers, Some(TopologyResponder::OneShot(tx)));
+        self.send(event)
+            .map_err(|_| TopologyErr::new("Unable to discover peers, error: closed cluster handle".to_string()))?;
+        rx.await
+            .map_err(|_| TopologyErr::new("Unable to discover peers, error: closed oneshot receiver".to_string()))?
+    }
     async fn remove_node(&self, address: SocketAddr) -> TopologyResponse {
         let (tx, rx) = tokio::sync::oneshot::channel();
         let event = ClusterEvent::Topology(Topology::RemoveNode(address), Some(TopologyResponder::OneShot(tx)));
@@ -694,3 +1101,101 @@ impl ClusterHandleExt for UnboundedHandle<ClusterEvent> {
             .map_err(|_| TopologyErr::new(format!("Unable to build ring, error: closed oneshot receiver")))?
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(address: &str, data_center: &str) -> Peer {
+        Peer {
+            address: address.parse().unwrap(),
+            data_center: data_center.to_string(),
+            rack: None,
+            tokens: Vec::new(),
+            host_id: None,
+        }
+    }
+
+    fn addresses(addresses: &[&str]) -> Vec<SocketAddr> {
+        addresses.iter().map(|address| address.parse().unwrap()).collect()
+    }
+
+    fn peers() -> Vec<Peer> {
+        vec![
+            peer("10.0.0.2:9042", "dc1"),
+            peer("10.0.1.1:9042", "dc2"),
+            peer("10.0.0.3:9042", "dc1"),
+            peer("10.0.1.2:9042", "dc2"),
+            peer("10.0.2.1:9042", "dc3"),
+        ]
+    }
+
+    #[test]
+    fn select_peers_skips_known_nodes() {
+        let known = addresses(&["10.0.0.2:9042", "10.0.1.2:9042"]);
+        let selected = select_peers(
+            peers(),
+            |address| known.contains(address),
+            0,
+            "dc1",
+            &PeerDiscoveryConfig::default(),
+        );
+        assert_eq!(
+            selected,
+            addresses(&["10.0.1.1:9042", "10.0.0.3:9042", "10.0.2.1:9042"])
+        );
+    }
+
+    #[test]
+    fn select_peers_filters_data_centers() {
+        let peer_discovery = PeerDiscoveryConfig {
+            data_centers: maplit::hashset! {"dc1".to_string(), "dc3".to_string()},
+            ..Default::default()
+        };
+        let selected = select_peers(peers(), |_| false, 0, "dc1", &peer_discovery);
+        assert_eq!(
+            selected,
+            addresses(&["10.0.0.2:9042", "10.0.0.3:9042", "10.0.2.1:9042"])
+        );
+    }
+
+    #[test]
+    fn select_peers_limits_remote_nodes() {
+        let peer_discovery = PeerDiscoveryConfig {
+            max_remote_nodes: Some(2),
+            ..Default::default()
+        };
+        let selected = select_peers(peers(), |_| false, 0, "dc1", &peer_discovery);
+        assert_eq!(
+            selected,
+            addresses(&["10.0.0.2:9042", "10.0.1.1:9042", "10.0.0.3:9042", "10.0.1.2:9042"])
+        );
+        // the remote nodes already in the cluster count toward the limit
+        let selected = select_peers(peers(), |_| false, 1, "dc1", &peer_discovery);
+        assert_eq!(
+            selected,
+            addresses(&["10.0.0.2:9042", "10.0.1.1:9042", "10.0.0.3:9042"])
+        );
+        let peer_discovery = PeerDiscoveryConfig {
+            max_remote_nodes: Some(0),
+            ..Default::default()
+        };
+        let selected = select_peers(peers(), |_| false, 0, "dc1", &peer_discovery);
+        assert_eq!(selected, addresses(&["10.0.0.2:9042", "10.0.0.3:9042"]));
+    }
+
+    #[test]
+    fn unlisted_nodes_are_stopped() {
+        let discovered: HashSet<SocketAddr> = addresses(&["10.0.0.2:9042", "10.0.0.3:9042", "10.0.1.1:9042"])
+            .into_iter()
+            .collect();
+        let fetched_from = "10.0.0.3:9042".parse().unwrap();
+        // 10.0.0.1 is a contact point, so it's never part of the discovered nodes
+        let listed = vec![peer("10.0.0.1:9042", "dc1"), peer("10.0.0.2:9042", "dc1")];
+        assert_eq!(
+            unlisted_nodes(&discovered, &fetched_from, &listed),
+            addresses(&["10.0.1.1:9042"])
+        );
+        assert!(unlisted_nodes(&HashSet::new(), &fetched_from, &listed).is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+use super::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::{
+    sync::atomic::{
+        AtomicU64,
+        AtomicUsize,
+        Ordering,
+    },
+    time::Duration,
+};
+
+/// The load of a shard, updated by its reporters and used by the load balancing policies
+#[derive(Debug, Default)]
+pub struct ShardLoad {
+    /// The requests sent to the shard which are still waiting for a response
+    in_flight: AtomicUsize,
+    /// The exponentially weighted moving average of the response times in nanoseconds, zero if unknown
+    latency: AtomicU64,
+    /// The requests queued by the reporters of the shard, waiting for a free stream
+    queued: AtomicUsize,
+}
+
+impl ShardLoad {
+    /// The weight of a new response time in the moving average is 1 / 2^LATENCY_SHIFT
+    const LATENCY_SHIFT: u32 = 3;
+    /// Returns the number of in-flight requests
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+    /// Returns the number of requests waiting for a free stream
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+    /// Returns the moving average of the response times, if any response has been received
+    pub fn latency(&self) -> Option<Duration> {
+        match self.latency.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+    /// Record a request queued by a reporter of the shard
+    pub(crate) fn request_queued(&self) {
+        self.queued.fetch_add(1, Ordering::Relaxed);
+    }
+    /// Record a request leaving the queue, either sent or failed
+    pub(crate) fn request_dequeued(&self) {
+        self.queued
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |queued| queued.checked_sub(1))
+            .ok();
+    }
+    /// Record a request sent to the shard
+    pub(crate) fn request_sent(&self) {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+    }
+    /// Record the end of an in-flight request, along its response time if the shard did respond
+    pub(crate) fn request_done(&self, response_time: Option<Duration>) {
+        self.in_flight
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |in_flight| {
+                in_flight.checked_sub(1)
+            })
+            .ok();
+        if let Some(response_time) = response_time {
+            let sample = (response_time.as_nanos() as u64).max(1);
+            self.latency
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |average| {
+                    if average == 0 {
+                        Some(sample)
+                    } else {
+                        Some((average - (average >> Self::LATENCY_SHIFT) + (sample >> Self::LATENCY_SHIFT)).max(1))
+                    }
+                })
+                .ok();
+        }
+    }
+}
+
+/// A replica shard of the request token
+#[derive(Debug, Clone, Copy)]
+pub struct Candidate<'a> {
+    /// The shard address, where the port is the shard id
+    pub shard: SocketAddr,
+    /// The datacenter of the replica
+    pub data_center: &'a str,
+    /// The rack of the replica
+    pub rack: &'a str,
+    /// The load of the shard
+    pub load: &'a ShardLoad,
+}
+
+/// The policy which picks the replica to send a request to.
+pub trait LoadBalancingPolicy: Send + Sync + std::fmt::Debug {
+    /// Pick the index of the candidate to send the request to. The candidates are the replicas of the request token
+    /// in the selected datacenter, in the ring order, and are never empty.
+    fn pick(&self, candidates: &[Candidate], rng: &mut ThreadRng) -> usize;
+}
+
+/// The load balancing policy of the requests, as configured in `Scylla`
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+pub enum LoadBalancing {
+    /// Round robin over the token replicas in the datacenter
+    #[default]
+    RoundRobin,
+    /// Round robin over the token replicas in the given rack, if any, otherwise over the datacenter ones
+    RackAware(Rack),
+    /// Pick the token replica shard with the least in-flight requests
+    LeastInFlight,
+    /// Pick the token replica shard with the lowest average response time, weighted by its in-flight requests
+    LatencyAware,
+}
+
+impl LoadBalancing {
+    /// Create the configured load balancing policy
+    pub fn policy(&self) -> Box<dyn LoadBalancingPolicy> {
+        match self {
+            Self::RoundRobin => Box::new(RoundRobin::default()),
+            Self::RackAware(rack) => Box::new(RackAware::new(rack.clone())),
+            Self::LeastInFlight => Box::new(LeastInFlight),
+            Self::LatencyAware => Box::new(LatencyAware),
+        }
+    }
+}
+
+/// Token aware and datacenter aware round robin over the replicas
+#[derive(Debug, Default)]
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl LoadBalancingPolicy for RoundRobin {
+    fn pick(&self, candidates: &[Candidate], _rng: &mut ThreadRng) -> usize {
+        self.next.fetch_add(1, Ordering::Relaxed) % candidates.len()
+    }
+}
+
+/// Round robin which prefers the replicas in the local rack
+#[derive(Debug, Default)]
+pub struct RackAware {
+    rack: Rack,
+    round_robin: RoundRobin,
+}
+
+impl RackAware {
+    /// Create the rack aware policy with the local rack
+    pub fn new<R: Into<Rack>>(rack: R) -> Self {
+        Self {
+            rack: rack.into(),
+            round_robin: RoundRobin::default(),
+        }
+    }
+}
+
+impl LoadBalancingPolicy for RackAware {
+    fn pick(&self, candidates: &[Candidate], rng: &mut ThreadRng) -> usize {
+        let local: Vec<usize> = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, candidate)| candidate.rack == self.rack)
+            .map(|(index, _)| index)
+            .collect();
+        if local.is_empty() {
+            self.round_robin.pick(candidates, rng)
+        } else {
+            local[self.round_robin.next.fetch_add(1, Ordering::Relaxed) % local.len()]
+        }
+    }
+}
+
+/// Pick the replica shard with the least in-flight requests, the ties are broken randomly
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LeastInFlight;
+
+impl LoadBalancingPolicy for LeastInFlight {
+    fn pick(&self, candidates: &[Candidate], rng: &mut ThreadRng) -> usize {
+        min_by_key(candidates, rng, |candidate| candidate.load.in_flight() as u128)
+    }
+}
+
+/// Pick the replica shard with the lowest average response time multiplied by its in-flight requests, the shards
+/// without any response yet are picked first
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LatencyAware;
+
+impl LoadBalancingPolicy for LatencyAware {
+    fn pick(&self, candidates: &[Candidate], rng: &mut ThreadRng) -> usize {
+        min_by_key(candidates, rng, |candidate| {
+            let latency = candidate.load.latency().map_or(0, |latency| latency.as_nanos());
+            latency * (candidate.load.in_flight() as u128 + 1)
+        })
+    }
+}
+
+/// Returns the index of the candidate with the minimum key, starting from a random candidate to break the ties
+fn min_by_key<F: Fn(&Candidate) -> u128>(candidates: &[Candidate], rng: &mut ThreadRng, key: F) -> usize {
+    let start = rng.sample(Uniform::new(0, candidates.len()));
+    (0..candidates.len())
+        .map(|offset| (start + offset) % candidates.len())
+        .min_by_key(|index| key(&candidates[*index]))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+pub(crate) fn test_candidate<'a>(id: u8, rack: &'a str, load: &'a ShardLoad) -> Candidate<'a> {
+    use std::net::{
+        IpAddr,
+        Ipv4Addr,
+    };
+    Candidate {
+        shard: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, id)), 0),
+        data_center: "datacenter1",
+        rack,
+        load,
+    }
+}
+
+#[test]
+fn load_balancing_policies() {
+    let mut rng = rand::thread_rng();
+    let (idle, busy, slow) = (ShardLoad::default(), ShardLoad::default(), ShardLoad::default());
+    (0..3).for_each(|_| busy.request_sent());
+    busy.request_done(Some(Duration::from_millis(1)));
+    slow.request_sent();
+    slow.request_done(Some(Duration::from_millis(50)));
+    let candidates = [
+        test_candidate(1, "rack1", &busy),
+        test_candidate(2, "rack2", &slow),
+        test_candidate(3, "rack2", &idle),
+    ];
+    assert_eq!(busy.in_flight(), 2);
+    // the queued requests are tracked apart from the in-flight ones
+    idle.request_queued();
+    assert_eq!((idle.queued(), idle.in_flight()), (1, 0));
+    (0..2).for_each(|_| idle.request_dequeued());
+    assert_eq!(idle.queued(), 0);
+    assert_eq!(slow.latency(), Some(Duration::from_millis(50)));
+    // round robin
+    let round_robin = LoadBalancing::RoundRobin.policy();
+    let picks: Vec<usize> = (0..4).map(|_| round_robin.pick(&candidates, &mut rng)).collect();
+    assert_eq!(picks, vec![0, 1, 2, 0]);
+    // rack aware
+    let rack_aware = LoadBalancing::RackAware("rack2".to_string()).policy();
+    let picks: Vec<usize> = (0..3).map(|_| rack_aware.pick(&candidates, &mut rng)).collect();
+    assert_eq!(picks, vec![1, 2, 1]);
+    let rack_aware = RackAware::new("rack3");
+    assert_eq!(rack_aware.pick(&candidates, &mut rng), 0);
+    // least in-flight
+    assert_eq!(LeastInFlight.pick(&candidates[..2], &mut rng), 1);
+    // latency aware, the shard without any response is explored first
+    assert_eq!(LatencyAware.pick(&candidates, &mut rng), 2);
+    idle.request_sent();
+    idle.request_done(Some(Duration::from_millis(200)));
+    // busy: 1ms * 3, slow: 50ms * 1, idle: 200ms * 1
+    assert_eq!(LatencyAware.pick(&candidates, &mut rng), 0);
+    // the moving average converges to the recent response times
+    (0..64).for_each(|_| {
+        idle.request_sent();
+        idle.request_done(Some(Duration::from_micros(100)));
+    });
+    assert!(idle.latency().unwrap() < Duration::from_millis(1));
+    assert_eq!(LatencyAware.pick(&candidates, &mut rng), 2);
+}
@@ -14,11 +14,16 @@ use rand::{
     Rng,
 };
 use std::{
-    collections::HashMap,
+    collections::{
+        HashMap,
+        HashSet,
+    },
     i64::{
         MAX,
         MIN,
     },
+    ops::Range,
+    sync::Arc,
 };
 /// The token of Ring.
 pub type Token = i64;
@@ -32,43 +37,67 @@ pub type ShardCount = u16;
 pub type VnodeTuple = (Token, Token, SocketAddr, DC, Msb, ShardCount);
 /// The data center string.
 pub type DC = String;
-type Replicas = HashMap<DC, Vec<Replica>>;
+/// The rack string.
+pub type Rack = String;
 type Replica = (SocketAddr, Msb, ShardCount);
-type Vcell = Box<dyn Vnode>;
+type Vcell = Arc<dyn Vnode>;
+
+/// The replica placement of a keyspace, which selects the replicas of the vnode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Placement {
+    /// NetworkTopologyStrategy with the datacenter replication factor, the replicas are spread over the racks.
+    NetworkTopology(usize),
+    /// SimpleStrategy with the replication factor, the replicas are the first nodes clockwise in any datacenter.
+    Simple(usize),
+}
+
+/// The replicas of a vnode.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Replicas {
+    /// The first distinct nodes of each datacenter in clockwise order, used for unknown placements
+    nodes: HashMap<DC, Vec<Replica>>,
+    /// The replicas of each datacenter for every placement used by the keyspaces
+    placements: HashMap<Placement, HashMap<DC, Vec<Replica>>>,
+}
+/// The vnode of the ring chain, along the distinct nodes clockwise which determine its replicas.
+#[derive(Clone, Debug, PartialEq)]
+struct ChainLink {
+    left: Token,
+    right: Token,
+    /// The distinct nodes clockwise from the vnode, up to the nodes required to select the replicas
+    walk: Vec<(Replica, DC)>,
+    /// The number of vnodes walked clockwise, the walk is reused as long as none of them changed
+    span: usize,
+    replicas: Replicas,
+}
+/// The subtree of the ring tree rooted at a vnode of the chain, along the chain vnodes it spans.
+#[derive(Clone, Debug)]
+struct Subtree {
+    span: Range<usize>,
+    root: Vcell,
+}
+/// The reporters of a shard along its load.
+#[derive(Clone)]
+pub struct ShardReporters {
+    /// The reporters handles by reporter id
+    pub handles: HashMap<u8, UnboundedHandle<ReporterEvent>>,
+    /// The load of the shard, shared by its reporters
+    pub load: Arc<ShardLoad>,
+}
 /// The registry of `SocketAddr` to its reporters.
-pub type Registry = HashMap<SocketAddr, HashMap<u8, UnboundedHandle<ReporterEvent>>>;
+pub type Registry = HashMap<SocketAddr, ShardReporters>;
 
 trait SmartId {
-    fn send_reporter(
-        &self,
-        token: Token,
-        registry: &Registry,
-        rng: &mut ThreadRng,
-        uniform: Uniform<u8>,
-        request: ReporterEvent,
-    ) -> anyhow::Result<(), RingSendError>;
+    fn shard(&self, token: Token) -> SocketAddr;
 }
 
 impl SmartId for Replica {
     #[inline]
-    fn send_reporter(
-        &self,
-        token: Token,
-        registry: &Registry,
-        rng: &mut ThreadRng,
-        uniform: Uniform<u8>,
-        request: ReporterEvent,
-    ) -> anyhow::Result<(), RingSendError> {
+    fn shard(&self, token: Token) -> SocketAddr {
         // shard awareness algo,
         let mut key = self.0;
         key.set_port((((((token as i128 + MIN as i128) as u64) << self.1) as u128 * self.2 as u128) >> 64) as u16);
-        registry
-            .get(&key)
-            .unwrap()
-            .get(&rng.sample(uniform))
-            .unwrap()
-            .send(request)
-            .map_err(|e| RingSendError::SendError(e))
+        key
     }
 }
 
@@ -110,17 +139,8 @@ impl Into<ReporterEvent> for RingSendError {
 
 /// Endpoints trait which should be implemented by `Replicas`.
 pub trait Endpoints: EndpointsClone + Send + Sync + std::fmt::Debug {
-    /// Send the request through the endpoints.
-    fn send(
-        &self,
-        data_center: &str,
-        replica_index: usize,
-        token: Token,
-        request: ReporterEvent,
-        registry: &Registry,
-        rng: &mut ThreadRng,
-        uniform: Uniform<u8>,
-    ) -> anyhow::Result<(), RingSendError>;
+    /// Get the replicas in the datacenter, in the ring order.
+    fn replicas(&self, data_center: &str, placement: Option<Placement>) -> &[Replica];
 }
 
 /// Clone the endpoints.
@@ -146,31 +166,23 @@ impl Clone for Box<dyn Endpoints> {
 
 impl Endpoints for Replicas {
     #[inline]
-    fn send(
-        &self,
-        data_center: &str,
-        replica_index: usize,
-        token: Token,
-        request: ReporterEvent,
-        registry: &Registry,
-        rng: &mut ThreadRng,
-        uniform: Uniform<u8>,
-    ) -> anyhow::Result<(), RingSendError> {
-        if let Some(replicas) = self.get(data_center) {
-            if let Some(replica) = replicas.get(replica_index) {
-                replica.send_reporter(token, registry, rng, uniform, request)
-            } else {
-                if replicas.len() != 0 {
-                    // send to a random node
-                    let rf = Uniform::new(0, replicas.len());
-                    let replica = replicas[rng.sample(rf)];
-                    replica.send_reporter(token, registry, rng, uniform, request)
-                } else {
-                    Err(RingSendError::NoReplica(request))
-                }
-            }
+    fn replicas(&self, data_center: &str, placement: Option<Placement>) -> &[Replica] {
+        let replicas = placement
+            .and_then(|placement| self.placements.get(&placement))
+            .and_then(|placement_replicas| placement_replicas.get(data_center))
+            .filter(|replicas| !replicas.is_empty());
+        if let Some(replicas) = replicas {
+            replicas
+        } else if let Some(nodes) = self.nodes.get(data_center) {
+            // fallback to the first datacenter nodes if the placement has no replicas in the datacenter,
+            // or to the primary replica for unknown keyspaces
+            let rf = match placement {
+                Some(Placement::NetworkTopology(rf)) | Some(Placement::Simple(rf)) => rf,
+                None => 1,
+            };
+            &nodes[..rf.min(nodes.len())]
         } else {
-            Err(RingSendError::NoReplica(request))
+            &[]
         }
     }
 }
@@ -229,6 +241,18 @@ impl Vnode for LeftMild {
     }
 }
 
+impl Vnode for RightMild {
+    #[inline]
+    fn search(&self, token: Token) -> &Box<dyn Endpoints> {
+        if token > self.left && token <= self.right {
+            &self.replicas
+        } else {
+            // proceed binary search; shift right
+            self.right_child.search(token)
+        }
+    }
+}
+
 impl Vnode for DeadEnd {
     #[inline]
     fn search(&self, _token: Token) -> &Box<dyn Endpoints> {
@@ -264,44 +288,143 @@ struct LeftMild {
     replicas: Box<dyn Endpoints>,
 }
 
-fn compute_vnode(chain: &[(Token, Token, Replicas)]) -> Vcell {
-    let index = chain.len() / 2;
-    let (left, right) = chain.split_at(index);
-    let (vnode, right) = right.split_first().unwrap();
-    if right.is_empty() && left.is_empty() {
-        // then the parent_vnode without any child so consider it deadend
-        Box::new(DeadEnd {
-            replicas: Box::new(vnode.2.to_owned()),
-        })
-    } else if !right.is_empty() && !left.is_empty() {
-        // parent_vnode is mild with left /right childern
-        // compute both left and right
-        let left_child = compute_vnode(left);
-        let right_child = compute_vnode(right);
-        Box::new(Mild {
-            left: vnode.0,
-            right: vnode.1,
+// as mild but with right child.
+#[derive(Clone, Debug)]
+struct RightMild {
+    left: Token,
+    right: Token,
+    right_child: Vcell,
+    replicas: Box<dyn Endpoints>,
+}
+
+/// The priority of the vnode in the ring tree, which is a treap of the vnodes by their priority. As the priority is
+/// derived from the right token, the tree shape only depends on the vnodes and a subtree stays the same as long as
+/// the vnodes it spans did not change.
+fn priority(right: Token) -> u64 {
+    // splitmix64 finalizer, to spread the tokens evenly
+    let mut z = (right as u64).wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Compute the ring tree of the chain, returns its root along the subtree rooted at every chain vnode. The subtrees
+/// of the previous tree are reused when they span the same reused links, given the previous chain index of every
+/// reused link.
+fn compute_tree(chain: &[ChainLink], previous: Option<(&[Option<usize>], &[Subtree])>) -> (Vcell, Vec<Subtree>) {
+    let mut subtrees = vec![None; chain.len()];
+    let mut reused = 0;
+    let index = compute_subtree(chain, 0..chain.len(), previous, &mut subtrees, &mut reused);
+    if previous.is_some() {
+        log::debug!("Reused the subtrees of {} out of {} vnodes", reused, chain.len());
+    }
+    let subtrees: Vec<Subtree> = subtrees.into_iter().map(Option::unwrap).collect();
+    (subtrees[index].root.clone(), subtrees)
+}
+
+/// Compute the subtree spanning the chain range, returns the index of its root vnode
+fn compute_subtree(
+    chain: &[ChainLink],
+    span: Range<usize>,
+    previous: Option<(&[Option<usize>], &[Subtree])>,
+    subtrees: &mut Vec<Option<Subtree>>,
+    reused: &mut usize,
+) -> usize {
+    let index = span.clone().max_by_key(|index| priority(chain[*index].right)).unwrap();
+    if let Some((previous_indexes, previous_subtrees)) = previous {
+        if let Some(previous_span) =
+            previous_indexes[index].map(|previous_index| &previous_subtrees[previous_index].span)
+        {
+            // the previous subtree is the same if it spans the same links
+            let same_links = previous_span.len() == span.len()
+                && span
+                    .clone()
+                    .zip(previous_span.clone())
+                    .all(|(index, previous_index)| previous_indexes[index] == Some(previous_index));
+            if same_links {
+                for (index, previous_index) in span.clone().zip(previous_span.clone()) {
+                    let Subtree {
+                        span: previous_span,
+                        root,
+                    } = &previous_subtrees[previous_index];
+                    let start = previous_span.start + index - previous_index;
+                    subtrees[index] = Some(Subtree {
+                        span: start..start + previous_span.len(),
+                        root: root.clone(),
+                    });
+                }
+                *reused += span.len();
+                return index;
+            }
+        }
+    }
+    let left_span = span.start..index;
+    let right_span = index + 1..span.end;
+    let mut child = |span: Range<usize>, subtrees: &mut Vec<Option<Subtree>>| -> Option<Vcell> {
+        if span.is_empty() {
+            None
+        } else {
+            let child_index = compute_subtree(chain, span, previous, subtrees, reused);
+            subtrees[child_index].as_ref().map(|subtree| subtree.root.clone())
+        }
+    };
+    let left_child = child(left_span, subtrees);
+    let right_child = child(right_span, subtrees);
+    let vnode = &chain[index];
+    let replicas = Box::new(vnode.replicas.to_owned());
+    let root: Vcell = match (left_child, right_child) {
+        // the vnode without any child so consider it deadend
+        (None, None) => Arc::new(DeadEnd { replicas }),
+        (Some(left_child), Some(right_child)) => Arc::new(Mild {
+            left: vnode.left,
+            right: vnode.right,
             left_child,
             right_child,
-            replicas: Box::new(vnode.2.to_owned()),
-        })
-    } else {
-        // if !left.is_empty() && right.is_empty()
-        // parent_vnode is leftmild
-        Box::new(LeftMild {
-            left: vnode.0,
-            right: vnode.1,
-            left_child: compute_vnode(left),
-            replicas: Box::new(vnode.2.to_owned()),
-        })
-    }
+            replicas,
+        }),
+        (Some(left_child), None) => Arc::new(LeftMild {
+            left: vnode.left,
+            right: vnode.right,
+            left_child,
+            replicas,
+        }),
+        (None, Some(right_child)) => Arc::new(RightMild {
+            left: vnode.left,
+            right: vnode.right,
+            right_child,
+            replicas,
+        }),
+    };
+    subtrees[index] = Some(Subtree { span, root });
+    index
 }
 
+/// Mod impl the load balancing policies
+pub mod balancing;
+/// Mod impl the keyspace replication strategies
+pub mod replication;
 /// Mod impl the shared ring
 pub mod shared;
+/// Mod impl the tablets of the tablets based keyspaces
+pub mod tablets;
 
+pub use balancing::{
+    Candidate,
+    LatencyAware,
+    LeastInFlight,
+    LoadBalancing,
+    LoadBalancingPolicy,
+    RackAware,
+    RoundRobin,
+    ShardLoad,
+};
+pub use replication::ReplicationStrategy;
 pub use shared::{
     ReplicationFactor,
     ReplicationInfo,
     SharedRing,
 };
+pub use tablets::{
+    TableTablets,
+    Tablets,
+};
@@ -0,0 +1,131 @@
+use super::*;
+use std::convert::TryFrom;
+
+/// Keyspace replication strategy, as defined by the replication options in `system_schema.keyspaces`
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ReplicationStrategy {
+    /// Replicate to `replication_factor` nodes in the ring, regardless of their datacenter
+    Simple(usize),
+    /// Replicate to the configured number of nodes in each datacenter
+    NetworkTopology(HashMap<DC, usize>),
+    /// Replicate to every node in the cluster
+    Everywhere,
+    /// Keep the data on the local node only, used by system keyspaces
+    Local,
+}
+
+impl TryFrom<&HashMap<String, String>> for ReplicationStrategy {
+    type Error = anyhow::Error;
+    fn try_from(replication: &HashMap<String, String>) -> anyhow::Result<Self> {
+        let class = replication
+            .get("class")
+            .ok_or_else(|| anyhow::anyhow!("Replication without class: {:?}", replication))?;
+        // the class may be fully qualified, ie org.apache.cassandra.locator.SimpleStrategy
+        let class = class.rsplit('.').next().unwrap_or(class);
+        let rf = |value: &String| {
+            value
+                .parse::<usize>()
+                .map_err(|e| anyhow::anyhow!("Invalid replication factor {}: {}", value, e))
+        };
+        Ok(match class {
+            "SimpleStrategy" => Self::Simple(rf(replication
+                .get("replication_factor")
+                .ok_or_else(|| anyhow::anyhow!("SimpleStrategy without replication_factor"))?)?),
+            "NetworkTopologyStrategy" => Self::NetworkTopology(
+                replication
+                    .iter()
+                    .filter(|(k, _)| k.as_str() != "class")
+                    .map(|(dc, value)| Ok((dc.clone(), rf(value)?)))
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            "EverywhereStrategy" => Self::Everywhere,
+            "LocalStrategy" => Self::Local,
+            _ => anyhow::bail!("Unsupported replication class {}", class),
+        })
+    }
+}
+
+impl ReplicationStrategy {
+    /// Compute the replication info of the keyspace for the nodes of the cluster.
+    /// Returns None if the keyspace is not replicated to any datacenter.
+    pub fn replication_info(&self, local_datacenter: &str, nodes: &Nodes) -> Option<ReplicationInfo> {
+        let mut nodes_per_dc: HashMap<&str, usize> = HashMap::new();
+        for node in nodes.values() {
+            *nodes_per_dc.entry(node.data_center.as_str()).or_default() += 1;
+        }
+        let mut info = ReplicationInfo::empty();
+        match self {
+            Self::Simple(rf) => {
+                info.simple_strategy(*rf);
+                for (dc, count) in nodes_per_dc {
+                    let rf = (*rf).min(count);
+                    if rf > 0 {
+                        info.upsert(dc, rf);
+                    }
+                }
+            }
+            Self::NetworkTopology(dcs) => {
+                for (dc, rf) in dcs {
+                    if *rf > 0 {
+                        info.upsert(dc.as_str(), *rf);
+                    }
+                }
+            }
+            Self::Everywhere => {
+                for (dc, count) in nodes_per_dc {
+                    info.upsert(dc, count);
+                }
+            }
+            Self::Local => info.upsert(local_datacenter, 1),
+        }
+        if info.is_empty() {
+            None
+        } else {
+            Some(info)
+        }
+    }
+}
+
+#[test]
+fn parse_replication_strategies() {
+    let parse = |options: &[(&str, &str)]| {
+        ReplicationStrategy::try_from(
+            &options
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+        )
+    };
+    assert_eq!(
+        parse(&[
+            ("class", "org.apache.cassandra.locator.SimpleStrategy"),
+            ("replication_factor", "3")
+        ])
+        .unwrap(),
+        ReplicationStrategy::Simple(3)
+    );
+    assert_eq!(
+        parse(&[("class", "NetworkTopologyStrategy"), ("US", "3"), ("EU", "0")]).unwrap(),
+        ReplicationStrategy::NetworkTopology(maplit::hashmap! {"US".to_string() => 3, "EU".to_string() => 0})
+    );
+    assert_eq!(
+        parse(&[("class", "org.apache.cassandra.locator.EverywhereStrategy")]).unwrap(),
+        ReplicationStrategy::Everywhere
+    );
+    assert_eq!(
+        parse(&[("class", "org.apache.cassandra.locator.LocalStrategy")]).unwrap(),
+        ReplicationStrategy::Local
+    );
+    assert!(parse(&[("class", "SimpleStrategy")]).is_err());
+    assert!(parse(&[("class", "NetworkTopologyStrategy"), ("US", "three")]).is_err());
+    assert!(parse(&[("class", "UnknownStrategy")]).is_err());
+    let nts = ReplicationStrategy::NetworkTopology(maplit::hashmap! {"US".to_string() => 3, "EU".to_string() => 0});
+    let info = nts.replication_info("US", &HashMap::new()).unwrap();
+    assert_eq!(info.get("US").unwrap().rf(), 3);
+    assert!(info.get("EU").is_none());
+    assert!(
+        ReplicationStrategy::NetworkTopology(maplit::hashmap! {"EU".to_string() => 0})
+            .replication_info("US", &HashMap::new())
+            .is_none()
+    );
+}
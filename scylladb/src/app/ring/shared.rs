@@ -1,4 +1,11 @@
-use super::*;
+use super::{
+    tablets::learn_tablet,
+    *,
+};
+use crate::cql::{
+    HostId,
+    TabletReplica,
+};
 use arc_swap::ArcSwapOption;
 use rand::{
     distributions::Uniform,
@@ -16,10 +23,21 @@ pub struct SharedRing {
     pub registry: Registry,
     /// static keyspaces
     pub keyspaces: HashMap<String, ReplicationInfo>,
+    /// The nodes address and datacenter by host id, to route the tablets replicas
+    pub hosts: HashMap<HostId, (SocketAddr, DC)>,
+    /// The racks of the nodes
+    pub racks: HashMap<SocketAddr, Rack>,
+    /// The default load balancing policy, which picks the replica of the requests
+    pub policy: Box<dyn LoadBalancingPolicy>,
     /// Uniform to sample reporter_id up to reporter_count == 255
     pub uniform: Uniform<u8>,
     /// Root of ring (binary tree)
     pub root: Vcell,
+    /// The vnodes along their replicas, to reuse the replicas of the unchanged vnodes once the ring gets rebuilt
+    chain: Vec<ChainLink>,
+    context: ChainContext,
+    /// The subtree rooted at every vnode of the chain, to reuse the unchanged subtrees once the ring gets rebuilt
+    subtrees: Vec<Subtree>,
 }
 
 impl SharedRing {
@@ -29,6 +47,7 @@ impl SharedRing {
         keyspaces: HashMap<String, ReplicationInfo>,
         mut reporter_count: u8,
         nodes: &Nodes,
+        load_balancing: &LoadBalancing,
     ) -> Self {
         if reporter_count == 0 {
             reporter_count = 1
@@ -51,80 +70,96 @@ impl SharedRing {
                 tokens.push(node_token)
             }
         }
-        // sort_unstable_by token
-        tokens.sort_unstable_by(|a, b| a.0.cmp(&b.0));
-        // create vnodes tuple from tokens
-        let mut vnodes = Vec::new();
-        let mut recent_left = MIN;
-        for (right, node_id, dc, msb, shard_count) in &tokens {
-            // create vnode tuple (starting from min)
-            let vnode = (recent_left, *right, *node_id, dc.clone(), *msb, *shard_count);
-            // push to vnodes
-            vnodes.push(vnode);
-            // update recent_left to right
-            recent_left = *right;
-        }
-        // the check bellow is only to make sure if scylla-node didn't already
-        // randmoly didn't gen the MIN token by luck.
-        // confirm if the vnode_min is not already exist in our token range
-        if vnodes.first().unwrap().1 == MIN {
-            // remove it, otherwise the first vnode will be(MIN, MIN, ..) and invalidate vnode conditions
-            vnodes.remove(0);
-        };
-        // we don't forget to add max vnode to our token range only if not already presented,
-        // the check bellow is only to make sure if scylla-node didn't already
-        // randmoly gen the MAX token by luck.
-        // the MAX to our last vnode(the largest token )
-        let last_vnode = vnodes.last().unwrap();
-        // confirm if the vnode max is not present in our token-range
-        if last_vnode.1 != MAX {
-            let max_vnode = (
-                recent_left,
-                MAX,
-                last_vnode.2,
-                last_vnode.3.clone(),
-                last_vnode.4,
-                last_vnode.5,
-            );
-            // now push it
-            vnodes.push(max_vnode);
-        }
-        // compute_ring
-        let root = compute_ring(&vnodes);
+        let vnodes = compute_vnodes(tokens);
+        let hosts = nodes
+            .values()
+            .filter_map(|node_info| {
+                node_info
+                    .host_id
+                    .map(|host_id| (host_id, (node_info.address, node_info.data_center.clone())))
+            })
+            .collect();
+        // the racks of the nodes, to spread the replicas over the racks
+        let racks: HashMap<SocketAddr, Rack> = nodes
+            .values()
+            .map(|node_info| (node_info.address, node_info.rack.clone()))
+            .collect();
+        // the placements used by the keyspaces
+        let placements: HashSet<Placement> = keyspaces.values().flat_map(|info| info.placements()).collect();
+        let context = ChainContext::new(&vnodes, racks.clone(), placements);
+        // compute the chain, reusing the walks and replicas of the unchanged vnodes of the current ring
+        let current = SHARED_RING.load();
+        let previous = current.as_ref().map(|ring| (ring.chain.as_slice(), &ring.context));
+        let (chain, previous_indexes) = compute_chain(&vnodes, &context, previous);
+        // compute balanced binary tree, reusing the unchanged subtrees of the current ring
+        let previous = current
+            .as_ref()
+            .map(|ring| (previous_indexes.as_slice(), ring.subtrees.as_slice()));
+        let (root, subtrees) = compute_tree(&chain, previous);
         Self {
             local_datacenter: local_datacenter.into(),
             registry,
             keyspaces,
+            hosts,
+            racks,
+            policy: load_balancing.policy(),
             uniform: Uniform::new(0, reporter_count),
             root,
+            chain,
+            context,
+            subtrees,
         }
     }
-    /// Send request to the first local datacenter with the given token and a random replica.
+    /// Send request to the first local datacenter with the given token, to the replica picked by the given load
+    /// balancing policy or by the default one.
+    /// The requests of the tablets based keyspaces are sent to a replica of the table tablet.
     #[inline]
-    pub fn send_local_random_replica(
+    pub fn send_local_replica(
         keyspace: Option<&str>,
+        table: Option<&str>,
         token: Token,
         request: ReporterEvent,
+        policy: Option<&dyn LoadBalancingPolicy>,
     ) -> Result<(), RingSendError> {
         if let Some(ring) = SHARED_RING.load().as_ref() {
-            ring.local_random_replica(keyspace, token, request)
+            ring.local_replica(keyspace, table, token, request, policy)
         } else {
             Err(RingSendError::NoRing(request))
         }
     }
-    /// Send request to the global datacenter with the given token and a random replica.
+    /// Send request to a random datacenter of the keyspace with the given token, to the replica picked by the given
+    /// load balancing policy or by the default one.
+    /// The requests of the tablets based keyspaces are sent to a replica of the table tablet.
     #[inline]
-    pub fn send_global_random_replica(
+    pub fn send_global_replica(
         keyspace: Option<&str>,
+        table: Option<&str>,
         token: Token,
         request: ReporterEvent,
+        policy: Option<&dyn LoadBalancingPolicy>,
     ) -> Result<(), RingSendError> {
         if let Some(ring) = SHARED_RING.load().as_ref() {
-            ring.global_random_replica(keyspace, token, request)
+            ring.global_replica(keyspace, table, token, request, policy)
         } else {
             Err(RingSendError::NoRing(request))
         }
     }
+    /// Returns the load of every shard in the ring, as the in-flight and queued requests and the response times
+    pub fn shard_loads() -> HashMap<SocketAddr, Arc<ShardLoad>> {
+        SHARED_RING.load().as_ref().map_or_else(HashMap::new, |ring| {
+            ring.registry
+                .iter()
+                .map(|(shard, reporters)| (*shard, reporters.load.clone()))
+                .collect()
+        })
+    }
+    /// Pick the index of the candidate with the configured load balancing policy, or a random one if there is no ring
+    pub fn pick(candidates: &[Candidate], rng: &mut ThreadRng) -> usize {
+        match SHARED_RING.load().as_ref() {
+            Some(ring) => ring.policy.pick(candidates, rng),
+            None => rng.gen_range(0..candidates.len()),
+        }
+    }
     /// Make the ring empty: None
     pub fn drop() {
         SHARED_RING.swap(None);
@@ -141,84 +176,165 @@ impl SharedRing {
     pub fn is_none() -> bool {
         SHARED_RING.load().is_none()
     }
+    /// Send the request of a tablets based keyspace to a replica of the table tablet, preferring the replicas in
+    /// the given datacenter. The request is returned back to be sent through the vnodes if the tablet is unknown,
+    /// then the server response teaches the tablet.
     #[inline]
-    fn global_random_replica(
+    fn tablet_replica(
         &self,
         keyspace: Option<&str>,
+        table: Option<&str>,
         token: Token,
         request: ReporterEvent,
-    ) -> Result<(), RingSendError> {
-        let mut rng = thread_rng();
-        // send request.
-        let (replica_index, dc) = keyspace
-            .and_then(|keyspace| {
-                self.keyspaces
-                    .get(keyspace)
-                    .and_then(|info| info.get_random_and_dc(&mut rng))
-                    .and_then(|(rf, dc)| Some((rf.random(&mut rng), dc)))
+        routing: &mut Routing,
+    ) -> Result<(), ReporterEvent> {
+        let (keyspace, table) = match (keyspace, table) {
+            (Some(keyspace), Some(table)) => (keyspace, table),
+            _ => return Err(request),
+        };
+        // learn the tablet from any response which carries it, as a keyspace is only known to be tablets based once
+        // one of its tablets is
+        let request = learn_tablet(keyspace, table, request);
+        let replicas = match Tablets::replicas(keyspace, table, token) {
+            Some(replicas) => replicas,
+            None => return Err(request),
+        };
+        let candidates: Vec<Candidate> = replicas
+            .iter()
+            .filter_map(|TabletReplica { host_id, shard }| {
+                let (address, dc) = self.hosts.get(host_id)?;
+                let mut key = *address;
+                key.set_port(*shard);
+                self.candidate(address, key, dc)
             })
-            .unwrap_or((0, &self.local_datacenter)); // default to (0, local_datacenter) for dyn non existing keyspace
-        self.root.search(token).send(
-            dc,
-            replica_index,
-            token,
-            request,
-            &self.registry,
-            &mut rng,
-            self.uniform,
-        )
+            .collect();
+        let preferred: Vec<Candidate> = candidates
+            .iter()
+            .filter(|candidate| {
+                routing
+                    .data_center
+                    .is_none_or(|data_center| data_center == candidate.data_center)
+            })
+            .copied()
+            .collect();
+        let candidates = if preferred.is_empty() { candidates } else { preferred };
+        self.send_candidate(&candidates, request, routing)
     }
     #[inline]
-    fn local_random_replica(
+    fn global_replica(
         &self,
         keyspace: Option<&str>,
+        table: Option<&str>,
         token: Token,
         request: ReporterEvent,
+        policy: Option<&dyn LoadBalancingPolicy>,
     ) -> Result<(), RingSendError> {
-        let mut rng = thread_rng();
-        // send request.
-        let replica_index = keyspace
+        let mut routing = Routing {
+            data_center: None,
+            policy: policy.unwrap_or(self.policy.as_ref()),
+            rng: thread_rng(),
+        };
+        let request = match self.tablet_replica(keyspace, table, token, request, &mut routing) {
+            Ok(()) => return Ok(()),
+            Err(request) => request,
+        };
+        let (dc, placement) = keyspace
             .and_then(|keyspace| {
-                self.keyspaces
-                    .get(keyspace)
-                    .and_then(|info| info.get(&self.local_datacenter))
-                    .and_then(|rf| Some(rf.random(&mut rng)))
+                self.keyspaces.get(keyspace).and_then(|info| {
+                    info.get_random_and_dc(&mut routing.rng)
+                        .map(|(_, dc)| (dc, info.placement(dc)))
+                })
             })
-            .unwrap_or(0); // default to 0 for dyn non existing keyspace
-        self.root.search(token).send(
-            &self.local_datacenter,
-            replica_index,
-            token,
-            request,
-            &self.registry,
-            &mut rng,
-            self.uniform,
-        )
-    }
-    #[allow(unused)]
-    fn local(&self, replica_index: usize, token: Token, request: ReporterEvent) -> Result<(), RingSendError> {
-        self.global(&self.local_datacenter, replica_index, token, request)
-    }
-    #[allow(unused)]
-    fn global(
+            .unwrap_or((&self.local_datacenter, None)); // default to local_datacenter for dyn non existing keyspace
+        self.replica(dc, placement, token, request, &mut routing)
+    }
+    #[inline]
+    fn local_replica(
+        &self,
+        keyspace: Option<&str>,
+        table: Option<&str>,
+        token: Token,
+        request: ReporterEvent,
+        policy: Option<&dyn LoadBalancingPolicy>,
+    ) -> Result<(), RingSendError> {
+        let mut routing = Routing {
+            data_center: Some(&self.local_datacenter),
+            policy: policy.unwrap_or(self.policy.as_ref()),
+            rng: thread_rng(),
+        };
+        let request = match self.tablet_replica(keyspace, table, token, request, &mut routing) {
+            Ok(()) => return Ok(()),
+            Err(request) => request,
+        };
+        let placement = keyspace
+            .and_then(|keyspace| self.keyspaces.get(keyspace))
+            .and_then(|info| info.placement(&self.local_datacenter)); // default to the primary replica for dyn non existing keyspace
+        self.replica(&self.local_datacenter, placement, token, request, &mut routing)
+    }
+    /// Send the request to the token replica in the datacenter picked by the load balancing policy
+    #[inline]
+    fn replica(
         &self,
         data_center: &str,
-        replica_index: usize,
+        placement: Option<Placement>,
         token: Token,
         request: ReporterEvent,
+        routing: &mut Routing,
     ) -> Result<(), RingSendError> {
-        let mut rng = thread_rng();
-        // send request.
-        self.root.search(token).send(
+        let candidates: Vec<Candidate> = self
+            .root
+            .search(token)
+            .replicas(data_center, placement)
+            .iter()
+            .filter_map(|replica| self.candidate(&replica.0, replica.shard(token), data_center))
+            .collect();
+        if candidates.is_empty() {
+            return Err(RingSendError::NoReplica(request));
+        }
+        self.send_candidate(&candidates, request, routing)
+            .map_err(|request| RingSendError::SendError(tokio::sync::mpsc::error::SendError(request)))
+    }
+    /// Get the candidate of the node shard, if it has reporters
+    fn candidate<'a>(&'a self, address: &SocketAddr, shard: SocketAddr, data_center: &'a str) -> Option<Candidate<'a>> {
+        self.registry.get(&shard).map(|shard_reporters| Candidate {
+            shard,
             data_center,
-            replica_index,
-            token,
-            request,
-            &self.registry,
-            &mut rng,
-            self.uniform,
-        )
+            rack: self.racks.get(address).map_or("", |rack| rack.as_str()),
+            load: shard_reporters.load.as_ref(),
+        })
     }
+    /// Send the request to a random reporter of the candidate picked by the load balancing policy
+    #[inline]
+    fn send_candidate(
+        &self,
+        candidates: &[Candidate],
+        request: ReporterEvent,
+        routing: &mut Routing,
+    ) -> Result<(), ReporterEvent> {
+        if candidates.is_empty() {
+            return Err(request);
+        }
+        let candidate = candidates
+            .get(routing.policy.pick(candidates, &mut routing.rng))
+            .unwrap_or(&candidates[0]);
+        match self
+            .registry
+            .get(&candidate.shard)
+            .and_then(|shard_reporters| shard_reporters.handles.get(&routing.rng.sample(self.uniform)))
+        {
+            Some(reporter) => reporter.send(request).map_err(|e| e.0),
+            None => Err(request),
+        }
+    }
+}
+
+/// How a request gets routed to a replica
+struct Routing<'a> {
+    /// The datacenter whose tablet replicas are preferred, if any
+    data_center: Option<&'a str>,
+    /// The load balancing policy which picks the replica
+    policy: &'a dyn LoadBalancingPolicy,
+    rng: ThreadRng,
 }
 
 #[derive(Clone, Debug)]
@@ -227,6 +343,8 @@ pub struct ReplicationInfo {
     uniform: Uniform<usize>,
     datacenter: Vec<DC>,
     replication_factor: HashMap<DC, ReplicationFactor>,
+    /// The SimpleStrategy replication factor, if the replicas are placed regardless of the datacenters
+    simple: Option<usize>,
 }
 impl Default for ReplicationInfo {
     fn default() -> Self {
@@ -234,6 +352,7 @@ impl Default for ReplicationInfo {
             uniform: Uniform::new(0, 1),
             datacenter: vec!["datacenter1".to_string()],
             replication_factor: maplit::hashmap! {"datacenter1".to_string() => ReplicationFactor::new(1)},
+            simple: None,
         }
     }
 }
@@ -281,8 +400,24 @@ impl ReplicationInfo {
             uniform: Uniform::new(0, 1),
             datacenter: Vec::new(),
             replication_factor: HashMap::new(),
+            simple: None,
+        }
+    }
+    /// Place the replicas as SimpleStrategy with the given replication factor, instead of per datacenter
+    pub fn simple_strategy(&mut self, rf: usize) {
+        self.simple.replace(rf);
+    }
+    /// Get the replica placement of the keyspace in the given datacenter
+    pub fn placement(&self, datacenter: &str) -> Option<Placement> {
+        match self.simple {
+            Some(rf) => Some(Placement::Simple(rf)),
+            None => self.get(datacenter).map(|rf| Placement::NetworkTopology(rf.rf())),
         }
     }
+    /// Get the replica placements of the keyspace in all its datacenters
+    pub fn placements(&self) -> Vec<Placement> {
+        self.datacenter.iter().filter_map(|dc| self.placement(dc)).collect()
+    }
     /// Insert/Update datacenter and its replication factor
     pub fn upsert<D: Into<String>, R: Into<ReplicationFactor>>(&mut self, datacenter: D, rf: R) {
         self.replication_factor.insert(datacenter.into(), rf.into());
@@ -296,6 +431,10 @@ impl ReplicationInfo {
         self.uniform = Uniform::new(0, self.datacenter.len());
         self.replication_factor.remove(&datacenter)
     }
+    /// Check if the keyspace is not replicated to any datacenter
+    pub fn is_empty(&self) -> bool {
+        self.datacenter.is_empty()
+    }
     /// get the replication factor for a given datacenter
     pub fn get(&self, datacenter: &str) -> Option<&ReplicationFactor> {
         self.replication_factor.get(datacenter)
@@ -314,47 +453,305 @@ impl ReplicationInfo {
     }
 }
 
-fn compute_ring(vnodes: &[VnodeTuple]) -> Vcell {
-    // compute chain (vnodes with replicas)
-    let chain = compute_chain(vnodes);
-    // compute balanced binary tree
-    compute_vnode(&chain)
+/// Compute the vnodes from the tokens of the nodes, every vnode (left, right] is owned by the node of the right token
+fn compute_vnodes(mut tokens: Tokens) -> Vec<VnodeTuple> {
+    // sort_unstable_by token
+    tokens.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    // create vnodes tuple from tokens
+    let mut vnodes = Vec::new();
+    let mut recent_left = MIN;
+    for (right, node_id, dc, msb, shard_count) in &tokens {
+        // create vnode tuple (starting from min)
+        let vnode = (recent_left, *right, *node_id, dc.clone(), *msb, *shard_count);
+        // push to vnodes
+        vnodes.push(vnode);
+        // update recent_left to right
+        recent_left = *right;
+    }
+    // the check bellow is only to make sure if scylla-node didn't already
+    // randmoly didn't gen the MIN token by luck.
+    // confirm if the vnode_min is not already exist in our token range
+    if vnodes.first().unwrap().1 == MIN {
+        // remove it, otherwise the first vnode will be(MIN, MIN, ..) and invalidate vnode conditions
+        vnodes.remove(0);
+    };
+    // we don't forget to add max vnode to our token range only if not already presented,
+    // the check bellow is only to make sure if scylla-node didn't already
+    // randmoly gen the MAX token by luck.
+    // the MAX to our last vnode(the largest token )
+    let last_vnode = vnodes.last().unwrap();
+    // confirm if the vnode max is not present in our token-range
+    if last_vnode.1 != MAX {
+        let max_vnode = (
+            recent_left,
+            MAX,
+            last_vnode.2,
+            last_vnode.3.clone(),
+            last_vnode.4,
+            last_vnode.5,
+        );
+        // now push it
+        vnodes.push(max_vnode);
+    }
+    vnodes
 }
 
-fn compute_chain(vnodes: &[VnodeTuple]) -> Vec<(Token, Token, Replicas)> {
-    // compute all possible replicas in advance for each vnode in vnodes
-    // prepare ring chain
-    let mut chain = Vec::new();
-    for (starting_index, (left, right, _, _, _, _)) in vnodes.iter().enumerate() {
-        let mut replicas: Replicas = HashMap::new();
-        // first walk clockwise phase (start..end)
-        walk_clockwise(starting_index, vnodes.len(), &vnodes, &mut replicas);
-        // second walk clockwise phase (0..start)
-        walk_clockwise(0, starting_index, &vnodes, &mut replicas);
-        // create vnode
-        chain.push((*left, *right, replicas));
-    }
-    chain
+/// The state of the ring which the replicas of the vnodes depend on, besides their distinct nodes clockwise
+#[derive(Clone, Debug, Default)]
+struct ChainContext {
+    racks: HashMap<SocketAddr, Rack>,
+    placements: HashSet<Placement>,
+    /// The nodes count and the racks count of every datacenter
+    datacenters: HashMap<DC, (usize, usize)>,
 }
 
-fn walk_clockwise(starting_index: usize, end_index: usize, vnodes: &[VnodeTuple], replicas: &mut Replicas) {
-    for vnode in vnodes.iter().take(end_index).skip(starting_index) {
-        // fetch replica
-        let (_, _, node_id, dc, msb, shard_count) = &vnode;
-        let replica: Replica = (*node_id, *msb, *shard_count);
-        // now push it to Replicas
-        match replicas.get_mut(dc) {
-            Some(vec_replicas_in_dc) => {
-                if !vec_replicas_in_dc.contains(&replica) {
-                    vec_replicas_in_dc.push(replica)
+impl ChainContext {
+    fn new(vnodes: &[VnodeTuple], racks: HashMap<SocketAddr, Rack>, placements: HashSet<Placement>) -> Self {
+        let mut dc_nodes: HashMap<&DC, HashSet<SocketAddr>> = HashMap::new();
+        for (_, _, node_id, dc, _, _) in vnodes {
+            dc_nodes.entry(dc).or_default().insert(*node_id);
+        }
+        let datacenters = dc_nodes
+            .into_iter()
+            .map(|(dc, nodes)| {
+                let racks_count = nodes
+                    .iter()
+                    .map(|node| racks.get(node).map(|rack| rack.as_str()).unwrap_or_default())
+                    .collect::<HashSet<_>>()
+                    .len();
+                (dc.clone(), (nodes.len(), racks_count))
+            })
+            .collect();
+        Self {
+            racks,
+            placements,
+            datacenters,
+        }
+    }
+    /// The nodes to walk in every datacenter, for the placements without replicas in the datacenter
+    fn depth(&self) -> usize {
+        self.placements
+            .iter()
+            .map(|placement| match placement {
+                Placement::NetworkTopology(rf) | Placement::Simple(rf) => *rf,
+            })
+            .max()
+            .unwrap_or_default()
+            .max(1)
+    }
+    /// Check whether the walked nodes of the datacenter are enough to select its replicas of all the placements
+    fn has_enough(&self, dc: &str, depth: usize, walked_nodes: usize, walked_racks: usize) -> bool {
+        let (nodes_count, racks_count) = match self.datacenters.get(dc) {
+            Some(datacenter) => *datacenter,
+            None => return true,
+        };
+        walked_nodes >= depth.min(nodes_count)
+            && self.placements.iter().all(|placement| match placement {
+                Placement::NetworkTopology(rf) => {
+                    // the nodes of the already selected racks are replicas only while the rf exceeds the racks count
+                    let repeats = (walked_nodes - walked_racks).min(rf.saturating_sub(racks_count));
+                    (walked_racks + repeats).min(*rf) >= (*rf).min(nodes_count)
+                }
+                Placement::Simple(_) => true,
+            })
+    }
+    /// Check whether the walks select the same nodes and replicas in the previous context, given the same vnodes
+    /// and racks of the walked nodes. The nodes and racks count of a datacenter matter only up to the walk depth
+    fn is_compatible(&self, previous: &Self) -> bool {
+        let depth = self.depth();
+        let counts = |context: &Self| -> HashMap<DC, (usize, usize)> {
+            context
+                .datacenters
+                .iter()
+                .map(|(dc, (nodes_count, racks_count))| {
+                    (dc.clone(), ((*nodes_count).min(depth), (*racks_count).min(depth)))
+                })
+                .collect()
+        };
+        self.placements == previous.placements && counts(self) == counts(previous)
+    }
+    /// Check whether the walked nodes are in the same racks in the previous context
+    fn has_same_racks(&self, previous: &Self, walk: &[(Replica, DC)]) -> bool {
+        walk.iter()
+            .all(|(replica, _)| self.racks.get(&replica.0) == previous.racks.get(&replica.0))
+    }
+}
+
+/// Count cumulatively the previous chain vnodes which changed: removed, split by a new token or owned by another
+/// node, so that the changes of the walked vnodes are counted at once
+fn count_changes(vnodes: &[VnodeTuple], previous: &[ChainLink]) -> Vec<usize> {
+    let mut vnodes = vnodes.iter().peekable();
+    let mut changes = vec![0];
+    for link in previous {
+        let mut changed = false;
+        // the new tokens within the previous vnode
+        while vnodes.next_if(|(_, right, ..)| *right < link.right).is_some() {
+            changed = true;
+        }
+        match vnodes.next_if(|(_, right, ..)| *right == link.right) {
+            // the owner of the vnode is the first walked node
+            Some((_, _, node_id, dc, msb, shard_count)) => {
+                changed |= !link
+                    .walk
+                    .first()
+                    .is_some_and(|(owner, owner_dc)| owner == &(*node_id, *msb, *shard_count) && owner_dc == dc)
+            }
+            None => changed = true,
+        }
+        changes.push(changes.last().unwrap() + changed as usize);
+    }
+    changes
+}
+
+/// Compute the replicas of every vnode. The walks and replicas of the previous chain are reused for the vnodes whose
+/// walked vnodes did not change. Returns the chain along the previous chain index of every reused link
+fn compute_chain(
+    vnodes: &[VnodeTuple],
+    context: &ChainContext,
+    previous: Option<(&[ChainLink], &ChainContext)>,
+) -> (Vec<ChainLink>, Vec<Option<usize>>) {
+    let previous = previous
+        .filter(|(_, previous_context)| context.is_compatible(previous_context))
+        .map(|(chain, previous_context)| (chain, previous_context, count_changes(vnodes, chain)));
+    let (chain, previous_indexes): (Vec<ChainLink>, Vec<Option<usize>>) = vnodes
+        .iter()
+        .enumerate()
+        .map(|(starting_index, (left, right, ..))| {
+            let previous_link = previous.as_ref().and_then(|(chain, previous_context, changes)| {
+                let index = chain.binary_search_by(|link| link.left.cmp(left)).ok()?;
+                let link = &chain[index];
+                let end = index + link.span;
+                let walked_changes = if end <= chain.len() {
+                    changes[end] - changes[index]
+                } else {
+                    // the walked vnodes wrap around the ring
+                    changes[chain.len()] - changes[index] + changes[end - chain.len()]
+                };
+                (link.right == *right && walked_changes == 0 && context.has_same_racks(previous_context, &link.walk))
+                    .then_some((index, link))
+            });
+            match previous_link {
+                Some((index, link)) => (link.clone(), Some(index)),
+                None => {
+                    let (walk, span) = walk_clockwise(starting_index, vnodes, context);
+                    let replicas = compute_replicas(&walk, context);
+                    let link = ChainLink {
+                        left: *left,
+                        right: *right,
+                        walk,
+                        span,
+                        replicas,
+                    };
+                    (link, None)
                 }
             }
-            None => {
-                let vec_replicas_in_dc = vec![replica];
-                replicas.insert(dc.clone(), vec_replicas_in_dc);
+        })
+        .unzip();
+    if previous.is_some() {
+        let reused = previous_indexes.iter().flatten().count();
+        log::debug!("Reused the walks of {} out of {} vnodes", reused, chain.len());
+    }
+    (chain, previous_indexes)
+}
+
+/// Iterate the vnodes clockwise from the starting index
+fn clockwise(starting_index: usize, vnodes: &[VnodeTuple]) -> impl Iterator<Item = &VnodeTuple> {
+    vnodes[starting_index..].iter().chain(vnodes[..starting_index].iter())
+}
+
+/// Walk clockwise from the vnode collecting the distinct nodes of every datacenter, until the datacenter has enough
+/// nodes to select its replicas of all the placements. Returns the walk along the number of walked vnodes
+fn walk_clockwise(starting_index: usize, vnodes: &[VnodeTuple], context: &ChainContext) -> (Vec<(Replica, DC)>, usize) {
+    let depth = context.depth();
+    let mut walk: Vec<(Replica, DC)> = Vec::new();
+    let mut span = 0;
+    // the walked nodes count and racks of every datacenter
+    let mut walked: HashMap<&str, (usize, HashSet<&str>)> = HashMap::new();
+    let mut satisfied = 0;
+    for (_, _, node_id, dc, msb, shard_count) in clockwise(starting_index, vnodes) {
+        if satisfied == context.datacenters.len() {
+            break;
+        }
+        span += 1;
+        let (walked_nodes, walked_racks) = walked.entry(dc.as_str()).or_default();
+        let replica: Replica = (*node_id, *msb, *shard_count);
+        if context.has_enough(dc, depth, *walked_nodes, walked_racks.len()) || walk.iter().any(|(r, _)| r == &replica) {
+            continue;
+        }
+        // now push it to the distinct nodes
+        *walked_nodes += 1;
+        walked_racks.insert(context.racks.get(node_id).map(|rack| rack.as_str()).unwrap_or_default());
+        walk.push((replica, dc.clone()));
+        if context.has_enough(dc, depth, *walked_nodes, walked_racks.len()) {
+            satisfied += 1;
+        }
+    }
+    (walk, span)
+}
+
+/// Compute the replicas of a vnode from its distinct nodes in clockwise order
+fn compute_replicas(nodes: &[(Replica, DC)], context: &ChainContext) -> Replicas {
+    let mut replicas = Replicas::default();
+    for (replica, dc) in nodes {
+        replicas.nodes.entry(dc.clone()).or_default().push(*replica);
+    }
+    for placement in &context.placements {
+        let placement_replicas = match placement {
+            Placement::NetworkTopology(rf) => replicas
+                .nodes
+                .iter()
+                .map(|(dc, dc_nodes)| {
+                    let racks_count = context.datacenters.get(dc).map_or(0, |(_, racks_count)| *racks_count);
+                    (
+                        dc.clone(),
+                        network_topology_replicas(dc_nodes, &context.racks, racks_count, *rf),
+                    )
+                })
+                .collect(),
+            Placement::Simple(rf) => {
+                let mut simple_replicas: HashMap<DC, Vec<Replica>> = HashMap::new();
+                for (replica, dc) in nodes.iter().take(*rf) {
+                    simple_replicas.entry(dc.clone()).or_default().push(*replica);
+                }
+                simple_replicas
             }
+        };
+        replicas.placements.insert(*placement, placement_replicas);
+    }
+    replicas
+}
+
+/// Select the replicas of a datacenter as Scylla's NetworkTopologyStrategy does:
+/// walking clockwise, the first node of every rack is a replica, and a node of an already
+/// selected rack is a replica only while the replication factor exceeds the racks count.
+/// The racks count is the datacenter one, as the nodes might be only the first ones clockwise.
+fn network_topology_replicas(
+    dc_nodes: &[Replica],
+    racks: &HashMap<SocketAddr, Rack>,
+    racks_count: usize,
+    rf: usize,
+) -> Vec<Replica> {
+    let rack_of = |replica: &Replica| racks.get(&replica.0).map(|rack| rack.as_str()).unwrap_or_default();
+    let mut rf_left = rf.min(dc_nodes.len());
+    let mut acceptable_rack_repeats = rf.saturating_sub(racks_count);
+    let mut seen_racks = HashSet::new();
+    let mut replicas = Vec::with_capacity(rf_left);
+    for replica in dc_nodes {
+        if rf_left == 0 {
+            break;
+        }
+        if seen_racks.insert(rack_of(replica)) {
+            // new rack
+            replicas.push(*replica);
+            rf_left -= 1;
+        } else if acceptable_rack_repeats > 0 {
+            // not enough racks to satisfy the replication factor
+            replicas.push(*replica);
+            acceptable_rack_repeats -= 1;
+            rf_left -= 1;
         }
     }
+    replicas
 }
 
 #[test]
@@ -407,23 +804,205 @@ fn generate_and_compute_fake_ring() {
     let max_vnode = (recent_left, MAX, *recent_node_id, recent_dc.clone(), 12, 8); //
     vnodes.push(max_vnode);
     // compute all possible replicas in advance for each vnode in vnodes
-    // prepare ring chain
-    let mut chain = Vec::new();
-    let mut starting_index = 0;
-    for (left, right, _, _, _, _) in &vnodes {
-        let mut replicas: Replicas = HashMap::new();
-        // first walk clockwise phase (start..end)
-        walk_clockwise(starting_index, vnodes.len(), &vnodes, &mut replicas);
-        // second walk clockwise phase (0..start)
-        walk_clockwise(0, starting_index, &vnodes, &mut replicas);
-        // update starting_index
-        starting_index += 1;
-        // create vnode
-        chain.push((*left, *right, replicas));
-    }
+    let placements = maplit::hashset! {Placement::NetworkTopology(3), Placement::Simple(3)};
+    let (chain, _) = compute_chain(&vnodes, &ChainContext::new(&vnodes, HashMap::new(), placements), None);
     // build computed binary search tree from chain
-    // we start spliting from the root which is chain.len()/2
-    // for example if chain length is 3 then the root vnode is at 3/2 = 1
-    // and it will be mild where both of its childern are deadends.
-    let _root = compute_vnode(&chain);
+    // the root is the vnode with the highest priority, which splits the chain into its left and right subtrees
+    // recursively, so a vnode without lower priority neighbours is a deadend.
+    let (root, _) = compute_tree(&chain, None);
+    for link in &chain {
+        assert_eq!(
+            root.search(link.right).replicas(&us, None),
+            link.replicas.replicas(&us, None)
+        );
+    }
+}
+
+#[cfg(test)]
+fn test_replica(id: u8) -> Replica {
+    use std::net::{
+        IpAddr,
+        Ipv4Addr,
+    };
+    (SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, id)), 0), 12, 8)
+}
+
+#[cfg(test)]
+fn test_racks(racks: &[(u8, &str)]) -> HashMap<SocketAddr, Rack> {
+    racks
+        .iter()
+        .map(|(id, rack)| (test_replica(*id).0, rack.to_string()))
+        .collect()
+}
+
+#[test]
+fn network_topology_rack_aware_placement() {
+    let place = |order: &[u8], racks: &[(u8, &str)], rf: usize| -> Vec<Replica> {
+        let dc_nodes: Vec<Replica> = order.iter().map(|id| test_replica(*id)).collect();
+        let racks = test_racks(racks);
+        let racks_count = dc_nodes
+            .iter()
+            .map(|replica| racks.get(&replica.0).map(|rack| rack.as_str()).unwrap_or_default())
+            .collect::<HashSet<_>>()
+            .len();
+        network_topology_replicas(&dc_nodes, &racks, racks_count, rf)
+    };
+    let replicas = |ids: &[u8]| -> Vec<Replica> { ids.iter().map(|id| test_replica(*id)).collect() };
+    let three_racks = [(1, "r1"), (2, "r1"), (3, "r2"), (4, "r2"), (5, "r3"), (6, "r3")];
+    // one replica per rack, skipping the nodes of the already selected racks
+    assert_eq!(place(&[1, 2, 3, 4, 5, 6], &three_racks, 3), replicas(&[1, 3, 5]));
+    assert_eq!(place(&[1, 2, 3, 4, 5, 6], &three_racks, 2), replicas(&[1, 3]));
+    assert_eq!(place(&[4, 3, 6, 1, 2, 5], &three_racks, 3), replicas(&[4, 6, 1]));
+    // rf greater than the racks count accepts rf - racks_count repeated racks in clockwise order
+    assert_eq!(place(&[1, 2, 3, 4, 5, 6], &three_racks, 5), replicas(&[1, 2, 3, 4, 5]));
+    assert_eq!(place(&[1, 3, 5, 2, 4, 6], &three_racks, 4), replicas(&[1, 3, 5, 2]));
+    let two_racks = [(1, "r1"), (2, "r1"), (3, "r1"), (4, "r2")];
+    assert_eq!(place(&[1, 2, 3, 4], &two_racks, 3), replicas(&[1, 2, 4]));
+    assert_eq!(place(&[1, 2, 3, 4], &two_racks, 2), replicas(&[1, 4]));
+    // a single rack degrades to the first rf nodes
+    let one_rack = [(1, "r1"), (2, "r1"), (3, "r1"), (4, "r1")];
+    assert_eq!(place(&[3, 1, 4, 2], &one_rack, 3), replicas(&[3, 1, 4]));
+    // nodes without known rack share the same rack
+    assert_eq!(place(&[1, 2, 3], &[(3, "r2")], 2), replicas(&[1, 3]));
+    // rf greater than the nodes count selects all the nodes
+    assert_eq!(place(&[1, 2], &two_racks, 3), replicas(&[1, 2]));
+    assert_eq!(place(&[1, 4], &two_racks, 0), replicas(&[]));
+}
+
+#[test]
+fn compute_chain_placements() {
+    let node = |id: u8| test_replica(id).0;
+    let us = "US".to_string();
+    let eu = "EU".to_string();
+    let vnodes: Vec<VnodeTuple> = vec![
+        (MIN, 10, node(1), us.clone(), 12, 8),
+        (10, 20, node(2), us.clone(), 12, 8),
+        (20, 30, node(3), eu.clone(), 12, 8),
+        (30, 40, node(4), us.clone(), 12, 8),
+        (40, MAX, node(5), eu.clone(), 12, 8),
+    ];
+    let racks = test_racks(&[(1, "r2"), (2, "r1"), (4, "r1"), (3, "e1"), (5, "e2")]);
+    let nts_2 = Placement::NetworkTopology(2);
+    let nts_3 = Placement::NetworkTopology(3);
+    let simple_2 = Placement::Simple(2);
+    let context = ChainContext::new(&vnodes, racks, maplit::hashset! {nts_2, nts_3, simple_2});
+    let (chain, _) = compute_chain(&vnodes, &context, None);
+    assert_eq!(chain.len(), vnodes.len());
+    // the vnode (10, 20] walks clockwise through 2, 3, 4, 5 then 1
+    let ChainLink {
+        left, right, replicas, ..
+    } = &chain[1];
+    assert_eq!((*left, *right), (10, 20));
+    let ids = |ids: &[u8]| -> Vec<Replica> { ids.iter().map(|id| test_replica(*id)).collect() };
+    assert_eq!(replicas.nodes[&us], ids(&[2, 4, 1]));
+    assert_eq!(replicas.nodes[&eu], ids(&[3, 5]));
+    assert_eq!(replicas.placements[&nts_2][&us], ids(&[2, 1]));
+    assert_eq!(replicas.placements[&nts_2][&eu], ids(&[3, 5]));
+    assert_eq!(replicas.placements[&nts_3][&us], ids(&[2, 4, 1]));
+    assert_eq!(replicas.placements[&nts_3][&eu], ids(&[3, 5]));
+    assert_eq!(replicas.placements[&simple_2][&us], ids(&[2]));
+    assert_eq!(replicas.placements[&simple_2][&eu], ids(&[3]));
+    // the last vnode (40, MAX] wraps around the ring
+    let replicas = &chain[4].replicas;
+    assert_eq!(replicas.placements[&nts_2][&us], ids(&[1, 2]));
+    assert_eq!(replicas.placements[&simple_2][&eu], ids(&[5]));
+    assert_eq!(replicas.placements[&simple_2][&us], ids(&[1]));
+}
+
+#[test]
+fn rebuild_reuses_unchanged_vnodes() {
+    let us = "US".to_string();
+    let nts_2 = Placement::NetworkTopology(2);
+    // every node owns two tokens and is in its own rack, so the walks are the two nodes clockwise
+    let tokens = |down: Option<u8>| -> Tokens {
+        (1..=12u8)
+            .map(|index| (index as Token * 10, (index - 1) % 6 + 1))
+            .filter(|(_, id)| Some(*id) != down)
+            .map(|(token, id)| (token, test_replica(id).0, us.clone(), 12, 8))
+            .collect()
+    };
+    let racks = [(1, "r1"), (2, "r2"), (3, "r3"), (4, "r4"), (5, "r5"), (6, "r6")];
+    let moved_racks = [(1, "r1"), (2, "r2"), (3, "r3"), (4, "r4"), (5, "r4"), (6, "r6")];
+    struct Ring {
+        chain: Vec<ChainLink>,
+        context: ChainContext,
+        root: Vcell,
+        subtrees: Vec<Subtree>,
+    }
+    // build the ring, returns it along the number of reused walks and subtrees
+    let build = |tokens: Tokens, racks: &[(u8, &str)], previous: Option<&Ring>| -> (Ring, usize, usize) {
+        let vnodes = compute_vnodes(tokens);
+        let context = ChainContext::new(
+            &vnodes,
+            test_racks(racks),
+            maplit::hashset! {nts_2, Placement::Simple(2)},
+        );
+        let (chain, previous_indexes) = compute_chain(
+            &vnodes,
+            &context,
+            previous.map(|ring| (ring.chain.as_slice(), &ring.context)),
+        );
+        let (root, subtrees) = compute_tree(
+            &chain,
+            previous.map(|ring| (previous_indexes.as_slice(), ring.subtrees.as_slice())),
+        );
+        let reused_walks = previous_indexes.iter().flatten().count();
+        let reused_subtrees = previous.map_or(0, |ring| {
+            subtrees
+                .iter()
+                .zip(&previous_indexes)
+                .filter(|(subtree, index)| {
+                    index.is_some_and(|index| Arc::ptr_eq(&subtree.root, &ring.subtrees[index].root))
+                })
+                .count()
+        });
+        // the incremental ring must be the same as the ring computed from scratch
+        let (scratch_chain, _) = compute_chain(&vnodes, &context, None);
+        assert_eq!(chain, scratch_chain);
+        let (scratch_root, _) = compute_tree(&scratch_chain, None);
+        for link in &chain {
+            for token in [link.left + 1, link.right] {
+                for placement in [Some(nts_2), None] {
+                    assert_eq!(
+                        root.search(token).replicas(&us, placement),
+                        scratch_root.search(token).replicas(&us, placement)
+                    );
+                }
+            }
+        }
+        let ring = Ring {
+            chain,
+            context,
+            root,
+            subtrees,
+        };
+        (ring, reused_walks, reused_subtrees)
+    };
+    let (ring, ..) = build(tokens(None), &racks, None);
+    assert_eq!(ring.chain.len(), 13);
+    // nothing changed, so the whole tree is reused
+    let (unchanged, walks, subtrees) = build(tokens(None), &racks, Some(&ring));
+    assert_eq!((walks, subtrees), (13, 13));
+    assert!(Arc::ptr_eq(&unchanged.root, &ring.root));
+    // the node 3 goes down, so the walks through its vnodes (20, 30] and (80, 90] are recomputed
+    let (down, walks, subtrees) = build(tokens(Some(3)), &racks, Some(&ring));
+    assert_eq!((down.chain.len(), walks, subtrees), (11, 7, 4));
+    // the node 3 comes back up, splitting the vnodes (20, 40] and (80, 100]
+    let (_, walks, subtrees) = build(tokens(None), &racks, Some(&down));
+    assert_eq!((walks, subtrees), (7, 4));
+    // the node 5 moves to the rack of the node 4, so the walks through the node 5 are recomputed
+    let (_, walks, subtrees) = build(tokens(None), &moved_racks, Some(&ring));
+    assert_eq!((walks, subtrees), (9, 5));
+}
+
+#[test]
+fn replication_info_placements() {
+    let mut info = ReplicationInfo::new("US", 3);
+    info.upsert("EU", 2);
+    assert_eq!(info.placement("US"), Some(Placement::NetworkTopology(3)));
+    assert_eq!(info.placement("EU"), Some(Placement::NetworkTopology(2)));
+    assert_eq!(info.placement("ASIA"), None);
+    info.simple_strategy(3);
+    assert_eq!(info.placement("EU"), Some(Placement::Simple(3)));
+    assert_eq!(info.placements(), vec![Placement::Simple(3), Placement::Simple(3)]);
 }
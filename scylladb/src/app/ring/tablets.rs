@@ -0,0 +1,225 @@
+use super::*;
+use crate::{
+    app::worker::{
+        ReporterHandle,
+        Worker,
+        WorkerError,
+    },
+    cql::{
+        Decoder,
+        Tablet,
+        TabletReplica,
+    },
+};
+use arc_swap::ArcSwapOption;
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    sync::Arc,
+    time::Duration,
+};
+
+static TABLETS: ArcSwapOption<Tablets> = ArcSwapOption::const_empty();
+
+/// The tablets of a table, keyed by their last token
+#[derive(Clone, Debug, Default)]
+pub struct TableTablets {
+    tablets: BTreeMap<Token, Tablet>,
+}
+
+impl TableTablets {
+    /// Create the table tablets
+    pub fn new(tablets: Vec<Tablet>) -> Self {
+        let mut table_tablets = Self::default();
+        for tablet in tablets {
+            table_tablets.insert(tablet);
+        }
+        table_tablets
+    }
+    /// Get the tablet which owns the token
+    pub fn get(&self, token: Token) -> Option<&Tablet> {
+        self.tablets
+            .range(token..)
+            .next()
+            .map(|(_, tablet)| tablet)
+            .filter(|tablet| tablet.first_token < token || tablet.first_token == i64::MIN)
+    }
+    /// Insert the tablet and drop the tablets it overlaps, as the tablets get split, merged or migrated
+    pub fn insert(&mut self, tablet: Tablet) {
+        let overlapping: Vec<Token> = self
+            .tablets
+            .range(tablet.first_token.saturating_add(1)..)
+            .take_while(|(_, other)| other.first_token < tablet.last_token)
+            .map(|(last_token, _)| *last_token)
+            .collect();
+        for last_token in overlapping {
+            self.tablets.remove(&last_token);
+        }
+        self.tablets.insert(tablet.last_token, tablet);
+    }
+    /// Returns the number of tablets
+    pub fn len(&self) -> usize {
+        self.tablets.len()
+    }
+    /// Check if the table has no tablets
+    pub fn is_empty(&self) -> bool {
+        self.tablets.is_empty()
+    }
+}
+
+/// The tablets of the tablets based keyspaces, keyed by keyspace and table name
+#[derive(Clone, Debug, Default)]
+pub struct Tablets {
+    keyspaces: HashMap<String, HashMap<String, Arc<TableTablets>>>,
+}
+
+impl Tablets {
+    /// Create the tablets from the tablets fetched from system.tablets
+    pub fn new(tablets: HashMap<String, HashMap<String, Vec<Tablet>>>) -> Self {
+        let keyspaces = tablets
+            .into_iter()
+            .map(|(keyspace, tables)| {
+                let tables = tables
+                    .into_iter()
+                    .map(|(table, tablets)| (table, Arc::new(TableTablets::new(tablets))))
+                    .collect();
+                (keyspace, tables)
+            })
+            .collect();
+        Self { keyspaces }
+    }
+    /// Commit the tablets
+    pub fn commit(self) {
+        TABLETS.store(Some(self.into()))
+    }
+    /// Make the tablets empty: None
+    pub fn drop() {
+        TABLETS.swap(None);
+    }
+    /// Check if the keyspace is tablets based
+    pub fn is_tablets_keyspace(keyspace: &str) -> bool {
+        TABLETS
+            .load()
+            .as_ref()
+            .is_some_and(|tablets| tablets.keyspaces.contains_key(keyspace))
+    }
+    /// Get the replicas of the table tablet which owns the token
+    pub fn replicas(keyspace: &str, table: &str, token: Token) -> Option<Vec<TabletReplica>> {
+        TABLETS.load().as_ref().and_then(|tablets| {
+            tablets
+                .keyspaces
+                .get(keyspace)
+                .and_then(|tables| tables.get(table))
+                .and_then(|table_tablets| table_tablets.get(token))
+                .map(|tablet| tablet.replicas.clone())
+        })
+    }
+    /// Update the table tablet, used once the server returns the tablet of a misrouted request
+    pub fn update(keyspace: &str, table: &str, tablet: Tablet) {
+        TABLETS.rcu(|tablets| {
+            let mut tablets = tablets.as_deref().cloned().unwrap_or_default();
+            let table_tablets = tablets
+                .keyspaces
+                .entry(keyspace.to_string())
+                .or_default()
+                .entry(table.to_string())
+                .or_default();
+            Arc::make_mut(table_tablets).insert(tablet.clone());
+            Some(Arc::new(tablets))
+        });
+    }
+}
+
+/// Wrap the request worker to learn the tablet returned by the server, if the request got misrouted
+pub(crate) fn learn_tablet(keyspace: &str, table: &str, request: ReporterEvent) -> ReporterEvent {
+    match request {
+        ReporterEvent::Request { worker, payload } => ReporterEvent::Request {
+            worker: Box::new(TabletWorker {
+                keyspace: keyspace.to_string(),
+                table: table.to_string(),
+                inner: worker,
+            }),
+            payload,
+        },
+        request => request,
+    }
+}
+
+/// Worker which updates the table tablet from the response custom payload, before handing the response to the inner
+/// worker
+#[derive(Debug)]
+struct TabletWorker {
+    keyspace: String,
+    table: String,
+    inner: Box<dyn Worker>,
+}
+
+impl Worker for TabletWorker {
+    fn handle_response(self: Box<Self>, giveload: Vec<u8>) -> anyhow::Result<()> {
+        if Tablet::has_custom_payload(&giveload) {
+            match Decoder::try_from(giveload.clone()).and_then(|decoder| Tablet::from_routing_payload(&decoder)) {
+                Ok(Some(tablet)) => Tablets::update(&self.keyspace, &self.table, tablet),
+                Ok(None) => (),
+                Err(e) => log::warn!("Unable to decode {}.{} tablet, error: {}", self.keyspace, self.table, e),
+            }
+        }
+        self.inner.handle_response(giveload)
+    }
+
+    fn handle_error(self: Box<Self>, error: WorkerError, reporter: Option<&ReporterHandle>) -> anyhow::Result<()> {
+        self.inner.handle_error(error, reporter)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout()
+    }
+
+    fn handle_sent(&mut self) {
+        self.inner.handle_sent()
+    }
+}
+
+#[cfg(test)]
+fn test_tablet(first_token: Token, last_token: Token, shard: u16) -> Tablet {
+    Tablet {
+        first_token,
+        last_token,
+        replicas: vec![TabletReplica {
+            host_id: [1; 16],
+            shard,
+        }],
+    }
+}
+
+#[test]
+fn table_tablets_lookup_and_migration() {
+    let mut table_tablets = TableTablets::new(vec![
+        test_tablet(i64::MIN, -100, 0),
+        test_tablet(-100, 0, 1),
+        test_tablet(0, 100, 2),
+        test_tablet(100, i64::MAX, 3),
+    ]);
+    assert_eq!(table_tablets.len(), 4);
+    assert_eq!(table_tablets.get(i64::MIN).unwrap().last_token, -100);
+    assert_eq!(table_tablets.get(-100).unwrap().last_token, -100);
+    assert_eq!(table_tablets.get(-99).unwrap().last_token, 0);
+    assert_eq!(table_tablets.get(0).unwrap().last_token, 0);
+    assert_eq!(table_tablets.get(1).unwrap().last_token, 100);
+    assert_eq!(table_tablets.get(i64::MAX).unwrap().last_token, i64::MAX);
+    // migrating a tablet replaces its replicas
+    table_tablets.insert(test_tablet(0, 100, 5));
+    assert_eq!(table_tablets.len(), 4);
+    assert_eq!(table_tablets.get(50).unwrap().replicas[0].shard, 5);
+    // merging two tablets drops both of them
+    table_tablets.insert(test_tablet(-100, 100, 6));
+    assert_eq!(table_tablets.len(), 3);
+    assert_eq!(table_tablets.get(-50).unwrap().replicas[0].shard, 6);
+    assert_eq!(table_tablets.get(50).unwrap().replicas[0].shard, 6);
+    assert_eq!(table_tablets.get(-100).unwrap().replicas[0].shard, 0);
+    // splitting a tablet keeps the unknown half uncovered until the server returns it
+    table_tablets.insert(test_tablet(-100, 0, 7));
+    assert_eq!(table_tablets.len(), 3);
+    assert_eq!(table_tablets.get(-50).unwrap().replicas[0].shard, 7);
+    assert!(table_tablets.get(50).is_none());
+    assert_eq!(table_tablets.get(101).unwrap().replicas[0].shard, 3);
+}
@@ -1,5 +1,9 @@
 use super::{
-    ring::Registry,
+    ring::{
+        Registry,
+        ShardLoad,
+        ShardReporters,
+    },
     Scylla,
 };
 use crate::cql::CqlBuilder;
@@ -25,6 +29,7 @@ use std::{
     collections::HashMap,
     net::SocketAddr,
     sync::Arc,
+    time::Duration,
 };
 use tokio::sync::RwLock;
 
@@ -136,8 +141,14 @@ where
         // start reporters where they can directly link to the sender handle
         let mut reporters_handles = HashMap::new();
         let mut reporter_id: u8 = 0;
+        let load = Arc::new(ShardLoad::default());
         for reporter_streams_ids in streams_iter {
-            let reporter = reporter::Reporter::new(reporter_streams_ids.into());
+            let reporter = reporter::Reporter::new(
+                reporter_streams_ids.into(),
+                load.clone(),
+                scylla.request_timeout.map(Duration::from_millis),
+                scylla.backpressure.clone(),
+            );
             let reporter_handle = rt.start(format!("reporter_{}", reporter_id), reporter).await?;
             reporters_handles.insert(reporter_id, reporter_handle);
             reporter_id += 1;
@@ -145,10 +156,13 @@ where
         rt.publish(reporters_handles.clone()).await;
         let mut shard_socket_addr = self.address.clone();
         shard_socket_addr.set_port(self.shard_id as u16);
-        reporters_registry
-            .write()
-            .await
-            .insert(shard_socket_addr.clone(), reporters_handles);
+        reporters_registry.write().await.insert(
+            shard_socket_addr.clone(),
+            ShardReporters {
+                handles: reporters_handles,
+                load,
+            },
+        );
         let (scope_id, receiver_service) = receiver_init_signal.initialized().await?;
         rt.upsert_microservice(scope_id, receiver_service);
         let (scope_id, sender_service) = sender_init_signal.initialized().await?;
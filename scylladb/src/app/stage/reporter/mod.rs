@@ -5,9 +5,13 @@ use super::{
     Payloads,
 };
 use crate::{
-    app::worker::{
-        Worker,
-        WorkerError,
+    app::{
+        ring::ShardLoad,
+        worker::{
+            Worker,
+            WorkerError,
+        },
+        BackpressureConfig,
     },
     cql::{
         CqlError,
@@ -28,9 +32,27 @@ use overclock::core::{
     UnboundedChannel,
     UnboundedHandle,
 };
-use std::collections::HashMap;
-/// Workers Map holds all the workers_ids
-type Workers = HashMap<i16, Box<dyn Worker>>;
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+        VecDeque,
+    },
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use timer::{
+    TimerWheel,
+    TICK,
+};
+
+mod timer;
+
+/// Workers Map holds all the workers_ids along the time their requests got sent
+type Workers = HashMap<i16, (Box<dyn Worker>, Instant)>;
 /// Reporter's handle, used to push cql request
 pub type ReporterHandle = UnboundedHandle<ReporterEvent>;
 
@@ -51,6 +73,8 @@ pub enum ReporterEvent {
     },
     /// The stream error.
     Err(anyhow::Error, i16),
+    /// The timer tick, which expires the requests exceeding their timeout.
+    Tick,
     /// Shutdown signal
     Shutdown,
 }
@@ -63,14 +87,37 @@ impl ShutdownEvent for ReporterEvent {
 pub struct Reporter {
     streams: Vec<i16>,
     workers: Workers,
+    load: Arc<ShardLoad>,
+    /// The default timeout of the requests
+    timeout: Option<Duration>,
+    timers: TimerWheel,
+    /// Whether the timer tick got spawned, which is only needed once a request may time out or get queued
+    ticking: bool,
+    /// The streams of the timed out requests, which are reused once their late response arrives
+    quarantined: HashSet<i16>,
+    /// The requests waiting for a free stream, along the time they got queued
+    queue: VecDeque<(Box<dyn Worker>, Vec<u8>, Instant)>,
+    backpressure: Option<BackpressureConfig>,
 }
 
 impl Reporter {
     /// Create new reporter
-    pub(super) fn new(streams: Vec<i16>) -> Self {
+    pub(super) fn new(
+        streams: Vec<i16>,
+        load: Arc<ShardLoad>,
+        timeout: Option<Duration>,
+        backpressure: Option<BackpressureConfig>,
+    ) -> Self {
         Self {
             streams,
             workers: HashMap::new(),
+            load,
+            timeout,
+            timers: TimerWheel::new(Instant::now()),
+            ticking: false,
+            quarantined: HashSet::new(),
+            queue: VecDeque::new(),
+            backpressure,
         }
     }
 }
@@ -97,33 +144,30 @@ where
             .await
             .ok_or_else(|| ActorError::exit_msg("reporter unables to lookup for payloads"))?;
         let sender_handle = rt.link(sender_scope_id, false).await.map_err(ActorError::exit)?;
+        if self.timeout.is_some() || self.backpressure.is_some() {
+            self.tick(rt.handle());
+        }
         Ok((payloads, sender_handle))
     }
     async fn run(&mut self, rt: &mut Rt<Self, S>, (mut payloads, sender): Self::Data) -> ActorResult<()> {
         while let Some(event) = rt.inbox_mut().next().await {
             match event {
-                ReporterEvent::Request { worker, mut payload } => {
-                    if let Some(stream) = self.streams.pop() {
-                        // Assign stream_id to the payload
-                        assign_stream_to_payload(stream, &mut payload);
-                        // store payload as reusable at payloads[stream]
-                        payloads[stream as usize].as_mut().replace(payload);
-                        self.workers.insert(stream, worker);
-                        sender.send(stream).unwrap_or_else(|e| log::error!("{}", e));
-                    } else {
-                        // Send overload to the worker in-case we don't have anymore streams
-                        worker
-                            .handle_error(WorkerError::Overload, Some(rt.handle()))
-                            .unwrap_or_else(|e| log::error!("{}", e));
-                    }
+                ReporterEvent::Request { worker, payload } => {
+                    self.handle_request(rt.handle(), worker, payload, &mut payloads, &sender);
                 }
                 ReporterEvent::Response { stream_id } => {
                     self.handle_response(rt.handle(), stream_id, &mut payloads)
                         .unwrap_or_else(|e| log::error!("{}", e));
+                    self.send_queued(&mut payloads, &sender);
                 }
                 ReporterEvent::Err(io_error, stream_id) => {
                     self.handle_error(rt.handle(), stream_id, &mut payloads, WorkerError::Other(io_error))
                         .unwrap_or_else(|e| log::error!("{}", e));
+                    self.send_queued(&mut payloads, &sender);
+                }
+                ReporterEvent::Tick => {
+                    self.handle_timeouts(rt.handle());
+                    self.handle_queue_timeouts(rt.handle());
                 }
                 ReporterEvent::Shutdown => {
                     rt.stop().await;
@@ -138,11 +182,116 @@ where
     }
 }
 impl Reporter {
+    /// Send the request if there is a free stream, otherwise queue it if the queue has room, or fail it with overload
+    fn handle_request(
+        &mut self,
+        handle: &ReporterHandle,
+        worker: Box<dyn Worker>,
+        payload: Vec<u8>,
+        payloads: &mut Payloads,
+        sender: &AbortableUnboundedHandle<SenderEvent>,
+    ) {
+        if worker.timeout().is_some() {
+            self.tick(handle);
+        }
+        if let Some(stream) = self.streams.pop() {
+            self.send_request(stream, worker, payload, payloads, sender);
+        } else if self
+            .backpressure
+            .as_ref()
+            .is_some_and(|backpressure| self.queue.len() < backpressure.queue_size)
+        {
+            // wait for a free stream
+            self.queue.push_back((worker, payload, Instant::now()));
+            self.load.request_queued();
+        } else {
+            // Send overload to the worker in-case we don't have anymore streams
+            worker
+                .handle_error(WorkerError::Overload, Some(handle))
+                .unwrap_or_else(|e| log::error!("{}", e));
+        }
+    }
+    fn send_request(
+        &mut self,
+        stream: i16,
+        mut worker: Box<dyn Worker>,
+        mut payload: Vec<u8>,
+        payloads: &mut Payloads,
+        sender: &AbortableUnboundedHandle<SenderEvent>,
+    ) {
+        // Assign stream_id to the payload
+        assign_stream_to_payload(stream, &mut payload);
+        // store payload as reusable at payloads[stream]
+        payloads[stream as usize].as_mut().replace(payload);
+        let sent_at = Instant::now();
+        if let Some(timeout) = worker.timeout().or(self.timeout) {
+            self.timers.insert(stream, sent_at, timeout);
+        }
+        worker.handle_sent();
+        self.workers.insert(stream, (worker, sent_at));
+        self.load.request_sent();
+        sender.send(stream).unwrap_or_else(|e| log::error!("{}", e));
+    }
+    /// Send the queued requests while there are free streams
+    fn send_queued(&mut self, payloads: &mut Payloads, sender: &AbortableUnboundedHandle<SenderEvent>) {
+        while !self.queue.is_empty() {
+            if let Some(stream) = self.streams.pop() {
+                if let Some((worker, payload, _)) = self.queue.pop_front() {
+                    self.load.request_dequeued();
+                    self.send_request(stream, worker, payload, payloads, sender);
+                }
+            } else {
+                break;
+            }
+        }
+    }
+    /// Fail the queued requests which waited longer than the max wait
+    fn handle_queue_timeouts(&mut self, handle: &ReporterHandle) {
+        let max_wait = match self.backpressure.as_ref() {
+            Some(backpressure) => Duration::from_millis(backpressure.max_wait),
+            None => return,
+        };
+        while self
+            .queue
+            .front()
+            .is_some_and(|(_, _, queued_at)| queued_at.elapsed() >= max_wait)
+        {
+            if let Some((worker, _, _)) = self.queue.pop_front() {
+                self.load.request_dequeued();
+                worker
+                    .handle_error(WorkerError::Overload, Some(handle))
+                    .unwrap_or_else(|e| log::error!("{}", e));
+            }
+        }
+    }
+    /// Spawn the timer tick, unless it got already spawned
+    fn tick(&mut self, handle: &ReporterHandle) {
+        if self.ticking {
+            return;
+        }
+        self.ticking = true;
+        let handle = handle.clone();
+        let tick_task = async move {
+            loop {
+                tokio::time::sleep(TICK).await;
+                if handle.send(ReporterEvent::Tick).is_err() {
+                    break;
+                }
+            }
+        };
+        overclock::spawn_task("reporter timer", tick_task);
+    }
     fn handle_response(&mut self, handle: &ReporterHandle, stream: i16, payloads: &mut Payloads) -> anyhow::Result<()> {
         // push the stream_id back to streams vector.
         self.streams.push(stream);
+        if self.quarantined.remove(&stream) {
+            // drop the late response of the timed out request
+            payloads[stream as usize].as_mut().take();
+            return Ok(());
+        }
         // remove the worker from workers.
-        if let Some(worker) = self.workers.remove(&stream) {
+        if let Some((worker, sent_at)) = self.workers.remove(&stream) {
+            self.load.request_done(Some(sent_at.elapsed()));
             if let Some(payload) = payloads[stream as usize].as_mut().take() {
                 if is_cql_error(&payload) {
                     let error = Decoder::try_from(payload)
@@ -169,8 +318,13 @@ impl Reporter {
     ) -> anyhow::Result<()> {
         // push the stream_id back to streams vector.
         self.streams.push(stream);
+        if self.quarantined.remove(&stream) {
+            payloads[stream as usize].as_mut().take();
+            return Ok(());
+        }
         // remove the worker from workers and send error.
-        if let Some(worker) = self.workers.remove(&stream) {
+        if let Some((worker, _)) = self.workers.remove(&stream) {
+            self.load.request_done(None);
             // drop payload.
             if let Some(_payload) = payloads[stream as usize].as_mut().take() {
                 worker.handle_error(error, Some(handle))?;
@@ -182,10 +336,36 @@ impl Reporter {
         }
         Ok(())
     }
+    fn handle_timeouts(&mut self, handle: &ReporterHandle) {
+        for (stream, sent_at) in self.timers.advance(Instant::now()) {
+            // the stream might have been responded and reused since the timer got scheduled
+            if self.workers.get(&stream).is_none_or(|(_, sent)| *sent != sent_at) {
+                continue;
+            }
+            if let Some((worker, _)) = self.workers.remove(&stream) {
+                self.load.request_done(None);
+                // keep the stream and its payload until the late response arrives, as the receiver writes it there
+                self.quarantined.insert(stream);
+                worker
+                    .handle_error(WorkerError::Timeout, Some(handle))
+                    .unwrap_or_else(|e| log::error!("{}", e));
+            }
+        }
+    }
     fn force_consistency(&mut self, handle: &ReporterHandle) {
-        for (stream_id, worker_id) in self.workers.drain() {
+        // the connection is gone, so the late responses will never arrive
+        self.streams.extend(self.quarantined.drain());
+        for (worker, _, _) in self.queue.drain(..) {
+            self.load.request_dequeued();
+            // the queued requests have not been sent
+            worker
+                .handle_error(WorkerError::Overload, Some(handle))
+                .unwrap_or_else(|e| log::error!("{}", e));
+        }
+        for (stream_id, (worker_id, _)) in self.workers.drain() {
             // push the stream_id back into the streams vector
             self.streams.push(stream_id);
+            self.load.request_done(None);
             // tell worker_id that we lost the response for his request, because we lost scylla connection in
             // middle of request cycle, still this is a rare case.
             worker_id
@@ -204,3 +384,204 @@ fn assign_stream_to_payload(stream: i16, payload: &mut Vec<u8>) {
 fn is_cql_error(buffer: &[u8]) -> bool {
     buffer[4] == 0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{
+        stage::Reusable,
+        worker::SentNotifyWorker,
+    };
+    use overclock::core::{
+        AbortableUnboundedChannel,
+        AbortableUnboundedInbox,
+        Channel,
+        UnboundedInbox,
+    };
+    use std::sync::Mutex;
+
+    type Events = Arc<Mutex<Vec<(usize, &'static str)>>>;
+
+    /// A worker which records what happened to its request
+    #[derive(Debug)]
+    struct RecordWorker {
+        id: usize,
+        events: Events,
+    }
+
+    impl RecordWorker {
+        fn new(id: usize, events: &Events) -> Box<Self> {
+            Box::new(Self {
+                id,
+                events: events.clone(),
+            })
+        }
+        fn record(&self, event: &'static str) {
+            self.events.lock().unwrap().push((self.id, event));
+        }
+    }
+
+    impl Worker for RecordWorker {
+        fn handle_response(self: Box<Self>, _giveload: Vec<u8>) -> anyhow::Result<()> {
+            self.record("response");
+            Ok(())
+        }
+        fn handle_error(self: Box<Self>, error: WorkerError, _reporter: Option<&ReporterHandle>) -> anyhow::Result<()> {
+            self.record(match error {
+                WorkerError::Overload => "overload",
+                WorkerError::Lost => "lost",
+                _ => "error",
+            });
+            Ok(())
+        }
+        fn handle_sent(&mut self) {
+            self.record("sent");
+        }
+    }
+
+    struct Fixture {
+        reporter: Reporter,
+        handle: ReporterHandle,
+        payloads: Payloads,
+        sender: AbortableUnboundedHandle<SenderEvent>,
+        events: Events,
+        _inbox: UnboundedInbox<ReporterEvent>,
+        _sender_inbox: AbortableUnboundedInbox<SenderEvent>,
+    }
+
+    impl Fixture {
+        /// A reporter owning a single stream
+        fn new(backpressure: Option<BackpressureConfig>) -> Self {
+            let reporter = Reporter::new(vec![0], Arc::new(ShardLoad::default()), None, backpressure);
+            let (handle, _inbox, ..) = UnboundedChannel::<ReporterEvent>::new().channel::<Reporter>(0);
+            let (sender, _sender_inbox, ..) = AbortableUnboundedChannel::<SenderEvent>::new().channel::<Reporter>(1);
+            Self {
+                reporter,
+                handle,
+                payloads: Arc::new(vec![Reusable::default()]),
+                sender,
+                events: Events::default(),
+                _inbox,
+                _sender_inbox,
+            }
+        }
+        fn request(&mut self, worker: Box<dyn Worker>) {
+            // a RESULT frame header
+            let payload = vec![4, 0, 0, 0, 8, 0, 0, 0, 0];
+            self.reporter
+                .handle_request(&self.handle, worker, payload, &mut self.payloads, &self.sender);
+        }
+        /// Respond to the request in flight, then send the queued requests as the reporter does
+        fn respond(&mut self) {
+            self.reporter
+                .handle_response(&self.handle, 0, &mut self.payloads)
+                .unwrap();
+            self.reporter.send_queued(&mut self.payloads, &self.sender);
+        }
+        fn events(&self) -> Vec<(usize, &'static str)> {
+            std::mem::take(&mut self.events.lock().unwrap())
+        }
+    }
+
+    fn backpressure(queue_size: usize, max_wait: u64) -> Option<BackpressureConfig> {
+        Some(BackpressureConfig { queue_size, max_wait })
+    }
+
+    #[test]
+    fn overload_without_free_stream() {
+        let mut fixture = Fixture::new(None);
+        fixture.request(RecordWorker::new(0, &fixture.events));
+        fixture.request(RecordWorker::new(1, &fixture.events));
+        assert_eq!(fixture.events(), vec![(0, "sent"), (1, "overload")]);
+    }
+
+    #[test]
+    fn overload_when_queue_is_full() {
+        let mut fixture = Fixture::new(backpressure(2, 1000));
+        for id in 0..4 {
+            fixture.request(RecordWorker::new(id, &fixture.events));
+        }
+        assert_eq!(fixture.events(), vec![(0, "sent"), (3, "overload")]);
+        assert_eq!(fixture.reporter.load.queued(), 2);
+    }
+
+    #[test]
+    fn queued_requests_expire_after_max_wait() {
+        let mut fixture = Fixture::new(backpressure(2, 1000));
+        fixture.request(RecordWorker::new(0, &fixture.events));
+        fixture.request(RecordWorker::new(1, &fixture.events));
+        fixture.reporter.handle_queue_timeouts(&fixture.handle);
+        assert_eq!(fixture.events(), vec![(0, "sent")]);
+        let mut fixture = Fixture::new(backpressure(2, 0));
+        fixture.request(RecordWorker::new(0, &fixture.events));
+        fixture.request(RecordWorker::new(1, &fixture.events));
+        fixture.request(RecordWorker::new(2, &fixture.events));
+        fixture.reporter.handle_queue_timeouts(&fixture.handle);
+        // the request in flight is left to its own timeout
+        assert_eq!(fixture.events(), vec![(0, "sent"), (1, "overload"), (2, "overload")]);
+        assert_eq!(fixture.reporter.load.queued(), 0);
+    }
+
+    #[test]
+    fn queued_requests_are_sent_in_order() {
+        let mut fixture = Fixture::new(backpressure(2, 1000));
+        for id in 0..3 {
+            fixture.request(RecordWorker::new(id, &fixture.events));
+        }
+        fixture.respond();
+        fixture.respond();
+        fixture.respond();
+        assert_eq!(
+            fixture.events(),
+            vec![
+                (0, "sent"),
+                (0, "response"),
+                (1, "sent"),
+                (1, "response"),
+                (2, "sent"),
+                (2, "response")
+            ]
+        );
+        assert_eq!(fixture.reporter.load.queued(), 0);
+        assert_eq!(fixture.reporter.streams, vec![0]);
+    }
+
+    #[test]
+    fn queued_requests_fail_when_connection_drops() {
+        let mut fixture = Fixture::new(backpressure(2, 1000));
+        fixture.request(RecordWorker::new(0, &fixture.events));
+        fixture.request(RecordWorker::new(1, &fixture.events));
+        fixture.reporter.force_consistency(&fixture.handle);
+        assert_eq!(fixture.events(), vec![(0, "sent"), (1, "overload"), (0, "lost")]);
+        assert_eq!(fixture.reporter.load.queued(), 0);
+        assert_eq!(fixture.reporter.streams, vec![0]);
+    }
+
+    #[test]
+    fn sent_notify_worker_resolves_once_sent() {
+        let mut fixture = Fixture::new(backpressure(1, 1000));
+        fixture.request(RecordWorker::new(0, &fixture.events));
+        let (worker, mut sent) = SentNotifyWorker::new(RecordWorker::new(1, &fixture.events));
+        fixture.request(worker);
+        assert!(sent.try_recv().is_err());
+        fixture.respond();
+        assert!(matches!(sent.try_recv(), Ok(Ok(()))));
+        fixture.respond();
+        assert_eq!(
+            fixture.events(),
+            vec![(0, "sent"), (0, "response"), (1, "sent"), (1, "response")]
+        );
+    }
+
+    #[test]
+    fn sent_notify_worker_returns_overload() {
+        let mut fixture = Fixture::new(backpressure(1, 0));
+        fixture.request(RecordWorker::new(0, &fixture.events));
+        let (worker, mut sent) = SentNotifyWorker::new(RecordWorker::new(1, &fixture.events));
+        fixture.request(worker);
+        fixture.reporter.handle_queue_timeouts(&fixture.handle);
+        assert!(matches!(sent.try_recv(), Ok(Err(WorkerError::Overload))));
+        // the error is returned to the caller rather than handed to the inner worker
+        assert_eq!(fixture.events(), vec![(0, "sent")]);
+    }
+}
@@ -0,0 +1,97 @@
+use std::time::{
+    Duration,
+    Instant,
+};
+
+/// The duration of a timer wheel tick
+pub(super) const TICK: Duration = Duration::from_millis(100);
+/// The number of slots of the timer wheel, a full rotation covers SLOTS * TICK
+const SLOTS: usize = 512;
+
+/// The deadline of an in-flight request
+#[derive(Debug, Clone, Copy)]
+struct Timer {
+    stream: i16,
+    sent_at: Instant,
+    deadline: Instant,
+}
+
+/// Hashed timer wheel which tracks the deadlines of the in-flight requests of a reporter.
+///
+/// The timers are never cancelled, once a timer expires the reporter checks whether its stream is still in-flight
+/// with the same sent time, as the stream might have been already responded and reused.
+#[derive(Debug)]
+pub(super) struct TimerWheel {
+    slots: Vec<Vec<Timer>>,
+    cursor: usize,
+    last_tick: Instant,
+}
+
+impl TimerWheel {
+    pub(super) fn new(now: Instant) -> Self {
+        Self {
+            slots: vec![Vec::new(); SLOTS],
+            cursor: 0,
+            last_tick: now,
+        }
+    }
+    /// Schedule the timeout of the request sent on the stream
+    pub(super) fn insert(&mut self, stream: i16, sent_at: Instant, timeout: Duration) {
+        let deadline = sent_at + timeout;
+        // round up, as the slot must not be visited before the deadline
+        let ticks = deadline
+            .saturating_duration_since(self.last_tick)
+            .as_nanos()
+            .div_ceil(TICK.as_nanos());
+        let slot = (self.cursor + (ticks.max(1) % SLOTS as u128) as usize) % SLOTS;
+        self.slots[slot].push(Timer {
+            stream,
+            sent_at,
+            deadline,
+        });
+    }
+    /// Advance the wheel up to now, returns the expired timers as (stream, sent_at)
+    pub(super) fn advance(&mut self, now: Instant) -> Vec<(i16, Instant)> {
+        let ticks = (now.saturating_duration_since(self.last_tick).as_nanos() / TICK.as_nanos()) as usize;
+        let mut expired = Vec::new();
+        for _ in 0..ticks.min(SLOTS) {
+            self.cursor = (self.cursor + 1) % SLOTS;
+            // keep the timers of the next rotations
+            self.slots[self.cursor].retain(|timer| {
+                if timer.deadline <= now {
+                    expired.push((timer.stream, timer.sent_at));
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        self.last_tick += TICK * ticks as u32;
+        expired
+    }
+}
+
+#[test]
+fn timer_wheel_expiration() {
+    let start = Instant::now();
+    let mut wheel = TimerWheel::new(start);
+    wheel.insert(1, start, Duration::from_millis(250));
+    wheel.insert(2, start, Duration::from_millis(50));
+    // beyond a full rotation
+    wheel.insert(3, start, TICK * (SLOTS as u32 + 3));
+    assert!(wheel.advance(start + Duration::from_millis(99)).is_empty());
+    assert_eq!(wheel.advance(start + TICK), vec![(2, start)]);
+    assert!(wheel.advance(start + Duration::from_millis(200)).is_empty());
+    assert_eq!(wheel.advance(start + Duration::from_millis(300)), vec![(1, start)]);
+    // a full rotation later the long timer is not expired yet
+    assert!(wheel.advance(start + TICK * SLOTS as u32).is_empty());
+    assert!(wheel.advance(start + TICK * (SLOTS as u32 + 2)).is_empty());
+    assert_eq!(wheel.advance(start + TICK * (SLOTS as u32 + 3)), vec![(3, start)]);
+    // a late tick expires all the overdue timers at once
+    let sent_at = start + TICK * (SLOTS as u32 + 3);
+    wheel.insert(4, sent_at, Duration::from_secs(1));
+    wheel.insert(5, sent_at, Duration::from_secs(2));
+    let mut expired = wheel.advance(sent_at + Duration::from_secs(5));
+    expired.sort();
+    assert_eq!(expired, vec![(4, sent_at), (5, sent_at)]);
+}
@@ -2,7 +2,10 @@ use super::{
     handle::*,
     *,
 };
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    time::Duration,
+};
 
 /// An Any worker
 #[derive(Clone, Debug)]
@@ -13,6 +16,8 @@ pub struct AnyWorker<H, R> {
     pub handle: H,
     /// The number of times this worker will retry on failure
     pub retries: usize,
+    /// The number of retries already attempted
+    pub attempts: usize,
 }
 
 impl<H, R> AnyWorker<H, R>
@@ -25,6 +30,7 @@ where
             request,
             handle,
             retries,
+            attempts: 0,
         }
     }
     /// Set retries to the worker
@@ -51,11 +57,21 @@ where
         RetryableWorker::<R>::send_global(self)
     }
 
-    pub(crate) fn from(BasicRetryWorker { request, retries }: BasicRetryWorker<R>, handle: H) -> Self
+    pub(crate) fn from(
+        BasicRetryWorker {
+            request,
+            retries,
+            attempts,
+        }: BasicRetryWorker<R>,
+        handle: H,
+    ) -> Self
     where
         R: 'static + SendRequestExt + Debug + Send + Sync,
     {
-        Self::new(request, handle, retries)
+        Self {
+            attempts,
+            ..Self::new(request, handle, retries)
+        }
     }
 }
 
@@ -83,12 +99,18 @@ where
                     worker.handle.handle_error(error)
                 })
             } else {
-                self.retry()?.map_or_else(|| Ok(()), |w| w.handle.handle_error(error))
+                self.retry(&error, reporter)?
+                    .map_or_else(|| Ok(()), |w| w.handle.handle_error(error))
             }
         } else {
-            self.retry()?.map_or_else(|| Ok(()), |w| w.handle.handle_error(error))
+            self.retry(&error, reporter)?
+                .map_or_else(|| Ok(()), |w| w.handle.handle_error(error))
         }
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.request.timeout()
+    }
 }
 
 impl<H, R> RetryableWorker<R> for AnyWorker<H, R>
@@ -107,6 +129,14 @@ where
     fn retries_mut(&mut self) -> &mut usize {
         &mut self.retries
     }
+
+    fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    fn attempts_mut(&mut self) -> &mut usize {
+        &mut self.attempts
+    }
 }
 
 impl<Ref, H, R> IntoReferencingWorker<Ref> for AnyWorker<H, R>
@@ -118,7 +148,10 @@ where
     type Output = AnyWorker<RefHandle<H, Ref>, R>;
     fn with_ref(self, reference: Ref) -> AnyWorker<RefHandle<H, Ref>, R> {
         let handle = RefHandle::new(self.handle, reference);
-        AnyWorker::new(self.request, handle, self.retries)
+        AnyWorker {
+            attempts: self.attempts,
+            ..AnyWorker::new(self.request, handle, self.retries)
+        }
     }
 }
 
@@ -130,7 +163,10 @@ where
     type Output = AnyWorker<DecHandle<H, R>, R>;
     fn with_decoder(self) -> Self::Output {
         let handle = DecHandle::<H, R>::new(self.handle);
-        AnyWorker::new(self.request, handle, self.retries)
+        AnyWorker {
+            attempts: self.attempts,
+            ..AnyWorker::new(self.request, handle, self.retries)
+        }
     }
 }
 
@@ -2,8 +2,12 @@ use super::*;
 use std::{
     fmt::Debug,
     marker::PhantomData,
+    time::Duration,
+};
+use tokio::sync::{
+    mpsc::UnboundedReceiver,
+    oneshot,
 };
-use tokio::sync::mpsc::UnboundedReceiver;
 
 /// A basic worker which cannot respond or retry
 #[derive(Debug, Clone)]
@@ -33,6 +37,8 @@ pub struct BasicRetryWorker<R> {
     pub request: R,
     /// The number of times this worker will retry on failure
     pub retries: usize,
+    /// The number of retries already attempted
+    pub attempts: usize,
 }
 
 impl<R> Debug for BasicRetryWorker<R>
@@ -43,6 +49,7 @@ where
         f.debug_struct("BasicRetryWorker")
             .field("request", &self.request)
             .field("retries", &self.retries)
+            .field("attempts", &self.attempts)
             .finish()
     }
 }
@@ -55,6 +62,7 @@ where
         Self {
             request: self.request.clone(),
             retries: self.retries,
+            attempts: self.attempts,
         }
     }
 }
@@ -79,7 +87,11 @@ where
 
 impl<R> From<R> for BasicRetryWorker<R> {
     fn from(request: R) -> Self {
-        Self { request, retries: 0 }
+        Self {
+            request,
+            retries: 0,
+            attempts: 0,
+        }
     }
 }
 
@@ -108,14 +120,18 @@ where
                     anyhow::anyhow!("Error trying to reprepare query!")
                 })
             } else {
-                self.retry()?
+                self.retry(&error, reporter_opt)?
                     .map_or_else(|| Ok(()), |_| anyhow::bail!("Basic worker consumed all retries"))
             }
         } else {
-            self.retry()?
+            self.retry(&error, reporter_opt)?
                 .map_or_else(|| Ok(()), |_| anyhow::bail!("Basic worker consumed all retries"))
         }
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.request.timeout()
+    }
 }
 
 impl<R> RetryableWorker<R> for BasicRetryWorker<R>
@@ -133,6 +149,59 @@ where
     fn retries_mut(&mut self) -> &mut usize {
         &mut self.retries
     }
+
+    fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    fn attempts_mut(&mut self) -> &mut usize {
+        &mut self.attempts
+    }
+}
+
+/// A worker which notifies once its request is sent, or returns the error which prevented it from being sent instead
+/// of handing it to the inner worker
+#[derive(Debug)]
+pub(crate) struct SentNotifyWorker<W> {
+    inner: Box<W>,
+    notify: Option<oneshot::Sender<Result<(), WorkerError>>>,
+}
+
+impl<W> SentNotifyWorker<W> {
+    pub(crate) fn new(inner: Box<W>) -> (Box<Self>, oneshot::Receiver<Result<(), WorkerError>>) {
+        let (notify, sent) = oneshot::channel();
+        let worker = Self {
+            inner,
+            notify: Some(notify),
+        };
+        (Box::new(worker), sent)
+    }
+}
+
+impl<W: Worker> Worker for SentNotifyWorker<W> {
+    fn handle_response(self: Box<Self>, giveload: Vec<u8>) -> anyhow::Result<()> {
+        self.inner.handle_response(giveload)
+    }
+
+    fn handle_error(mut self: Box<Self>, error: WorkerError, reporter: Option<&ReporterHandle>) -> anyhow::Result<()> {
+        if let Some(notify) = self.notify.take() {
+            notify.send(Err(error)).ok();
+            Ok(())
+        } else {
+            self.inner.handle_error(error, reporter)
+        }
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout()
+    }
+
+    fn handle_sent(&mut self) {
+        if let Some(notify) = self.notify.take() {
+            notify.send(Ok(())).ok();
+        }
+        self.inner.handle_sent()
+    }
 }
 
 /// A worker which can spawn cloneable workers and await their responses
@@ -8,12 +8,15 @@ use crate::{
         ring::RingSendError,
     },
     cql::{
+        set_frame_consistency,
+        void_result,
         CqlError,
         Decoder,
     },
 };
 pub use any::AnyWorker;
 use anyhow::anyhow;
+pub(crate) use basic::SentNotifyWorker;
 pub use basic::{
     BasicRetryWorker,
     BasicWorker,
@@ -25,7 +28,30 @@ pub use handle::{
 };
 use log::*;
 pub use prepare::PrepareWorker;
-use std::convert::TryFrom;
+pub use retry::{
+    retry_decision,
+    set_retry_policy,
+    DefaultRetryPolicy,
+    DowngradingConsistencyRetryPolicy,
+    FallthroughRetryPolicy,
+    RetryDecision,
+    RetryPolicy,
+    RetryPolicyConfig,
+    RetryTarget,
+};
+pub(crate) use speculative::{
+    send_speculative,
+    SpeculativeExecution,
+};
+pub use speculative::{
+    set_speculative_execution,
+    SpeculativeDelay,
+    SpeculativeExecutionConfig,
+};
+use std::{
+    convert::TryFrom,
+    time::Duration,
+};
 use thiserror::Error;
 use tokio::{
     runtime::Handle,
@@ -37,6 +63,8 @@ mod any;
 mod basic;
 mod handle;
 mod prepare;
+mod retry;
+mod speculative;
 
 /// WorkerId trait type which will be implemented by worker in order to send their channel_tx.
 pub trait Worker: Send + Sync + std::fmt::Debug + 'static {
@@ -44,6 +72,12 @@ pub trait Worker: Send + Sync + std::fmt::Debug + 'static {
     fn handle_response(self: Box<Self>, giveload: Vec<u8>) -> anyhow::Result<()>;
     /// Reporter will invoke this method to Send the worker error to worker
     fn handle_error(self: Box<Self>, error: WorkerError, reporter: Option<&ReporterHandle>) -> anyhow::Result<()>;
+    /// The client side timeout of the request, which overrides the default one of the reporter
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+    /// Reporter will invoke this method once the request got a stream and is about to be sent
+    fn handle_sent(&mut self) {}
 }
 
 #[derive(Error, Debug)]
@@ -64,6 +98,9 @@ pub enum WorkerError {
     /// There is no ring initialized.
     #[error("Worker NoRing")]
     NoRing,
+    /// No response has been received within the request timeout.
+    #[error("Worker Timeout")]
+    Timeout,
 }
 
 /// should be implemented on the handle of the worker
@@ -81,6 +118,10 @@ pub trait RetryableWorker<R>: Worker {
     fn retries(&self) -> usize;
     /// Mutably access the number of retries remaining
     fn retries_mut(&mut self) -> &mut usize;
+    /// Get the number of retries already attempted
+    fn attempts(&self) -> usize;
+    /// Mutably access the number of retries already attempted
+    fn attempts_mut(&mut self) -> &mut usize;
     /// Get the request
     fn request(&self) -> &R;
     /// Update the retry count
@@ -92,30 +133,66 @@ pub trait RetryableWorker<R>: Worker {
         self
     }
 
-    /// Retry the worker
-    fn retry(mut self) -> anyhow::Result<Option<Box<Self>>>
+    /// Retry the worker, if the retry policy decides so, otherwise returns the worker to handle the error
+    fn retry(mut self, error: &WorkerError, reporter: Option<&ReporterHandle>) -> anyhow::Result<Option<Box<Self>>>
     where
         Self: 'static + Sized + Into<Box<Self>> + Worker,
         R: Request,
     {
-        if self.retries() > 0 {
-            *self.retries_mut() -= 1;
-            // currently we assume all cql/worker errors are retryable, but we might change this in future
-            if let Err(ring_send_error) = send_global(
-                self.request().keyspace().as_ref().map(|s| s.as_str()),
-                self.request().token(),
-                self.request().payload(),
-                self.into(),
-            ) {
-                if let ReporterEvent::Request { worker, .. } = ring_send_error.into() {
-                    worker.handle_error(WorkerError::Other(anyhow::Error::msg("Retrying on the fly")), None)?;
-                } else {
-                    unreachable!("Unexpected report event variant while sending retry request using send global");
-                };
+        match retry_decision(error, self.request().is_idempotent(), self.attempts()) {
+            RetryDecision::Retry { target, consistency } if self.retries() > 0 => {
+                *self.retries_mut() -= 1;
+                *self.attempts_mut() += 1;
+                let mut payload = self.request().payload();
+                if let Some(consistency) = consistency {
+                    payload = set_frame_consistency(payload.clone(), consistency).unwrap_or_else(|e| {
+                        warn!("Unable to downgrade the retry request consistency, error: {}", e);
+                        payload
+                    });
+                }
+                match (target, reporter) {
+                    (RetryTarget::SameHost, Some(reporter)) => {
+                        let worker: Box<Self> = self.into();
+                        if let Err(send_error) = reporter.send(ReporterEvent::Request { worker, payload }) {
+                            if let ReporterEvent::Request { worker, .. } = send_error.0 {
+                                worker.handle_error(
+                                    WorkerError::Other(anyhow::Error::msg("Retrying on the fly")),
+                                    None,
+                                )?;
+                            } else {
+                                unreachable!("Unexpected report event variant while sending retry request to reporter");
+                            }
+                        }
+                    }
+                    _ => {
+                        if let Err(ring_send_error) = send_global(
+                            self.request().keyspace().as_ref().map(|s| s.as_str()),
+                            self.request().table().as_ref().map(|s| s.as_str()),
+                            self.request().token(),
+                            payload,
+                            self.into(),
+                        ) {
+                            if let ReporterEvent::Request { worker, .. } = ring_send_error.into() {
+                                worker.handle_error(
+                                    WorkerError::Other(anyhow::Error::msg("Retrying on the fly")),
+                                    None,
+                                )?;
+                            } else {
+                                unreachable!(
+                                    "Unexpected report event variant while sending retry request using send global"
+                                );
+                            };
+                        }
+                    }
+                }
+                Ok(None)
             }
-            Ok(None)
-        } else {
-            Ok(Some(self.into()))
+            RetryDecision::Ignore => {
+                let worker: Box<Self> = self.into();
+                worker.handle_response(void_result())?;
+                Ok(None)
+            }
+            _ => Ok(Some(self.into())),
         }
     }
 
@@ -139,8 +216,11 @@ pub trait RetryableWorker<R>: Worker {
         Self: 'static + Sized + Into<Box<Self>> + Worker,
         R: SendRequestExt,
     {
-        if let Err(ring_send_error) = send_local(
+        if let Err(ring_send_error) = send_speculative(
+            true,
+            SpeculativeExecution::of(self.request()),
             self.request().keyspace().as_ref().map(|s| s.as_str()),
+            self.request().table().as_ref().map(|s| s.as_str()),
             self.request().token(),
             self.request().payload(),
             self.into(),
@@ -162,8 +242,11 @@ pub trait RetryableWorker<R>: Worker {
         Self: 'static + Sized + Into<Box<Self>> + Worker,
         R: SendRequestExt,
     {
-        if let Err(ring_send_error) = send_global(
+        if let Err(ring_send_error) = send_speculative(
+            false,
+            SpeculativeExecution::of(self.request()),
             self.request().keyspace().as_ref().map(|s| s.as_str()),
+            self.request().table().as_ref().map(|s| s.as_str()),
             self.request().token(),
             self.request().payload(),
             self.into(),
@@ -351,11 +434,16 @@ where
 }
 
 /// retry to send a request
-pub fn retry_send(keyspace: Option<&str>, mut r: RingSendError, mut retries: u8) -> Result<(), Box<dyn Worker>> {
+pub fn retry_send(
+    keyspace: Option<&str>,
+    table: Option<&str>,
+    mut r: RingSendError,
+    mut retries: u8,
+) -> Result<(), Box<dyn Worker>> {
     loop {
         if let ReporterEvent::Request { worker, payload } = r.into() {
             if retries > 0 {
-                if let Err(still_error) = send_global(keyspace, rand::random(), payload, worker) {
+                if let Err(still_error) = send_global(keyspace, table, rand::random(), payload, worker) {
                     r = still_error;
                     retries -= 1;
                 } else {
@@ -1,6 +1,9 @@
 use super::*;
 use scylladb_parse::DataManipulationStatement;
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    time::Duration,
+};
 
 /// A statement prepare worker
 #[derive(Debug)]
@@ -8,6 +11,7 @@ pub struct PrepareWorker {
     /// The expected id for this statement
     pub(crate) id: [u8; 16],
     pub(crate) retries: usize,
+    pub(crate) attempts: usize,
     pub(crate) request: PrepareRequest,
 }
 impl PrepareWorker {
@@ -16,6 +20,7 @@ impl PrepareWorker {
         Box::new(Self {
             id,
             retries: 0,
+            attempts: 0,
             request: PrepareRequest {
                 statement,
                 token: rand::random(),
@@ -28,6 +33,7 @@ impl From<PrepareRequest> for PrepareWorker {
         Self {
             id: md5::compute(Request::statement(&request).to_string().as_bytes()).into(),
             retries: 0,
+            attempts: 0,
             request,
         }
     }
@@ -40,15 +46,18 @@ impl Worker for PrepareWorker {
         );
         Ok(())
     }
-    fn handle_error(self: Box<Self>, error: WorkerError, _reporter: Option<&ReporterHandle>) -> anyhow::Result<()> {
+    fn handle_error(self: Box<Self>, error: WorkerError, reporter: Option<&ReporterHandle>) -> anyhow::Result<()> {
         error!(
             "Failed to prepare statement: {}, error: {}",
             Request::statement(&self.request),
             error
         );
-        self.retry().ok();
+        self.retry(&error, reporter).ok();
         Ok(())
     }
+    fn timeout(&self) -> Option<Duration> {
+        self.request.timeout()
+    }
 }
 
 impl RetryableWorker<PrepareRequest> for PrepareWorker {
@@ -60,6 +69,14 @@ impl RetryableWorker<PrepareRequest> for PrepareWorker {
         &mut self.retries
     }
 
+    fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    fn attempts_mut(&mut self) -> &mut usize {
+        &mut self.attempts
+    }
+
     fn request(&self) -> &PrepareRequest {
         &self.request
     }
@@ -75,6 +92,7 @@ where
         RespondingPrepareWorker {
             id: self.id,
             retries: self.retries,
+            attempts: self.attempts,
             request: self.request,
             handle,
         }
@@ -89,6 +107,7 @@ pub struct RespondingPrepareWorker<H> {
     pub(crate) id: [u8; 16],
     pub(crate) request: PrepareRequest,
     pub(crate) retries: usize,
+    pub(crate) attempts: usize,
     pub(crate) handle: H,
 }
 
@@ -102,14 +121,17 @@ where
             Err(e) => self.handle.handle_error(WorkerError::Other(e)),
         }
     }
-    fn handle_error(self: Box<Self>, error: WorkerError, _reporter: Option<&ReporterHandle>) -> anyhow::Result<()> {
+    fn handle_error(self: Box<Self>, error: WorkerError, reporter: Option<&ReporterHandle>) -> anyhow::Result<()> {
         error!("{}", error);
-        if let Some(worker) = self.retry()? {
+        if let Some(worker) = self.retry(&error, reporter)? {
             worker.handle.handle_error(error)
         } else {
             Ok(())
         }
     }
+    fn timeout(&self) -> Option<Duration> {
+        self.request.timeout()
+    }
 }
 
 impl<H> RetryableWorker<PrepareRequest> for RespondingPrepareWorker<H>
@@ -124,6 +146,14 @@ where
         &mut self.retries
     }
 
+    fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    fn attempts_mut(&mut self) -> &mut usize {
+        &mut self.attempts
+    }
+
     fn request(&self) -> &PrepareRequest {
         &self.request
     }
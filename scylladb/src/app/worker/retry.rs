@@ -0,0 +1,396 @@
+use super::*;
+use crate::cql::{
+    Additional,
+    Consistency,
+    ErrorCodes,
+    WriteType,
+};
+use arc_swap::ArcSwapOption;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::sync::Arc;
+
+static RETRY_POLICY: ArcSwapOption<Box<dyn RetryPolicy>> = ArcSwapOption::const_empty();
+
+/// The host to retry the request on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryTarget {
+    /// Retry on the same host, through the reporter which received the error
+    SameHost,
+    /// Retry on the next host picked by the ring
+    NextHost,
+}
+
+/// The decision of the retry policy on a request error
+#[derive(Debug, Clone)]
+pub enum RetryDecision {
+    /// Retry the request, with the given consistency level if it got downgraded
+    Retry {
+        /// The host to retry the request on
+        target: RetryTarget,
+        /// The downgraded consistency level, if any
+        consistency: Option<Consistency>,
+    },
+    /// Ignore the error and complete the request with a void result
+    Ignore,
+    /// Hand the error to the worker
+    Rethrow,
+}
+
+impl RetryDecision {
+    /// Retry the request with the same consistency level
+    pub fn retry(target: RetryTarget) -> Self {
+        Self::Retry {
+            target,
+            consistency: None,
+        }
+    }
+    /// Retry the request on the same host with a downgraded consistency level
+    pub fn downgrade(consistency: Consistency) -> Self {
+        Self::Retry {
+            target: RetryTarget::SameHost,
+            consistency: Some(consistency),
+        }
+    }
+}
+
+/// The policy which decides whether a failed request should be retried.
+pub trait RetryPolicy: Send + Sync + std::fmt::Debug {
+    /// Decide how to handle the error of the request, given whether the request is idempotent and the number of
+    /// retries already attempted.
+    fn decide(&self, error: &WorkerError, idempotent: bool, attempt: usize) -> RetryDecision;
+}
+
+/// The retry policy of the workers, as configured in `Scylla`
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+pub enum RetryPolicyConfig {
+    /// Retry only when the request is likely to succeed on retry, see `DefaultRetryPolicy`
+    #[default]
+    Default,
+    /// Never retry, see `FallthroughRetryPolicy`
+    Fallthrough,
+    /// Retry with a downgraded consistency level when not enough replicas responded, see
+    /// `DowngradingConsistencyRetryPolicy`
+    DowngradingConsistency,
+}
+
+impl RetryPolicyConfig {
+    /// Create the configured retry policy
+    pub fn policy(&self) -> Box<dyn RetryPolicy> {
+        match self {
+            Self::Default => Box::new(DefaultRetryPolicy),
+            Self::Fallthrough => Box::new(FallthroughRetryPolicy),
+            Self::DowngradingConsistency => Box::new(DowngradingConsistencyRetryPolicy),
+        }
+    }
+}
+
+/// Set the retry policy of the workers, which otherwise use the `DefaultRetryPolicy`
+pub fn set_retry_policy(policy: Box<dyn RetryPolicy>) {
+    RETRY_POLICY.store(Some(Arc::new(policy)));
+}
+
+/// Decide how to handle the request error with the retry policy of the workers
+pub fn retry_decision(error: &WorkerError, idempotent: bool, attempt: usize) -> RetryDecision {
+    match RETRY_POLICY.load().as_ref() {
+        Some(policy) => policy.decide(error, idempotent, attempt),
+        None => DefaultRetryPolicy.decide(error, idempotent, attempt),
+    }
+}
+
+/// The default retry policy, which retries once:
+/// - a read timeout on the same host, if enough replicas responded but the data was not retrieved
+/// - an idempotent batch log write timeout on the same host
+/// - an unavailable error on the next host
+/// - an idempotent request which got lost or timed out, or failed due to an overloaded or bootstrapping host, on the
+///   next host
+/// - a request which was never sent due to the lack of streams, on the next host
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultRetryPolicy;
+
+impl RetryPolicy for DefaultRetryPolicy {
+    fn decide(&self, error: &WorkerError, idempotent: bool, attempt: usize) -> RetryDecision {
+        match error {
+            WorkerError::Cql(cql_error) => match (&cql_error.code, &cql_error.additional) {
+                (_, Some(Additional::ReadTimeout(timeout))) => {
+                    if attempt == 0 && timeout.received >= timeout.blockfor && timeout.replica_had_not_responded() {
+                        RetryDecision::retry(RetryTarget::SameHost)
+                    } else {
+                        RetryDecision::Rethrow
+                    }
+                }
+                (_, Some(Additional::WriteTimeout(timeout))) => {
+                    if attempt == 0 && idempotent && matches!(timeout.writetype, WriteType::BatchLog) {
+                        RetryDecision::retry(RetryTarget::SameHost)
+                    } else {
+                        RetryDecision::Rethrow
+                    }
+                }
+                (_, Some(Additional::UnavailableException(_))) if attempt == 0 => {
+                    RetryDecision::retry(RetryTarget::NextHost)
+                }
+                (ErrorCodes::Overloaded, _) | (ErrorCodes::IsBoostrapping, _) | (ErrorCodes::ServerError, _)
+                    if idempotent =>
+                {
+                    RetryDecision::retry(RetryTarget::NextHost)
+                }
+                _ => RetryDecision::Rethrow,
+            },
+            // the request has not been sent
+            WorkerError::Overload => RetryDecision::retry(RetryTarget::NextHost),
+            WorkerError::Lost | WorkerError::Timeout | WorkerError::Other(_) if idempotent => {
+                RetryDecision::retry(RetryTarget::NextHost)
+            }
+            _ => RetryDecision::Rethrow,
+        }
+    }
+}
+
+/// The retry policy which never retries, the workers handle all the errors
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FallthroughRetryPolicy;
+
+impl RetryPolicy for FallthroughRetryPolicy {
+    fn decide(&self, _error: &WorkerError, _idempotent: bool, _attempt: usize) -> RetryDecision {
+        RetryDecision::Rethrow
+    }
+}
+
+/// The retry policy which behaves as the `DefaultRetryPolicy`, but once retries with a downgraded consistency level
+/// when not enough replicas responded or are alive, and ignores the write timeouts of the writes which got persisted
+/// by at least one replica. It breaks the consistency guarantees, hence it should be used with care.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DowngradingConsistencyRetryPolicy;
+
+impl DowngradingConsistencyRetryPolicy {
+    /// The highest consistency level which is likely to succeed with the given number of replicas
+    fn max_likely_to_work(replicas: i32) -> Option<Consistency> {
+        match replicas {
+            r if r >= 3 => Some(Consistency::Three),
+            2 => Some(Consistency::Two),
+            1 => Some(Consistency::One),
+            _ => None,
+        }
+    }
+}
+
+impl RetryPolicy for DowngradingConsistencyRetryPolicy {
+    fn decide(&self, error: &WorkerError, idempotent: bool, attempt: usize) -> RetryDecision {
+        let additional = match error {
+            WorkerError::Cql(cql_error) if attempt == 0 => cql_error.additional.as_ref(),
+            _ => None,
+        };
+        match additional {
+            Some(Additional::ReadTimeout(timeout)) => {
+                if matches!(timeout.cl, Consistency::Serial | Consistency::LocalSerial) {
+                    RetryDecision::Rethrow
+                } else if timeout.received < timeout.blockfor {
+                    Self::max_likely_to_work(timeout.received).map_or(RetryDecision::Rethrow, RetryDecision::downgrade)
+                } else if timeout.replica_had_not_responded() {
+                    RetryDecision::retry(RetryTarget::SameHost)
+                } else {
+                    RetryDecision::Rethrow
+                }
+            }
+            Some(Additional::WriteTimeout(timeout)) => match timeout.writetype {
+                // the write got persisted by at least one replica, and will eventually be replicated
+                WriteType::Simple | WriteType::Batch if timeout.received > 0 => RetryDecision::Ignore,
+                WriteType::UnloggedBatch if idempotent => {
+                    Self::max_likely_to_work(timeout.received).map_or(RetryDecision::Rethrow, RetryDecision::downgrade)
+                }
+                WriteType::BatchLog if idempotent => RetryDecision::retry(RetryTarget::SameHost),
+                _ => RetryDecision::Rethrow,
+            },
+            Some(Additional::UnavailableException(unavailable)) => {
+                if matches!(unavailable.cl, Consistency::Serial | Consistency::LocalSerial) {
+                    RetryDecision::retry(RetryTarget::NextHost)
+                } else {
+                    Self::max_likely_to_work(unavailable.alive).map_or(RetryDecision::Rethrow, RetryDecision::downgrade)
+                }
+            }
+            _ => DefaultRetryPolicy.decide(error, idempotent, attempt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cql::{
+        CqlError,
+        ReadTimeout,
+        UnavailableException,
+        WriteTimeout,
+    };
+
+    fn cql_error(code: ErrorCodes, additional: Option<Additional>) -> WorkerError {
+        WorkerError::Cql(CqlError {
+            code,
+            message: String::new(),
+            additional,
+        })
+    }
+
+    fn read_timeout(received: i32, blockfor: i32, data_present: u8) -> WorkerError {
+        cql_error(
+            ErrorCodes::ReadTimeout,
+            Some(Additional::ReadTimeout(ReadTimeout {
+                cl: Consistency::Quorum,
+                received,
+                blockfor,
+                data_present,
+            })),
+        )
+    }
+
+    fn write_timeout(received: i32, writetype: WriteType) -> WorkerError {
+        cql_error(
+            ErrorCodes::WriteTimeout,
+            Some(Additional::WriteTimeout(WriteTimeout {
+                cl: Consistency::Quorum,
+                received,
+                blockfor: 2,
+                writetype,
+            })),
+        )
+    }
+
+    fn unavailable(alive: i32) -> WorkerError {
+        cql_error(
+            ErrorCodes::UnavailableException,
+            Some(Additional::UnavailableException(UnavailableException {
+                cl: Consistency::Quorum,
+                required: 2,
+                alive,
+            })),
+        )
+    }
+
+    fn is_retry(decision: RetryDecision, expected_target: RetryTarget, expected_consistency: Option<u16>) -> bool {
+        match decision {
+            RetryDecision::Retry { target, consistency } => {
+                target == expected_target && consistency.map(|cl| cl as u16) == expected_consistency
+            }
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn default_retry_policy() {
+        let policy = DefaultRetryPolicy;
+        assert!(is_retry(
+            policy.decide(&read_timeout(2, 2, 0), false, 0),
+            RetryTarget::SameHost,
+            None
+        ));
+        assert!(matches!(
+            policy.decide(&read_timeout(2, 2, 0), false, 1),
+            RetryDecision::Rethrow
+        ));
+        assert!(matches!(
+            policy.decide(&read_timeout(1, 2, 0), true, 0),
+            RetryDecision::Rethrow
+        ));
+        // non idempotent writes are never retried
+        assert!(matches!(
+            policy.decide(&write_timeout(0, WriteType::BatchLog), false, 0),
+            RetryDecision::Rethrow
+        ));
+        assert!(is_retry(
+            policy.decide(&write_timeout(0, WriteType::BatchLog), true, 0),
+            RetryTarget::SameHost,
+            None
+        ));
+        assert!(matches!(
+            policy.decide(&write_timeout(1, WriteType::Simple), true, 0),
+            RetryDecision::Rethrow
+        ));
+        assert!(is_retry(
+            policy.decide(&unavailable(1), false, 0),
+            RetryTarget::NextHost,
+            None
+        ));
+        assert!(matches!(
+            policy.decide(&cql_error(ErrorCodes::SyntaxError, None), true, 0),
+            RetryDecision::Rethrow
+        ));
+        assert!(is_retry(
+            policy.decide(&cql_error(ErrorCodes::Overloaded, None), true, 0),
+            RetryTarget::NextHost,
+            None
+        ));
+        assert!(matches!(
+            policy.decide(&cql_error(ErrorCodes::Overloaded, None), false, 0),
+            RetryDecision::Rethrow
+        ));
+        assert!(is_retry(
+            policy.decide(&WorkerError::Overload, false, 3),
+            RetryTarget::NextHost,
+            None
+        ));
+        assert!(matches!(
+            policy.decide(&WorkerError::Lost, false, 0),
+            RetryDecision::Rethrow
+        ));
+        assert!(is_retry(
+            policy.decide(&WorkerError::Timeout, true, 1),
+            RetryTarget::NextHost,
+            None
+        ));
+        assert!(matches!(
+            FallthroughRetryPolicy.decide(&WorkerError::Overload, true, 0),
+            RetryDecision::Rethrow
+        ));
+    }
+
+    #[test]
+    fn downgrading_consistency_retry_policy() {
+        let policy = RetryPolicyConfig::DowngradingConsistency.policy();
+        assert!(is_retry(
+            policy.decide(&read_timeout(1, 2, 0), false, 0),
+            RetryTarget::SameHost,
+            Some(Consistency::One as u16)
+        ));
+        assert!(matches!(
+            policy.decide(&read_timeout(0, 2, 0), false, 0),
+            RetryDecision::Rethrow
+        ));
+        assert!(matches!(
+            policy.decide(&read_timeout(1, 2, 0), false, 1),
+            RetryDecision::Rethrow
+        ));
+        assert!(matches!(
+            policy.decide(&write_timeout(1, WriteType::Simple), false, 0),
+            RetryDecision::Ignore
+        ));
+        assert!(matches!(
+            policy.decide(&write_timeout(0, WriteType::Simple), false, 0),
+            RetryDecision::Rethrow
+        ));
+        assert!(matches!(
+            policy.decide(&write_timeout(2, WriteType::UnloggedBatch), false, 0),
+            RetryDecision::Rethrow
+        ));
+        assert!(is_retry(
+            policy.decide(&write_timeout(2, WriteType::UnloggedBatch), true, 0),
+            RetryTarget::SameHost,
+            Some(Consistency::Two as u16)
+        ));
+        assert!(matches!(
+            policy.decide(&write_timeout(1, WriteType::Counter), true, 0),
+            RetryDecision::Rethrow
+        ));
+        assert!(is_retry(
+            policy.decide(&unavailable(4), false, 0),
+            RetryTarget::SameHost,
+            Some(Consistency::Three as u16)
+        ));
+        assert!(is_retry(
+            policy.decide(&cql_error(ErrorCodes::IsBoostrapping, None), true, 0),
+            RetryTarget::NextHost,
+            None
+        ));
+    }
+}
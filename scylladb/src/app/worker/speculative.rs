@@ -0,0 +1,457 @@
+use super::*;
+use crate::app::ring::{
+    Candidate,
+    LoadBalancingPolicy,
+    SharedRing,
+};
+use arc_swap::ArcSwapOption;
+use rand::prelude::ThreadRng;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{
+        atomic::{
+            AtomicU64,
+            AtomicUsize,
+            Ordering,
+        },
+        Arc,
+        Mutex,
+    },
+    time::Instant,
+};
+
+static SPECULATIVE_EXECUTIONS: ArcSwapOption<HashMap<RequestType, Arc<SpeculativeExecution>>> =
+    ArcSwapOption::const_empty();
+
+/// The delay before sending a speculative attempt of a request which has not been answered yet
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum SpeculativeDelay {
+    /// Fixed delay in milliseconds
+    Fixed(u64),
+    /// The given percentile of the observed response times of the request type, the requests are not speculatively
+    /// executed until enough response times are observed
+    Percentile(u8),
+}
+
+/// Configuration of the speculative execution of the idempotent requests of a request type
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct SpeculativeExecutionConfig {
+    /// The delay before sending each speculative attempt
+    pub delay: SpeculativeDelay,
+    /// The maximum number of speculative attempts besides the first one
+    pub max_executions: usize,
+}
+
+impl Default for SpeculativeExecutionConfig {
+    fn default() -> Self {
+        Self {
+            delay: SpeculativeDelay::Percentile(99),
+            max_executions: 1,
+        }
+    }
+}
+
+/// Set the speculative execution of the request types, the requests of any other type are never speculatively
+/// executed
+pub fn set_speculative_execution(configs: HashMap<RequestType, SpeculativeExecutionConfig>) {
+    let executions = configs
+        .into_iter()
+        .map(|(request_type, config)| (request_type, Arc::new(SpeculativeExecution::new(config))))
+        .collect();
+    SPECULATIVE_EXECUTIONS.store(Some(Arc::new(executions)));
+}
+
+/// The speculative execution of a request type, along the observed response times of its requests
+#[derive(Debug)]
+pub(crate) struct SpeculativeExecution {
+    config: SpeculativeExecutionConfig,
+    latencies: LatencyHistogram,
+}
+
+impl SpeculativeExecution {
+    fn new(config: SpeculativeExecutionConfig) -> Self {
+        Self {
+            config,
+            latencies: LatencyHistogram::default(),
+        }
+    }
+    /// Get the speculative execution of the request, if it is idempotent and its type is configured
+    pub(crate) fn of<R: SendRequestExt>(request: &R) -> Option<Arc<Self>> {
+        if !request.is_idempotent() {
+            return None;
+        }
+        SPECULATIVE_EXECUTIONS
+            .load()
+            .as_ref()
+            .and_then(|executions| executions.get(&R::TYPE).cloned())
+    }
+    fn delay(&self) -> Option<Duration> {
+        match self.config.delay {
+            SpeculativeDelay::Fixed(delay) => Some(Duration::from_millis(delay)),
+            SpeculativeDelay::Percentile(percentile) => self.latencies.percentile(percentile),
+        }
+    }
+}
+
+/// Histogram of the response times in microseconds, with four buckets per power of two
+#[derive(Debug)]
+struct LatencyHistogram {
+    buckets: [AtomicU64; Self::BUCKETS],
+    count: AtomicU64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            count: AtomicU64::new(0),
+        }
+    }
+}
+
+impl LatencyHistogram {
+    const BUCKETS: usize = 128;
+    /// The response times required to compute the percentiles
+    const MIN_SAMPLES: u64 = 100;
+    /// The counts are halved once reached, so the recent response times weigh more
+    const MAX_SAMPLES: u64 = 100_000;
+    fn record(&self, latency: Duration) {
+        let micros = latency.as_micros().max(1) as f64;
+        let bucket = ((micros.log2() * 4.0) as usize).min(Self::BUCKETS - 1);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        if self.count.fetch_add(1, Ordering::Relaxed) + 1 >= Self::MAX_SAMPLES {
+            let mut count = 0;
+            for bucket in self.buckets.iter() {
+                let halved = bucket.load(Ordering::Relaxed) / 2;
+                bucket.store(halved, Ordering::Relaxed);
+                count += halved;
+            }
+            self.count.store(count, Ordering::Relaxed);
+        }
+    }
+    fn percentile(&self, percentile: u8) -> Option<Duration> {
+        let counts: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect();
+        let count: u64 = counts.iter().sum();
+        if count < Self::MIN_SAMPLES {
+            return None;
+        }
+        let rank = (count * percentile.min(100) as u64).div_ceil(100).max(1);
+        let mut cumulative = 0;
+        counts.iter().enumerate().find_map(|(bucket, bucket_count)| {
+            cumulative += bucket_count;
+            // the upper bound of the bucket
+            (cumulative >= rank).then(|| Duration::from_micros(2f64.powf((bucket + 1) as f64 / 4.0) as u64))
+        })
+    }
+}
+
+/// The state shared by the attempts of a speculatively executed request, the first response is handed to the worker
+/// and the others are discarded
+#[derive(Debug)]
+struct Attempts {
+    worker: Mutex<Option<Box<dyn Worker>>>,
+    in_flight: AtomicUsize,
+    timeout: Option<Duration>,
+    /// The nodes which got an attempt, the next attempts are sent to the other replicas
+    tried: Mutex<Vec<IpAddr>>,
+    execution: Arc<SpeculativeExecution>,
+    started: Instant,
+}
+
+impl Attempts {
+    fn new(worker: Box<dyn Worker>, execution: Arc<SpeculativeExecution>) -> Arc<Self> {
+        Arc::new(Self {
+            timeout: worker.timeout(),
+            worker: Mutex::new(Some(worker)),
+            in_flight: AtomicUsize::new(0),
+            tried: Mutex::new(Vec::new()),
+            execution,
+            started: Instant::now(),
+        })
+    }
+    /// Create the next attempt, unless the request is already done
+    fn attempt(self: &Arc<Self>) -> Option<Box<SpeculativeWorker>> {
+        if self.is_done() {
+            return None;
+        }
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        Some(Box::new(SpeculativeWorker { attempts: self.clone() }))
+    }
+    fn take(&self) -> Option<Box<dyn Worker>> {
+        self.worker.lock().ok().and_then(|mut worker| worker.take())
+    }
+    fn is_done(&self) -> bool {
+        self.worker.lock().map_or(true, |worker| worker.is_none())
+    }
+}
+
+/// Pick the replica with the configured load balancing policy among the replicas which did not get an attempt yet,
+/// or among all of them once they all got one
+impl LoadBalancingPolicy for Attempts {
+    fn pick(&self, candidates: &[Candidate], rng: &mut ThreadRng) -> usize {
+        let mut tried = match self.tried.lock() {
+            Ok(tried) => tried,
+            Err(_) => return SharedRing::pick(candidates, rng),
+        };
+        let untried: Vec<usize> = (0..candidates.len())
+            .filter(|index| !tried.contains(&candidates[*index].shard.ip()))
+            .collect();
+        let index = if untried.is_empty() {
+            SharedRing::pick(candidates, rng)
+        } else {
+            let untried_candidates: Vec<Candidate> = untried.iter().map(|index| candidates[*index]).collect();
+            untried
+                .get(SharedRing::pick(&untried_candidates, rng))
+                .copied()
+                .unwrap_or(untried[0])
+        };
+        tried.push(candidates[index].shard.ip());
+        index
+    }
+}
+
+/// An attempt of a speculatively executed request
+#[derive(Debug)]
+struct SpeculativeWorker {
+    attempts: Arc<Attempts>,
+}
+
+impl Worker for SpeculativeWorker {
+    fn handle_response(self: Box<Self>, giveload: Vec<u8>) -> anyhow::Result<()> {
+        self.attempts.in_flight.fetch_sub(1, Ordering::AcqRel);
+        if let Some(worker) = self.attempts.take() {
+            self.attempts
+                .execution
+                .latencies
+                .record(self.attempts.started.elapsed());
+            worker.handle_response(giveload)
+        } else {
+            // another attempt already responded
+            Ok(())
+        }
+    }
+
+    fn handle_error(self: Box<Self>, error: WorkerError, reporter: Option<&ReporterHandle>) -> anyhow::Result<()> {
+        // the other attempts may still respond
+        if self.attempts.in_flight.fetch_sub(1, Ordering::AcqRel) > 1 {
+            debug!("Discarding the error of a speculative attempt: {}", error);
+            return Ok(());
+        }
+        if let Some(worker) = self.attempts.take() {
+            worker.handle_error(error, reporter)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.attempts.timeout
+    }
+
+    fn handle_sent(&mut self) {
+        if let Ok(mut worker) = self.attempts.worker.lock() {
+            if let Some(worker) = worker.as_mut() {
+                worker.handle_sent();
+            }
+        }
+    }
+}
+
+/// Send the request to the local datacenter or to a global one, and if the request is speculatively executed, send
+/// another attempt to a different replica every delay until any attempt responds or the speculative attempts are
+/// exhausted.
+pub(crate) fn send_speculative(
+    local: bool,
+    execution: Option<Arc<SpeculativeExecution>>,
+    keyspace: Option<&str>,
+    table: Option<&str>,
+    token: i64,
+    payload: Vec<u8>,
+    worker: Box<dyn Worker>,
+) -> Result<(), RingSendError> {
+    let execution = match execution {
+        Some(execution) => execution,
+        None if local => return send_local(keyspace, table, token, payload, worker),
+        None => return send_global(keyspace, table, token, payload, worker),
+    };
+    let attempts = Attempts::new(worker, execution);
+    let attempt = match attempts.attempt() {
+        Some(attempt) => attempt,
+        None => return Ok(()),
+    };
+    if local {
+        send_local_with_policy(keyspace, table, token, payload.clone(), attempt, attempts.as_ref())?;
+    } else {
+        send_global_with_policy(keyspace, table, token, payload.clone(), attempt, attempts.as_ref())?;
+    }
+    if let Some(delay) = attempts.execution.delay() {
+        let (keyspace, table) = (keyspace.map(String::from), table.map(String::from));
+        let speculative_execution_task = async move {
+            for _ in 0..attempts.execution.config.max_executions {
+                tokio::time::sleep(delay).await;
+                let attempt = match attempts.attempt() {
+                    Some(attempt) => attempt,
+                    None => break,
+                };
+                let (keyspace, table) = (keyspace.as_deref(), table.as_deref());
+                let sent = if local {
+                    send_local_with_policy(keyspace, table, token, payload.clone(), attempt, attempts.as_ref())
+                } else {
+                    send_global_with_policy(keyspace, table, token, payload.clone(), attempt, attempts.as_ref())
+                };
+                if let Err(e) = sent {
+                    attempts.in_flight.fetch_sub(1, Ordering::AcqRel);
+                    debug!("Unable to send a speculative attempt: {}", e);
+                    break;
+                }
+            }
+        };
+        overclock::spawn_task("speculative execution", speculative_execution_task);
+    }
+    Ok(())
+}
+
+#[test]
+fn latency_histogram_percentiles() {
+    let histogram = LatencyHistogram::default();
+    (0..50).for_each(|_| histogram.record(Duration::from_millis(1)));
+    assert_eq!(histogram.percentile(99), None);
+    (0..49).for_each(|_| histogram.record(Duration::from_millis(1)));
+    histogram.record(Duration::from_millis(100));
+    // the buckets upper bound is at most 19% above the response time
+    let p50 = histogram.percentile(50).unwrap();
+    assert!(p50 > Duration::from_millis(1) && p50 < Duration::from_micros(1190));
+    let p99 = histogram.percentile(99).unwrap();
+    assert!(p99 < Duration::from_micros(1190));
+    let p100 = histogram.percentile(100).unwrap();
+    assert!(p100 > Duration::from_millis(100) && p100 < Duration::from_millis(119));
+}
+
+#[cfg(test)]
+type Events = Arc<Mutex<Vec<&'static str>>>;
+
+/// A worker which records what happened to its request
+#[cfg(test)]
+#[derive(Debug)]
+struct RecordWorker {
+    events: Events,
+}
+
+#[cfg(test)]
+impl Worker for RecordWorker {
+    fn handle_response(self: Box<Self>, _giveload: Vec<u8>) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push("response");
+        Ok(())
+    }
+    fn handle_error(self: Box<Self>, error: WorkerError, _reporter: Option<&ReporterHandle>) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(match error {
+            WorkerError::Lost => "lost",
+            _ => "error",
+        });
+        Ok(())
+    }
+    fn handle_sent(&mut self) {
+        self.events.lock().unwrap().push("sent");
+    }
+}
+
+#[cfg(test)]
+fn test_attempts(events: &Events) -> Arc<Attempts> {
+    let execution = Arc::new(SpeculativeExecution::new(SpeculativeExecutionConfig::default()));
+    Attempts::new(Box::new(RecordWorker { events: events.clone() }), execution)
+}
+
+#[test]
+fn speculative_first_response_wins() {
+    let events = Events::default();
+    let attempts = test_attempts(&events);
+    let (mut first, second) = (attempts.attempt().unwrap(), attempts.attempt().unwrap());
+    first.handle_sent();
+    second.handle_response(Vec::new()).unwrap();
+    assert_eq!(*events.lock().unwrap(), ["sent", "response"]);
+    // the late attempts are discarded
+    first.handle_response(Vec::new()).unwrap();
+    assert_eq!(*events.lock().unwrap(), ["sent", "response"]);
+    assert_eq!(attempts.in_flight.load(Ordering::Acquire), 0);
+    // no attempts once the request is done
+    assert!(attempts.attempt().is_none());
+    assert_eq!(attempts.execution.latencies.count.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn speculative_errors_while_in_flight() {
+    let events = Events::default();
+    let attempts = test_attempts(&events);
+    let (first, second) = (attempts.attempt().unwrap(), attempts.attempt().unwrap());
+    // the other attempt may still respond
+    first.handle_error(WorkerError::Lost, None).unwrap();
+    assert!(events.lock().unwrap().is_empty());
+    second.handle_response(Vec::new()).unwrap();
+    assert_eq!(*events.lock().unwrap(), ["response"]);
+    // the worker gets the error of the last attempt once all of them failed
+    let events = Events::default();
+    let attempts = test_attempts(&events);
+    let (first, second) = (attempts.attempt().unwrap(), attempts.attempt().unwrap());
+    first.handle_error(WorkerError::Timeout, None).unwrap();
+    second.handle_error(WorkerError::Lost, None).unwrap();
+    assert_eq!(*events.lock().unwrap(), ["lost"]);
+    assert!(attempts.attempt().is_none());
+}
+
+#[test]
+fn speculative_attempts_pick_untried_replicas() {
+    use crate::app::ring::{
+        balancing::test_candidate,
+        ShardLoad,
+    };
+    let mut rng = rand::thread_rng();
+    let attempts = test_attempts(&Events::default());
+    let loads = [ShardLoad::default(), ShardLoad::default(), ShardLoad::default()];
+    let candidates = [
+        test_candidate(1, "rack1", &loads[0]),
+        test_candidate(2, "rack1", &loads[1]),
+        test_candidate(3, "rack1", &loads[2]),
+    ];
+    let mut picks: Vec<usize> = (0..3).map(|_| attempts.pick(&candidates, &mut rng)).collect();
+    picks.sort_unstable();
+    assert_eq!(picks, [0, 1, 2]);
+    // every replica got an attempt
+    assert!(attempts.pick(&candidates, &mut rng) < candidates.len());
+}
+
+#[test]
+fn speculative_execution_of_idempotent_requests() {
+    use crate::prelude::*;
+    set_speculative_execution(maplit::hashmap! {
+        RequestType::Select => SpeculativeExecutionConfig::default(),
+        RequestType::Insert => SpeculativeExecutionConfig::default(),
+    });
+    // the selects are idempotent
+    let select = parse_statement!("SELECT col1 FROM my_keyspace.my_table WHERE key = ?")
+        .as_select_query::<f32>(&[&3], &[])
+        .build()
+        .unwrap();
+    assert!(SpeculativeExecution::of(&select).is_some());
+    let insert = parse_statement!("INSERT INTO my_keyspace.my_table (key, val) VALUES (?, ?)")
+        .as_insert_query(&[&3], &[&8.0])
+        .build()
+        .unwrap();
+    assert!(SpeculativeExecution::of(&insert).is_none());
+    assert!(SpeculativeExecution::of(&insert.idempotent()).is_some());
+    // the request types which are not configured are never speculatively executed
+    let delete = parse_statement!("DELETE FROM my_keyspace.my_table WHERE key = ?")
+        .as_delete_query(&[&3], &[])
+        .build()
+        .unwrap()
+        .idempotent();
+    assert!(SpeculativeExecution::of(&delete).is_none());
+}
@@ -1,6 +1,21 @@
-use super::tokens::{
-    Info,
-    Row,
+use super::{
+    peers::{
+        Peer,
+        Peers,
+        PeersV2,
+    },
+    schema::{
+        KeyspaceRow,
+        Keyspaces,
+    },
+    tablets::{
+        TabletRow,
+        Tablets,
+    },
+    tokens::{
+        Info,
+        Row,
+    },
 };
 use crate::cql::{
     compression::{
@@ -21,11 +36,18 @@ use crate::cql::{
             Decoder,
             Frame,
         },
+        event::SchemaChangeEvent,
         options::Options,
         query::Query,
+        register::Register,
         rows::Rows,
         startup::Startup,
         supported::Supported,
+        tablets::{
+            HostId,
+            Tablet,
+            TABLETS_ROUTING_V1,
+        },
         Statements,
     },
 };
@@ -76,6 +98,8 @@ pub struct Cql {
     address: SocketAddr,
     tokens: Option<Vec<i64>>,
     dc: Option<String>,
+    rack: Option<String>,
+    host_id: Option<HostId>,
     shard_id: u16,
     shard_aware_port: u16,
     shard_count: u16,
@@ -168,6 +192,10 @@ impl<Auth: Authenticator> CqlBuilder<Auth> {
         if let Some(compression) = MyCompression::option() {
             options.insert("COMPRESSION".to_owned(), compression.to_owned());
         }
+        // enable the tablets routing (if supported), so the server returns the tablet of misrouted requests
+        if supported.get_options().contains_key(TABLETS_ROUTING_V1) {
+            options.insert(TABLETS_ROUTING_V1.to_owned(), String::new());
+        }
         // create startup frame using the selected options;
         let Startup(startup_buf) = Startup::new().options(&options).build();
         // write_all startup frame to stream;
@@ -249,6 +277,8 @@ impl<Auth: Authenticator> CqlBuilder<Auth> {
             shard_count: nr_shard,
             msb: ignore_msb,
             dc: None,
+            rack: None,
+            host_id: None,
         };
         self.cql.replace(cqlconn);
         Ok(())
@@ -388,8 +418,15 @@ impl Cql {
         let decoder = Decoder::new(buffer, MyCompression::get())?;
 
         if decoder.is_rows() {
-            let Row { data_center, tokens } = Info::new(decoder)?.next().ok_or(anyhow!("No info found!"))?;
+            let Row {
+                data_center,
+                rack,
+                tokens,
+                host_id,
+            } = Info::new(decoder)?.next().ok_or(anyhow!("No info found!"))?;
             self.dc.replace(data_center);
+            self.rack.replace(rack);
+            self.host_id = host_id.and_then(|host_id| host_id.into_inner().try_into().ok());
             self.tokens.replace(
                 tokens
                     .iter()
@@ -402,6 +439,114 @@ impl Cql {
         }
         Ok(())
     }
+    async fn query(&mut self, query: Vec<u8>) -> anyhow::Result<Decoder> {
+        self.stream.write_all(query.as_slice()).await?;
+        let buffer = collect_frame_response(&mut self.stream).await?;
+        Decoder::new(buffer, MyCompression::get())
+    }
+    /// Fetch the replication options of all the keyspaces from `system_schema.keyspaces`, keyed by keyspace name
+    pub async fn fetch_replication(&mut self) -> anyhow::Result<HashMap<String, HashMap<String, String>>> {
+        let mut decoder = self.query(fetch_replication_query()?).await?;
+        if decoder.is_rows() {
+            Ok(Keyspaces::new(decoder)?
+                .map(
+                    |KeyspaceRow {
+                         keyspace_name,
+                         replication,
+                     }| (keyspace_name, replication),
+                )
+                .collect())
+        } else if decoder.is_error() {
+            bail!(
+                "CQL connection didn't return keyspaces due to CqlError: {}",
+                decoder.get_error()?
+            );
+        } else {
+            bail!("CQL connection didn't return keyspaces rows");
+        }
+    }
+    /// Fetch the tablets from `system.tablets`, keyed by keyspace and table name.
+    /// Returns no tablets if the node doesn't support tablets.
+    pub async fn fetch_tablets(&mut self) -> anyhow::Result<HashMap<String, HashMap<String, Vec<Tablet>>>> {
+        let mut decoder = self.query(fetch_tablets_query()?).await?;
+        let mut tablets: HashMap<String, HashMap<String, Vec<Tablet>>> = HashMap::new();
+        if decoder.is_rows() {
+            // the tablets of a table are ordered by their last token
+            for TabletRow {
+                keyspace_name,
+                table_name,
+                last_token,
+                replicas,
+            } in Tablets::new(decoder)?
+            {
+                let table_tablets = tablets.entry(keyspace_name).or_default().entry(table_name).or_default();
+                let first_token = table_tablets.last().map_or(i64::MIN, |tablet| tablet.last_token);
+                table_tablets.push(Tablet {
+                    first_token,
+                    last_token,
+                    replicas,
+                });
+            }
+        } else if decoder.is_error() {
+            // system.tablets doesn't exist in the scylla versions without tablets
+            log::debug!("No tablets, due to CqlError: {}", decoder.get_error()?);
+        } else {
+            bail!("CQL connection didn't return tablets rows");
+        }
+        Ok(tablets)
+    }
+    /// Fetch the peers of the connected node from `system.peers_v2`, or from `system.peers` if it's not available.
+    /// The peers without address, datacenter or tokens are skipped.
+    pub async fn fetch_peers(&mut self) -> anyhow::Result<Vec<Peer>> {
+        let port = self.address.port();
+        let decoder = self.query(fetch_peers_v2_query()?).await?;
+        if decoder.is_rows() {
+            return Ok(PeersV2::new(decoder)?
+                .filter_map(|row| row.into_peer(port).map_err(|e| log::warn!("Skipping peer: {}", e)).ok())
+                .collect());
+        }
+        let mut decoder = self.query(fetch_peers_query()?).await?;
+        if decoder.is_rows() {
+            Ok(Peers::new(decoder)?
+                .filter_map(|row| row.into_peer(port).map_err(|e| log::warn!("Skipping peer: {}", e)).ok())
+                .collect())
+        } else if decoder.is_error() {
+            bail!(
+                "CQL connection didn't return peers due to CqlError: {}",
+                decoder.get_error()?
+            );
+        } else {
+            bail!("CQL connection didn't return peers rows");
+        }
+    }
+    /// Register the connection for the provided event types, such as `SCHEMA_CHANGE`
+    pub async fn register(&mut self, event_types: &[&str]) -> anyhow::Result<()> {
+        let Register(payload) = Register::new().event_types(event_types).build(MyCompression::get())?;
+        self.stream.write_all(payload.as_slice()).await?;
+        let buffer = collect_frame_response(&mut self.stream).await?;
+        let mut decoder = Decoder::new(buffer, MyCompression::get())?;
+        if decoder.is_error() {
+            bail!(
+                "CQL connection not registered due to CqlError: {}",
+                decoder.get_error()?
+            );
+        }
+        ensure!(decoder.is_ready(), "CQL connection not registered!");
+        Ok(())
+    }
+    /// Wait for the next schema change event, the connection must be registered for `SCHEMA_CHANGE` events
+    pub async fn next_schema_change(&mut self) -> anyhow::Result<SchemaChangeEvent> {
+        loop {
+            let buffer = collect_frame_response(&mut self.stream).await?;
+            let mut decoder = Decoder::new(buffer, MyCompression::get())?;
+            // skip any other event type
+            if decoder.is_event() {
+                if let Ok(event) = SchemaChangeEvent::new(&mut decoder) {
+                    return Ok(event);
+                }
+            }
+        }
+    }
     /// Get the socket stream behind the cql connection
     pub fn stream(&mut self) -> &mut TcpStream {
         &mut self.stream
@@ -419,6 +564,14 @@ impl Cql {
     pub fn take_dc(&mut self) -> Option<String> {
         self.dc.take()
     }
+    /// Take the rack of the connected scylla node
+    pub fn take_rack(&mut self) -> Option<String> {
+        self.rack.take()
+    }
+    /// Take the host id of the connected scylla node
+    pub fn take_host_id(&mut self) -> Option<HostId> {
+        self.host_id.take()
+    }
     /// Get the shard_id of the connection
     pub fn shard_id(&self) -> u16 {
         self.shard_id
@@ -449,10 +602,46 @@ async fn collect_frame_response(stream: &mut TcpStream) -> anyhow::Result<Vec<u8
     Ok(buffer)
 }
 
-/// Query the data center, and tokens from the ScyllaDB.
+/// Query the peers from the ScyllaDB, if it provides system.peers_v2.
+fn fetch_peers_v2_query() -> anyhow::Result<Vec<u8>> {
+    let Query(payload) = Query::new()
+        .statement("SELECT native_address, native_port, data_center, rack, tokens, host_id FROM system.peers_v2")
+        .consistency(Consistency::One)
+        .build()?;
+    Ok(payload)
+}
+
+/// Query the peers from the ScyllaDB.
+fn fetch_peers_query() -> anyhow::Result<Vec<u8>> {
+    let Query(payload) = Query::new()
+        .statement("SELECT rpc_address, data_center, rack, tokens, host_id FROM system.peers")
+        .consistency(Consistency::One)
+        .build()?;
+    Ok(payload)
+}
+
+/// Query the replication options of the keyspaces from the ScyllaDB.
+fn fetch_replication_query() -> anyhow::Result<Vec<u8>> {
+    let Query(payload) = Query::new()
+        .statement("SELECT keyspace_name, replication FROM system_schema.keyspaces")
+        .consistency(Consistency::One)
+        .build()?;
+    Ok(payload)
+}
+
+/// Query the tablets of the tablets based keyspaces from the ScyllaDB.
+fn fetch_tablets_query() -> anyhow::Result<Vec<u8>> {
+    let Query(payload) = Query::new()
+        .statement("SELECT keyspace_name, table_name, last_token, replicas FROM system.tablets")
+        .consistency(Consistency::One)
+        .build()?;
+    Ok(payload)
+}
+
+/// Query the data center, rack, tokens and host id from the ScyllaDB.
 fn fetch_tokens_query() -> anyhow::Result<Vec<u8>> {
     let Query(payload) = Query::new()
-        .statement("SELECT data_center, tokens FROM system.local")
+        .statement("SELECT data_center, rack, tokens, host_id FROM system.local")
         .consistency(Consistency::One)
         .build()?;
     Ok(payload)
@@ -1,7 +1,11 @@
 mod cql;
+mod peers;
+mod schema;
+mod tablets;
 mod tokens;
 
 pub use cql::{
     Cql,
     CqlBuilder,
 };
+pub use peers::Peer;
@@ -0,0 +1,207 @@
+use crate::{
+    cql::{
+        frame::decoder::{
+            ColumnDecoder,
+            Frame,
+        },
+        Decoder,
+        Metadata,
+        Rows,
+    },
+    rows,
+};
+use std::{
+    convert::TryInto,
+    io::Cursor,
+    net::{
+        IpAddr,
+        Ipv4Addr,
+        Ipv6Addr,
+        SocketAddr,
+    },
+};
+
+rows!(
+    rows: PeersV2,
+    row: PeerV2Row {
+        native_address: Option<Cursor<Vec<u8>>>,
+        native_port: Option<i32>,
+        data_center: Option<String>,
+        rack: Option<String>,
+        tokens: Option<Vec<String>>,
+        host_id: Option<Cursor<Vec<u8>>>,
+    },
+    row_into: PeerV2Row
+);
+
+rows!(
+    rows: Peers,
+    row: PeerRow {
+        rpc_address: Option<Cursor<Vec<u8>>>,
+        data_center: Option<String>,
+        rack: Option<String>,
+        tokens: Option<Vec<String>>,
+        host_id: Option<Cursor<Vec<u8>>>,
+    },
+    row_into: PeerRow
+);
+
+/// A peer of the connected scylla node, as listed in `system.peers_v2` or `system.peers`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Peer {
+    /// The address which accepts cql connections
+    pub address: SocketAddr,
+    /// The datacenter of the peer
+    pub data_center: String,
+    /// The rack of the peer
+    pub rack: Option<String>,
+    /// The tokens owned by the peer
+    pub tokens: Vec<i64>,
+    /// The host id of the peer
+    pub host_id: Option<String>,
+}
+
+impl Peer {
+    fn new(
+        address: Option<Cursor<Vec<u8>>>,
+        port: u16,
+        data_center: Option<String>,
+        rack: Option<String>,
+        tokens: Option<Vec<String>>,
+        host_id: Option<Cursor<Vec<u8>>>,
+    ) -> anyhow::Result<Self> {
+        let address = address
+            .ok_or_else(|| anyhow::anyhow!("Peer without address"))?
+            .into_inner();
+        let ip = match address.len() {
+            4 => IpAddr::V4(Ipv4Addr::from(TryInto::<[u8; 4]>::try_into(address.as_slice())?)),
+            16 => IpAddr::V6(Ipv6Addr::from(TryInto::<[u8; 16]>::try_into(address.as_slice())?)),
+            len => anyhow::bail!("Invalid peer address length {}", len),
+        };
+        let data_center = data_center.ok_or_else(|| anyhow::anyhow!("Peer {} without data center", ip))?;
+        let tokens = tokens
+            .ok_or_else(|| anyhow::anyhow!("Peer {} without tokens", ip))?
+            .iter()
+            .map(|t| t.parse())
+            .collect::<Result<Vec<i64>, _>>()?;
+        let host_id = host_id.map(|h| {
+            h.into_inner()
+                .iter()
+                .enumerate()
+                .map(|(i, b)| match i {
+                    4 | 6 | 8 | 10 => format!("-{:02x}", b),
+                    _ => format!("{:02x}", b),
+                })
+                .collect()
+        });
+        Ok(Self {
+            address: SocketAddr::new(ip, port),
+            data_center,
+            rack,
+            tokens,
+            host_id,
+        })
+    }
+}
+
+impl PeerV2Row {
+    pub(crate) fn into_peer(self, default_port: u16) -> anyhow::Result<Peer> {
+        let port = match self.native_port {
+            Some(port) => port.try_into()?,
+            None => default_port,
+        };
+        Peer::new(
+            self.native_address,
+            port,
+            self.data_center,
+            self.rack,
+            self.tokens,
+            self.host_id,
+        )
+    }
+}
+
+impl PeerRow {
+    pub(crate) fn into_peer(self, port: u16) -> anyhow::Result<Peer> {
+        Peer::new(
+            self.rpc_address,
+            port,
+            self.data_center,
+            self.rack,
+            self.tokens,
+            self.host_id,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST_ID: [u8; 16] = [
+        0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x14, 0x17, 0x40, 0x00,
+    ];
+
+    fn peer_v2(address: &[u8], port: Option<i32>) -> PeerV2Row {
+        PeerV2Row {
+            native_address: Some(Cursor::new(address.to_vec())),
+            native_port: port,
+            data_center: Some("dc1".to_string()),
+            rack: Some("rack1".to_string()),
+            tokens: Some(vec!["-10".to_string(), "20".to_string()]),
+            host_id: Some(Cursor::new(HOST_ID.to_vec())),
+        }
+    }
+
+    #[test]
+    fn peers_v2_row_into_peer() {
+        let peer = peer_v2(&[10, 0, 0, 1], Some(19042)).into_peer(9042).unwrap();
+        assert_eq!(
+            peer,
+            Peer {
+                address: "10.0.0.1:19042".parse().unwrap(),
+                data_center: "dc1".to_string(),
+                rack: Some("rack1".to_string()),
+                tokens: vec![-10, 20],
+                host_id: Some("123e4567-e89b-12d3-a456-426614174000".to_string()),
+            }
+        );
+        // the default port is used when the peer does not list its native port
+        let peer = peer_v2(&Ipv6Addr::LOCALHOST.octets(), None).into_peer(9042).unwrap();
+        assert_eq!(peer.address, "[::1]:9042".parse().unwrap());
+        assert!(peer_v2(&[10, 0, 0], None).into_peer(9042).is_err());
+        assert!(peer_v2(&[10, 0, 0, 1], Some(-1)).into_peer(9042).is_err());
+    }
+
+    #[test]
+    fn peers_row_into_peer() {
+        let row = PeerRow {
+            rpc_address: Some(Cursor::new(vec![10, 0, 0, 2])),
+            data_center: Some("dc2".to_string()),
+            rack: None,
+            tokens: Some(vec!["5".to_string()]),
+            host_id: None,
+        };
+        let peer = row.into_peer(9042).unwrap();
+        assert_eq!(peer.address, "10.0.0.2:9042".parse().unwrap());
+        assert_eq!(peer.data_center, "dc2");
+        assert_eq!(peer.tokens, vec![5]);
+        assert_eq!(peer.host_id, None);
+        let row = PeerRow {
+            rpc_address: None,
+            data_center: Some("dc2".to_string()),
+            rack: None,
+            tokens: Some(Vec::new()),
+            host_id: None,
+        };
+        assert!(row.into_peer(9042).is_err());
+        let row = PeerRow {
+            rpc_address: Some(Cursor::new(vec![10, 0, 0, 2])),
+            data_center: None,
+            rack: None,
+            tokens: Some(Vec::new()),
+            host_id: None,
+        };
+        assert!(row.into_peer(9042).is_err());
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{
+    cql::{
+        frame::decoder::{
+            ColumnDecoder,
+            Frame,
+        },
+        Decoder,
+        Metadata,
+        Rows,
+    },
+    rows,
+};
+use std::collections::HashMap;
+
+rows!(
+    rows: Keyspaces,
+    row: KeyspaceRow {
+        keyspace_name: String,
+        replication: HashMap<String, String>,
+    },
+    row_into: KeyspaceRow
+);
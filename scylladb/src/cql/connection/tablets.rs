@@ -0,0 +1,24 @@
+use crate::{
+    cql::{
+        frame::decoder::{
+            ColumnDecoder,
+            Frame,
+        },
+        Decoder,
+        Metadata,
+        Rows,
+        TabletReplica,
+    },
+    rows,
+};
+
+rows!(
+    rows: Tablets,
+    row: TabletRow {
+        keyspace_name: String,
+        table_name: String,
+        last_token: i64,
+        replicas: Vec<TabletReplica>,
+    },
+    row_into: TabletRow
+);
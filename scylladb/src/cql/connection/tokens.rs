@@ -10,12 +10,15 @@ use crate::{
     },
     rows,
 };
+use std::io::Cursor;
 
 rows!(
     rows: Info,
     row: Row {
         data_center: String,
+        rack: String,
         tokens: Vec<String>,
+        host_id: Option<Cursor<Vec<u8>>>,
     },
     row_into: Row
 );
@@ -1,8 +1,28 @@
 //! This module defines the consistency enum.
-use anyhow::anyhow;
+use super::{
+    decoder::ColumnDecoder,
+    header::COMPRESSION,
+    opcode::{
+        BATCH,
+        EXECUTE,
+        QUERY,
+    },
+};
+use crate::cql::compression::{
+    Compression,
+    MyCompression,
+};
+use anyhow::{
+    anyhow,
+    bail,
+    ensure,
+};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
-use std::convert::TryFrom;
+use std::{
+    convert::TryFrom,
+    io::Cursor,
+};
 #[derive(Debug, FromPrimitive, Clone)]
 #[repr(u16)]
 /// The consistency level enum.
@@ -38,3 +58,116 @@ impl TryFrom<u16> for Consistency {
         Consistency::from_u16(v).ok_or(anyhow!("No consistency representation for provided bytes!"))
     }
 }
+
+/// Set the consistency level of a QUERY, EXECUTE or BATCH request frame, used to retry the request with another
+/// consistency level.
+pub fn set_frame_consistency(frame: Vec<u8>, consistency: Consistency) -> anyhow::Result<Vec<u8>> {
+    let compressed = frame.get(1).is_some_and(|flags| flags & COMPRESSION == COMPRESSION);
+    let mut frame = MyCompression::get().decompress(frame)?;
+    let index = consistency_index(&frame)?;
+    frame[index..index + 2].copy_from_slice(&u16::to_be_bytes(consistency as u16));
+    if compressed {
+        MyCompression::get().compress(frame)
+    } else {
+        Ok(frame)
+    }
+}
+
+/// Get the index of the consistency level in the uncompressed request frame.
+fn consistency_index(frame: &[u8]) -> anyhow::Result<usize> {
+    let reader = &mut Cursor::new(frame);
+    reader.set_position(9);
+    match frame.get(4).copied() {
+        // [long string] query
+        Some(QUERY) => {
+            let length = i32::try_decode_column(reader)?;
+            skip(reader, length)?
+        }
+        // [short bytes] id
+        Some(EXECUTE) => {
+            let length = u16::try_decode_column(reader)? as i32;
+            skip(reader, length)?
+        }
+        Some(BATCH) => {
+            // batch type
+            skip(reader, 1)?;
+            let statements_count = u16::try_decode_column(reader)?;
+            for _ in 0..statements_count {
+                let length = match u8::try_decode_column(reader)? {
+                    0 => i32::try_decode_column(reader)?,
+                    _ => u16::try_decode_column(reader)? as i32,
+                };
+                skip(reader, length)?;
+                let values_count = u16::try_decode_column(reader)?;
+                for _ in 0..values_count {
+                    // null and unset values have negative length
+                    let length = i32::try_decode_column(reader)?;
+                    skip(reader, length.max(0))?;
+                }
+            }
+        }
+        _ => bail!("Unable to set the consistency of a non QUERY/EXECUTE/BATCH frame"),
+    }
+    let index = reader.position() as usize;
+    ensure!(index + 2 <= frame.len(), "Frame is too small!");
+    Ok(index)
+}
+
+fn skip(reader: &mut Cursor<&[u8]>, length: i32) -> anyhow::Result<()> {
+    ensure!(length >= 0, "Invalid length!");
+    reader.set_position(reader.position() + length as u64);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            Batch,
+            Query,
+            Statements,
+        },
+        *,
+    };
+    use crate::cql::Binder;
+
+    fn frame_consistency(frame: &[u8]) -> u16 {
+        let index = consistency_index(frame).unwrap();
+        u16::from_be_bytes([frame[index], frame[index + 1]])
+    }
+
+    #[test]
+    fn set_query_and_batch_consistency() {
+        let query = Query::new()
+            .statement("SELECT * FROM ks.table WHERE key = ?")
+            .consistency(Consistency::Quorum)
+            .bind_values()
+            .value(1i32)
+            .build()
+            .unwrap();
+        assert_eq!(frame_consistency(&query.0), Consistency::Quorum as u16);
+        let query = set_frame_consistency(query.0, Consistency::One).unwrap();
+        assert_eq!(frame_consistency(&query), Consistency::One as u16);
+        let execute = Query::new()
+            .id(&[7; 16])
+            .consistency(Consistency::All)
+            .bind_values()
+            .build()
+            .unwrap();
+        let execute = set_frame_consistency(execute.0, Consistency::Two).unwrap();
+        assert_eq!(frame_consistency(&execute), Consistency::Two as u16);
+        let batch = Batch::new()
+            .logged()
+            .statement("INSERT INTO ks.table (key, value) VALUES (?, ?)")
+            .value(1i32)
+            .value(Option::<i32>::None)
+            .id(&[7; 16])
+            .value("value")
+            .consistency(Consistency::Quorum)
+            .build()
+            .unwrap();
+        assert_eq!(frame_consistency(&batch.0), Consistency::Quorum as u16);
+        let batch = set_frame_consistency(batch.0, Consistency::One).unwrap();
+        assert_eq!(frame_consistency(&batch), Consistency::One as u16);
+    }
+}
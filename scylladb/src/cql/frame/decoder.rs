@@ -33,6 +33,7 @@ use chrono::{
     NaiveDateTime,
     NaiveTime,
 };
+use scylladb_parse::CqlValue;
 use std::{
     collections::{
         BTreeMap,
@@ -170,6 +171,8 @@ pub trait Frame {
     fn is_supported(&self) -> bool;
     /// Check whether the opcode is `READY`.
     fn is_ready(&self) -> bool;
+    /// Check whether the opcode is `EVENT`.
+    fn is_event(&self) -> bool;
     /// Check whether the body kind is `VOID`.
     fn is_void(&self) -> bool;
     /// Check whether the body kind is `ROWS`.
@@ -310,7 +313,11 @@ impl Header {
         } else {
             None
         };
-        let custom_payload = flags & header::CUSTOM_PAYLOAD == header::CUSTOM_PAYLOAD;
+        let custom_payload = if flags & header::CUSTOM_PAYLOAD == header::CUSTOM_PAYLOAD {
+            Some(bytes_map(reader)?)
+        } else {
+            None
+        };
         let mut body_kind = 0;
         if self.is_error() || self.is_result() {
             let mut buf = [0u8; 4];
@@ -332,7 +339,7 @@ impl Header {
 pub struct HeaderFlags {
     compression: bool,
     tracing: Option<[u8; 16]>,
-    custom_payload: bool,
+    custom_payload: Option<HashMap<String, Vec<u8>>>,
     warnings: Option<Vec<String>>,
     // Body kind (if available)
     body_kind: i32,
@@ -352,6 +359,10 @@ impl HeaderFlags {
     fn take_warnings(&mut self) -> Option<Vec<String>> {
         self.warnings.take()
     }
+    /// Get the custom payload of the frame (if any).
+    pub fn custom_payload(&self) -> Option<&HashMap<String, Vec<u8>>> {
+        self.custom_payload.as_ref()
+    }
     /// Returns the body kind (if available), else it's zero
     pub fn body_kind(&self) -> i32 {
         self.body_kind
@@ -389,6 +400,9 @@ impl Frame for Decoder {
     fn is_ready(&self) -> bool {
         self.opcode() == opcode::READY
     }
+    fn is_event(&self) -> bool {
+        self.opcode() == opcode::EVENT
+    }
     fn is_void(&self) -> bool {
         (self.opcode() == opcode::RESULT) && (self.header_flags.body_kind() == result::VOID)
     }
@@ -688,6 +702,24 @@ impl ColumnDecoder for Cursor<Vec<u8>> {
     }
 }
 
+/// Raw values, which can be rendered as literals with `CqlValue::to_term` given the column type.
+impl ColumnDecoder for CqlValue {
+    fn try_decode<R: Read>(reader: &mut R) -> anyhow::Result<Self> {
+        let len = i32::try_decode_column(reader)?;
+        if len < 0 {
+            Ok(CqlValue::Null)
+        } else {
+            Self::try_decode_column(&mut reader.take(len as u64))
+        }
+    }
+
+    fn try_decode_column<R: Read>(reader: &mut R) -> anyhow::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Ok(CqlValue::Bytes(bytes))
+    }
+}
+
 impl<E> ColumnDecoder for Vec<E>
 where
     E: ColumnDecoder,
@@ -902,3 +934,16 @@ pub fn string_multimap<R: Read>(reader: &mut R) -> anyhow::Result<HashMap<String
     }
     Ok(multimap)
 }
+
+/// Get hashmap of string to bytes from slice, null values are skipped.
+pub fn bytes_map<R: Read>(reader: &mut R) -> anyhow::Result<HashMap<String, Vec<u8>>> {
+    let length = u16::try_decode_column(reader)? as usize;
+    let mut map = HashMap::with_capacity(length);
+    for _ in 0..length {
+        let key = string(reader)?;
+        if let Some(value) = bytes(reader)? {
+            map.insert(key, value);
+        }
+    }
+    Ok(map)
+}
@@ -7,6 +7,7 @@ use chrono::{
     NaiveTime,
     Timelike,
 };
+use scylladb_parse::CqlValue;
 use std::{
     collections::{
         BTreeMap,
@@ -486,6 +487,24 @@ impl ColumnEncoder for NaiveDateTime {
     }
 }
 
+/// Values which were already encoded for their target type, i.e. with `Term::to_cql_value`.
+impl ColumnEncoder for CqlValue {
+    fn encode_column(&self, buffer: &mut Vec<u8>) {
+        if let CqlValue::Bytes(b) = self {
+            buffer.extend(b);
+        }
+    }
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        match self {
+            CqlValue::Null => NULL_VALUE.encode(buffer),
+            CqlValue::Bytes(b) => {
+                buffer.extend(&i32::to_be_bytes(b.len() as i32));
+                buffer.extend(b);
+            }
+        }
+    }
+}
+
 /// An encode chain. Allows sequential encodes stored back-to-back in a buffer.
 #[derive(Default, Debug)]
 pub struct TokenEncodeChain {
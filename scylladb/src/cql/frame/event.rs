@@ -0,0 +1,43 @@
+//! This module implements the Event frame.
+
+use super::{
+    decoder::{
+        string,
+        Decoder,
+        Frame,
+    },
+    register::SCHEMA_CHANGE,
+};
+use anyhow::ensure;
+
+/// The schema change event, pushed by the server to connections registered for `SCHEMA_CHANGE` events.
+#[derive(Debug, Clone)]
+pub struct SchemaChangeEvent {
+    /// The kind of change, `CREATED`, `UPDATED` or `DROPPED`
+    pub change_type: String,
+    /// The kind of the changed element, `KEYSPACE`, `TABLE`, `TYPE`, `FUNCTION` or `AGGREGATE`
+    pub target: String,
+    /// The keyspace of the changed element
+    pub keyspace: String,
+}
+
+impl SchemaChangeEvent {
+    /// Create a SchemaChangeEvent from frame decoder.
+    pub fn new(decoder: &mut Decoder) -> anyhow::Result<Self> {
+        ensure!(decoder.is_event(), "Frame is not an event!");
+        let event_type = string(decoder.reader())?;
+        ensure!(event_type == SCHEMA_CHANGE, "Unexpected {} event!", event_type);
+        let change_type = string(decoder.reader())?;
+        let target = string(decoder.reader())?;
+        let keyspace = string(decoder.reader())?;
+        Ok(Self {
+            change_type,
+            target,
+            keyspace,
+        })
+    }
+    /// Check whether the change targets the keyspace itself, such as its replication.
+    pub fn is_keyspace_change(&self) -> bool {
+        self.target == "KEYSPACE"
+    }
+}
@@ -11,16 +11,19 @@ pub(crate) mod consistency;
 pub(crate) mod decoder;
 pub(crate) mod encoder;
 pub(crate) mod error;
+pub(crate) mod event;
 pub(crate) mod header;
 pub(crate) mod opcode;
 pub(crate) mod options;
 pub(crate) mod prepare;
 pub(crate) mod query;
 pub(crate) mod queryflags;
+pub(crate) mod register;
 pub(crate) mod result;
 pub(crate) mod rows;
 pub(crate) mod startup;
 pub(crate) mod supported;
+pub(crate) mod tablets;
 
 pub use auth_response::{
     AllowAllAuth,
@@ -28,7 +31,10 @@ pub use auth_response::{
 };
 pub use auth_success::AuthSuccess;
 pub use batch::*;
-pub use consistency::Consistency;
+pub use consistency::{
+    set_frame_consistency,
+    Consistency,
+};
 pub use decoder::{
     ColumnDecoder,
     Decoder,
@@ -44,9 +50,15 @@ pub use encoder::{
     TokenEncoder,
 };
 pub use error::{
+    Additional,
     CqlError,
     ErrorCodes,
+    ReadTimeout,
+    UnavailableException,
+    WriteTimeout,
+    WriteType,
 };
+pub use event::SchemaChangeEvent;
 pub use prepare::Prepare;
 pub use query::{
     PreparedStatement,
@@ -60,12 +72,25 @@ pub use query::{
     QueryStatement,
     QueryValues,
 };
+pub use register::{
+    SCHEMA_CHANGE,
+    STATUS_CHANGE,
+    TOPOLOGY_CHANGE,
+};
+pub use result::void_result;
 pub use rows::*;
 pub use std::convert::TryInto;
 use std::{
     collections::HashMap,
     io::Cursor,
 };
+pub use tablets::{
+    HostId,
+    Tablet,
+    TabletReplica,
+    TABLETS_ROUTING_PAYLOAD,
+    TABLETS_ROUTING_V1,
+};
 
 use self::encoder::{
     Null,
@@ -0,0 +1,62 @@
+//! This module implements the Register frame.
+
+use super::opcode::REGISTER;
+use crate::cql::compression::{
+    Compression,
+    MyCompression,
+};
+
+/// Blanket cql frame header for REGISTER frame.
+const REGISTER_HEADER: &'static [u8] = &[4, 0, 0, 0, REGISTER, 0, 0, 0, 0];
+
+/// The schema change event type.
+pub const SCHEMA_CHANGE: &'static str = "SCHEMA_CHANGE";
+/// The topology change event type.
+pub const TOPOLOGY_CHANGE: &'static str = "TOPOLOGY_CHANGE";
+/// The status change event type.
+pub const STATUS_CHANGE: &'static str = "STATUS_CHANGE";
+
+/// The Register frame, which subscribes the connection to server pushed events.
+pub(crate) struct Register(pub Vec<u8>);
+
+impl Register {
+    pub(crate) fn new() -> Self {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&REGISTER_HEADER);
+        Register(buffer)
+    }
+    /// Update the event types to register for.
+    pub(crate) fn event_types(mut self, event_types: &[&str]) -> Self {
+        self.0.extend(&u16::to_be_bytes(event_types.len() as u16));
+        for event_type in event_types {
+            self.0.extend(&u16::to_be_bytes(event_type.len() as u16));
+            self.0.extend(event_type.bytes());
+        }
+        self
+    }
+    /// Build a register frame with a assigned compression type.
+    pub(crate) fn build(mut self, compression: impl Compression) -> anyhow::Result<Self> {
+        // apply compression flag(if any to the header)
+        self.0[1] |= MyCompression::flag();
+        self.0 = compression.compress(self.0)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cql::compression::UNCOMPRESSED;
+
+    #[test]
+    fn simple_register_builder_test() {
+        let Register(payload) = Register::new()
+            .event_types(&[SCHEMA_CHANGE])
+            .build(UNCOMPRESSED)
+            .unwrap();
+        assert_eq!(payload[4], REGISTER);
+        assert_eq!(&payload[5..9], &i32::to_be_bytes(2 + 2 + SCHEMA_CHANGE.len() as i32));
+        assert_eq!(&payload[9..11], &[0, 1]);
+        assert_eq!(&payload[13..], SCHEMA_CHANGE.as_bytes());
+    }
+}
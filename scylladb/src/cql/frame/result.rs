@@ -6,3 +6,11 @@ pub const ROWS: i32 = 0x0002;
 pub const SETKEYSPACE: i32 = 0x0003;
 pub const PREPARED: i32 = 0x0004;
 pub const SCHEMACHANGE: i32 = 0x0005;
+
+/// Create a void result frame, used to complete a request whose error is ignored.
+pub fn void_result() -> Vec<u8> {
+    let mut frame = vec![0x84, 0, 0, 0, super::opcode::RESULT];
+    frame.extend(&i32::to_be_bytes(4));
+    frame.extend(&i32::to_be_bytes(VOID));
+    frame
+}
@@ -0,0 +1,142 @@
+//! This module implements the tablets routing protocol extension.
+
+use super::{
+    decoder::{
+        ColumnDecoder,
+        Decoder,
+        Frame,
+    },
+    header::CUSTOM_PAYLOAD,
+};
+use std::{
+    convert::TryInto,
+    io::{
+        Cursor,
+        Read,
+    },
+};
+
+/// The protocol extension which makes the server return the tablet of misrouted requests.
+pub const TABLETS_ROUTING_V1: &'static str = "TABLETS_ROUTING_V1";
+/// The custom payload key of the tablet returned by the server.
+pub const TABLETS_ROUTING_PAYLOAD: &'static str = "tablets-routing-v1";
+
+/// The host id of a scylla node.
+pub type HostId = [u8; 16];
+
+/// A tablet replica, the host and the shard which own the tablet data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabletReplica {
+    /// The host id of the replica node
+    pub host_id: HostId,
+    /// The shard of the replica node which owns the tablet
+    pub shard: u16,
+}
+
+/// A tablet of a table, which owns the tokens range (first_token, last_token].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tablet {
+    /// The exclusive first token of the tablet
+    pub first_token: i64,
+    /// The inclusive last token of the tablet
+    pub last_token: i64,
+    /// The replicas of the tablet
+    pub replicas: Vec<TabletReplica>,
+}
+
+impl ColumnDecoder for TabletReplica {
+    fn try_decode_column<R: Read>(reader: &mut R) -> anyhow::Result<Self> {
+        // frozen<tuple<uuid, int>>
+        let host_id = Cursor::<Vec<u8>>::try_decode(reader)?
+            .into_inner()
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid tablet replica host id"))?;
+        let shard = i32::try_decode(reader)?;
+        Ok(Self {
+            host_id,
+            shard: shard.try_into()?,
+        })
+    }
+}
+
+impl Tablet {
+    /// Check if the frame has a custom payload, which may contain the tablet of a misrouted request.
+    pub fn has_custom_payload(frame: &[u8]) -> bool {
+        frame
+            .get(1)
+            .is_some_and(|flags| flags & CUSTOM_PAYLOAD == CUSTOM_PAYLOAD)
+    }
+    /// Decode the tablet returned by the server in the custom payload of a misrouted request response, if any.
+    pub fn from_routing_payload(decoder: &Decoder) -> anyhow::Result<Option<Self>> {
+        if let Some(payload) = decoder
+            .flags()
+            .custom_payload()
+            .and_then(|custom_payload| custom_payload.get(TABLETS_ROUTING_PAYLOAD))
+        {
+            // tuple<bigint, bigint, list<frozen<tuple<uuid, int>>>>
+            let reader = &mut Cursor::new(payload.as_slice());
+            let first_token = i64::try_decode(reader)?;
+            let last_token = i64::try_decode(reader)?;
+            let replicas = Vec::<TabletReplica>::try_decode(reader)?;
+            Ok(Some(Self {
+                first_token,
+                last_token,
+                replicas,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            opcode::RESULT,
+            result::VOID,
+        },
+        *,
+    };
+    use crate::cql::compression::UNCOMPRESSED;
+
+    fn bytes(value: &[u8]) -> Vec<u8> {
+        let mut bytes = i32::to_be_bytes(value.len() as i32).to_vec();
+        bytes.extend(value);
+        bytes
+    }
+
+    #[test]
+    fn decode_routing_payload() {
+        let mut replica = bytes(&[7; 16]);
+        replica.extend(bytes(&i32::to_be_bytes(3)));
+        let mut replicas = i32::to_be_bytes(1).to_vec();
+        replicas.extend(bytes(&replica));
+        let mut tablet = bytes(&i64::to_be_bytes(-10));
+        tablet.extend(bytes(&i64::to_be_bytes(20)));
+        tablet.extend(bytes(&replicas));
+        // custom payload bytes map, followed by the void result kind
+        let mut body = u16::to_be_bytes(1).to_vec();
+        body.extend(u16::to_be_bytes(TABLETS_ROUTING_PAYLOAD.len() as u16));
+        body.extend(TABLETS_ROUTING_PAYLOAD.as_bytes());
+        body.extend(bytes(&tablet));
+        body.extend(i32::to_be_bytes(VOID));
+        let mut buffer = vec![0x84, CUSTOM_PAYLOAD, 0, 0, RESULT];
+        buffer.extend(i32::to_be_bytes(body.len() as i32));
+        buffer.extend(body);
+        assert!(Tablet::has_custom_payload(&buffer));
+        let decoder = Decoder::new(buffer, UNCOMPRESSED).unwrap();
+        assert!(decoder.is_void());
+        assert_eq!(
+            Tablet::from_routing_payload(&decoder).unwrap(),
+            Some(Tablet {
+                first_token: -10,
+                last_token: 20,
+                replicas: vec![TabletReplica {
+                    host_id: [7; 16],
+                    shard: 3
+                }],
+            })
+        );
+    }
+}